@@ -5,6 +5,8 @@ use super::error::CdpError;
 use super::transport::{
     KeepAliveConfig, ReconnectConfig, TransportCommand, TransportHandle, spawn_transport,
 };
+#[cfg(unix)]
+use super::transport::{PIPE_URL, spawn_pipe_transport};
 use super::types::CdpEvent;
 
 /// Configuration for a CDP client connection.
@@ -34,11 +36,11 @@ impl Default for CdpConfig {
     }
 }
 
-/// A CDP client connected to Chrome over WebSocket.
+/// A CDP client connected to Chrome over WebSocket or a debugging pipe.
 ///
 /// This is the main entry point for sending CDP commands and subscribing
 /// to events. It communicates with a background transport task that owns
-/// the WebSocket connection.
+/// the connection.
 #[derive(Debug)]
 pub struct CdpClient {
     handle: TransportHandle,
@@ -77,6 +79,29 @@ impl CdpClient {
         })
     }
 
+    /// Connect over the `--remote-debugging-pipe` descriptors of a Chrome
+    /// process: `writer` feeds Chrome's fd 3 and `reader` drains its fd 4.
+    ///
+    /// Only `command_timeout` and `channel_capacity` from `config` apply; a
+    /// pipe has no handshake, keep-alive, or reconnection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CdpError::Connection` if either descriptor is not a pipe.
+    #[cfg(unix)]
+    pub fn connect_pipe(
+        writer: std::os::fd::OwnedFd,
+        reader: std::os::fd::OwnedFd,
+        config: CdpConfig,
+    ) -> Result<Self, CdpError> {
+        let handle = spawn_pipe_transport(writer, reader, config.channel_capacity)?;
+        Ok(Self {
+            handle,
+            config,
+            url: PIPE_URL.to_owned(),
+        })
+    }
+
     /// Send a CDP command (browser-level, no session).
     ///
     /// # Errors
@@ -99,6 +124,55 @@ impl CdpClient {
         .await
     }
 
+    /// Send a CDP command on behalf of an arbitrary session.
+    ///
+    /// Used by the daemon to relay commands whose `sessionId` was chosen by
+    /// a downstream client rather than by a [`CdpSession`] it owns.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`send_command`](Self::send_command).
+    pub async fn send_command_with_session(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        session_id: Option<String>,
+    ) -> Result<serde_json::Value, CdpError> {
+        send_command_impl(
+            &self.handle,
+            self.config.command_timeout,
+            method,
+            params,
+            session_id,
+        )
+        .await
+    }
+
+    /// Hand a command to the transport without waiting for its response.
+    ///
+    /// Commands reach Chrome in the order this returns, so a relay can keep
+    /// one client's commands in sequence while their responses are still
+    /// outstanding. Await the returned future for the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CdpError::Internal` if the transport task has exited.
+    pub async fn queue_command_with_session(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        session_id: Option<String>,
+    ) -> Result<PendingResponse, CdpError> {
+        queue_command_impl(
+            &self.handle,
+            self.config.command_timeout,
+            method,
+            params,
+            session_id,
+        )
+        .await
+    }
+
     /// Subscribe to CDP events matching a method name.
     ///
     /// Returns a receiver that yields `CdpEvent` values. Events stop
@@ -111,6 +185,24 @@ impl CdpClient {
         subscribe_impl(&self.handle, self.config.channel_capacity, method, None).await
     }
 
+    /// Subscribe to every event on the connection, across all methods and
+    /// sessions.
+    ///
+    /// Unlike [`subscribe`](Self::subscribe) the channel is unbounded: no
+    /// event is dropped while the receiver falls behind, so the caller must
+    /// keep up or cap what it stores.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CdpError::Internal` if the transport task has exited.
+    pub async fn subscribe_all(&self) -> Result<mpsc::UnboundedReceiver<CdpEvent>, CdpError> {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        self.handle
+            .send(TransportCommand::SubscribeAll { event_tx })
+            .await?;
+        Ok(event_rx)
+    }
+
     /// Create a CDP session attached to a specific target.
     ///
     /// Sends `Target.attachToTarget` and returns a `CdpSession` bound
@@ -222,6 +314,11 @@ impl CdpSession {
 // Shared helpers
 // =============================================================================
 
+/// A command already handed to the transport, awaiting Chrome's response.
+pub type PendingResponse = std::pin::Pin<
+    Box<dyn std::future::Future<Output = Result<serde_json::Value, CdpError>> + Send>,
+>;
+
 /// Send a CDP command via the transport handle and await the response.
 async fn send_command_impl(
     handle: &TransportHandle,
@@ -230,6 +327,19 @@ async fn send_command_impl(
     params: Option<serde_json::Value>,
     session_id: Option<String>,
 ) -> Result<serde_json::Value, CdpError> {
+    queue_command_impl(handle, command_timeout, method, params, session_id)
+        .await?
+        .await
+}
+
+/// Queue a CDP command on the transport and return its pending response.
+async fn queue_command_impl(
+    handle: &TransportHandle,
+    command_timeout: Duration,
+    method: &str,
+    params: Option<serde_json::Value>,
+    session_id: Option<String>,
+) -> Result<PendingResponse, CdpError> {
     let id = handle.next_message_id();
     let command = super::types::CdpCommand {
         id,
@@ -249,9 +359,11 @@ async fn send_command_impl(
         })
         .await?;
 
-    response_rx
-        .await
-        .map_err(|_| CdpError::Internal("transport task exited before responding".into()))?
+    Ok(Box::pin(async move {
+        response_rx
+            .await
+            .map_err(|_| CdpError::Internal("transport task exited before responding".into()))?
+    }))
 }
 
 /// Register an event subscription via the transport handle.
@@ -3,7 +3,9 @@ mod error;
 mod transport;
 mod types;
 
-pub use client::{CdpClient, CdpConfig, CdpSession};
+pub use client::{CdpClient, CdpConfig, CdpSession, PendingResponse};
 pub use error::CdpError;
-pub use transport::{KeepAliveConfig, ReconnectConfig};
+#[cfg(unix)]
+pub use transport::pass_pipe_fds;
+pub use transport::{KeepAliveConfig, PIPE_URL, ReconnectConfig, UNIX_SOCKET_SCHEME};
 pub use types::{CdpEvent, CdpResponse};
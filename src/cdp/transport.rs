@@ -3,16 +3,27 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::{Duration, Instant};
+use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::Message;
-use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
 use super::error::CdpError;
 use super::types::{CdpCommand, CdpEvent, MessageKind, RawCdpMessage};
 
-type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+/// URL scheme for WebSocket endpoints served over a Unix domain socket
+/// (e.g. `ws+unix:///home/me/.agentchrome/daemon.sock`).
+pub const UNIX_SOCKET_SCHEME: &str = "ws+unix://";
+
+/// Byte stream underneath the WebSocket: TCP for Chrome, or a Unix domain
+/// socket for the local daemon.
+trait CdpStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> CdpStream for T {}
+
+type WsStream = WebSocketStream<Box<dyn CdpStream>>;
 
 /// Key for the subscriber map: (`method_name`, `session_id`).
 type SubscriberKey = (String, Option<String>);
@@ -31,6 +42,12 @@ pub enum TransportCommand {
         session_id: Option<String>,
         event_tx: mpsc::Sender<CdpEvent>,
     },
+    /// Subscribe to every event on the connection, regardless of method or
+    /// session. The channel is unbounded so a slow consumer never loses
+    /// events; the consumer is expected to bound its own storage.
+    SubscribeAll {
+        event_tx: mpsc::UnboundedSender<CdpEvent>,
+    },
     /// Shut down the transport gracefully.
     Shutdown,
 }
@@ -135,6 +152,56 @@ pub async fn spawn_transport(
     keepalive: KeepAliveConfig,
 ) -> Result<TransportHandle, CdpError> {
     let ws_stream = connect_ws(url, connect_timeout).await?;
+    Ok(spawn_task(
+        Link::Ws(Box::new(ws_stream)),
+        url,
+        channel_capacity,
+        reconnect_config,
+        connect_timeout,
+        keepalive,
+    ))
+}
+
+/// Spawn the transport background task over Chrome's
+/// `--remote-debugging-pipe` file descriptors: `writer` is connected to
+/// Chrome's fd 3 and `reader` to its fd 4. Messages are NUL-delimited JSON.
+///
+/// A pipe cannot be reopened, so the transport never reconnects and sends no
+/// keep-alive pings; once Chrome closes its end, every command fails with
+/// `CdpError::ReconnectFailed`.
+///
+/// # Errors
+///
+/// Returns `CdpError::Connection` if either descriptor is not a pipe.
+#[cfg(unix)]
+pub fn spawn_pipe_transport(
+    writer: std::os::fd::OwnedFd,
+    reader: std::os::fd::OwnedFd,
+    channel_capacity: usize,
+) -> Result<TransportHandle, CdpError> {
+    let link = PipeLink::new(writer, reader, channel_capacity)?;
+    let keepalive = KeepAliveConfig {
+        interval: None,
+        ..KeepAliveConfig::default()
+    };
+    Ok(spawn_task(
+        Link::Pipe(link),
+        PIPE_URL,
+        channel_capacity,
+        ReconnectConfig::default(),
+        Duration::ZERO,
+        keepalive,
+    ))
+}
+
+fn spawn_task(
+    link: Link,
+    url: &str,
+    channel_capacity: usize,
+    reconnect_config: ReconnectConfig,
+    connect_timeout: Duration,
+    keepalive: KeepAliveConfig,
+) -> TransportHandle {
     let connected = Arc::new(AtomicBool::new(true));
     let next_id = Arc::new(AtomicU64::new(1));
     let (command_tx, command_rx) = mpsc::channel(channel_capacity);
@@ -148,10 +215,11 @@ pub async fn spawn_transport(
     let url_owned = url.to_owned();
     tokio::spawn(async move {
         let mut task = TransportTask {
-            ws_stream,
+            link,
             command_rx,
             pending: HashMap::new(),
             subscribers: HashMap::new(),
+            catch_all: Vec::new(),
             connected,
             url: url_owned,
             reconnect_config,
@@ -166,24 +234,221 @@ pub async fn spawn_transport(
         task.run().await;
     });
 
-    Ok(handle)
+    handle
 }
 
 /// Establish a WebSocket connection with a timeout.
 async fn connect_ws(url: &str, timeout: Duration) -> Result<WsStream, CdpError> {
-    match tokio::time::timeout(timeout, tokio_tungstenite::connect_async(url)).await {
-        Ok(Ok((stream, _response))) => Ok(stream),
-        Ok(Err(e)) => Err(CdpError::Connection(e.to_string())),
+    match tokio::time::timeout(timeout, open_ws(url)).await {
+        Ok(result) => result,
         Err(_) => Err(CdpError::ConnectionTimeout),
     }
 }
 
-/// The background transport task that owns the WebSocket connection.
+/// Open the underlying stream for `url` and run the WebSocket handshake.
+async fn open_ws(url: &str) -> Result<WsStream, CdpError> {
+    let stream: Box<dyn CdpStream> = if let Some(path) = url.strip_prefix(UNIX_SOCKET_SCHEME) {
+        Box::new(connect_unix(path).await?)
+    } else {
+        let parsed = url::Url::parse(url).map_err(|e| CdpError::Connection(e.to_string()))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| CdpError::Connection(format!("missing host in {url}")))?;
+        let port = parsed.port_or_known_default().unwrap_or(80);
+        let tcp = TcpStream::connect((host, port))
+            .await
+            .map_err(|e| CdpError::Connection(e.to_string()))?;
+        Box::new(tcp)
+    };
+    // The request URI for a Unix socket endpoint is nominal; only the path matters.
+    let request_url = if url.starts_with(UNIX_SOCKET_SCHEME) {
+        "ws://localhost/"
+    } else {
+        url
+    };
+    match tokio_tungstenite::client_async(request_url, stream).await {
+        Ok((ws, _response)) => Ok(ws),
+        Err(e) => Err(CdpError::Connection(e.to_string())),
+    }
+}
+
+#[cfg(unix)]
+async fn connect_unix(path: &str) -> Result<tokio::net::UnixStream, CdpError> {
+    tokio::net::UnixStream::connect(path)
+        .await
+        .map_err(|e| CdpError::Connection(format!("{path}: {e}")))
+}
+
+#[cfg(not(unix))]
+async fn connect_unix(path: &str) -> Result<TcpStream, CdpError> {
+    Err(CdpError::Connection(format!(
+        "{path}: Unix domain sockets are not supported on this platform"
+    )))
+}
+
+/// What a [`Link`] produced when polled.
+enum Inbound {
+    Text(String),
+    Pong,
+    Closed,
+    /// Binary, Ping, and raw frames, which CDP does not use.
+    Ignored,
+}
+
+/// The connection underneath the transport task.
+enum Link {
+    Ws(Box<WsStream>),
+    #[cfg(unix)]
+    Pipe(PipeLink),
+}
+
+impl Link {
+    async fn next(&mut self) -> Inbound {
+        match self {
+            Self::Ws(ws) => match ws.next().await {
+                Some(Ok(Message::Text(text))) => Inbound::Text(text.to_string()),
+                Some(Ok(Message::Pong(_))) => Inbound::Pong,
+                Some(Ok(Message::Close(_)) | Err(_)) | None => Inbound::Closed,
+                Some(Ok(_)) => Inbound::Ignored,
+            },
+            #[cfg(unix)]
+            Self::Pipe(pipe) => pipe
+                .incoming
+                .recv()
+                .await
+                .map_or(Inbound::Closed, Inbound::Text),
+        }
+    }
+
+    async fn send_text(&mut self, text: String) -> Result<(), String> {
+        match self {
+            Self::Ws(ws) => ws
+                .send(Message::Text(text.into()))
+                .await
+                .map_err(|e| format!("WebSocket write error: {e}")),
+            #[cfg(unix)]
+            Self::Pipe(pipe) => pipe.send(text.as_bytes()).await,
+        }
+    }
+
+    async fn ping(&mut self) {
+        if let Self::Ws(ws) = self {
+            let _ = ws.send(Message::Ping(Vec::new().into())).await;
+        }
+    }
+
+    async fn close(&mut self) {
+        if let Self::Ws(ws) = self {
+            let _ = (**ws).close(None).await;
+        }
+    }
+
+    /// Whether a lost connection can be re-established by dialing again.
+    fn can_reconnect(&self) -> bool {
+        matches!(self, Self::Ws(_))
+    }
+}
+
+/// Nominal URL reported by clients connected over a pipe.
+pub const PIPE_URL: &str = "pipe:";
+
+/// Chrome's `--remote-debugging-pipe` protocol: NUL-terminated JSON messages.
+/// A reader task splits the incoming stream so [`Link::next`] stays
+/// cancel-safe inside `select!`.
+#[cfg(unix)]
+struct PipeLink {
+    incoming: mpsc::Receiver<String>,
+    writer: tokio::net::unix::pipe::Sender,
+}
+
+#[cfg(unix)]
+impl PipeLink {
+    fn new(
+        writer: std::os::fd::OwnedFd,
+        reader: std::os::fd::OwnedFd,
+        channel_capacity: usize,
+    ) -> Result<Self, CdpError> {
+        use tokio::io::AsyncBufReadExt;
+        use tokio::net::unix::pipe;
+
+        let pipe_error = |e: std::io::Error| CdpError::Connection(format!("debugging pipe: {e}"));
+        let writer = pipe::Sender::from_owned_fd(writer).map_err(pipe_error)?;
+        let reader = pipe::Receiver::from_owned_fd(reader).map_err(pipe_error)?;
+
+        let (tx, incoming) = mpsc::channel(channel_capacity);
+        tokio::spawn(async move {
+            let mut reader = tokio::io::BufReader::new(reader);
+            let mut buf = Vec::new();
+            loop {
+                buf.clear();
+                match reader.read_until(0, &mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if buf.last() == Some(&0) {
+                            buf.pop();
+                        }
+                        let text = String::from_utf8_lossy(&buf).into_owned();
+                        if tx.send(text).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+        Ok(Self { incoming, writer })
+    }
+
+    async fn send(&mut self, message: &[u8]) -> Result<(), String> {
+        use tokio::io::AsyncWriteExt;
+
+        let framed = [message, &[0]].concat();
+        self.writer
+            .write_all(&framed)
+            .await
+            .map_err(|e| format!("debugging pipe write error: {e}"))
+    }
+}
+
+/// Arrange for `fd3` and `fd4` of the current process to become descriptors
+/// 3 and 4 of the child `cmd` spawns — the layout Chrome expects for
+/// `--remote-debugging-pipe`.
+#[cfg(unix)]
+pub fn pass_pipe_fds(
+    cmd: &mut std::process::Command,
+    fd3: std::os::fd::RawFd,
+    fd4: std::os::fd::RawFd,
+) {
+    use std::os::unix::process::CommandExt;
+
+    // SAFETY: `pre_exec` runs in the child after fork and before exec. The
+    // closure only calls `fcntl` and `dup2`, which are async-signal-safe.
+    // Both sources are first copied above 4 so that placing one cannot
+    // clobber the other; `dup2` clears close-on-exec on the targets.
+    unsafe {
+        cmd.pre_exec(move || {
+            let high3 = libc::fcntl(fd3, libc::F_DUPFD, 5);
+            let high4 = libc::fcntl(fd4, libc::F_DUPFD, 5);
+            if high3 == -1
+                || high4 == -1
+                || libc::dup2(high3, 3) == -1
+                || libc::dup2(high4, 4) == -1
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+            libc::close(high3);
+            libc::close(high4);
+            Ok(())
+        });
+    }
+}
+
+/// The background transport task that owns the connection.
 struct TransportTask {
-    ws_stream: WsStream,
+    link: Link,
     command_rx: mpsc::Receiver<TransportCommand>,
     pending: HashMap<u64, PendingRequest>,
     subscribers: HashMap<SubscriberKey, Vec<mpsc::Sender<CdpEvent>>>,
+    catch_all: Vec<mpsc::UnboundedSender<CdpEvent>>,
     connected: Arc<AtomicBool>,
     url: String,
     reconnect_config: ReconnectConfig,
@@ -212,7 +477,9 @@ impl TransportTask {
                         }));
                         continue;
                     }
-                    Some(TransportCommand::Subscribe { .. }) => continue,
+                    Some(
+                        TransportCommand::Subscribe { .. } | TransportCommand::SubscribeAll { .. },
+                    ) => continue,
                     Some(TransportCommand::Shutdown) | None => return,
                 }
             }
@@ -253,22 +520,20 @@ impl TransportTask {
             };
 
             tokio::select! {
-                ws_msg = self.ws_stream.next() => {
-                    match ws_msg {
-                        Some(Ok(Message::Text(text))) => {
+                inbound = self.link.next() => {
+                    match inbound {
+                        Inbound::Text(text) => {
                             self.handle_text_message(&text);
                         }
-                        Some(Ok(Message::Pong(_))) => {
+                        Inbound::Pong => {
                             self.last_ping_at = None;
                         }
-                        Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                        Inbound::Closed => {
                             self.handle_disconnect().await;
                             self.last_ping_at = None;
                             self.last_outbound = Instant::now();
                         }
-                        Some(Ok(_)) => {
-                            // Binary, Ping, Frame — ignore
-                        }
+                        Inbound::Ignored => {}
                     }
                 }
 
@@ -283,9 +548,12 @@ impl TransportTask {
                                 .or_default()
                                 .push(event_tx);
                         }
+                        Some(TransportCommand::SubscribeAll { event_tx }) => {
+                            self.catch_all.push(event_tx);
+                        }
                         Some(TransportCommand::Shutdown) | None => {
                             self.drain_pending();
-                            let _ = self.ws_stream.close(None).await;
+                            self.link.close().await;
                             self.connected.store(false, Ordering::Relaxed);
                             return;
                         }
@@ -298,7 +566,7 @@ impl TransportTask {
 
                 () = keepalive_tick => {
                     if self.keepalive.interval.is_some() {
-                        let _ = self.ws_stream.send(Message::Ping(Vec::new().into())).await;
+                        self.link.ping().await;
                         let now = Instant::now();
                         self.last_outbound = now;
                         self.last_ping_at = Some(now);
@@ -359,6 +627,7 @@ impl TransportTask {
                 self.subscribers.remove(&key);
             }
         }
+        self.catch_all.retain(|tx| tx.send(event.clone()).is_ok());
     }
 
     async fn handle_send_command(
@@ -379,10 +648,8 @@ impl TransportTask {
             }
         };
 
-        if let Err(e) = self.ws_stream.send(Message::Text(json.into())).await {
-            let _ = response_tx.send(Err(CdpError::Connection(format!(
-                "WebSocket write error: {e}"
-            ))));
+        if let Err(e) = self.link.send_text(json).await {
+            let _ = response_tx.send(Err(CdpError::Connection(e)));
             return;
         }
 
@@ -432,6 +699,11 @@ impl TransportTask {
         self.connected.store(false, Ordering::Relaxed);
         self.drain_pending();
 
+        if !self.link.can_reconnect() {
+            self.reconnect_failure = Some((0, "debugging pipe closed".to_string()));
+            return;
+        }
+
         let mut backoff = self.reconnect_config.initial_backoff;
         let mut last_error_msg = String::from("no retries configured");
 
@@ -440,7 +712,7 @@ impl TransportTask {
 
             match connect_ws(&self.url, self.connect_timeout).await {
                 Ok(new_stream) => {
-                    self.ws_stream = new_stream;
+                    self.link = Link::Ws(Box::new(new_stream));
                     self.connected.store(true, Ordering::Relaxed);
                     return;
                 }
@@ -0,0 +1,267 @@
+use std::collections::BTreeSet;
+use std::io::Write as _;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use agentchrome::cdp::{CdpError, CdpEvent};
+use agentchrome::error::{AppError, ExitCode};
+
+use crate::cli::{CdpArgs, CdpCommand, CdpListenArgs, CdpSendArgs, GlobalOpts};
+use crate::output::{connect_from_global, print_output, setup_session_bare};
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    method: &'a str,
+    params: &'a Value,
+}
+
+// =============================================================================
+// Input validation
+// =============================================================================
+
+/// Check that `name` looks like `Domain.member` and return the domain part.
+fn split_domain<'a>(name: &'a str, kind: &str) -> Result<&'a str, AppError> {
+    match name.split_once('.') {
+        Some((domain, member)) if !domain.is_empty() && !member.is_empty() => Ok(domain),
+        _ => Err(AppError {
+            message: format!("Invalid CDP {kind} '{name}': expected Domain.{kind}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }),
+    }
+}
+
+/// Parse the optional PARAMS argument, which must be a JSON object.
+fn parse_params(raw: Option<&str>) -> Result<Option<Value>, AppError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let value: Value = serde_json::from_str(raw).map_err(|e| AppError {
+        message: format!("Invalid PARAMS JSON: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    if !value.is_object() {
+        return Err(AppError {
+            message: "PARAMS must be a JSON object".into(),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        });
+    }
+    Ok(Some(value))
+}
+
+/// Distinct domains of the requested events, in sorted order.
+fn event_domains(events: &[String]) -> Result<BTreeSet<&str>, AppError> {
+    events.iter().map(|e| split_domain(e, "event")).collect()
+}
+
+/// Domain enables that Chrome rejects (e.g. `Input.enable` does not exist)
+/// are not fatal: some domains emit events without being enabled.
+fn ignore_protocol_error(result: Result<(), CdpError>) -> Result<(), AppError> {
+    match result {
+        Ok(()) | Err(CdpError::Protocol { .. }) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `cdp` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the connection fails or Chrome rejects the command.
+pub async fn execute_cdp(global: &GlobalOpts, args: &CdpArgs) -> Result<(), AppError> {
+    match &args.command {
+        CdpCommand::Send(send_args) => execute_send(global, send_args).await,
+        CdpCommand::Listen(listen_args) => execute_listen(global, listen_args).await,
+    }
+}
+
+// =============================================================================
+// Send
+// =============================================================================
+
+async fn execute_send(global: &GlobalOpts, args: &CdpSendArgs) -> Result<(), AppError> {
+    split_domain(&args.method, "method")?;
+    let params = parse_params(args.params.as_deref())?;
+
+    let result = if args.browser {
+        let conn = connect_from_global(global).await?;
+        conn.client.send_command(&args.method, params).await?
+    } else {
+        let (_client, managed) = setup_session_bare(global).await?;
+        managed.send_command(&args.method, params).await?
+    };
+
+    print_output(&result, &global.output)
+}
+
+// =============================================================================
+// Listen
+// =============================================================================
+
+async fn execute_listen(global: &GlobalOpts, args: &CdpListenArgs) -> Result<(), AppError> {
+    let domains = event_domains(&args.events)?;
+
+    // Subscribe before enabling so events Chrome replays on enable (e.g.
+    // Target.targetCreated for existing targets) are not missed.
+    let (tx, rx) = mpsc::channel::<CdpEvent>(256);
+    if args.browser {
+        let conn = connect_from_global(global).await?;
+        for event in &args.events {
+            forward(conn.client.subscribe(event).await?, &tx);
+        }
+        drop(tx);
+        for domain in domains {
+            let sent = if domain == "Target" {
+                let params = serde_json::json!({ "discover": true });
+                conn.client
+                    .send_command("Target.setDiscoverTargets", Some(params))
+                    .await
+            } else {
+                conn.client
+                    .send_command(&format!("{domain}.enable"), None)
+                    .await
+            };
+            ignore_protocol_error(sent.map(|_| ()))?;
+        }
+        stream_events(rx, args).await
+    } else {
+        let (_client, mut managed) = setup_session_bare(global).await?;
+        for event in &args.events {
+            forward(managed.subscribe(event).await?, &tx);
+        }
+        drop(tx);
+        for domain in domains {
+            ignore_protocol_error(managed.ensure_domain(domain).await)?;
+        }
+        stream_events(rx, args).await
+    }
+}
+
+/// Print events as NDJSON until `--count`, `--timeout`, or Ctrl+C.
+async fn stream_events(
+    mut rx: mpsc::Receiver<CdpEvent>,
+    args: &CdpListenArgs,
+) -> Result<(), AppError> {
+    let deadline = args
+        .timeout
+        .map(|ms| tokio::time::Instant::now() + Duration::from_millis(ms));
+    let mut printed: u64 = 0;
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else {
+                    return Err(AppError {
+                        message: "CDP connection closed".to_string(),
+                        code: ExitCode::ConnectionError,
+                        custom_json: None,
+                    });
+                };
+                let line = EventLine { method: &event.method, params: &event.params };
+                println!("{}", serde_json::to_string(&line).unwrap_or_default());
+                let _ = std::io::stdout().flush();
+                printed += 1;
+                if args.count.is_some_and(|max| printed >= max) {
+                    break;
+                }
+            }
+            () = async {
+                if let Some(d) = deadline {
+                    tokio::time::sleep_until(d).await;
+                } else {
+                    std::future::pending::<()>().await;
+                }
+            } => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Pump one subscription into the shared output channel.
+fn forward(mut rx: mpsc::Receiver<CdpEvent>, tx: &mpsc::Sender<CdpEvent>) {
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_domain_accepts_qualified_names() {
+        assert_eq!(
+            split_domain("Animation.enable", "method").unwrap(),
+            "Animation"
+        );
+        assert_eq!(
+            split_domain("Security.visibleSecurityStateChanged", "event").unwrap(),
+            "Security"
+        );
+    }
+
+    #[test]
+    fn split_domain_rejects_bare_names() {
+        for bad in ["navigate", ".navigate", "Page.", ""] {
+            let err = split_domain(bad, "method").unwrap_err();
+            assert!(matches!(err.code, ExitCode::GeneralError));
+            assert!(err.message.contains("Domain.method"), "{}", err.message);
+        }
+    }
+
+    #[test]
+    fn parse_params_accepts_objects_only() {
+        assert_eq!(parse_params(None).unwrap(), None);
+        assert_eq!(
+            parse_params(Some(r#"{"playbackRate":0.5}"#)).unwrap(),
+            Some(serde_json::json!({"playbackRate": 0.5}))
+        );
+        assert!(
+            parse_params(Some("[1,2]"))
+                .unwrap_err()
+                .message
+                .contains("JSON object")
+        );
+        assert!(
+            parse_params(Some("{nope"))
+                .unwrap_err()
+                .message
+                .contains("Invalid PARAMS JSON")
+        );
+    }
+
+    #[test]
+    fn event_domains_deduplicates() {
+        let events = vec![
+            "Animation.animationStarted".to_string(),
+            "Animation.animationCanceled".to_string(),
+            "Security.visibleSecurityStateChanged".to_string(),
+        ];
+        let domains: Vec<_> = event_domains(&events).unwrap().into_iter().collect();
+        assert_eq!(domains, ["Animation", "Security"]);
+    }
+}
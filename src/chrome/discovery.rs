@@ -48,6 +48,11 @@ pub struct TargetInfo {
     pub ws_debugger_url: Option<String>,
 }
 
+/// Port recorded for a Chrome launched with `connect --launch --pipe`. Such a
+/// browser has no HTTP endpoint, so the queries below answer over CDP through
+/// the daemon that holds its debugging pipe.
+pub const PIPE_PORT: u16 = 0;
+
 /// Query Chrome's `/json/version` endpoint.
 ///
 /// # Errors
@@ -55,6 +60,9 @@ pub struct TargetInfo {
 /// Returns `ChromeError::HttpError` on connection failure or `ChromeError::ParseError`
 /// if the response cannot be deserialized.
 pub async fn query_version(host: &str, port: u16) -> Result<BrowserVersion, ChromeError> {
+    if port == PIPE_PORT {
+        return super::pipe::query_version().await;
+    }
     let body = http_get(host, port, "/json/version").await?;
     serde_json::from_str(&body).map_err(|e| ChromeError::ParseError(e.to_string()))
 }
@@ -67,6 +75,9 @@ pub async fn query_version(host: &str, port: u16) -> Result<BrowserVersion, Chro
 /// if the response cannot be deserialized.
 #[allow(dead_code)]
 pub async fn query_targets(host: &str, port: u16) -> Result<Vec<TargetInfo>, ChromeError> {
+    if port == PIPE_PORT {
+        return super::pipe::query_targets().await;
+    }
     let body = http_get(host, port, "/json/list").await?;
     serde_json::from_str(&body).map_err(|e| ChromeError::ParseError(e.to_string()))
 }
@@ -81,6 +92,9 @@ pub async fn query_targets(host: &str, port: u16) -> Result<Vec<TargetInfo>, Chr
 ///
 /// Returns `ChromeError::HttpError` on connection failure.
 pub async fn activate_target(host: &str, port: u16, target_id: &str) -> Result<(), ChromeError> {
+    if port == PIPE_PORT {
+        return super::pipe::activate_target(target_id).await;
+    }
     let path = format!("/json/activate/{target_id}");
     let _body = http_get(host, port, &path).await?;
     Ok(())
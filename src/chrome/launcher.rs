@@ -22,6 +22,18 @@ pub struct LaunchConfig {
     pub extra_args: Vec<String>,
     /// User data directory. If `None`, a temporary directory is created.
     pub user_data_dir: Option<PathBuf>,
+    /// Speak CDP over file descriptors 3 and 4 (`--remote-debugging-pipe`)
+    /// instead of opening `port`. Unix only.
+    pub pipe: bool,
+}
+
+/// Parent ends of a `--remote-debugging-pipe` connection.
+#[cfg(unix)]
+pub struct DebugPipe {
+    /// Write end of the pipe Chrome reads commands from (its fd 3).
+    pub writer: std::os::fd::OwnedFd,
+    /// Read end of the pipe Chrome writes responses to (its fd 4).
+    pub reader: std::os::fd::OwnedFd,
 }
 
 /// A handle to a running Chrome process.
@@ -29,6 +41,8 @@ pub struct ChromeProcess {
     child: Option<std::process::Child>,
     port: u16,
     temp_dir: Option<TempDir>,
+    #[cfg(unix)]
+    pipe: Option<DebugPipe>,
 }
 
 /// A temporary directory that is removed on drop.
@@ -56,6 +70,13 @@ impl ChromeProcess {
         self.port
     }
 
+    /// Take the debugging pipe of a process launched with
+    /// [`LaunchConfig::pipe`]. Dropping it closes Chrome's connection.
+    #[cfg(unix)]
+    pub fn take_pipe(&mut self) -> Option<DebugPipe> {
+        self.pipe.take()
+    }
+
     /// Kill the Chrome process and clean up.
     pub fn kill(&mut self) {
         if let Some(child) = self.child.as_mut() {
@@ -137,8 +158,13 @@ pub fn find_available_port() -> Result<u16, ChromeError> {
 
 /// Build the Chrome command-line arguments from a launch configuration.
 fn build_chrome_args(config: &LaunchConfig, data_dir: &Path) -> Vec<String> {
+    let debugging = if config.pipe {
+        "--remote-debugging-pipe".to_string()
+    } else {
+        format!("--remote-debugging-port={}", config.port)
+    };
     let mut args = vec![
-        format!("--remote-debugging-port={}", config.port),
+        debugging,
         format!("--user-data-dir={}", data_dir.display()),
         "--no-first-run".to_string(),
         "--no-default-browser-check".to_string(),
@@ -205,9 +231,34 @@ fn configure_detached_process(cmd: &mut Command) {
     }
 }
 
+/// Create the two pipes of `--remote-debugging-pipe` and map the child's ends
+/// to descriptors 3 and 4 of `cmd`. The child ends must stay open until the
+/// process is spawned.
+#[cfg(unix)]
+fn attach_debug_pipe(
+    cmd: &mut Command,
+) -> Result<(DebugPipe, (std::io::PipeReader, std::io::PipeWriter)), ChromeError> {
+    use std::os::fd::AsRawFd;
+
+    let pipe_error =
+        |e: std::io::Error| ChromeError::LaunchFailed(format!("could not create pipe: {e}"));
+    let (command_reader, command_writer) = std::io::pipe().map_err(pipe_error)?;
+    let (response_reader, response_writer) = std::io::pipe().map_err(pipe_error)?;
+    crate::cdp::pass_pipe_fds(cmd, command_reader.as_raw_fd(), response_writer.as_raw_fd());
+    Ok((
+        DebugPipe {
+            writer: command_writer.into(),
+            reader: response_reader.into(),
+        },
+        (command_reader, response_writer),
+    ))
+}
+
 /// Launch a Chrome process with the given configuration.
 ///
 /// Polls the Chrome debug endpoint until it responds or the timeout expires.
+/// A pipe launch has no HTTP endpoint, so it returns right after spawning and
+/// the caller confirms readiness over [`ChromeProcess::take_pipe`].
 ///
 /// # Errors
 ///
@@ -236,6 +287,19 @@ pub async fn launch_chrome(
     configure_detached_process(&mut cmd);
     cmd.stdout(Stdio::null()).stderr(Stdio::null());
 
+    #[cfg(unix)]
+    let pipe_ends = if config.pipe {
+        Some(attach_debug_pipe(&mut cmd)?)
+    } else {
+        None
+    };
+    #[cfg(not(unix))]
+    if config.pipe {
+        return Err(ChromeError::LaunchFailed(
+            "--remote-debugging-pipe is only supported on Unix".into(),
+        ));
+    }
+
     let child = cmd.spawn().map_err(|e| {
         ChromeError::LaunchFailed(format!(
             "failed to spawn {}: {e}",
@@ -247,8 +311,23 @@ pub async fn launch_chrome(
         child: Some(child),
         port: config.port,
         temp_dir,
+        // Dropping the child's ends here leaves Chrome as their only holder,
+        // so its exit shows up as end-of-file on `reader`.
+        #[cfg(unix)]
+        pipe: pipe_ends.map(|(pipe, _child_ends)| pipe),
     };
 
+    if config.pipe {
+        if let Some(child) = process.child.as_mut()
+            && let Ok(Some(status)) = child.try_wait()
+        {
+            return Err(ChromeError::LaunchFailed(format!(
+                "Chrome exited with status {status} on startup"
+            )));
+        }
+        return Ok(process);
+    }
+
     // Poll until Chrome is ready or timeout
     let start = tokio::time::Instant::now();
     let poll_interval = Duration::from_millis(100);
@@ -294,6 +373,7 @@ mod tests {
             headless: false,
             extra_args: vec![],
             user_data_dir: None,
+            pipe: false,
         }
     }
 
@@ -324,6 +404,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pipe_launch_replaces_debugging_port() {
+        let mut config = default_launch_config(9222);
+        config.pipe = true;
+        let data_dir = PathBuf::from("/tmp/test-data");
+        let args = build_chrome_args(&config, &data_dir);
+        assert!(
+            args.iter().any(|a| a == "--remote-debugging-pipe"),
+            "Expected --remote-debugging-pipe in args: {args:?}"
+        );
+        assert!(
+            !args
+                .iter()
+                .any(|a| a.starts_with("--remote-debugging-port")),
+            "Did not expect a debugging port in args: {args:?}"
+        );
+    }
+
     #[test]
     fn extra_args_do_not_conflict_with_automation_flag() {
         let mut config = default_launch_config(9222);
@@ -392,6 +490,8 @@ mod tests {
             child: None,
             port: 9222,
             temp_dir: Some(TempDir { path: path.clone() }),
+            #[cfg(unix)]
+            pipe: None,
         };
 
         let (_pid, port) = process.detach();
@@ -3,13 +3,16 @@
 mod discovery;
 mod error;
 mod launcher;
+mod pipe;
 mod platform;
 
 pub use discovery::{
-    BrowserVersion, TargetInfo, activate_target, discover_chrome, query_targets, query_version,
-    read_devtools_active_port, read_devtools_active_port_from,
+    BrowserVersion, PIPE_PORT, TargetInfo, activate_target, discover_chrome, query_targets,
+    query_version, read_devtools_active_port, read_devtools_active_port_from,
 };
 pub use error::ChromeError;
+#[cfg(unix)]
+pub use launcher::DebugPipe;
 pub use launcher::{ChromeProcess, LaunchConfig, find_available_port, launch_chrome};
 pub use platform::{
     Channel, ProbeResult, default_user_data_dir, find_chrome_executable, is_process_alive,
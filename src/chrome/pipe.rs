@@ -0,0 +1,111 @@
+//! Discovery for a Chrome launched with `connect --launch --pipe`.
+//!
+//! Such a browser has no HTTP endpoint: the daemon holds its
+//! `--remote-debugging-pipe` connection, so the `/json/*` queries are answered
+//! with the equivalent CDP commands sent through the daemon socket.
+
+use std::time::Duration;
+
+use super::ChromeError;
+use super::discovery::{BrowserVersion, TargetInfo};
+use crate::cdp::{CdpClient, CdpConfig};
+
+/// Connect to the daemon holding the pipe.
+async fn daemon_client() -> Result<(CdpClient, String), ChromeError> {
+    let record = crate::daemon::running_daemon().ok_or_else(|| {
+        ChromeError::NotRunning("the daemon holding the debugging pipe has exited".into())
+    })?;
+    let url = record.url();
+    let config = CdpConfig {
+        connect_timeout: Duration::from_secs(2),
+        command_timeout: Duration::from_secs(5),
+        ..CdpConfig::default()
+    };
+    let client = CdpClient::connect(&url, config)
+        .await
+        .map_err(|e| ChromeError::NotRunning(e.to_string()))?;
+    Ok((client, url))
+}
+
+async fn send(
+    client: &CdpClient,
+    method: &str,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, ChromeError> {
+    client
+        .send_command(method, params)
+        .await
+        .map_err(|e| ChromeError::NotRunning(format!("{method}: {e}")))
+}
+
+/// `/json/version` equivalent. The reported WebSocket URL is the daemon's.
+pub(super) async fn query_version() -> Result<BrowserVersion, ChromeError> {
+    let (client, url) = daemon_client().await?;
+    let version = send(&client, "Browser.getVersion", None).await?;
+    Ok(BrowserVersion {
+        browser: version["product"].as_str().unwrap_or_default().to_string(),
+        protocol_version: version["protocolVersion"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        ws_debugger_url: url,
+    })
+}
+
+/// `/json/list` equivalent.
+pub(super) async fn query_targets() -> Result<Vec<TargetInfo>, ChromeError> {
+    let (client, _) = daemon_client().await?;
+    let result = send(&client, "Target.getTargets", None).await?;
+    Ok(targets_from_cdp(&result))
+}
+
+/// `/json/activate/{id}` equivalent.
+pub(super) async fn activate_target(target_id: &str) -> Result<(), ChromeError> {
+    let (client, _) = daemon_client().await?;
+    let params = serde_json::json!({ "targetId": target_id });
+    send(&client, "Target.activateTarget", Some(params))
+        .await
+        .map(|_| ())
+}
+
+/// Map a `Target.getTargets` result onto `/json/list` entries.
+fn targets_from_cdp(result: &serde_json::Value) -> Vec<TargetInfo> {
+    result["targetInfos"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| {
+            Some(TargetInfo {
+                id: t["targetId"].as_str()?.to_string(),
+                target_type: t["type"].as_str().unwrap_or_default().to_string(),
+                title: t["title"].as_str().unwrap_or_default().to_string(),
+                url: t["url"].as_str().unwrap_or_default().to_string(),
+                ws_debugger_url: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_from_cdp_maps_target_infos() {
+        let result = serde_json::json!({
+            "targetInfos": [
+                {"targetId": "T1", "type": "page", "title": "Example", "url": "https://example.com/"},
+                {"type": "page"},
+                {"targetId": "W1", "type": "service_worker", "url": "https://example.com/sw.js"}
+            ]
+        });
+        let targets = targets_from_cdp(&result);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].id, "T1");
+        assert_eq!(targets[0].target_type, "page");
+        assert_eq!(targets[0].title, "Example");
+        assert!(targets[0].ws_debugger_url.is_none());
+        assert_eq!(targets[1].target_type, "service_worker");
+        assert_eq!(targets[1].title, "");
+    }
+}
@@ -74,7 +74,8 @@ ENVIRONMENT VARIABLES:
   AGENTCHROME_PORT     CDP port number (default: 9222)
   AGENTCHROME_HOST     CDP host address (default: 127.0.0.1)
   AGENTCHROME_TIMEOUT  Default command timeout in milliseconds
-  AGENTCHROME_CONFIG   Path to configuration file",
+  AGENTCHROME_CONFIG   Path to configuration file
+  AGENTCHROME_SESSION  Named session for connection and state files",
     term_width = 100
 )]
 pub struct Cli {
@@ -124,6 +125,16 @@ pub struct GlobalOpts {
     #[arg(long, global = true, env = "AGENTCHROME_CONFIG")]
     pub config: Option<PathBuf>,
 
+    /// Named session whose connection and state files to use [default: default]
+    #[arg(
+        long,
+        global = true,
+        env = "AGENTCHROME_SESSION",
+        value_name = "NAME",
+        value_parser = parse_session_name
+    )]
+    pub session: Option<String>,
+
     /// WebSocket keep-alive interval in milliseconds (default: 30000; 0 disables)
     #[arg(
         long = "keepalive-interval",
@@ -174,6 +185,22 @@ pub struct OutputFormat {
     pub large_response_threshold: Option<usize>,
 }
 
+/// Session names become directory names under `~/.agentchrome/sessions/`, so
+/// they are limited to a portable, traversal-free character set.
+fn parse_session_name(s: &str) -> Result<String, String> {
+    let valid_chars = s
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if s.is_empty() || s.len() > 64 || s.starts_with('.') || !valid_chars {
+        return Err(
+            "session names are 1-64 characters of letters, digits, '-', '_' or '.', \
+             and must not start with '.'"
+                .to_string(),
+        );
+    }
+    Ok(s.to_string())
+}
+
 fn parse_nonzero_usize(s: &str) -> Result<usize, String> {
     let val: usize = s.parse().map_err(|e| format!("{e}"))?;
     if val == 0 {
@@ -219,6 +246,19 @@ SESSION FILE:
     Unix:    ~/.agentchrome/session.json
     Windows: %USERPROFILE%\\.agentchrome\\session.json
 
+PIPE TRANSPORT:
+  --launch --pipe starts Chrome with --remote-debugging-pipe, so no TCP port
+  is opened for other local processes to hijack. The pipe cannot outlive
+  this command, so it is handed to a background daemon (see 'agentchrome
+  daemon') that serves every later command. 'daemon stop' closes the
+  browser. Unix only.
+
+NAMED SESSIONS:
+  --session <NAME> (or AGENTCHROME_SESSION) keeps a separate session file and
+  separate state (snapshot UIDs, emulation, routes, daemon) per name under
+  ~/.agentchrome/sessions/<NAME>/, so one machine can drive several Chrome
+  instances. Without it, the 'default' session uses ~/.agentchrome/.
+
 RESOLUTION PRECEDENCE (highest → lowest):
   1. --ws-url
   2. --port
@@ -233,6 +273,9 @@ EXAMPLES:
   # Launch a new headless Chrome instance
   agentchrome connect --launch --headless
 
+  # Launch without opening a debugging port; a daemon holds the pipe
+  agentchrome connect --launch --headless --pipe
+
   # Cross-invocation auto-discovery (no flags in shell B):
   #   shell A
   agentchrome connect --launch --headless
@@ -248,6 +291,13 @@ EXAMPLES:
   # Disconnect and remove session file
   agentchrome connect --disconnect
 
+  # Drive a second Chrome under its own session
+  agentchrome --session agent-b connect --launch --headless
+  agentchrome --session agent-b tabs list
+
+  # List sessions and their connections
+  agentchrome connect --list-sessions
+
   # Run a long command with a custom keep-alive interval
   agentchrome --keepalive-interval 60000 console follow
 
@@ -256,6 +306,27 @@ EXAMPLES:
     )]
     Connect(ConnectArgs),
 
+    /// Background process that keeps the CDP connection and event history alive
+    #[command(
+        long_about = "Run a background daemon that holds the Chrome DevTools Protocol connection \
+            between invocations. The daemon attaches to every page, buffers console, network, \
+            dialog and target events per tab, and serves other agentchrome commands over a Unix \
+            socket. While it runs, 'network list' reads passive history instead of reloading the \
+            page, 'console read' sees messages from before the command started, and each command \
+            skips the WebSocket handshake to Chrome. Unix only.",
+        after_long_help = "\
+EXAMPLES:
+  # Start the daemon against the current session
+  agentchrome daemon start
+
+  # Check whether the daemon is running and what it has buffered
+  agentchrome daemon status
+
+  # Stop the daemon
+  agentchrome daemon stop"
+    )]
+    Daemon(DaemonArgs),
+
     /// Tab management (list, create, close, activate)
     #[command(
         long_about = "Tab management commands: list open tabs, create new tabs, close tabs, and \
@@ -277,6 +348,26 @@ EXAMPLES:
     )]
     Tabs(TabsArgs),
 
+    /// Isolated browser contexts (create, list, dispose)
+    #[command(
+        long_about = "Manage isolated browser contexts. Each context is an incognito-like \
+            sandbox with its own cookies, storage, and cache inside the same Chrome process, \
+            so parallel agents can share one browser without sharing state. Open tabs in a \
+            context with 'tabs create --context <ID>' and address them with --page-id.",
+        after_long_help = "\
+EXAMPLES:
+  # Create a context and open a tab in it
+  agentchrome context create
+  agentchrome tabs create https://example.com --context <CONTEXT_ID>
+
+  # List contexts and their tabs
+  agentchrome context list
+
+  # Dispose a context and close its tabs
+  agentchrome context dispose <CONTEXT_ID>"
+    )]
+    Context(ContextArgs),
+
     /// URL navigation and history
     #[command(
         long_about = "Navigate to URLs, reload pages, go back/forward in history, and wait for \
@@ -353,7 +444,8 @@ EXAMPLES:
     #[command(
         long_about = "Execute JavaScript expressions or scripts in the page context. Returns \
             the result as structured JSON. Supports both synchronous expressions and async \
-            functions.",
+            functions. 'js inject' registers preload scripts that run before the page's own \
+            scripts on every navigation.",
         after_long_help = "\
 EXAMPLES:
   # Get the page title
@@ -366,7 +458,10 @@ EXAMPLES:
   agentchrome js exec --uid s3 \"(el) => el.textContent\"
 
   # Read from stdin
-  echo 'document.URL' | agentchrome js exec -"
+  echo 'document.URL' | agentchrome js exec -
+
+  # Run a shim on every page load in this tab
+  agentchrome js inject add --file shim.js"
     )]
     Js(JsArgs),
 
@@ -393,8 +488,9 @@ EXAMPLES:
     /// Network request monitoring and interception
     #[command(
         long_about = "Monitor and intercept network requests. List recent requests, filter by \
-            URL pattern or resource type, capture request/response bodies, and stream requests \
-            in real time.",
+            URL pattern or resource type, capture request/response bodies, stream requests \
+            in real time, export traffic as a HAR file and replay it, and define routes that \
+            mock, abort, or rewrite matching requests.",
         after_long_help = "\
 EXAMPLES:
   # List recent network requests
@@ -407,7 +503,13 @@ EXAMPLES:
   agentchrome network get 42
 
   # Stream network requests in real time
-  agentchrome network follow --url api.example.com"
+  agentchrome network follow --url api.example.com
+
+  # Export traffic for a HAR viewer
+  agentchrome network har --file session.har
+
+  # Stub an API endpoint with a canned error
+  agentchrome network route add --url-pattern '*/api/users*' --status 500"
     )]
     Network(NetworkArgs),
 
@@ -507,12 +609,13 @@ EXAMPLES:
     )]
     Perf(PerfArgs),
 
-    /// Browser cookie management (list, set, delete, clear)
+    /// Browser cookie management (list, set, delete, clear, export, import)
     #[command(
         long_about = "Manage browser cookies via the Chrome DevTools Protocol. List cookies \
             for the current page or all cookies, set new cookies with optional flags, delete \
-            specific cookies by name, or clear all cookies. Provides full access to HttpOnly \
-            and Secure cookies that are not accessible via document.cookie.",
+            specific cookies by name, clear all cookies, or export and import them in bulk as \
+            JSON or Netscape cookies.txt. Provides full access to HttpOnly and Secure cookies \
+            that are not accessible via document.cookie.",
         after_long_help = "\
 EXAMPLES:
   # List cookies for the current page
@@ -534,10 +637,67 @@ EXAMPLES:
   agentchrome cookie delete session_id --domain example.com
 
   # Clear all cookies
-  agentchrome cookie clear"
+  agentchrome cookie clear
+
+  # Export cookies for curl, and import them back
+  agentchrome cookie export --format netscape --file cookies.txt
+  agentchrome cookie import --file cookies.txt"
     )]
     Cookie(CookieArgs),
 
+    /// Web storage management (localStorage, sessionStorage, IndexedDB)
+    #[command(
+        long_about = "Inspect and seed web storage via the DOMStorage and IndexedDB domains of \
+            the Chrome DevTools Protocol. 'storage local' works on localStorage and 'storage \
+            session' on sessionStorage; both offer list, get, set, remove, and clear. 'storage \
+            idb' lists IndexedDB databases and object stores, dumps records, and clears \
+            stores. Storage is keyed by \
+            origin: by default the security origin of the targeted frame is used (the main frame, \
+            or the frame chosen with --frame), and --origin names one explicitly. Unlike \
+            'js exec' snippets, this reaches cross-origin iframes.",
+        after_long_help = "\
+EXAMPLES:
+  # List localStorage for the current page's origin
+  agentchrome storage local list
+
+  # Read a single sessionStorage value
+  agentchrome storage session get cart
+
+  # Seed a localStorage value for a specific origin
+  agentchrome storage local set theme dark --origin https://example.com
+
+  # Remove a key from an iframe's localStorage
+  agentchrome storage --frame 1 local remove token
+
+  # Clear sessionStorage
+  agentchrome storage session clear
+
+  # Dump an IndexedDB object store
+  agentchrome storage idb dump app-db todos --limit 20"
+    )]
+    Storage(StorageArgs),
+
+    /// Save and restore authentication state (cookies and web storage)
+    #[command(
+        long_about = "Save the browser's cookies plus the localStorage and sessionStorage of \
+            each origin to a JSON file, and restore them later so a logged-in state can be \
+            reused instead of logging in on every run. The file uses Playwright's storageState \
+            format ({cookies, origins: [{origin, localStorage}]}), with sessionStorage as an \
+            extra per-origin array, so fixtures can be shared with Playwright suites.",
+        after_long_help = "\
+EXAMPLES:
+  # Save cookies and storage of every origin in the current page
+  agentchrome state save --file auth.json
+
+  # Save storage for specific origins only
+  agentchrome state save --file auth.json --origins https://app.example.com,https://sso.example.com
+
+  # Restore state before navigating
+  agentchrome state load --file auth.json
+  agentchrome navigate https://app.example.com"
+    )]
+    State(StateArgs),
+
     /// Browser dialog handling (alert, confirm, prompt, beforeunload)
     #[command(
         long_about = "Detect and handle browser JavaScript dialogs (alert, confirm, prompt, \
@@ -686,6 +846,28 @@ EXAMPLES:
     )]
     Markdown(MarkdownArgs),
 
+    /// Raw Chrome DevTools Protocol passthrough (send, listen)
+    #[command(
+        long_about = "Send arbitrary Chrome DevTools Protocol commands and stream arbitrary \
+            events, for domains agentchrome does not wrap yet (Animation, Security, WebAuthn, \
+            ...). Commands run in a session attached to the tab selected by --tab/--page-id, or \
+            on the browser connection with --browser. Protocol errors exit with code 5.",
+        after_long_help = "\
+EXAMPLES:
+  # Call a CDP method on the current tab
+  agentchrome cdp send Security.enable
+
+  # Pass parameters as a JSON object
+  agentchrome cdp send Animation.setPlaybackRate '{\"playbackRate\":0.5}'
+
+  # Call a browser-level method
+  agentchrome cdp send --browser Browser.getVersion
+
+  # Stream two events for 5 seconds as NDJSON
+  agentchrome cdp listen Animation.animationStarted Animation.animationCanceled --timeout 5000"
+    )]
+    Cdp(CdpArgs),
+
     /// Agentic tool skill installation and management
     #[command(
         long_about = "Install, update, uninstall, or list agentchrome skill files for agentic \
@@ -973,8 +1155,9 @@ pub enum TabsCommand {
     /// List open tabs
     #[command(
         long_about = "List all open browser tabs. Returns JSON with each tab's ID, title, URL, \
-            and type. By default, only page tabs are shown; use --all to include internal \
-            Chrome pages (chrome://, chrome-extension://).",
+            and type, plus the browser context for tabs outside the default context. By \
+            default, only page tabs are shown; use --all to include internal Chrome pages \
+            (chrome://, chrome-extension://).",
         after_long_help = "\
 EXAMPLES:
   # List page tabs
@@ -989,7 +1172,8 @@ EXAMPLES:
     #[command(
         long_about = "Create a new browser tab. Optionally specify a URL to open; defaults to \
             about:blank. Returns JSON with the new tab's ID and URL. Use --background to open \
-            the tab without switching focus to it.",
+            the tab without switching focus to it, and --context to open it in a browser \
+            context created with 'context create'.",
         after_long_help = "\
 EXAMPLES:
   # Open a blank tab
@@ -999,7 +1183,10 @@ EXAMPLES:
   agentchrome tabs create https://example.com
 
   # Open in the background
-  agentchrome tabs create https://example.com --background"
+  agentchrome tabs create https://example.com --background
+
+  # Open in an isolated browser context
+  agentchrome tabs create https://example.com --context <CONTEXT_ID>"
     )]
     Create(TabsCreateArgs),
 
@@ -1051,6 +1238,10 @@ pub struct TabsCreateArgs {
     /// Open the tab in the background without activating it
     #[arg(long)]
     pub background: bool,
+
+    /// Browser context to open the tab in (see 'context create')
+    #[arg(long, value_name = "ID")]
+    pub context: Option<String>,
 }
 
 /// Arguments for `tabs close`.
@@ -1074,6 +1265,59 @@ pub struct TabsActivateArgs {
     pub quiet: bool,
 }
 
+/// Arguments for the `context` subcommand group.
+#[derive(Args)]
+pub struct ContextArgs {
+    #[command(subcommand)]
+    pub command: ContextCommand,
+}
+
+/// Browser context subcommands.
+#[derive(Subcommand)]
+pub enum ContextCommand {
+    /// Create an isolated browser context
+    #[command(
+        long_about = "Create a new browser context with its own cookies, storage, and cache. \
+            Returns JSON with the context ID. The context lasts until it is disposed or \
+            Chrome exits.",
+        after_long_help = "\
+EXAMPLES:
+  # Create a context
+  agentchrome context create"
+    )]
+    Create,
+
+    /// List browser contexts and their tabs
+    #[command(
+        long_about = "List the browser contexts besides the default one. Returns a JSON array \
+            with each context's ID and the IDs of the tabs open in it.",
+        after_long_help = "\
+EXAMPLES:
+  # List contexts
+  agentchrome context list"
+    )]
+    List,
+
+    /// Dispose a browser context and close its tabs
+    #[command(
+        long_about = "Dispose a browser context. Chrome closes every tab in the context and \
+            discards its cookies and storage. Returns JSON with the disposed context ID and \
+            the tabs that were closed.",
+        after_long_help = "\
+EXAMPLES:
+  # Dispose a context
+  agentchrome context dispose 6A1F2B3C4D5E6F708192A3B4C5D6E7F8"
+    )]
+    Dispose(ContextDisposeArgs),
+}
+
+/// Arguments for `context dispose`.
+#[derive(Args)]
+pub struct ContextDisposeArgs {
+    /// Browser context ID (as printed by 'context create' or 'context list')
+    pub id: String,
+}
+
 /// Arguments for the `navigate` subcommand group.
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
@@ -1192,8 +1436,15 @@ EXAMPLES:
     #[command(
         long_about = "Capture the accessibility tree (AX tree) of the current page. Each \
             interactive element is assigned a UID (e.g., s1, s2, s3) that can be used with \
-            'interact', 'form', and 'js exec --uid' commands. Use --verbose to include \
-            additional properties like checked, disabled, and level.",
+            'interact', 'form', and 'js exec --uid' commands. An element keeps its UID across \
+            snapshots while it exists; using the UID of an element that has disappeared fails \
+            with a stale-uid error naming its last known role and name. Use --verbose to include \
+            additional properties like checked, disabled, and level. Use --diff to return only \
+            the nodes added, removed, or changed (role, name, value, state) since the previous \
+            snapshot, keyed by backend DOM node identity. Use --update-golden to record the \
+            compacted tree (roles and names) as YAML and --assert to compare against it later; \
+            --ignore-name and --ignore-order relax the comparison, and any mismatch exits \
+            non-zero with a report of each missing, unexpected, or renamed node.",
         after_long_help = "\
 EXAMPLES:
   # Capture the accessibility tree
@@ -1203,7 +1454,14 @@ EXAMPLES:
   agentchrome page snapshot --verbose
 
   # Save to a file
-  agentchrome page snapshot --file snapshot.txt"
+  agentchrome page snapshot --file snapshot.txt
+
+  # Only what changed since the previous snapshot
+  agentchrome page snapshot --diff
+
+  # Record a golden, then check the page against it in CI
+  agentchrome page snapshot --update-golden golden.yaml
+  agentchrome page snapshot --assert golden.yaml --ignore-name \"^Updated \""
     )]
     Snapshot(PageSnapshotArgs),
 
@@ -1235,7 +1493,13 @@ EXAMPLES:
             viewport region. Supports PNG (default), JPEG, and WebP formats. Use --full-page \
             to capture the entire scrollable page, --selector or --uid to capture a specific \
             element, or --clip to capture a region. Note: --full-page conflicts with \
-            --selector, --uid, and --clip.",
+            --selector, --uid, and --clip. Use --compare to diff the capture against a baseline \
+            PNG: pixels are compared with a small colour tolerance, anti-aliasing differences \
+            are ignored, and the command exits non-zero when the mismatched-pixel ratio exceeds \
+            --threshold. --diff-out writes an image with mismatches highlighted in red. Use \
+            --annotate uids to label every interactive element from the latest snapshot with its \
+            UID (main-frame elements only), or --annotate grid to overlay a labelled coordinate grid; the output then \
+            includes a legend mapping each label to its UID, role, name, and bounding box.",
         after_long_help = "\
 EXAMPLES:
   # Screenshot the visible viewport
@@ -1248,7 +1512,18 @@ EXAMPLES:
   agentchrome page screenshot --uid s3 --file element.png
 
   # JPEG format with quality
-  agentchrome page screenshot --format jpeg --quality 80 --file shot.jpg"
+  agentchrome page screenshot --format jpeg --quality 80 --file shot.jpg
+
+  # Label interactive elements with their snapshot UIDs
+  agentchrome page snapshot
+  agentchrome page screenshot --annotate uids --file marked.png
+
+  # Overlay a coordinate grid on a canvas-heavy page
+  agentchrome page screenshot --annotate grid --file grid.png
+
+  # Compare an element against a baseline, tolerating 1% of pixels
+  agentchrome page screenshot --selector \"#header\" --compare header.png --threshold 0.01 \\
+    --diff-out header-diff.png"
     )]
     Screenshot(PageScreenshotArgs),
 
@@ -1405,6 +1680,160 @@ EXAMPLES:
   agentchrome page coords --selector s7"
     )]
     Coords(PageCoordsArgs),
+
+    /// Print the page to a PDF file
+    #[command(
+        long_about = "Print the current page to a PDF file using Chrome's print pipeline \
+            (Page.printToPDF). The document is streamed to disk in chunks, so large reports do \
+            not have to fit in a single protocol message. Paper size defaults to US Letter; use \
+            --paper for a named size or --paper-width/--paper-height for a custom one. Margins \
+            take one to four comma-separated values in CSS order (top, right, bottom, left) with \
+            an optional in, cm, mm, or px unit (default in). Header and footer templates are \
+            HTML that may use the classes date, title, url, pageNumber, and totalPages.",
+        after_long_help = "\
+EXAMPLES:
+  # Save the page as a PDF
+  agentchrome page pdf --file out.pdf
+
+  # A4 landscape with backgrounds and 1cm margins
+  agentchrome page pdf --file report.pdf --paper a4 --landscape --print-background --margin 1cm
+
+  # Only pages 1-3 with a page-number footer
+  agentchrome page pdf --file invoice.pdf --page-ranges 1-3 \\
+    --footer-template \"<div style='font-size:8px;margin:auto'><span class='pageNumber'></span></div>\"
+
+  # Let the page's CSS @page rule decide the paper size
+  agentchrome page pdf --file out.pdf --prefer-css-page-size"
+    )]
+    Pdf(PagePdfArgs),
+
+    /// Save the page as a single-file MHTML archive, or a bug-report bundle
+    #[command(
+        long_about = "Save the current page as a single-file MHTML archive (Page.captureSnapshot) \
+            that opens in Chrome with its styles, images, and frames inlined. With --bundle, \
+            write a directory instead, holding the MHTML (page.mhtml), the accessibility \
+            snapshot as text and JSON (snapshot.txt, snapshot.json), a viewport screenshot \
+            (screenshot.png), the console messages (console.json), the network request summary \
+            (network.json), and a manifest.json listing the page URL, title, capture time, and \
+            each artifact. Only the MHTML is required; an artifact that cannot be captured is \
+            recorded with its error in the manifest. The network summary comes from the daemon's \
+            event history; without the daemon it is skipped and recorded as an error, since \
+            capturing traffic would reload the page. Reusing a bundle directory first removes \
+            the previous bundle's files; other files are left alone.",
+        after_long_help = "\
+EXAMPLES:
+  # Save the page as MHTML
+  agentchrome page archive --file page.mhtml
+
+  # Write a reproducible bug-report bundle
+  agentchrome page archive --bundle ./bug-1234"
+    )]
+    Archive(PageArchiveArgs),
+
+    /// Record the page as a screencast while commands run
+    #[command(
+        long_about = "Record what happens in the current tab with Page.startScreencast. \
+            'page record start' launches a background recorder that saves each frame to the \
+            recording directory; 'page record stop' ends it, assembles the frames into an \
+            animated PNG (recording.png), and writes timeline.json with each frame's timestamp \
+            plus a marker for every 'interact' and 'form' command run while recording.",
+        after_long_help = "\
+EXAMPLES:
+  # Start recording at up to 5 frames per second
+  agentchrome page record start --dir frames/ --max-fps 5
+
+  # ... run interact/form commands ...
+
+  # Stop and assemble recording.png and timeline.json
+  agentchrome page record stop"
+    )]
+    Record(PageRecordArgs),
+
+    /// Extract data tables as JSON rows or CSV
+    #[command(
+        long_about = "Extract the data tables of the current page, or of the element matched by \
+            --selector, as structured rows. Layout tables (no header cells and a single row or \
+            column, or role=presentation) are skipped unless --selector names the table itself. \
+            colspan and rowspan are expanded so every row has a value for every column. Header \
+            rows come from <thead>, or from leading rows made only of <th> cells; stacked header \
+            rows are joined with ' / '. A key/value table with header cells down its first column \
+            and no header row is turned so each value column becomes one row. Blank headers are \
+            named column_N and repeated ones get a _2, _3 suffix. JSON output lists each table \
+            with its document index, caption, headers, and one object per row keyed by header \
+            text; --format csv writes each table as CSV, separated by a blank line.",
+        after_long_help = "\
+EXAMPLES:
+  # Every data table on the page as JSON
+  agentchrome page tables
+
+  # One table as CSV
+  agentchrome page tables --selector \"#prices\" --format csv > prices.csv
+
+  # Tables inside a section, from an iframe
+  agentchrome page --frame 1 tables --selector main"
+    )]
+    Tables(PageTablesArgs),
+
+    /// Extract typed data described by a JSON schema
+    #[command(
+        long_about = "Extract structured data from the page with a JSON schema instead of a \
+            bespoke 'js exec' scraper. The schema is an object mapping output field names to a \
+            selector string, or to an object with: selector (CSS, 'css:' CSS, a snapshot UID \
+            such as s7, or role:ROLE / role:ROLE[name=\"NAME\"] matched against the \
+            accessibility tree), attribute (read an attribute instead of the text), type \
+            (string, number, integer, boolean, date, or url), list (collect every match), \
+            required, fields (nested fields read inside each matched element), and frame (a \
+            --frame value for a top-level field: index, path such as 1/0, or auto). Numbers \
+            take the first number in the value, dates are normalized to ISO 8601, and urls are \
+            resolved against the document base URL; a url field without an attribute reads \
+            href, then src. Returns JSON with the typed data and an errors array naming each \
+            field path that was missing or could not be converted. If a required field fails, \
+            the command exits nonzero with the same report on stderr.",
+        after_long_help = "\
+EXAMPLES:
+  # Extract fields described in schema.json
+  agentchrome page extract --schema schema.json
+
+  # Example schema
+  {
+    \"title\": \"h1\",
+    \"price\": { \"selector\": \".price\", \"type\": \"number\", \"required\": true },
+    \"next\": { \"selector\": \"role:link[name=Next]\", \"type\": \"url\" },
+    \"reviews\": { \"selector\": \".review\", \"list\": true, \"fields\": {
+      \"author\": \".author\",
+      \"date\": { \"selector\": \"time\", \"type\": \"date\" } } },
+    \"ad\": { \"selector\": \"#ad-text\", \"frame\": \"1\" }
+  }"
+    )]
+    Extract(PageExtractArgs),
+}
+
+/// Named paper sizes for `page pdf`.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    /// 8.5 x 11 in (default)
+    #[default]
+    Letter,
+    /// 8.5 x 14 in
+    Legal,
+    /// 11 x 17 in
+    Tabloid,
+    /// 17 x 11 in
+    Ledger,
+    /// 841 x 1189 mm
+    A0,
+    /// 594 x 841 mm
+    A1,
+    /// 420 x 594 mm
+    A2,
+    /// 297 x 420 mm
+    A3,
+    /// 210 x 297 mm
+    A4,
+    /// 148 x 210 mm
+    A5,
+    /// 105 x 148 mm
+    A6,
 }
 
 /// Image format for screenshots.
@@ -1419,6 +1848,15 @@ pub enum ScreenshotFormat {
     Webp,
 }
 
+/// Overlay drawn by `page screenshot --annotate`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AnnotateMode {
+    /// Boxes and sN labels for the interactive elements of the latest snapshot
+    Uids,
+    /// Labelled 100px coordinate grid
+    Grid,
+}
+
 /// Arguments for `page screenshot`.
 #[derive(Args)]
 pub struct PageScreenshotArgs {
@@ -1453,6 +1891,22 @@ pub struct PageScreenshotArgs {
     /// Capture a specific viewport region as X,Y,WIDTH,HEIGHT (e.g. 10,20,200,100)
     #[arg(long)]
     pub clip: Option<String>,
+
+    /// Compare the capture against a baseline PNG; exits non-zero above --threshold
+    #[arg(long, value_name = "BASELINE")]
+    pub compare: Option<PathBuf>,
+
+    /// Largest tolerated fraction of mismatched pixels, 0.0-1.0 [default: 0]
+    #[arg(long, value_name = "RATIO", requires = "compare")]
+    pub threshold: Option<f64>,
+
+    /// Write an image highlighting mismatched pixels in red (requires --compare)
+    #[arg(long, value_name = "FILE", requires = "compare")]
+    pub diff_out: Option<PathBuf>,
+
+    /// Draw UID boxes or a coordinate grid onto the capture and return a legend [possible values: uids, grid]
+    #[arg(long, value_enum, conflicts_with_all = ["full_page", "compare"])]
+    pub annotate: Option<AnnotateMode>,
 }
 
 /// Arguments for `page text`.
@@ -1490,6 +1944,26 @@ pub struct PageSnapshotArgs {
     /// Aggregate every iframe's accessibility tree into a single tree (mutually exclusive with --frame)
     #[arg(long)]
     pub include_iframes: bool,
+
+    /// Return only nodes added, removed, or changed since the previous snapshot
+    #[arg(long, conflicts_with_all = ["verbose", "compact"])]
+    pub diff: bool,
+
+    /// Compare the compacted tree against a golden YAML file; exits non-zero on mismatch
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["diff", "file", "update_golden"])]
+    pub assert: Option<PathBuf>,
+
+    /// Write the compacted tree to a golden YAML file for later --assert runs
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["diff", "file"])]
+    pub update_golden: Option<PathBuf>,
+
+    /// Ignore names matching this regex when asserting (repeatable)
+    #[arg(long, value_name = "REGEX", requires = "assert")]
+    pub ignore_name: Vec<String>,
+
+    /// Match sibling nodes regardless of order when asserting
+    #[arg(long, requires = "assert")]
+    pub ignore_order: bool,
 }
 
 /// Arguments for `page find`.
@@ -1661,6 +2135,119 @@ EXAMPLES:
   agentchrome js exec --no-await \"fetch('/api/data')\""
     )]
     Exec(JsExecArgs),
+
+    /// Manage preload scripts that run on every new document
+    #[command(
+        long_about = "Register scripts that run in each new document of the current tab before \
+            the page's own scripts, via Page.addScriptToEvaluateOnNewDocument. Scripts are saved \
+            per tab in the session state directory and re-registered on every connection, the \
+            same way emulation overrides are, so feature-flag and mock-fetch shims survive \
+            navigations.",
+        after_long_help = "\
+EXAMPLES:
+  # Inject a shim into the page's main world
+  agentchrome js inject add --file shim.js
+
+  # Inject into an isolated world under a custom name
+  agentchrome js inject add --file helpers.js --world isolated --name helpers
+
+  # List the current tab's scripts
+  agentchrome js inject list
+
+  # Remove a script
+  agentchrome js inject remove helpers"
+    )]
+    Inject(JsInjectArgs),
+}
+
+/// Arguments for the `js inject` subcommand group.
+#[derive(Args)]
+pub struct JsInjectArgs {
+    #[command(subcommand)]
+    pub command: JsInjectCommand,
+}
+
+/// Preload script subcommands.
+#[derive(Subcommand)]
+pub enum JsInjectCommand {
+    /// Add a preload script to the current tab
+    #[command(
+        long_about = "Read a script file and register it for the current tab. The script also \
+            runs once in the current document, then again in every document the tab loads. \
+            Adding a script under an existing name replaces it. Returns JSON with the script's \
+            name, world, source file, and tab.",
+        after_long_help = "\
+EXAMPLES:
+  # Named after the file ('shim')
+  agentchrome js inject add --file shim.js
+
+  # Keep page scripts from seeing the script's globals
+  agentchrome js inject add --file helpers.js --world isolated"
+    )]
+    Add(JsInjectAddArgs),
+
+    /// List the current tab's preload scripts
+    #[command(
+        long_about = "List the preload scripts saved for the current tab in registration order. \
+            Returns a JSON array with each script's name, world, source file, and size.",
+        after_long_help = "\
+EXAMPLES:
+  # List scripts
+  agentchrome js inject list"
+    )]
+    List,
+
+    /// Remove preload scripts from the current tab
+    #[command(
+        long_about = "Stop registering a preload script (or all of them with --all). Documents \
+            loaded afterwards no longer run it; the current document keeps whatever the script \
+            already did until the next navigation.",
+        after_long_help = "\
+EXAMPLES:
+  # Remove one script
+  agentchrome js inject remove shim
+
+  # Remove every script from the tab
+  agentchrome js inject remove --all"
+    )]
+    Remove(JsInjectRemoveArgs),
+}
+
+/// Arguments for `js inject add`.
+#[derive(Args)]
+pub struct JsInjectAddArgs {
+    /// Script file to inject
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// JavaScript world the script runs in
+    #[arg(long, value_enum, default_value_t = InjectWorld::Main)]
+    pub world: InjectWorld,
+
+    /// Name for the script (default: the file name without its extension)
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+/// Arguments for `js inject remove`.
+#[derive(Args)]
+pub struct JsInjectRemoveArgs {
+    /// Name of the script to remove
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub name: Option<String>,
+
+    /// Remove every script from the tab
+    #[arg(long)]
+    pub all: bool,
+}
+
+/// JavaScript world for `js inject add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InjectWorld {
+    /// The page's own world, sharing its globals
+    Main,
+    /// A separate world that shares the DOM but not JavaScript globals
+    Isolated,
 }
 
 /// Arguments for `js exec`.
@@ -1772,6 +2359,83 @@ EXAMPLES:
   agentchrome cookie clear"
     )]
     Clear,
+
+    /// Export all cookies to a file
+    #[command(
+        long_about = "Write every browser cookie (or those matching --domain) to a file. The \
+            json format is the array printed by 'cookie list'; the netscape format is the \
+            cookies.txt layout read by curl (-b) and wget (--load-cookies), with HttpOnly \
+            cookies marked by the '#HttpOnly_' domain prefix.",
+        after_long_help = "\
+EXAMPLES:
+  # Export cookies as JSON
+  agentchrome cookie export --file cookies.json
+
+  # Export cookies for curl
+  agentchrome cookie export --format netscape --file cookies.txt
+
+  # Export one site's cookies
+  agentchrome cookie export --domain example.com --file example.json"
+    )]
+    Export(CookieExportArgs),
+
+    /// Import cookies from a file
+    #[command(
+        long_about = "Set every cookie in a JSON or Netscape cookies.txt file with a single \
+            Network.setCookies call. JSON may be a cookie array (as written by 'cookie export' \
+            or 'cookie list') or an object with a 'cookies' array (Playwright storageState). \
+            The format is detected from the content unless --format is given. Each cookie is \
+            validated first (name and domain present, SameSite one of Strict/Lax/None, \
+            SameSite=None and partitioned cookies Secure); invalid or rejected cookies, and \
+            cookies.txt lines that cannot be parsed (with their line number), are reported \
+            under 'failed' and the rest are still imported. Host-only cookies (cookies.txt \
+            include-subdomains FALSE, or a domain without a leading dot) stay host-only.",
+        after_long_help = "\
+EXAMPLES:
+  # Import cookies saved by curl -c
+  agentchrome cookie import --file cookies.txt
+
+  # Import a JSON export
+  agentchrome cookie import --file cookies.json --format json"
+    )]
+    Import(CookieImportArgs),
+}
+
+/// Cookie file formats for `cookie export` and `cookie import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CookieFormat {
+    /// JSON array of cookie objects
+    Json,
+    /// Netscape cookies.txt (curl, wget)
+    Netscape,
+}
+
+/// Arguments for `cookie export`.
+#[derive(Args)]
+pub struct CookieExportArgs {
+    /// File format
+    #[arg(long, value_enum, default_value_t = CookieFormat::Json)]
+    pub format: CookieFormat,
+
+    /// File to write the cookies to
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// Only export cookies whose domain contains this value
+    #[arg(long)]
+    pub domain: Option<String>,
+}
+
+/// Arguments for `cookie import`.
+#[derive(Args)]
+pub struct CookieImportArgs {
+    /// Cookie file to import
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// File format (detected from the content when omitted)
+    #[arg(long, value_enum)]
+    pub format: Option<CookieFormat>,
 }
 
 /// Arguments for `cookie list`.
@@ -1834,6 +2498,351 @@ pub struct CookieDeleteArgs {
     pub domain: Option<String>,
 }
 
+/// Arguments for the `storage` subcommand group.
+#[derive(Args)]
+pub struct StorageArgs {
+    /// Target frame by index, path (1/0), or 'auto'
+    #[arg(long)]
+    pub frame: Option<String>,
+
+    #[command(subcommand)]
+    pub area: StorageArea,
+}
+
+/// Web storage areas.
+#[derive(Subcommand)]
+pub enum StorageArea {
+    /// Operate on localStorage
+    #[command(
+        long_about = "Operate on localStorage, which persists across sessions for an origin.",
+        after_long_help = "\
+EXAMPLES:
+  # List localStorage entries
+  agentchrome storage local list
+
+  # Set a value
+  agentchrome storage local set theme dark"
+    )]
+    Local(StorageAreaArgs),
+
+    /// Operate on sessionStorage
+    #[command(
+        long_about = "Operate on sessionStorage, which lives as long as the tab for an origin.",
+        after_long_help = "\
+EXAMPLES:
+  # List sessionStorage entries
+  agentchrome storage session list
+
+  # Read a value
+  agentchrome storage session get cart"
+    )]
+    Session(StorageAreaArgs),
+
+    /// Inspect IndexedDB databases
+    #[command(
+        long_about = "Inspect the IndexedDB databases of an origin: list databases, describe \
+            their object stores and indexes, dump records, and clear object stores. Record keys \
+            and values are returned as JSON; large dumps are written to a temp file.",
+        after_long_help = "\
+EXAMPLES:
+  # List databases
+  agentchrome storage idb databases
+
+  # Describe the object stores of a database
+  agentchrome storage idb stores app-db
+
+  # Dump the first 20 records of a store
+  agentchrome storage idb dump app-db todos --limit 20"
+    )]
+    Idb(StorageIdbArgs),
+}
+
+/// Arguments for `storage local` and `storage session`.
+#[derive(Args)]
+pub struct StorageAreaArgs {
+    #[command(subcommand)]
+    pub command: StorageCommand,
+}
+
+/// Web storage subcommands.
+#[derive(Subcommand)]
+pub enum StorageCommand {
+    /// List all key/value pairs
+    #[command(
+        long_about = "List every key/value pair stored for the origin. Output is a JSON array \
+            of {key, value} objects; plain output prints one 'key: value' line per entry.",
+        after_long_help = "\
+EXAMPLES:
+  # List localStorage for the current page
+  agentchrome storage local list
+
+  # List sessionStorage for another origin
+  agentchrome storage session list --origin https://accounts.example.com"
+    )]
+    List(StorageOriginArgs),
+
+    /// Get the value of a key
+    #[command(
+        long_about = "Get the value stored under a key. Exits with an error if the key is not \
+            set for the origin.",
+        after_long_help = "\
+EXAMPLES:
+  # Read a value
+  agentchrome storage local get theme
+
+  # Plain output prints only the value
+  agentchrome storage local get theme --plain"
+    )]
+    Get(StorageKeyArgs),
+
+    /// Set the value of a key
+    #[command(
+        long_about = "Set a key to a value, creating or replacing it.",
+        after_long_help = "\
+EXAMPLES:
+  # Seed a value
+  agentchrome storage local set theme dark
+
+  # Seed a JSON value for another origin
+  agentchrome storage session set cart '{\"items\":[]}' --origin https://shop.example.com"
+    )]
+    Set(StorageSetArgs),
+
+    /// Remove a key
+    #[command(
+        long_about = "Remove a key. Succeeds whether or not the key was set; 'removed' is false \
+            when it was not.",
+        after_long_help = "\
+EXAMPLES:
+  # Remove a key
+  agentchrome storage local remove token"
+    )]
+    Remove(StorageKeyArgs),
+
+    /// Remove every key
+    #[command(
+        long_about = "Remove every key stored for the origin. Returns the number of entries \
+            that were cleared.",
+        after_long_help = "\
+EXAMPLES:
+  # Clear sessionStorage
+  agentchrome storage session clear"
+    )]
+    Clear(StorageOriginArgs),
+}
+
+/// Arguments for the `state` subcommand group.
+#[derive(Args)]
+pub struct StateArgs {
+    #[command(subcommand)]
+    pub command: StateCommand,
+}
+
+/// State subcommands.
+#[derive(Subcommand)]
+pub enum StateCommand {
+    /// Save cookies and web storage to a file
+    #[command(
+        long_about = "Save all browser cookies plus localStorage and sessionStorage to a \
+            Playwright storageState JSON file. Storage is read for the origins of the frames in \
+            the current page, or for the origins given with --origins (each must be loaded in \
+            one of the page's frames). Origins without any stored items are omitted.",
+        after_long_help = "\
+EXAMPLES:
+  # Save state after logging in
+  agentchrome state save --file auth.json
+
+  # Limit storage to one origin
+  agentchrome state save --file auth.json --origins https://app.example.com"
+    )]
+    Save(StateSaveArgs),
+
+    /// Restore cookies and web storage from a file
+    #[command(
+        long_about = "Restore cookies and web storage from a Playwright storageState JSON file. \
+            Cookies are validated and set as with 'cookie import'; cookies the browser does \
+            not accept are reported under 'failed' and the rest are still set. Storage is seeded by loading each \
+            origin in the current tab with an intercepted, empty document (no request reaches \
+            the site), after which the tab returns to its previous URL. Run it before \
+            navigating to the application.",
+        after_long_help = "\
+EXAMPLES:
+  # Restore a saved login, then open the app
+  agentchrome state load --file auth.json
+  agentchrome navigate https://app.example.com"
+    )]
+    Load(StateLoadArgs),
+}
+
+/// Arguments for `state save`.
+#[derive(Args)]
+pub struct StateSaveArgs {
+    /// File to write the state to
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// Origins whose storage to save (comma-separated; defaults to the page's frame origins)
+    #[arg(long, value_delimiter = ',')]
+    pub origins: Vec<String>,
+}
+
+/// Arguments for `state load`.
+#[derive(Args)]
+pub struct StateLoadArgs {
+    /// State file to restore
+    #[arg(long)]
+    pub file: PathBuf,
+}
+
+/// Arguments for `storage idb`.
+#[derive(Args)]
+pub struct StorageIdbArgs {
+    #[command(subcommand)]
+    pub command: StorageIdbCommand,
+}
+
+/// IndexedDB subcommands.
+#[derive(Subcommand)]
+pub enum StorageIdbCommand {
+    /// List the IndexedDB databases of an origin
+    #[command(
+        long_about = "List the names of the IndexedDB databases stored for the origin.",
+        after_long_help = "\
+EXAMPLES:
+  # List databases for the current page
+  agentchrome storage idb databases
+
+  # List databases for another origin
+  agentchrome storage idb databases --origin https://app.example.com"
+    )]
+    Databases(StorageOriginArgs),
+
+    /// Describe the object stores of a database
+    #[command(
+        long_about = "Describe a database: its version and, for each object store, the key \
+            path, whether keys auto-increment, and its indexes.",
+        after_long_help = "\
+EXAMPLES:
+  # Describe a database
+  agentchrome storage idb stores app-db"
+    )]
+    Stores(StorageIdbDatabaseArgs),
+
+    /// Dump the records of an object store
+    #[command(
+        long_about = "Dump records of an object store in key order as {key, primaryKey, value} \
+            objects, with 'hasMore' set when the limit cut the dump short. --key-range \
+            restricts keys to an inclusive LOWER..UPPER range; either bound may be omitted, and \
+            bounds that parse as numbers are numeric keys. Output above the large-response \
+            threshold is written to a temp file.",
+        after_long_help = "\
+EXAMPLES:
+  # Dump the first 100 records
+  agentchrome storage idb dump app-db todos
+
+  # Dump numeric keys 10 through 20
+  agentchrome storage idb dump app-db todos --key-range 10..20
+
+  # Dump string keys from \"m\" onwards
+  agentchrome storage idb dump app-db users --key-range m.. --limit 10"
+    )]
+    Dump(StorageIdbDumpArgs),
+
+    /// Clear the records of a database's object stores
+    #[command(
+        long_about = "Delete every record of an object store, or of every object store in the \
+            database when no store is named. The database and its schema are kept.",
+        after_long_help = "\
+EXAMPLES:
+  # Clear one object store
+  agentchrome storage idb clear app-db todos
+
+  # Clear every object store in a database
+  agentchrome storage idb clear app-db"
+    )]
+    Clear(StorageIdbClearArgs),
+}
+
+/// Arguments for `storage idb stores`.
+#[derive(Args)]
+pub struct StorageIdbDatabaseArgs {
+    /// Database name
+    pub database: String,
+
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// Arguments for `storage idb dump`.
+#[derive(Args)]
+pub struct StorageIdbDumpArgs {
+    /// Database name
+    pub database: String,
+
+    /// Object store name
+    pub store: String,
+
+    /// Maximum number of records to return
+    #[arg(long, default_value_t = 100)]
+    pub limit: u32,
+
+    /// Inclusive key range as LOWER..UPPER (either bound may be omitted)
+    #[arg(long, value_name = "RANGE")]
+    pub key_range: Option<String>,
+
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// Arguments for `storage idb clear`.
+#[derive(Args)]
+pub struct StorageIdbClearArgs {
+    /// Database name
+    pub database: String,
+
+    /// Object store to clear (all stores when omitted)
+    pub store: Option<String>,
+
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// Arguments for `storage <area> list` and `storage <area> clear`.
+#[derive(Args)]
+pub struct StorageOriginArgs {
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// Arguments for `storage <area> get` and `storage <area> remove`.
+#[derive(Args)]
+pub struct StorageKeyArgs {
+    /// Storage key
+    pub key: String,
+
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// Arguments for `storage <area> set`.
+#[derive(Args)]
+pub struct StorageSetArgs {
+    /// Storage key
+    pub key: String,
+
+    /// Value to store
+    pub value: String,
+
+    /// Storage origin (scheme://host[:port]); defaults to the target frame's origin
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
 /// Arguments for the `dialog` subcommand group.
 #[derive(Args)]
 pub struct DialogArgs {
@@ -2138,14 +3147,106 @@ pub struct MarkdownArgs {
     #[arg(long)]
     pub include_images: bool,
 
-    /// Maximum raw input bytes accepted from --file, --stdin, or --url.
-    #[arg(
-        long,
-        value_name = "BYTES",
-        value_parser = parse_nonzero_usize,
-        default_value_t = DEFAULT_MARKDOWN_MAX_INPUT_BYTES
-    )]
-    pub max_input_bytes: usize,
+    /// Maximum raw input bytes accepted from --file, --stdin, or --url.
+    #[arg(
+        long,
+        value_name = "BYTES",
+        value_parser = parse_nonzero_usize,
+        default_value_t = DEFAULT_MARKDOWN_MAX_INPUT_BYTES
+    )]
+    pub max_input_bytes: usize,
+}
+
+/// Arguments for the `cdp` subcommand group.
+#[derive(Args)]
+pub struct CdpArgs {
+    #[command(subcommand)]
+    pub command: CdpCommand,
+}
+
+/// Raw CDP subcommands.
+#[derive(Subcommand)]
+pub enum CdpCommand {
+    /// Send a single CDP command and print its result
+    #[command(
+        long_about = "Send one Chrome DevTools Protocol command and print the raw result object \
+            as JSON. PARAMS must be a JSON object; omit it for methods without parameters. The \
+            command runs in a session attached to the selected tab unless --browser is given, \
+            in which case it is sent on the browser-level connection (Browser.*, Target.*, \
+            SystemInfo.*). Chrome's error response is reported on stderr with exit code 5.",
+        after_long_help = "\
+EXAMPLES:
+  # Read the current security state
+  agentchrome cdp send Security.enable
+
+  # Slow down all animations on a specific tab
+  agentchrome --tab 2 cdp send Animation.setPlaybackRate '{\"playbackRate\":0.1}'
+
+  # Add a virtual WebAuthn authenticator
+  agentchrome cdp send WebAuthn.enable
+  agentchrome cdp send WebAuthn.addVirtualAuthenticator \\
+    '{\"options\":{\"protocol\":\"ctap2\",\"transport\":\"usb\"}}'
+
+  # List all targets from the browser connection
+  agentchrome cdp send --browser Target.getTargets"
+    )]
+    Send(CdpSendArgs),
+
+    /// Stream CDP events as NDJSON
+    #[command(
+        long_about = "Stream the named Chrome DevTools Protocol events as they arrive, one JSON \
+            object per line ({\"method\",\"params\"}). The domain of each event is enabled \
+            in the listening session first (e.g. Animation.enable for \
+            Animation.animationStarted). Stops after --count events, after --timeout \
+            milliseconds, or on Ctrl+C, and exits 0 in all three cases.",
+        after_long_help = "\
+EXAMPLES:
+  # Print the next security state change
+  agentchrome cdp listen Security.visibleSecurityStateChanged --count 1
+
+  # Watch animations for 10 seconds
+  agentchrome cdp listen Animation.animationCreated Animation.animationStarted --timeout 10000
+
+  # Listen for new targets on the browser connection
+  agentchrome cdp listen --browser Target.targetCreated --count 3"
+    )]
+    Listen(CdpListenArgs),
+}
+
+/// Arguments for `cdp send`.
+#[derive(Args)]
+pub struct CdpSendArgs {
+    /// Fully-qualified CDP method (e.g. Animation.setPlaybackRate)
+    #[arg(value_name = "METHOD")]
+    pub method: String,
+
+    /// Method parameters as a JSON object
+    #[arg(value_name = "PARAMS")]
+    pub params: Option<String>,
+
+    /// Send on the browser-level connection instead of a tab session
+    #[arg(long)]
+    pub browser: bool,
+}
+
+/// Arguments for `cdp listen`.
+#[derive(Args)]
+pub struct CdpListenArgs {
+    /// Fully-qualified CDP event names (e.g. Animation.animationStarted)
+    #[arg(value_name = "EVENT", required = true)]
+    pub events: Vec<String>,
+
+    /// Listen on the browser-level connection instead of a tab session
+    #[arg(long)]
+    pub browser: bool,
+
+    /// Auto-exit after the specified number of milliseconds
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Exit after this many events have been printed
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub count: Option<u64>,
 }
 
 /// Arguments for the `skill` subcommand group.
@@ -2291,6 +3392,10 @@ pub struct ConnectArgs {
     #[arg(long, conflicts_with_all = ["launch", "status"])]
     pub disconnect: bool,
 
+    /// List named sessions and their connections
+    #[arg(long, conflicts_with_all = ["launch", "status", "disconnect"])]
+    pub list_sessions: bool,
+
     /// Launch Chrome in headless mode
     #[arg(long, requires = "launch")]
     pub headless: bool,
@@ -2306,6 +3411,72 @@ pub struct ConnectArgs {
     /// Additional arguments to pass to Chrome (can be repeated)
     #[arg(long, requires = "launch")]
     pub chrome_arg: Vec<String>,
+
+    /// Talk to the launched Chrome over a pipe instead of a TCP debugging port (Unix only)
+    #[arg(long, requires = "launch")]
+    pub pipe: bool,
+}
+
+/// Arguments for the `daemon` subcommand group.
+#[derive(Args)]
+pub struct DaemonArgs {
+    #[command(subcommand)]
+    pub command: DaemonCommand,
+}
+
+/// Daemon subcommands.
+#[derive(Subcommand)]
+pub enum DaemonCommand {
+    /// Start the daemon in the background
+    #[command(
+        long_about = "Start the daemon for the Chrome instance resolved from --ws-url, --port, or \
+            the session file. The command returns once the daemon socket is ready. Use \
+            --foreground to run the daemon in the current process instead (for supervisors and \
+            debugging). Fails if a daemon is already running.",
+        after_long_help = "\
+EXAMPLES:
+  # Start in the background
+  agentchrome daemon start
+
+  # Run in the foreground (Ctrl-C to stop)
+  agentchrome daemon start --foreground"
+    )]
+    Start(DaemonStartArgs),
+
+    /// Stop the running daemon
+    #[command(
+        long_about = "Ask the running daemon to exit and remove its socket and record file. \
+            Succeeds with {\"stopped\": false} when no daemon is running.",
+        after_long_help = "\
+EXAMPLES:
+  # Stop the daemon
+  agentchrome daemon stop"
+    )]
+    Stop,
+
+    /// Show daemon status and buffered event counts
+    #[command(
+        long_about = "Report whether a daemon is running, which browser it is attached to, how \
+            many clients are connected, and how many events are buffered per tab. Exits 0 \
+            whether or not a daemon is running.",
+        after_long_help = "\
+EXAMPLES:
+  # Show daemon status
+  agentchrome daemon status"
+    )]
+    Status,
+}
+
+/// Arguments for `daemon start`.
+#[derive(Args)]
+pub struct DaemonStartArgs {
+    /// Run the daemon in this process instead of detaching
+    #[arg(long)]
+    pub foreground: bool,
+
+    /// Relay CDP over inherited descriptors 3 and 4 (set by 'connect --launch --pipe')
+    #[arg(long, hide = true, requires = "foreground")]
+    pub pipe: bool,
 }
 
 /// Arguments for the `interact` subcommand group.
@@ -2538,6 +3709,10 @@ pub struct ClickArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2577,6 +3752,10 @@ pub struct ClickAtArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2597,6 +3776,10 @@ pub struct HoverArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2615,6 +3798,10 @@ pub struct DragArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2652,6 +3839,10 @@ pub struct DragAtArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2681,6 +3872,10 @@ pub struct MouseDownAtArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2710,6 +3905,10 @@ pub struct MouseUpAtArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2730,6 +3929,10 @@ pub struct TypeArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2750,6 +3953,10 @@ pub struct KeyArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2814,6 +4021,10 @@ pub struct ScrollArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2925,7 +4136,10 @@ EXAMPLES:
   agentchrome form submit s5
 
   # Include updated snapshot after submit
-  agentchrome form submit s3 --include-snapshot"
+  agentchrome form submit s3 --include-snapshot
+
+  # Return only what changed after submit
+  agentchrome form submit s3 --include-snapshot-diff"
     )]
     Submit(FormSubmitArgs),
 }
@@ -2947,6 +4161,10 @@ pub struct FormFillArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2967,6 +4185,10 @@ pub struct FormFillManyArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -2982,6 +4204,10 @@ pub struct FormClearArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -3001,6 +4227,10 @@ pub struct FormUploadArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -3017,6 +4247,10 @@ pub struct FormSubmitArgs {
     #[arg(long)]
     pub include_snapshot: bool,
 
+    /// Include only the nodes added, removed, or changed since the previous snapshot
+    #[arg(long)]
+    pub include_snapshot_diff: bool,
+
     /// Use compact mode for the included snapshot (only interactive and landmark elements)
     #[arg(long)]
     pub compact: bool,
@@ -3036,8 +4270,15 @@ pub enum ConsoleCommand {
     #[command(
         long_about = "Read captured console messages from the current page. Without arguments, \
             lists recent messages with their IDs, types, and text. Pass a message ID to get \
-            full details including stack trace and arguments. Filter by type or use --errors-only \
-            for error and assert messages only.",
+            full details including stack trace and arguments. Uncaught errors and unhandled \
+            promise rejections appear as 'exception' entries carrying exceptionDetails, a \
+            structured stackTrace, and the frameId of the originating frame. Browser Log domain \
+            entries (failed resource loads, CSP and mixed-content violations, deprecations, \
+            interventions) are read with --source log or --source all; every entry has a \
+            'source' field (console, exception, or log). Only the selected streams are read, so \
+            message IDs number those streams: pass the same --source when reading a message by \
+            ID. Filter by type or use --errors-only for error, assert, and exception entries \
+            only.",
         after_long_help = "\
 EXAMPLES:
   # List recent console messages
@@ -3046,9 +4287,15 @@ EXAMPLES:
   # Get details of a specific message
   agentchrome console read 42
 
-  # Show only errors
+  # Show only errors and uncaught exceptions
   agentchrome console read --errors-only
 
+  # List uncaught exceptions with stack traces
+  agentchrome console read --type exception
+
+  # Show CSP, mixed-content, and failed-load messages from the Log domain
+  agentchrome console read --source log
+
   # Filter by type
   agentchrome console read --type warn,error --limit 20"
     )]
@@ -3058,12 +4305,15 @@ EXAMPLES:
     #[command(
         long_about = "Stream new console messages in real time as they are logged, similar to \
             'tail -f'. Each message is printed as a JSON line. Use --timeout to auto-exit \
-            after a specified duration. Filter by type or use --errors-only to stream only \
-            error and assert messages.\n\n\
+            after a specified duration. Uncaught exceptions and unhandled promise rejections \
+            are streamed as 'exception' entries with their stack trace, and with --source log \
+            or --source all, browser Log domain entries as 'source: log' entries. Filter by \
+            type or use --errors-only to stream only error, assert, and exception entries.\n\n\
             By default, the command monitors output and exits 0 when the timeout elapses \
             or Ctrl+C is pressed, regardless of log levels observed. Pass --fail-on-error \
-            to turn the stream into a CI assertion: if any error-level message is observed \
-            during the window, the command exits 1 with a JSON error on stderr \
+            to turn the stream into a CI assertion: if any error-level message (including an \
+            uncaught exception) is observed during the window, the command exits 1 with a \
+            JSON error on stderr \
             ({\"error\":\"Error-level console messages were seen\",\"code\":1}).",
         after_long_help = "\
 EXAMPLES:
@@ -3076,6 +4326,9 @@ EXAMPLES:
   # Stream specific message types
   agentchrome console follow --type log,warn
 
+  # Stream only browser Log domain entries (interventions, violations)
+  agentchrome console follow --source log
+
   # CI assertion — exit 1 if any console.error is observed during the window
   agentchrome console follow --timeout 10000 --fail-on-error"
     )]
@@ -3088,14 +4341,18 @@ pub struct ConsoleReadArgs {
     /// Message ID to get detailed information about a specific message
     pub msg_id: Option<u64>,
 
-    /// Filter by message type (comma-separated: log,error,warn,info,debug,dir,table,trace,assert,count,timeEnd)
+    /// Filter by message type (comma-separated: log,error,warn,info,debug,dir,table,trace,assert,count,timeEnd,exception)
     #[arg(long, value_name = "TYPES", conflicts_with = "errors_only")]
     pub r#type: Option<String>,
 
-    /// Show only error and assert messages (shorthand for --type error,assert)
+    /// Show only error, assert, and exception entries (shorthand for --type error,assert,exception)
     #[arg(long, conflicts_with = "type")]
     pub errors_only: bool,
 
+    /// Which event streams to read: console API calls, uncaught exceptions, browser Log entries, or all; message IDs number the selected streams [default: console and exception]
+    #[arg(long, value_enum)]
+    pub source: Option<ConsoleSource>,
+
     /// Maximum number of messages to return
     #[arg(long, default_value_t = 50)]
     pub limit: usize,
@@ -3109,22 +4366,39 @@ pub struct ConsoleReadArgs {
     pub include_preserved: bool,
 }
 
+/// Event streams read by the `console` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConsoleSource {
+    /// Browser Log domain entries (network failures, CSP, deprecations, interventions)
+    Log,
+    /// Console API calls (console.log, console.error, ...)
+    Console,
+    /// Uncaught exceptions and unhandled promise rejections
+    Exception,
+    /// All of the above
+    All,
+}
+
 /// Arguments for `console follow`.
 #[derive(Args)]
 pub struct ConsoleFollowArgs {
-    /// Filter by message type (comma-separated: log,error,warn,info,debug,dir,table,trace,assert,count,timeEnd)
+    /// Filter by message type (comma-separated: log,error,warn,info,debug,dir,table,trace,assert,count,timeEnd,exception)
     #[arg(long, value_name = "TYPES", conflicts_with = "errors_only")]
     pub r#type: Option<String>,
 
-    /// Show only error and assert messages (shorthand for --type error,assert)
+    /// Show only error, assert, and exception entries (shorthand for --type error,assert,exception)
     #[arg(long, conflicts_with = "type")]
     pub errors_only: bool,
 
+    /// Which event streams to follow: console API calls, uncaught exceptions, browser Log entries, or all [default: console and exception]
+    #[arg(long, value_enum)]
+    pub source: Option<ConsoleSource>,
+
     /// Auto-exit after the specified number of milliseconds
     #[arg(long)]
     pub timeout: Option<u64>,
 
-    /// Exit with code 1 if any error-level console message is observed during the window
+    /// Exit with code 1 if any error-level message or uncaught exception is observed during the window
     #[arg(long)]
     pub fail_on_error: bool,
 }
@@ -3198,6 +4472,226 @@ EXAMPLES:
   agentchrome network follow --verbose --timeout 30000"
     )]
     Follow(NetworkFollowArgs),
+
+    /// Export captured requests as a HAR 1.2 file
+    #[command(
+        long_about = "Write the captured network requests to an HTTP Archive (HAR 1.2) file that \
+            Chrome DevTools and other HAR viewers can open. Uses the same capture as \
+            'network list': request and response headers, timing phases, redirect hops, and \
+            posted request bodies. Response bodies are only included with --include-bodies; \
+            binary bodies are stored base64-encoded. Filter by resource type or URL pattern \
+            like 'network list'. Prints the file path with the number of entries and pages \
+            written.",
+        after_long_help = "\
+EXAMPLES:
+  # Export the current page's traffic
+  agentchrome network har --file session.har
+
+  # Include response bodies
+  agentchrome network har --file session.har --include-bodies
+
+  # Export API calls only, including earlier navigations
+  agentchrome network har --file api.har --type xhr,fetch --include-preserved"
+    )]
+    Har(NetworkHarArgs),
+
+    /// Answer requests from a HAR file instead of the network
+    #[command(
+        long_about = "Serve the page's requests from a HAR archive (for example one written by \
+            'network har') through Fetch interception, until --timeout or Ctrl+C. A request is \
+            answered by the entries with the same method and URL, falling back to entries \
+            whose URL differs only in the query string; with --match-body the posted body \
+            must match too. Repeated requests step through their matching entries, exact \
+            URL matches first, and then keep getting the last one. Requests with no entry are \
+            failed (--not-found abort, the default) or sent to the network (--not-found \
+            passthrough). On exit, prints how many requests were served and lists the \
+            unmatched ones.",
+        after_long_help = "\
+EXAMPLES:
+  # Replay a recording offline for 30 seconds
+  agentchrome network replay --har recording.har --timeout 30000
+
+  # Let requests missing from the recording through
+  agentchrome network replay --har recording.har --not-found passthrough
+
+  # Distinguish POSTs to the same URL by their body
+  agentchrome network replay --har recording.har --match-body"
+    )]
+    Replay(NetworkReplayArgs),
+
+    /// Mock, abort, or rewrite matching requests (add, list, remove, clear)
+    #[command(
+        long_about = "Manage request interception rules. Rules are saved in \
+            ~/.agentchrome/routes.json and enforced through the CDP Fetch domain by whichever \
+            agentchrome process holds a connection open: 'network follow', 'script run', or \
+            the background daemon (which picks up changes immediately). One-shot commands do \
+            not enforce routes on their own. Rules are evaluated in the order they were added; \
+            the first rule whose URL glob matches wins.",
+        after_long_help = "\
+EXAMPLES:
+  # Serve a canned JSON body with a 500 status
+  agentchrome network route add --url-pattern '*/api/users*' \\
+    --respond-file users.json --status 500 --header content-type:application/json
+
+  # Make image requests fail
+  agentchrome network route add --url-pattern '*.png' --abort
+
+  # Inject a request header
+  agentchrome network route add --url-pattern '*/api/*' --set-header 'authorization:Bearer test'
+
+  # Show, remove, and clear rules
+  agentchrome network route list
+  agentchrome network route remove 2
+  agentchrome network route clear"
+    )]
+    Route(NetworkRouteArgs),
+}
+
+/// Arguments for `network route`.
+#[derive(Args)]
+pub struct NetworkRouteArgs {
+    #[command(subcommand)]
+    pub command: NetworkRouteCommand,
+}
+
+/// Route subcommands.
+#[derive(Subcommand)]
+pub enum NetworkRouteCommand {
+    /// Add an interception rule
+    #[command(
+        long_about = "Add a rule for requests whose URL matches --url-pattern (a glob where * \
+            also matches '/'). Choose one mode: respond with --respond-file/--status/--header \
+            (status defaults to 200), fail the request with --abort, or let it through with \
+            request headers changed via --set-header/--remove-header. Prints the stored rule \
+            with its ID.",
+        after_long_help = "\
+EXAMPLES:
+  # Respond with a file
+  agentchrome network route add --url-pattern '*/config.json' --respond-file config.json
+
+  # Respond with an empty 503
+  agentchrome network route add --url-pattern '*/api/*' --status 503
+
+  # Simulate a DNS failure
+  agentchrome network route add --url-pattern '*cdn.example.com*' --abort \\
+    --error-reason name-not-resolved
+
+  # Strip cookies from API calls
+  agentchrome network route add --url-pattern '*/api/*' --remove-header cookie"
+    )]
+    Add(NetworkRouteAddArgs),
+
+    /// List interception rules in evaluation order
+    #[command(
+        long_about = "List the saved interception rules in the order they are evaluated. \
+            Returns a JSON array of rules with their IDs, URL patterns, and actions.",
+        after_long_help = "\
+EXAMPLES:
+  # List rules
+  agentchrome network route list"
+    )]
+    List,
+
+    /// Remove an interception rule by ID
+    #[command(
+        long_about = "Remove the interception rule with the given ID (as printed by 'network \
+            route add' and 'network route list').",
+        after_long_help = "\
+EXAMPLES:
+  # Remove rule 2
+  agentchrome network route remove 2"
+    )]
+    Remove(NetworkRouteRemoveArgs),
+
+    /// Remove all interception rules
+    #[command(
+        long_about = "Remove every interception rule. Requests are no longer paused once the \
+            rules are gone.",
+        after_long_help = "\
+EXAMPLES:
+  # Remove all rules
+  agentchrome network route clear"
+    )]
+    Clear,
+}
+
+/// Arguments for `network route add`.
+#[derive(Args)]
+pub struct NetworkRouteAddArgs {
+    /// URL glob to match (e.g. '*/api/users*')
+    #[arg(long, value_name = "GLOB")]
+    pub url_pattern: String,
+
+    /// Respond with the contents of this file
+    #[arg(long, value_name = "FILE")]
+    pub respond_file: Option<PathBuf>,
+
+    /// Response status code (default: 200)
+    #[arg(long, value_parser = clap::value_parser!(u16).range(100..=599))]
+    pub status: Option<u16>,
+
+    /// Response header as NAME:VALUE (repeatable)
+    #[arg(long = "header", value_name = "NAME:VALUE", value_parser = parse_header_arg)]
+    pub headers: Vec<String>,
+
+    /// Fail matching requests with a network error
+    #[arg(long, conflicts_with_all = ["respond_file", "status", "headers", "set_headers", "remove_headers"])]
+    pub abort: bool,
+
+    /// Network error reported by --abort
+    #[arg(long, value_enum, default_value_t = RouteErrorReason::Failed, requires = "abort")]
+    pub error_reason: RouteErrorReason,
+
+    /// Set a request header as NAME:VALUE before continuing (repeatable)
+    #[arg(
+        long = "set-header",
+        value_name = "NAME:VALUE",
+        value_parser = parse_header_arg,
+        conflicts_with_all = ["respond_file", "status", "headers"]
+    )]
+    pub set_headers: Vec<String>,
+
+    /// Remove a request header before continuing (repeatable)
+    #[arg(
+        long = "remove-header",
+        value_name = "NAME",
+        conflicts_with_all = ["respond_file", "status", "headers"]
+    )]
+    pub remove_headers: Vec<String>,
+}
+
+/// Arguments for `network route remove`.
+#[derive(Args)]
+pub struct NetworkRouteRemoveArgs {
+    /// Rule ID to remove
+    pub id: u32,
+}
+
+/// Network error reported for requests aborted by a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RouteErrorReason {
+    Failed,
+    Aborted,
+    TimedOut,
+    AccessDenied,
+    ConnectionClosed,
+    ConnectionReset,
+    ConnectionRefused,
+    ConnectionAborted,
+    ConnectionFailed,
+    NameNotResolved,
+    InternetDisconnected,
+    AddressUnreachable,
+    BlockedByClient,
+    BlockedByResponse,
+}
+
+/// Validate a `NAME:VALUE` header argument.
+fn parse_header_arg(s: &str) -> Result<String, String> {
+    match s.split_once(':') {
+        Some((name, _)) if !name.trim().is_empty() => Ok(s.to_string()),
+        _ => Err("expected NAME:VALUE".to_string()),
+    }
 }
 
 /// Arguments for `network list`.
@@ -3275,6 +4769,59 @@ pub struct NetworkFollowArgs {
     pub verbose: bool,
 }
 
+/// Arguments for `network har`.
+#[derive(Args)]
+pub struct NetworkHarArgs {
+    /// Path of the HAR file to write
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// Include response bodies (fetched from Chrome for each request)
+    #[arg(long)]
+    pub include_bodies: bool,
+
+    /// Filter by resource type (comma-separated: document,stylesheet,image,media,font,script,xhr,fetch,websocket,manifest,other)
+    #[arg(long, value_name = "TYPES")]
+    pub r#type: Option<String>,
+
+    /// Filter by URL pattern (substring match)
+    #[arg(long)]
+    pub url: Option<String>,
+
+    /// Include requests from previous navigations
+    #[arg(long)]
+    pub include_preserved: bool,
+}
+
+/// Arguments for `network replay`.
+#[derive(Args)]
+pub struct NetworkReplayArgs {
+    /// HAR file to answer requests from
+    #[arg(long, value_name = "FILE")]
+    pub har: PathBuf,
+
+    /// What to do with requests the HAR file has no entry for
+    #[arg(long, value_enum, default_value_t = ReplayNotFound::Abort)]
+    pub not_found: ReplayNotFound,
+
+    /// Also require the request body to match the recorded one
+    #[arg(long)]
+    pub match_body: bool,
+
+    /// Stop replaying after the specified number of milliseconds
+    #[arg(long)]
+    pub timeout: Option<u64>,
+}
+
+/// Handling of requests missing from a replayed HAR file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReplayNotFound {
+    /// Fail the request with a network error
+    Abort,
+    /// Send the request to the network
+    Passthrough,
+}
+
 /// Arguments for `page resize`.
 #[derive(Args)]
 pub struct PageResizeArgs {
@@ -3282,6 +4829,162 @@ pub struct PageResizeArgs {
     pub size: String,
 }
 
+/// Arguments for `page extract`.
+#[derive(Args)]
+pub struct PageExtractArgs {
+    /// JSON file describing the fields to extract
+    #[arg(long, value_name = "FILE")]
+    pub schema: PathBuf,
+}
+
+/// Output formats for `page tables`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TableFormat {
+    /// JSON with one object per row (default)
+    #[default]
+    Json,
+    /// CSV with a header line, tables separated by a blank line
+    Csv,
+}
+
+/// Arguments for `page tables`.
+#[derive(Args)]
+pub struct PageTablesArgs {
+    /// CSS selector of a table, or of an element whose tables to extract
+    #[arg(long)]
+    pub selector: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TableFormat::Json)]
+    pub format: TableFormat,
+}
+
+/// Arguments for `page record`.
+#[derive(Args)]
+pub struct PageRecordArgs {
+    #[command(subcommand)]
+    pub command: PageRecordCommand,
+}
+
+/// Screencast recording subcommands.
+#[derive(Subcommand)]
+pub enum PageRecordCommand {
+    /// Start recording the current tab
+    #[command(
+        long_about = "Start a screencast of the current tab. A background recorder process \
+            attaches to the tab, saves every frame Chrome sends as a JPEG in --dir \
+            (frame-00000.jpg, ...), and appends its timestamp to frames.jsonl. Only one \
+            recording can run per session. Returns JSON with the recording directory, the \
+            recorder's pid, and the start time.",
+        after_long_help = "\
+EXAMPLES:
+  # Record into frames/
+  agentchrome page record start --dir frames/
+
+  # Fewer, smaller frames
+  agentchrome page record start --dir frames/ --max-fps 2 --quality 60"
+    )]
+    Start(PageRecordStartArgs),
+
+    /// Stop recording and assemble the animation and timeline
+    #[command(
+        long_about = "Stop the running recording, assemble its frames into an animated PNG \
+            (recording.png) that plays at the captured pace, and write timeline.json with every \
+            frame's timestamp and offset plus the interact/form command markers. Returns JSON \
+            with the paths written, the frame and marker counts, and the duration.",
+        after_long_help = "\
+EXAMPLES:
+  # Stop and assemble
+  agentchrome page record stop"
+    )]
+    Stop,
+}
+
+/// Arguments for `page record start`.
+#[derive(Args)]
+pub struct PageRecordStartArgs {
+    /// Directory to write frames, the animation, and the timeline to
+    #[arg(long, value_name = "DIR")]
+    pub dir: PathBuf,
+
+    /// Keep at most this many frames per second (default: every frame Chrome sends)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=60))]
+    pub max_fps: Option<u32>,
+
+    /// JPEG quality of captured frames, 1-100
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub quality: u8,
+
+    /// Record in this process until interrupted instead of detaching
+    #[arg(long)]
+    pub foreground: bool,
+}
+
+/// Arguments for `page archive`.
+#[derive(Args)]
+pub struct PageArchiveArgs {
+    /// Path of the MHTML file to write
+    #[arg(long, required_unless_present = "bundle", conflicts_with = "bundle")]
+    pub file: Option<PathBuf>,
+
+    /// Write a bundle directory with the MHTML, snapshot, screenshot, console, network, and manifest
+    #[arg(long, value_name = "DIR")]
+    pub bundle: Option<PathBuf>,
+}
+
+/// Arguments for `page pdf`.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Args)]
+pub struct PagePdfArgs {
+    /// Path of the PDF file to write
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// Named paper size [default: letter]
+    #[arg(long, value_enum, default_value_t = PaperSize::Letter, hide_default_value = true)]
+    pub paper: PaperSize,
+
+    /// Custom paper width in inches (overrides --paper)
+    #[arg(long, value_name = "INCHES")]
+    pub paper_width: Option<f64>,
+
+    /// Custom paper height in inches (overrides --paper)
+    #[arg(long, value_name = "INCHES")]
+    pub paper_height: Option<f64>,
+
+    /// Page margins: 1-4 comma-separated values in CSS order, each with an optional in/cm/mm/px unit
+    #[arg(long)]
+    pub margin: Option<String>,
+
+    /// Use landscape orientation
+    #[arg(long)]
+    pub landscape: bool,
+
+    /// Pages to print, e.g. "1-5, 8, 11-13" (default: all pages)
+    #[arg(long)]
+    pub page_ranges: Option<String>,
+
+    /// HTML template for the page header (enables header and footer)
+    #[arg(long, value_name = "HTML")]
+    pub header_template: Option<String>,
+
+    /// HTML template for the page footer (enables header and footer)
+    #[arg(long, value_name = "HTML")]
+    pub footer_template: Option<String>,
+
+    /// Print background colors and images
+    #[arg(long)]
+    pub print_background: bool,
+
+    /// Use the page size defined by the page's CSS @page rule, if any
+    #[arg(long)]
+    pub prefer_css_page_size: bool,
+
+    /// Scale of the page rendering, 0.1-2.0
+    #[arg(long)]
+    pub scale: Option<f64>,
+}
+
 /// Arguments for `page element`.
 #[derive(Args)]
 pub struct PageElementArgs {
@@ -4229,4 +5932,25 @@ mod tests {
             "dom select 'query' alias must be hidden, not visible"
         );
     }
+
+    #[test]
+    fn session_flag_accepts_portable_names() {
+        let cli = try_parse("--session agent-b.2 tabs list").expect("valid session name");
+        assert_eq!(cli.global.session.as_deref(), Some("agent-b.2"));
+    }
+
+    #[test]
+    fn session_flag_rejects_path_like_names() {
+        for name in ["../other", "a/b", ".hidden", ""] {
+            assert!(
+                try_parse(&format!("--session={name} tabs list")).is_err(),
+                "session name {name:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn list_sessions_conflicts_with_launch() {
+        assert!(try_parse("connect --list-sessions --launch").is_err());
+    }
 }
@@ -416,6 +416,9 @@ pub struct CommandConnection {
 /// directly; the `connect` subcommand is exempt because it is what creates the
 /// session file in the first place.
 ///
+/// When `agentchrome daemon start` is running against the resolved browser,
+/// the returned client talks to the daemon's Unix socket instead of Chrome.
+///
 /// # Errors
 ///
 /// Propagates `AppError` from the resolution layer (including
@@ -439,6 +442,17 @@ pub async fn connect_for_command(
         ),
         ..CdpConfig::default()
     };
+
+    // Prefer a running daemon attached to the same browser: it keeps event
+    // history and saves the WebSocket handshake. Fall back to a direct
+    // connection if the daemon socket turns out to be unusable.
+    if let Some(record) = crate::daemon::running_daemon()
+        && record.ws_url == resolved.ws_url
+        && let Ok(client) = CdpClient::connect(&record.url(), config.clone()).await
+    {
+        return Ok(CommandConnection { client, resolved });
+    }
+
     let client = CdpClient::connect(&resolved.ws_url, config).await?;
 
     Ok(CommandConnection { client, resolved })
@@ -491,9 +505,39 @@ pub fn select_target<'a>(
     }
 }
 
+/// Select a target by explicit `--tab`/`--page-id` value, falling back to a
+/// tab recorded under one of the session's browser contexts when the target
+/// list does not report it. Recorded tabs are only as fresh as the last
+/// `tabs list` or `context list`, which forget tabs Chrome no longer has.
+///
+/// # Errors
+///
+/// Returns the [`select_target`] error if the value matches neither.
+pub fn select_target_or_context_tab(
+    targets: &[TargetInfo],
+    session: Option<&SessionData>,
+    value: &str,
+) -> Result<TargetInfo, AppError> {
+    match select_target(targets, Some(value)) {
+        Ok(target) => Ok(target.clone()),
+        Err(e) => session
+            .filter(|s| s.has_context_tab(value))
+            .map(|_| TargetInfo {
+                id: value.to_string(),
+                target_type: "page".to_string(),
+                title: String::new(),
+                url: String::new(),
+                ws_debugger_url: None,
+            })
+            .ok_or(e),
+    }
+}
+
 /// Resolve the target tab from the `--tab` or `--page-id` option by querying Chrome for targets.
 ///
 /// Resolution priority: `page_id` > `tab` > session active tab > first page target.
+/// Tabs opened with `tabs create --context` are also resolved from the
+/// session's recorded browser contexts.
 ///
 /// # Errors
 ///
@@ -505,25 +549,28 @@ pub async fn resolve_target(
     page_id: Option<&str>,
 ) -> Result<TargetInfo, AppError> {
     let targets = query_targets(host, port).await?;
+    let session_data = session::read_session().ok().flatten();
 
-    // Highest priority: explicit --page-id bypasses session state entirely
+    // Highest priority: explicit --page-id bypasses the persisted active tab
     if let Some(pid) = page_id {
-        return select_target(&targets, Some(pid)).cloned();
+        return select_target_or_context_tab(&targets, session_data.as_ref(), pid);
     }
 
     // When no --tab flag, check session for persisted active tab
     if tab.is_none()
-        && let Some(active_id) = session::read_session()
-            .ok()
-            .flatten()
-            .and_then(|s| s.active_tab_id)
-        && let Ok(target) = select_target(&targets, Some(&active_id))
+        && let Some(active_id) = session_data
+            .as_ref()
+            .and_then(|s| s.active_tab_id.as_deref())
+        && let Ok(target) = select_target_or_context_tab(&targets, session_data.as_ref(), active_id)
     {
-        return Ok(target.clone());
+        return Ok(target);
     }
     // Persisted target not found (tab closed) — fall through to default
 
-    select_target(&targets, tab).cloned()
+    match tab {
+        Some(value) => select_target_or_context_tab(&targets, session_data.as_ref(), value),
+        None => select_target(&targets, None).cloned(),
+    }
 }
 
 /// Timeout for `Page.enable` during auto-dismiss setup (milliseconds).
@@ -696,6 +743,19 @@ window.prompt=function(m,d){s('prompt',m,d);return oP.apply(this,arguments);};
         }))
     }
 
+    /// Enforce persisted `network route` rules in this session for as long
+    /// as it stays open. Returns `Ok(None)` when no rules are defined; see
+    /// [`crate::route::spawn_route_handler`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError` if the rules cannot be read or `Fetch.enable` fails.
+    pub async fn spawn_route_handler(
+        &self,
+    ) -> Result<Option<tokio::task::JoinHandle<()>>, AppError> {
+        crate::route::spawn_route_handler(&self.session).await
+    }
+
     /// Spawn the auto-dismiss background task and subscribe to
     /// `Page.javascriptDialogClosed` so callers can later await a bounded
     /// settle with [`await_dialog_settle`]. Pairing the two prevents the
@@ -839,6 +899,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn select_target_or_context_tab_falls_back_to_recorded_tab() {
+        let targets = vec![make_target("a", "page")];
+        let mut session_data = SessionData {
+            ws_url: "ws://127.0.0.1:9222/devtools/browser/x".into(),
+            port: 9222,
+            pid: None,
+            active_tab_id: None,
+            timestamp: "2026-02-17T12:00:00Z".into(),
+            last_reconnect_at: None,
+            reconnect_count: 0,
+            contexts: Vec::new(),
+        };
+        assert!(select_target_or_context_tab(&targets, Some(&session_data), "CTX-TAB").is_err());
+
+        session_data.add_context_tab("CTX-1", "CTX-TAB");
+        let result =
+            select_target_or_context_tab(&targets, Some(&session_data), "CTX-TAB").unwrap();
+        assert_eq!(result.id, "CTX-TAB");
+        assert_eq!(result.target_type, "page");
+
+        // Listed targets still win, and nothing is invented without a session
+        let listed = select_target_or_context_tab(&targets, Some(&session_data), "a").unwrap();
+        assert_eq!(listed.id, "a");
+        assert!(select_target_or_context_tab(&targets, None, "CTX-TAB").is_err());
+    }
+
     #[tokio::test]
     async fn managed_session_enables_domain_once() {
         use crate::cdp::{CdpClient, CdpConfig, KeepAliveConfig, ReconnectConfig};
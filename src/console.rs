@@ -1,11 +1,15 @@
+use std::collections::HashMap;
 use std::io::Write;
 use std::time::Duration;
 
 use serde::Serialize;
 
+use agentchrome::connection::ManagedSession;
 use agentchrome::error::{AppError, ExitCode};
 
-use crate::cli::{ConsoleArgs, ConsoleCommand, ConsoleFollowArgs, ConsoleReadArgs, GlobalOpts};
+use crate::cli::{
+    ConsoleArgs, ConsoleCommand, ConsoleFollowArgs, ConsoleReadArgs, ConsoleSource, GlobalOpts,
+};
 use crate::output::{self, print_output, setup_session};
 
 // =============================================================================
@@ -18,11 +22,31 @@ pub struct ConsoleMessage {
     id: usize,
     #[serde(rename = "type")]
     msg_type: String,
+    /// Event stream the entry came from: `console`, `exception`, or `log`.
+    source: &'static str,
+    /// Log domain source (e.g. `network`, `security`, `violation`) of `log` entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
     text: String,
     timestamp: String,
     url: String,
     line: u64,
     column: u64,
+    #[serde(flatten)]
+    exception: Option<ExceptionInfo>,
+}
+
+/// The extra fields of an `exception` entry (an uncaught error or unhandled
+/// promise rejection reported by `Runtime.exceptionThrown`).
+#[derive(Clone, Debug, Serialize)]
+struct ExceptionInfo {
+    #[serde(rename = "exceptionDetails")]
+    exception_details: serde_json::Value,
+    #[serde(rename = "stackTrace")]
+    stack_trace: Vec<StackFrame>,
+    /// Frame whose execution context threw, when known.
+    #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
+    frame_id: Option<String>,
 }
 
 /// A console message in detail mode (single message with full args and stack trace).
@@ -31,6 +55,11 @@ struct ConsoleMessageDetail {
     id: usize,
     #[serde(rename = "type")]
     msg_type: String,
+    /// Event stream the entry came from: `console`, `exception`, or `log`.
+    source: &'static str,
+    /// Log domain source (e.g. `network`, `security`, `violation`) of `log` entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
     text: String,
     timestamp: String,
     url: String,
@@ -39,6 +68,10 @@ struct ConsoleMessageDetail {
     args: Vec<serde_json::Value>,
     #[serde(rename = "stackTrace")]
     stack_trace: Vec<StackFrame>,
+    #[serde(rename = "exceptionDetails", skip_serializing_if = "Option::is_none")]
+    exception_details: Option<serde_json::Value>,
+    #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
+    frame_id: Option<String>,
 }
 
 /// A single stack frame in a console message detail.
@@ -56,36 +89,40 @@ struct StackFrame {
 struct StreamMessage {
     #[serde(rename = "type")]
     msg_type: String,
+    /// Event stream the entry came from: `console`, `exception`, or `log`.
+    source: &'static str,
+    /// Log domain source (e.g. `network`, `security`, `violation`) of `log` entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
     text: String,
     timestamp: String,
+    #[serde(flatten)]
+    exception: Option<ExceptionInfo>,
 }
 
 // =============================================================================
 // Output formatting
 // =============================================================================
 
+fn plain_prefix(msg_type: &str) -> &'static str {
+    match msg_type {
+        "error" | "assert" => "ERR",
+        EXCEPTION_TYPE => "EXC",
+        "warn" => "WRN",
+        "info" => "INF",
+        "debug" => "DBG",
+        _ => "LOG",
+    }
+}
+
 fn print_read_plain(messages: &[ConsoleMessage]) {
     for msg in messages {
-        let prefix = match msg.msg_type.as_str() {
-            "error" | "assert" => "ERR",
-            "warn" => "WRN",
-            "info" => "INF",
-            "debug" => "DBG",
-            _ => "LOG",
-        };
-        println!("[{prefix}] {}", msg.text);
+        println!("[{}] {}", plain_prefix(&msg.msg_type), msg.text);
     }
 }
 
 fn print_detail_plain(detail: &ConsoleMessageDetail) {
-    let prefix = match detail.msg_type.as_str() {
-        "error" | "assert" => "ERR",
-        "warn" => "WRN",
-        "info" => "INF",
-        "debug" => "DBG",
-        _ => "LOG",
-    };
-    println!("[{prefix}] {}", detail.text);
+    println!("[{}] {}", plain_prefix(&detail.msg_type), detail.text);
     println!("  Source: {}:{}:{}", detail.url, detail.line, detail.column);
     println!("  Timestamp: {}", detail.timestamp);
     if !detail.stack_trace.is_empty() {
@@ -111,6 +148,61 @@ fn print_detail_plain(detail: &ConsoleMessageDetail) {
 /// Maximum number of stack frames to include in detail output.
 const MAX_STACK_FRAMES: usize = 50;
 
+/// Message type reported for `Runtime.exceptionThrown` events.
+const EXCEPTION_TYPE: &str = "exception";
+
+/// `source` of entries from `Runtime.consoleAPICalled`.
+const SOURCE_CONSOLE: &str = "console";
+/// `source` of entries from `Runtime.exceptionThrown`.
+const SOURCE_EXCEPTION: &str = "exception";
+/// `source` of entries from `Log.entryAdded`.
+const SOURCE_LOG: &str = "log";
+
+/// Which stream produced a replayed or buffered event, judged by the shape of
+/// its params.
+fn event_source(event_params: &serde_json::Value) -> &'static str {
+    if event_params.get("exceptionDetails").is_some() {
+        SOURCE_EXCEPTION
+    } else if event_params.get("entry").is_some() {
+        SOURCE_LOG
+    } else {
+        SOURCE_CONSOLE
+    }
+}
+
+/// Whether `--source` selects entries from `source`. Without `--source`,
+/// console calls and exceptions are read but not Log entries.
+fn source_selected(selected: Option<ConsoleSource>, source: &str) -> bool {
+    match selected {
+        None => source != SOURCE_LOG,
+        Some(ConsoleSource::All) => true,
+        Some(ConsoleSource::Console) => source == SOURCE_CONSOLE,
+        Some(ConsoleSource::Exception) => source == SOURCE_EXCEPTION,
+        Some(ConsoleSource::Log) => source == SOURCE_LOG,
+    }
+}
+
+/// Event timestamp in epoch milliseconds; Log entries nest it under `entry`.
+fn event_timestamp(event_params: &serde_json::Value) -> Option<f64> {
+    event_params["timestamp"]
+        .as_f64()
+        .or_else(|| event_params["entry"]["timestamp"].as_f64())
+}
+
+/// Frame ID of each execution context, from `Runtime.executionContextCreated`.
+type ContextFrames = HashMap<i64, String>;
+
+/// Remember which frame an execution context belongs to.
+fn record_context(frames: &mut ContextFrames, event_params: &serde_json::Value) {
+    let context = &event_params["context"];
+    if let (Some(id), Some(frame_id)) = (
+        context["id"].as_i64(),
+        context["auxData"]["frameId"].as_str(),
+    ) {
+        frames.insert(id, frame_id.to_string());
+    }
+}
+
 /// Map CDP console type names to our simplified type names.
 fn map_cdp_type(cdp_type: &str) -> &str {
     match cdp_type {
@@ -119,6 +211,14 @@ fn map_cdp_type(cdp_type: &str) -> &str {
     }
 }
 
+/// Map `Log.LogEntry` levels onto console message types.
+fn map_log_level(level: &str) -> &str {
+    match level {
+        "verbose" => "debug",
+        other => map_cdp_type(other),
+    }
+}
+
 /// Format CDP `RemoteObject` args into a single text string.
 fn format_console_args(args: &[serde_json::Value]) -> String {
     args.iter()
@@ -238,11 +338,14 @@ fn parse_console_event(event_params: &serde_json::Value, id: usize) -> Option<Co
     Some(ConsoleMessage {
         id,
         msg_type,
+        source: SOURCE_CONSOLE,
+        category: None,
         text,
         timestamp,
         url,
         line,
         column,
+        exception: None,
     })
 }
 
@@ -273,6 +376,8 @@ fn parse_console_event_detail(
     Some(ConsoleMessageDetail {
         id,
         msg_type,
+        source: SOURCE_CONSOLE,
+        category: None,
         text,
         timestamp,
         url,
@@ -280,13 +385,192 @@ fn parse_console_event_detail(
         column,
         args,
         stack_trace,
+        exception_details: None,
+        frame_id: None,
     })
 }
 
+/// One-line summary of a thrown value, as `DevTools` prints it
+/// (e.g. `Uncaught TypeError: x is not a function`).
+fn exception_text(details: &serde_json::Value) -> String {
+    let prefix = details["text"].as_str().unwrap_or("Uncaught");
+    let exception = &details["exception"];
+    let message = exception["description"]
+        .as_str()
+        .and_then(|d| d.lines().next())
+        .map(String::from)
+        .or_else(|| match &exception["value"] {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        });
+    match message {
+        Some(message) if !message.is_empty() => format!("{prefix} {message}"),
+        _ => prefix.to_string(),
+    }
+}
+
+fn exception_info(details: &serde_json::Value, frames: &ContextFrames) -> ExceptionInfo {
+    ExceptionInfo {
+        exception_details: details.clone(),
+        stack_trace: extract_stack_trace(&details["stackTrace"], MAX_STACK_FRAMES),
+        frame_id: details["executionContextId"]
+            .as_i64()
+            .and_then(|id| frames.get(&id).cloned()),
+    }
+}
+
+/// Parse a `Runtime.exceptionThrown` event into a `ConsoleMessage` whose
+/// location is where the exception was thrown.
+fn parse_exception_event(
+    event_params: &serde_json::Value,
+    id: usize,
+    frames: &ContextFrames,
+) -> ConsoleMessage {
+    let details = &event_params["exceptionDetails"];
+    let info = exception_info(details, frames);
+    let url = details["url"]
+        .as_str()
+        .map(String::from)
+        .or_else(|| info.stack_trace.first().map(|f| f.file.clone()))
+        .unwrap_or_default();
+
+    ConsoleMessage {
+        id,
+        msg_type: EXCEPTION_TYPE.to_string(),
+        source: SOURCE_EXCEPTION,
+        category: None,
+        text: exception_text(details),
+        timestamp: event_params["timestamp"]
+            .as_f64()
+            .map_or_else(String::new, timestamp_to_iso),
+        url,
+        line: details["lineNumber"].as_u64().unwrap_or(0),
+        column: details["columnNumber"].as_u64().unwrap_or(0),
+        exception: Some(info),
+    }
+}
+
+/// Parse a `Log.entryAdded` event (network failures, CSP and mixed-content
+/// violations, deprecations, interventions) into a `ConsoleMessage`.
+fn parse_log_event(event_params: &serde_json::Value, id: usize) -> ConsoleMessage {
+    let entry = &event_params["entry"];
+    let stack_trace = extract_stack_trace(&entry["stackTrace"], 1);
+    ConsoleMessage {
+        id,
+        msg_type: map_log_level(entry["level"].as_str().unwrap_or("info")).to_string(),
+        source: SOURCE_LOG,
+        category: entry["source"].as_str().map(String::from),
+        text: entry["text"].as_str().unwrap_or_default().to_string(),
+        timestamp: entry["timestamp"]
+            .as_f64()
+            .map_or_else(String::new, timestamp_to_iso),
+        url: entry["url"].as_str().unwrap_or_default().to_string(),
+        line: entry["lineNumber"].as_u64().unwrap_or(0),
+        column: stack_trace.first().map_or(0, |f| f.column),
+        exception: None,
+    }
+}
+
+/// Parse a console, exception, or Log event for list mode.
+fn parse_event(
+    event_params: &serde_json::Value,
+    id: usize,
+    frames: &ContextFrames,
+) -> Option<ConsoleMessage> {
+    match event_source(event_params) {
+        SOURCE_EXCEPTION => Some(parse_exception_event(event_params, id, frames)),
+        SOURCE_LOG => Some(parse_log_event(event_params, id)),
+        _ => parse_console_event(event_params, id),
+    }
+}
+
+/// Parse a console, exception, or Log event for detail mode.
+fn parse_event_detail(
+    event_params: &serde_json::Value,
+    id: usize,
+    frames: &ContextFrames,
+) -> Option<ConsoleMessageDetail> {
+    let (message, args, stack_trace) = match event_source(event_params) {
+        SOURCE_EXCEPTION => {
+            let message = parse_exception_event(event_params, id, frames);
+            (message, Vec::new(), Vec::new())
+        }
+        SOURCE_LOG => {
+            let entry = &event_params["entry"];
+            (
+                parse_log_event(event_params, id),
+                entry["args"].as_array().cloned().unwrap_or_default(),
+                extract_stack_trace(&entry["stackTrace"], MAX_STACK_FRAMES),
+            )
+        }
+        _ => return parse_console_event_detail(event_params, id),
+    };
+    let (stack_trace, exception_details, frame_id) = match message.exception {
+        Some(info) => (
+            info.stack_trace,
+            Some(info.exception_details),
+            info.frame_id,
+        ),
+        None => (stack_trace, None, None),
+    };
+    Some(ConsoleMessageDetail {
+        id,
+        msg_type: message.msg_type,
+        source: message.source,
+        category: message.category,
+        text: message.text,
+        timestamp: message.timestamp,
+        url: message.url,
+        line: message.line,
+        column: message.column,
+        args,
+        stack_trace,
+        exception_details,
+        frame_id,
+    })
+}
+
+/// Build the `console follow` line for a console, exception, or Log event.
+fn stream_message(event_params: &serde_json::Value, frames: &ContextFrames) -> StreamMessage {
+    let message = match event_source(event_params) {
+        SOURCE_EXCEPTION => parse_exception_event(event_params, 0, frames),
+        SOURCE_LOG => parse_log_event(event_params, 0),
+        _ => {
+            let raw_type = event_params["type"].as_str().unwrap_or("log");
+            return StreamMessage {
+                msg_type: map_cdp_type(raw_type).to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
+                text: event_params["args"]
+                    .as_array()
+                    .map(|a| format_console_args(a))
+                    .unwrap_or_default(),
+                timestamp: event_params["timestamp"]
+                    .as_f64()
+                    .map_or_else(String::new, timestamp_to_iso),
+                exception: None,
+            };
+        }
+    };
+    StreamMessage {
+        msg_type: message.msg_type,
+        source: message.source,
+        category: message.category,
+        text: message.text,
+        timestamp: message.timestamp,
+        exception: message.exception,
+    }
+}
+
 /// Resolve `--type` / `--errors-only` into an optional type filter list.
 fn resolve_type_filter(type_arg: Option<&str>, errors_only: bool) -> Option<Vec<String>> {
     if errors_only {
-        return Some(vec!["error".to_string(), "assert".to_string()]);
+        return Some(vec![
+            "error".to_string(),
+            "assert".to_string(),
+            EXCEPTION_TYPE.to_string(),
+        ]);
     }
     type_arg.map(|types| types.split(',').map(|t| t.trim().to_string()).collect())
 }
@@ -307,7 +591,7 @@ fn paginate(messages: Vec<ConsoleMessage>, limit: usize, page: usize) -> Vec<Con
 
 /// Check if a message type is error-level (for follow exit code tracking).
 fn is_error_level(msg_type: &str) -> bool {
-    matches!(msg_type, "error" | "assert")
+    matches!(msg_type, "error" | "assert" | EXCEPTION_TYPE)
 }
 
 // =============================================================================
@@ -318,7 +602,7 @@ fn is_error_level(msg_type: &str) -> bool {
 ///
 /// Fields:
 /// - `message_count`: total number of messages
-/// - `error_count`: messages with type `error` or `assert`
+/// - `error_count`: messages with type `error`, `assert`, or `exception`
 /// - `warning_count`: messages with type `warn`
 /// - `levels_seen`: distinct message types present
 fn summary_of_read(messages: &[ConsoleMessage]) -> serde_json::Value {
@@ -326,7 +610,7 @@ fn summary_of_read(messages: &[ConsoleMessage]) -> serde_json::Value {
     let message_count = messages.len() as u64;
     let error_count = messages
         .iter()
-        .filter(|m| m.msg_type == "error" || m.msg_type == "assert")
+        .filter(|m| is_error_level(&m.msg_type))
         .count() as u64;
     let warning_count = messages.iter().filter(|m| m.msg_type == "warn").count() as u64;
     let mut levels: Vec<String> = messages
@@ -371,28 +655,45 @@ const DEFAULT_DRAIN_TIMEOUT_MS: u64 = 5000;
 /// Idle timeout — no event within this window means drain is complete (ms).
 const IDLE_DRAIN_MS: u64 = 200;
 
-async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(), AppError> {
-    let (_client, mut managed) = setup_session(global).await?;
-
-    let total_timeout = Duration::from_millis(global.timeout.unwrap_or(DEFAULT_DRAIN_TIMEOUT_MS));
+fn subscribe_error(e: impl std::fmt::Display) -> AppError {
+    AppError {
+        message: format!("Failed to subscribe to console events: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
 
+/// Collect the console messages, exceptions, and Log entries Chrome replays
+/// when `Runtime.enable` and `Log.enable` are sent, stopping once the stream goes idle or
+/// `total_timeout` elapses. Only the streams `source` selects are collected,
+/// and the Log domain is only enabled when they include its entries. Also
+/// returns the frame of each execution context reported along the way.
+async fn drain_replayed_events(
+    managed: &mut ManagedSession,
+    source: Option<ConsoleSource>,
+    total_timeout: Duration,
+) -> Result<(Vec<serde_json::Value>, ContextFrames), AppError> {
     // Subscribe to console events BEFORE enabling Runtime domain.
-    // CDP replays buffered Runtime.consoleAPICalled events when Runtime.enable
-    // is called; subscribing first ensures we capture the replay.
-    let mut console_rx = managed
-        .subscribe("Runtime.consoleAPICalled")
+    // CDP replays buffered Runtime.consoleAPICalled and Runtime.exceptionThrown
+    // events when Runtime.enable is called; subscribing first ensures we
+    // capture the replay.
+    let mut console_rx = subscribe_source(managed, source, SOURCE_CONSOLE).await?;
+    let mut exception_rx = subscribe_source(managed, source, SOURCE_EXCEPTION).await?;
+    let mut log_rx = subscribe_source(managed, source, SOURCE_LOG).await?;
+    let mut context_rx = managed
+        .subscribe("Runtime.executionContextCreated")
         .await
-        .map_err(|e| AppError {
-            message: format!("Failed to subscribe to console events: {e}"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+        .map_err(subscribe_error)?;
 
-    // Enable Runtime domain — triggers CDP replay buffer drain
+    // Enable Runtime (and Log) domains — triggers CDP replay buffer drain
     managed.ensure_domain("Runtime").await?;
+    if log_rx.is_some() {
+        managed.ensure_domain("Log").await?;
+    }
 
     // Drain replayed events until idle timeout or absolute deadline
     let mut events: Vec<serde_json::Value> = Vec::new();
+    let mut frames = ContextFrames::new();
     let absolute_deadline = tokio::time::Instant::now() + total_timeout;
     let mut idle_deadline = tokio::time::Instant::now() + Duration::from_millis(IDLE_DRAIN_MS);
 
@@ -403,22 +704,93 @@ async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(),
             break;
         }
 
-        tokio::select! {
-            event = console_rx.recv() => {
-                match event {
-                    Some(ev) => {
-                        events.push(ev.params);
-                        // Reset idle timer on each received event
-                        idle_deadline = tokio::time::Instant::now()
-                            + Duration::from_millis(IDLE_DRAIN_MS);
-                    }
-                    None => break,
-                }
+        let event = tokio::select! {
+            event = recv_source(&mut console_rx) => event,
+            event = recv_source(&mut exception_rx) => event,
+            event = recv_source(&mut log_rx) => event,
+            Some(ev) = context_rx.recv() => {
+                record_context(&mut frames, &ev.params);
+                continue;
             }
             () = tokio::time::sleep(remaining) => break,
-        }
+        };
+        let Some(ev) = event else { break };
+        events.push(ev.params);
+        // Reset idle timer on each received event
+        idle_deadline = tokio::time::Instant::now() + Duration::from_millis(IDLE_DRAIN_MS);
     }
 
+    // Console messages, exceptions, and Log entries arrive on separate
+    // channels; restore the order they were logged in (the sort is stable for
+    // equal times).
+    events.sort_by(|a, b| {
+        let time = |v: &serde_json::Value| event_timestamp(v).unwrap_or(0.0);
+        time(a).total_cmp(&time(b))
+    });
+
+    Ok((events, frames))
+}
+
+/// Gather the raw console events for the current tab: the daemon's buffer
+/// when connected through it, otherwise Chrome's replay on domain enable.
+/// Only the streams `source` selects are gathered, so message IDs number
+/// the selected streams.
+async fn collect_events(
+    client: &agentchrome::cdp::CdpClient,
+    managed: &mut ManagedSession,
+    source: Option<ConsoleSource>,
+    timeout_ms: Option<u64>,
+) -> Result<(Vec<serde_json::Value>, ContextFrames), AppError> {
+    if agentchrome::daemon::is_daemon_client(client) {
+        // The daemon has been listening since before this command started,
+        // so its buffer covers messages Chrome would no longer replay. It
+        // does not keep execution contexts, so exceptions carry no frameId.
+        let events = agentchrome::daemon::buffered_events(
+            managed,
+            agentchrome::daemon::EventCategory::Console,
+        )
+        .await?
+        .into_iter()
+        .filter(|ev| match ev.method.as_str() {
+            "Runtime.consoleAPICalled" => source_selected(source, SOURCE_CONSOLE),
+            "Runtime.exceptionThrown" => source_selected(source, SOURCE_EXCEPTION),
+            "Log.entryAdded" => source_selected(source, SOURCE_LOG),
+            _ => false,
+        })
+        .map(|ev| ev.params)
+        .collect();
+        Ok((events, ContextFrames::new()))
+    } else {
+        let total_timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_DRAIN_TIMEOUT_MS));
+        drain_replayed_events(managed, source, total_timeout).await
+    }
+}
+
+/// All console messages currently available for the tab, unfiltered, as
+/// `console read` lists them.
+///
+/// # Errors
+///
+/// Returns `AppError` if the console events cannot be collected.
+pub(crate) async fn read_messages(
+    client: &agentchrome::cdp::CdpClient,
+    managed: &mut ManagedSession,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<ConsoleMessage>, AppError> {
+    let (events, frames) =
+        collect_events(client, managed, Some(ConsoleSource::All), timeout_ms).await?;
+    Ok(events
+        .iter()
+        .enumerate()
+        .filter_map(|(i, params)| parse_event(params, i, &frames))
+        .collect())
+}
+
+async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(), AppError> {
+    let (client, mut managed) = setup_session(global).await?;
+    let (events, frames) =
+        collect_events(&client, &mut managed, args.source, global.timeout).await?;
+
     // Handle detail mode (MSG_ID provided)
     if let Some(msg_id) = args.msg_id {
         #[allow(clippy::cast_possible_truncation)]
@@ -430,7 +802,7 @@ async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(),
                 custom_json: None,
             });
         }
-        let detail = parse_console_event_detail(&events[id], id).ok_or_else(|| AppError {
+        let detail = parse_event_detail(&events[id], id, &frames).ok_or_else(|| AppError {
             message: format!("Failed to parse message ID {id}"),
             code: ExitCode::GeneralError,
             custom_json: None,
@@ -447,10 +819,11 @@ async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(),
     let messages: Vec<ConsoleMessage> = events
         .iter()
         .enumerate()
-        .filter_map(|(i, params)| parse_console_event(params, i))
+        .filter_map(|(i, params)| parse_event(params, i, &frames))
         .collect();
 
-    // Apply type filter
+    // Apply the type filter; IDs stay those of the list collected for the
+    // selected sources
     let type_filter = resolve_type_filter(args.r#type.as_deref(), args.errors_only);
     let messages = if let Some(ref types) = type_filter {
         filter_by_type(messages, types)
@@ -478,74 +851,75 @@ async fn execute_read(global: &GlobalOpts, args: &ConsoleReadArgs) -> Result<(),
 // Follow: streaming mode
 // =============================================================================
 
+type EventReceiver = tokio::sync::mpsc::Receiver<agentchrome::cdp::CdpEvent>;
+
+/// Subscribe to the CDP event behind `source` if `--source` selects it.
+async fn subscribe_source(
+    managed: &ManagedSession,
+    selected: Option<ConsoleSource>,
+    source: &str,
+) -> Result<Option<EventReceiver>, AppError> {
+    if !source_selected(selected, source) {
+        return Ok(None);
+    }
+    let method = match source {
+        SOURCE_EXCEPTION => "Runtime.exceptionThrown",
+        SOURCE_LOG => "Log.entryAdded",
+        _ => "Runtime.consoleAPICalled",
+    };
+    managed
+        .subscribe(method)
+        .await
+        .map(Some)
+        .map_err(subscribe_error)
+}
+
+/// Receive from an optional subscription; unselected sources never yield.
+async fn recv_source(rx: &mut Option<EventReceiver>) -> Option<agentchrome::cdp::CdpEvent> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 async fn execute_follow(global: &GlobalOpts, args: &ConsoleFollowArgs) -> Result<(), AppError> {
     let (_client, mut managed) = setup_session(global).await?;
 
+    // Execution contexts are replayed by Runtime.enable, so subscribe to them
+    // first; console messages and exceptions are subscribed afterwards so
+    // only new ones are streamed.
+    let mut context_rx = managed
+        .subscribe("Runtime.executionContextCreated")
+        .await
+        .map_err(subscribe_error)?;
+
     // Enable Runtime domain
     managed.ensure_domain("Runtime").await?;
 
-    // Subscribe to console events
-    let mut console_rx = managed
-        .subscribe("Runtime.consoleAPICalled")
-        .await
-        .map_err(|e| AppError {
-            message: format!("Failed to subscribe to console events: {e}"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+    // Subscribe to the selected sources; Log is enabled before subscribing so
+    // entries it replays are not streamed
+    let mut console_rx = subscribe_source(&managed, args.source, SOURCE_CONSOLE).await?;
+    let mut exception_rx = subscribe_source(&managed, args.source, SOURCE_EXCEPTION).await?;
+    if source_selected(args.source, SOURCE_LOG) {
+        managed.ensure_domain("Log").await?;
+    }
+    let mut log_rx = subscribe_source(&managed, args.source, SOURCE_LOG).await?;
 
     let type_filter = resolve_type_filter(args.r#type.as_deref(), args.errors_only);
     let mut saw_errors = false;
+    let mut frames = ContextFrames::new();
 
     let timeout_duration = args.timeout.map(Duration::from_millis);
     let deadline = timeout_duration.map(|d| tokio::time::Instant::now() + d);
 
     loop {
-        tokio::select! {
-            event = console_rx.recv() => {
-                match event {
-                    Some(ev) => {
-                        let raw_type = ev.params["type"].as_str().unwrap_or("log");
-                        let msg_type = map_cdp_type(raw_type).to_string();
-
-                        // Track error-level messages
-                        if is_error_level(&msg_type) {
-                            saw_errors = true;
-                        }
-
-                        // Apply type filter
-                        if let Some(ref types) = type_filter
-                            && !types.iter().any(|t| t == &msg_type) {
-                                continue;
-                            }
-
-                        let args_arr = ev.params["args"].as_array();
-                        let text = args_arr
-                            .map(|a| format_console_args(a))
-                            .unwrap_or_default();
-                        let timestamp = ev.params["timestamp"]
-                            .as_f64()
-                            .map_or_else(String::new, timestamp_to_iso);
-
-                        let stream_msg = StreamMessage {
-                            msg_type,
-                            text,
-                            timestamp,
-                        };
-
-                        let json = serde_json::to_string(&stream_msg).unwrap_or_default();
-                        println!("{json}");
-                        let _ = std::io::stdout().flush();
-                    }
-                    None => {
-                        // Connection closed
-                        return Err(AppError {
-                            message: "CDP connection closed".to_string(),
-                            code: ExitCode::ConnectionError,
-                            custom_json: None,
-                        });
-                    }
-                }
+        let event = tokio::select! {
+            event = recv_source(&mut console_rx) => event,
+            event = recv_source(&mut exception_rx) => event,
+            event = recv_source(&mut log_rx) => event,
+            Some(ev) = context_rx.recv() => {
+                record_context(&mut frames, &ev.params);
+                continue;
             }
             () = async {
                 if let Some(d) = deadline {
@@ -561,7 +935,33 @@ async fn execute_follow(global: &GlobalOpts, args: &ConsoleFollowArgs) -> Result
                 // Ctrl+C
                 break;
             }
+        };
+        let Some(ev) = event else {
+            // Connection closed
+            return Err(AppError {
+                message: "CDP connection closed".to_string(),
+                code: ExitCode::ConnectionError,
+                custom_json: None,
+            });
+        };
+
+        let stream_msg = stream_message(&ev.params, &frames);
+
+        // Track error-level messages
+        if is_error_level(&stream_msg.msg_type) {
+            saw_errors = true;
+        }
+
+        // Apply type filter
+        if let Some(ref types) = type_filter
+            && !types.contains(&stream_msg.msg_type)
+        {
+            continue;
         }
+
+        let json = serde_json::to_string(&stream_msg).unwrap_or_default();
+        println!("{json}");
+        let _ = std::io::stdout().flush();
     }
 
     if args.fail_on_error && saw_errors {
@@ -610,11 +1010,14 @@ mod tests {
         let msg = ConsoleMessage {
             id: 0,
             msg_type: "log".to_string(),
+            source: SOURCE_CONSOLE,
+            category: None,
             text: "hello".to_string(),
             timestamp: "2026-02-14T12:00:00.000Z".to_string(),
             url: "https://example.com/script.js".to_string(),
             line: 42,
             column: 5,
+            exception: None,
         };
         let json: serde_json::Value = serde_json::to_value(&msg).unwrap();
         assert_eq!(json["id"], 0);
@@ -637,6 +1040,8 @@ mod tests {
         let detail = ConsoleMessageDetail {
             id: 1,
             msg_type: "error".to_string(),
+            source: SOURCE_CONSOLE,
+            category: None,
             text: "fail".to_string(),
             timestamp: "2026-02-14T12:00:01.000Z".to_string(),
             url: "https://example.com/app.js".to_string(),
@@ -649,6 +1054,8 @@ mod tests {
                 column: 3,
                 function_name: "handleClick".to_string(),
             }],
+            exception_details: None,
+            frame_id: None,
         };
         let json: serde_json::Value = serde_json::to_value(&detail).unwrap();
         assert_eq!(json["type"], "error");
@@ -731,20 +1138,26 @@ mod tests {
             ConsoleMessage {
                 id: 0,
                 msg_type: "log".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "a".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
             ConsoleMessage {
                 id: 1,
                 msg_type: "error".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "b".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
         ];
         let filtered = filter_by_type(messages, &["error".to_string()]);
@@ -758,29 +1171,38 @@ mod tests {
             ConsoleMessage {
                 id: 0,
                 msg_type: "log".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "a".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
             ConsoleMessage {
                 id: 1,
                 msg_type: "error".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "b".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
             ConsoleMessage {
                 id: 2,
                 msg_type: "warn".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "c".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
         ];
         let filtered = filter_by_type(messages, &["error".to_string(), "warn".to_string()]);
@@ -802,7 +1224,8 @@ mod tests {
         let types = result.unwrap();
         assert!(types.contains(&"error".to_string()));
         assert!(types.contains(&"assert".to_string()));
-        assert_eq!(types.len(), 2);
+        assert!(types.contains(&"exception".to_string()));
+        assert_eq!(types.len(), 3);
     }
 
     #[test]
@@ -829,11 +1252,14 @@ mod tests {
             .map(|i| ConsoleMessage {
                 id: i,
                 msg_type: "log".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: format!("msg {i}"),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             })
             .collect()
     }
@@ -984,6 +1410,7 @@ mod tests {
     fn is_error_level_checks() {
         assert!(is_error_level("error"));
         assert!(is_error_level("assert"));
+        assert!(is_error_level("exception"));
         assert!(!is_error_level("log"));
         assert!(!is_error_level("warn"));
     }
@@ -996,14 +1423,18 @@ mod tests {
     fn stream_message_serialization() {
         let msg = StreamMessage {
             msg_type: "log".to_string(),
+            source: SOURCE_CONSOLE,
+            category: None,
             text: "hello".to_string(),
             timestamp: "2026-02-14T12:00:00.000Z".to_string(),
+            exception: None,
         };
         let json: serde_json::Value = serde_json::to_value(&msg).unwrap();
         assert_eq!(json["type"], "log");
         assert_eq!(json["text"], "hello");
         assert_eq!(json["timestamp"], "2026-02-14T12:00:00.000Z");
         assert!(json.get("msg_type").is_none());
+        assert!(json.get("exceptionDetails").is_none());
     }
 
     // =========================================================================
@@ -1021,20 +1452,26 @@ mod tests {
             ConsoleMessage {
                 id: 0,
                 msg_type: "log".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "hello".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
             ConsoleMessage {
                 id: 1,
                 msg_type: "error".to_string(),
+                source: SOURCE_CONSOLE,
+                category: None,
                 text: "fail".to_string(),
                 timestamp: String::new(),
                 url: String::new(),
                 line: 0,
                 column: 0,
+                exception: None,
             },
         ];
         print_read_plain(&messages);
@@ -1045,6 +1482,8 @@ mod tests {
         let detail = ConsoleMessageDetail {
             id: 0,
             msg_type: "warn".to_string(),
+            source: SOURCE_CONSOLE,
+            category: None,
             text: "warning".to_string(),
             timestamp: "2026-02-14T12:00:00.000Z".to_string(),
             url: "script.js".to_string(),
@@ -1057,6 +1496,8 @@ mod tests {
                 column: 10,
                 function_name: "handleClick".to_string(),
             }],
+            exception_details: None,
+            frame_id: None,
         };
         print_detail_plain(&detail);
     }
@@ -1105,11 +1546,14 @@ mod tests {
         ConsoleMessage {
             id,
             msg_type: msg_type.to_string(),
+            source: SOURCE_CONSOLE,
+            category: None,
             text: format!("msg {id}"),
             timestamp: String::new(),
             url: String::new(),
             line: 0,
             column: 0,
+            exception: None,
         }
     }
 
@@ -1232,4 +1676,223 @@ mod tests {
         assert_eq!(detail.line, 0);
         assert_eq!(detail.column, 0);
     }
+
+    // =========================================================================
+    // Runtime.exceptionThrown
+    // =========================================================================
+
+    fn exception_params() -> serde_json::Value {
+        serde_json::json!({
+            "timestamp": 1_707_912_000_000.0_f64,
+            "exceptionDetails": {
+                "exceptionId": 1,
+                "text": "Uncaught",
+                "lineNumber": 12,
+                "columnNumber": 8,
+                "url": "https://example.com/app.js",
+                "executionContextId": 7,
+                "stackTrace": {
+                    "callFrames": [
+                        {
+                            "url": "https://example.com/app.js",
+                            "lineNumber": 12,
+                            "columnNumber": 8,
+                            "functionName": "render"
+                        },
+                        {
+                            "url": "https://example.com/main.js",
+                            "lineNumber": 3,
+                            "columnNumber": 1,
+                            "functionName": ""
+                        }
+                    ]
+                },
+                "exception": {
+                    "type": "object",
+                    "subtype": "error",
+                    "className": "TypeError",
+                    "description": "TypeError: x is not a function\n    at render (app.js:13:9)"
+                }
+            }
+        })
+    }
+
+    fn context_frames() -> ContextFrames {
+        let mut frames = ContextFrames::new();
+        record_context(
+            &mut frames,
+            &serde_json::json!({
+                "context": {"id": 7, "auxData": {"frameId": "F1", "isDefault": true}}
+            }),
+        );
+        frames
+    }
+
+    #[test]
+    fn parse_exception_event_list_entry() {
+        let msg = parse_event(&exception_params(), 4, &context_frames()).unwrap();
+        assert_eq!(msg.id, 4);
+        assert_eq!(msg.msg_type, "exception");
+        assert_eq!(msg.text, "Uncaught TypeError: x is not a function");
+        assert_eq!(msg.timestamp, "2024-02-14T12:00:00.000Z");
+        assert_eq!(msg.url, "https://example.com/app.js");
+        assert_eq!((msg.line, msg.column), (12, 8));
+
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["type"], "exception");
+        assert_eq!(json["frameId"], "F1");
+        assert_eq!(json["exceptionDetails"]["exceptionId"], 1);
+        assert_eq!(json["stackTrace"][0]["functionName"], "render");
+        assert_eq!(json["stackTrace"][1]["file"], "https://example.com/main.js");
+    }
+
+    #[test]
+    fn parse_exception_event_unknown_context_has_no_frame() {
+        let msg = parse_event(&exception_params(), 0, &ContextFrames::new()).unwrap();
+        let json = serde_json::to_value(&msg).unwrap();
+        assert!(json.get("frameId").is_none());
+        assert!(json.get("exceptionDetails").is_some());
+    }
+
+    #[test]
+    fn parse_exception_event_detail() {
+        let detail = parse_event_detail(&exception_params(), 2, &context_frames()).unwrap();
+        assert_eq!(detail.msg_type, "exception");
+        assert!(detail.args.is_empty());
+        assert_eq!(detail.stack_trace.len(), 2);
+        assert_eq!(detail.frame_id.as_deref(), Some("F1"));
+        assert!(detail.exception_details.is_some());
+    }
+
+    #[test]
+    fn exception_text_for_rejected_primitive() {
+        let details = serde_json::json!({
+            "text": "Uncaught (in promise)",
+            "exception": {"type": "string", "value": "nope"}
+        });
+        assert_eq!(exception_text(&details), "Uncaught (in promise) nope");
+        assert_eq!(exception_text(&serde_json::json!({})), "Uncaught");
+    }
+
+    #[test]
+    fn stream_message_for_exception() {
+        let msg = stream_message(&exception_params(), &context_frames());
+        assert!(is_error_level(&msg.msg_type));
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["type"], "exception");
+        assert_eq!(json["frameId"], "F1");
+        assert_eq!(json["stackTrace"][0]["line"], 12);
+    }
+
+    #[test]
+    fn errors_only_keeps_exceptions() {
+        let messages = vec![
+            make_msg(0, "log"),
+            make_msg(1, "exception"),
+            make_msg(2, "error"),
+        ];
+        let types = resolve_type_filter(None, true).unwrap();
+        let filtered = filter_by_type(messages.clone(), &types);
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(summary_of_read(&messages)["error_count"], 2);
+    }
+
+    // =========================================================================
+    // Log.entryAdded
+    // =========================================================================
+
+    fn log_params() -> serde_json::Value {
+        serde_json::json!({
+            "entry": {
+                "source": "network",
+                "level": "error",
+                "text": "Failed to load resource: net::ERR_NAME_NOT_RESOLVED",
+                "timestamp": 1_707_912_000_000.0_f64,
+                "url": "https://missing.example/app.js",
+                "lineNumber": 3,
+                "args": [{"type": "string", "value": "extra"}],
+                "stackTrace": {"callFrames": [{
+                    "functionName": "load",
+                    "url": "https://example.com/main.js",
+                    "lineNumber": 3,
+                    "columnNumber": 14
+                }]}
+            }
+        })
+    }
+
+    #[test]
+    fn parse_log_event_list_entry() {
+        let msg = parse_event(&log_params(), 1, &ContextFrames::new()).unwrap();
+        assert_eq!(msg.msg_type, "error");
+        assert_eq!(msg.source, "log");
+        assert_eq!(msg.category.as_deref(), Some("network"));
+        assert_eq!(msg.timestamp, "2024-02-14T12:00:00.000Z");
+        assert_eq!(msg.url, "https://missing.example/app.js");
+        assert_eq!((msg.line, msg.column), (3, 14));
+
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["source"], "log");
+        assert_eq!(json["category"], "network");
+        assert!(json.get("exceptionDetails").is_none());
+    }
+
+    #[test]
+    fn parse_log_event_detail() {
+        let detail = parse_event_detail(&log_params(), 0, &ContextFrames::new()).unwrap();
+        assert_eq!(detail.source, "log");
+        assert_eq!(detail.args.len(), 1);
+        assert_eq!(detail.stack_trace[0].function_name, "load");
+        assert!(detail.exception_details.is_none());
+    }
+
+    #[test]
+    fn log_levels_map_to_console_types() {
+        assert_eq!(map_log_level("verbose"), "debug");
+        assert_eq!(map_log_level("warning"), "warn");
+        assert_eq!(map_log_level("info"), "info");
+    }
+
+    #[test]
+    fn console_and_exception_events_report_source() {
+        let frames = ContextFrames::new();
+        let exception = parse_event(&exception_params(), 0, &frames).unwrap();
+        assert_eq!(exception.source, "exception");
+        let console = stream_message(
+            &serde_json::json!({"type": "log", "args": [], "timestamp": 0.0}),
+            &frames,
+        );
+        let json = serde_json::to_value(&console).unwrap();
+        assert_eq!(json["source"], "console");
+        assert!(json.get("category").is_none());
+    }
+
+    #[test]
+    fn source_selection() {
+        assert!(source_selected(Some(ConsoleSource::All), SOURCE_LOG));
+        assert!(source_selected(Some(ConsoleSource::Log), SOURCE_LOG));
+        assert!(!source_selected(Some(ConsoleSource::Log), SOURCE_CONSOLE));
+        assert!(!source_selected(
+            Some(ConsoleSource::Console),
+            SOURCE_EXCEPTION
+        ));
+        assert!(source_selected(
+            Some(ConsoleSource::Exception),
+            SOURCE_EXCEPTION
+        ));
+        // Without --source, Log entries are left out
+        assert!(source_selected(None, SOURCE_CONSOLE));
+        assert!(source_selected(None, SOURCE_EXCEPTION));
+        assert!(!source_selected(None, SOURCE_LOG));
+    }
+
+    #[test]
+    fn event_timestamp_reads_log_entries() {
+        assert_eq!(event_timestamp(&log_params()), Some(1_707_912_000_000.0));
+        assert_eq!(
+            event_timestamp(&serde_json::json!({"timestamp": 5.0})),
+            Some(5.0)
+        );
+        assert_eq!(event_timestamp(&serde_json::json!({})), None);
+    }
 }
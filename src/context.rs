@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use agentchrome::cdp::CdpClient;
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::session;
+
+use crate::cli::{ContextArgs, ContextCommand, ContextDisposeArgs, GlobalOpts};
+use crate::output::{connect_from_global, print_output};
+
+/// Execute the `context` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the connection fails or Chrome rejects the command.
+pub async fn execute_context(global: &GlobalOpts, args: &ContextArgs) -> Result<(), AppError> {
+    match &args.command {
+        ContextCommand::Create => execute_create(global).await,
+        ContextCommand::List => execute_list(global).await,
+        ContextCommand::Dispose(dispose_args) => execute_dispose(global, dispose_args).await,
+    }
+}
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct CreateResult {
+    id: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ContextInfo {
+    id: String,
+    tabs: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DisposeResult {
+    disposed: String,
+    closed_tabs: Vec<String>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn context_not_found(id: &str) -> AppError {
+    AppError {
+        message: format!("Browser context '{id}' not found. Run 'agentchrome context list'."),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
+
+/// IDs of all browser contexts except the default one.
+async fn browser_context_ids(client: &CdpClient) -> Result<Vec<String>, AppError> {
+    let result = client
+        .send_command("Target.getBrowserContexts", None)
+        .await?;
+    Ok(result["browserContextIds"]
+        .as_array()
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| id.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Map page target IDs to their browser context, keeping only the contexts
+/// listed in `context_ids` (i.e. dropping tabs in the default context).
+fn contexts_by_target(
+    targets: &serde_json::Value,
+    context_ids: &[String],
+) -> HashMap<String, String> {
+    targets["targetInfos"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|t| t["type"] == "page")
+        .filter_map(|t| {
+            let context = t["browserContextId"].as_str()?;
+            let id = t["targetId"].as_str()?;
+            context_ids
+                .iter()
+                .any(|c| c == context)
+                .then(|| (id.to_string(), context.to_string()))
+        })
+        .collect()
+}
+
+/// Browser context of each tab that is not in the default context.
+///
+/// # Errors
+///
+/// Returns `AppError` if Chrome cannot list its contexts or targets.
+pub async fn tab_contexts(client: &CdpClient) -> Result<HashMap<String, String>, AppError> {
+    let context_ids = browser_context_ids(client).await?;
+    if context_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let targets = client.send_command("Target.getTargets", None).await?;
+    Ok(contexts_by_target(&targets, &context_ids))
+}
+
+/// Check that `id` names a live browser context.
+///
+/// # Errors
+///
+/// Returns `AppError` if the context does not exist.
+pub async fn ensure_context(client: &CdpClient, id: &str) -> Result<(), AppError> {
+    if browser_context_ids(client).await?.iter().any(|c| c == id) {
+        Ok(())
+    } else {
+        Err(context_not_found(id))
+    }
+}
+
+/// Group tabs under their contexts, keeping contexts without tabs.
+fn group_tabs(context_ids: Vec<String>, tabs: &HashMap<String, String>) -> Vec<ContextInfo> {
+    context_ids
+        .into_iter()
+        .map(|id| {
+            let mut context_tabs: Vec<String> = tabs
+                .iter()
+                .filter(|(_, context)| **context == id)
+                .map(|(tab, _)| tab.clone())
+                .collect();
+            context_tabs.sort();
+            ContextInfo {
+                id,
+                tabs: context_tabs,
+            }
+        })
+        .collect()
+}
+
+/// Apply `update` to the session's recorded contexts. Best-effort: without a
+/// session file (e.g. `--ws-url`) there is nothing to record.
+pub fn update_session(update: impl FnOnce(&mut session::SessionData)) {
+    if let Ok(Some(mut session_data)) = session::read_session() {
+        update(&mut session_data);
+        if let Err(e) = session::write_session(&session_data) {
+            eprintln!("warning: could not record browser context in session: {e}");
+        }
+    }
+}
+
+// =============================================================================
+// Subcommand handlers
+// =============================================================================
+
+async fn execute_create(global: &GlobalOpts) -> Result<(), AppError> {
+    let conn = connect_from_global(global).await?;
+
+    // Short-lived CLI connections must not take the context down with them.
+    let params = serde_json::json!({ "disposeOnDetach": false });
+    let result = conn
+        .client
+        .send_command("Target.createBrowserContext", Some(params))
+        .await?;
+    let id = result["browserContextId"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    update_session(|s| s.add_context(&id));
+
+    if global.output.plain {
+        println!("{id}");
+        return Ok(());
+    }
+    print_output(&CreateResult { id }, &global.output)
+}
+
+async fn execute_list(global: &GlobalOpts) -> Result<(), AppError> {
+    let conn = connect_from_global(global).await?;
+
+    let context_ids = browser_context_ids(&conn.client).await?;
+    let tabs = tab_contexts(&conn.client).await?;
+
+    // Forget recorded contexts and tabs that Chrome no longer has.
+    update_session(|s| {
+        s.contexts.retain(|c| context_ids.contains(&c.id));
+        s.retain_live_context_tabs(&tabs);
+    });
+
+    let contexts = group_tabs(context_ids, &tabs);
+    if global.output.plain {
+        if contexts.is_empty() {
+            println!("No browser contexts");
+        }
+        for context in &contexts {
+            println!("{}  {} tab(s)", context.id, context.tabs.len());
+        }
+        return Ok(());
+    }
+    print_output(&contexts, &global.output)
+}
+
+async fn execute_dispose(global: &GlobalOpts, args: &ContextDisposeArgs) -> Result<(), AppError> {
+    let conn = connect_from_global(global).await?;
+
+    ensure_context(&conn.client, &args.id).await?;
+    let mut closed_tabs: Vec<String> = tab_contexts(&conn.client)
+        .await?
+        .into_iter()
+        .filter(|(_, context)| *context == args.id)
+        .map(|(tab, _)| tab)
+        .collect();
+    closed_tabs.sort();
+
+    let params = serde_json::json!({ "browserContextId": args.id });
+    conn.client
+        .send_command("Target.disposeBrowserContext", Some(params))
+        .await?;
+
+    // A persisted active tab inside the context is gone now; fall back to the
+    // default target resolution.
+    update_session(|s| {
+        s.remove_context(&args.id);
+        if s.active_tab_id
+            .as_ref()
+            .is_some_and(|tab| closed_tabs.contains(tab))
+        {
+            s.active_tab_id = None;
+        }
+    });
+
+    if global.output.plain {
+        println!("Disposed {} (closed {} tab(s))", args.id, closed_tabs.len());
+        return Ok(());
+    }
+    print_output(
+        &DisposeResult {
+            disposed: args.id.clone(),
+            closed_tabs,
+        },
+        &global.output,
+    )
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> serde_json::Value {
+        serde_json::json!({
+            "targetInfos": [
+                {"targetId": "T1", "type": "page", "browserContextId": "DEFAULT"},
+                {"targetId": "T2", "type": "page", "browserContextId": "C1"},
+                {"targetId": "T3", "type": "page", "browserContextId": "C1"},
+                {"targetId": "W1", "type": "service_worker", "browserContextId": "C1"},
+                {"targetId": "T4", "type": "page"}
+            ]
+        })
+    }
+
+    #[test]
+    fn contexts_by_target_skips_default_context_and_non_pages() {
+        let map = contexts_by_target(&targets(), &["C1".to_string(), "C2".to_string()]);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["T2"], "C1");
+        assert_eq!(map["T3"], "C1");
+    }
+
+    #[test]
+    fn group_tabs_keeps_empty_contexts() {
+        let map = contexts_by_target(&targets(), &["C1".to_string(), "C2".to_string()]);
+        let grouped = group_tabs(vec!["C1".to_string(), "C2".to_string()], &map);
+        assert_eq!(
+            grouped,
+            [
+                ContextInfo {
+                    id: "C1".into(),
+                    tabs: vec!["T2".into(), "T3".into()],
+                },
+                ContextInfo {
+                    id: "C2".into(),
+                    tabs: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn context_not_found_points_to_list() {
+        let err = context_not_found("C9");
+        assert!(matches!(err.code, ExitCode::GeneralError));
+        assert!(err.message.contains("'C9' not found"));
+        assert!(err.message.contains("context list"));
+    }
+}
@@ -1,9 +1,14 @@
-use serde::Serialize;
+use std::fmt::Write as _;
 
+use serde::{Deserialize, Serialize};
+
+use agentchrome::connection::ManagedSession;
 use agentchrome::error::{AppError, ExitCode};
+use agentchrome::session;
 
 use crate::cli::{
-    CookieArgs, CookieCommand, CookieDeleteArgs, CookieListArgs, CookieSetArgs, GlobalOpts,
+    CookieArgs, CookieCommand, CookieDeleteArgs, CookieExportArgs, CookieFormat, CookieImportArgs,
+    CookieListArgs, CookieSetArgs, GlobalOpts,
 };
 use crate::output::{print_output, setup_session_with_interceptors as setup_session};
 
@@ -12,18 +17,21 @@ use crate::output::{print_output, setup_session_with_interceptors as setup_sessi
 // =============================================================================
 
 #[derive(Serialize)]
-struct CookieInfo {
-    name: String,
-    value: String,
-    domain: String,
-    path: String,
-    expires: f64,
+pub(crate) struct CookieInfo {
+    pub(crate) name: String,
+    pub(crate) value: String,
+    pub(crate) domain: String,
+    pub(crate) path: String,
+    pub(crate) expires: f64,
     #[serde(rename = "httpOnly")]
-    http_only: bool,
-    secure: bool,
+    pub(crate) http_only: bool,
+    pub(crate) secure: bool,
     #[serde(rename = "sameSite")]
-    same_site: String,
-    size: u64,
+    pub(crate) same_site: String,
+    pub(crate) size: u64,
+    /// CHIPS partition key of partitioned cookies.
+    #[serde(rename = "partitionKey", skip_serializing_if = "Option::is_none")]
+    pub(crate) partition_key: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -38,6 +46,31 @@ struct DeleteResult {
     deleted: u64,
 }
 
+#[derive(Serialize)]
+struct ExportResult {
+    file: String,
+    format: &'static str,
+    exported: usize,
+}
+
+#[derive(Serialize)]
+struct ImportResult {
+    file: String,
+    format: &'static str,
+    imported: usize,
+    failed: Vec<ImportFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ImportFailure {
+    name: String,
+    domain: String,
+    /// Line of a cookies.txt entry that could not be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    error: String,
+}
+
 // =============================================================================
 // Output formatting
 // =============================================================================
@@ -64,6 +97,34 @@ fn print_clear_plain(result: &DeleteResult) {
     println!("Cleared {} cookie(s)", result.deleted);
 }
 
+fn print_export_plain(result: &ExportResult) {
+    println!(
+        "Exported {} cookie(s) to {} ({})",
+        result.exported, result.file, result.format
+    );
+}
+
+fn print_import_plain(result: &ImportResult) {
+    println!(
+        "Imported {} cookie(s) from {}",
+        result.imported, result.file
+    );
+    print_failures_plain(&result.failed);
+}
+
+pub(crate) fn print_failures_plain(failed: &[ImportFailure]) {
+    for failure in failed {
+        if let Some(line) = failure.line {
+            println!("Failed: line {line}: {}", failure.error);
+        } else {
+            println!(
+                "Failed: {} (domain: {}): {}",
+                failure.name, failure.domain, failure.error
+            );
+        }
+    }
+}
+
 // =============================================================================
 // Dispatcher
 // =============================================================================
@@ -79,6 +140,8 @@ pub async fn execute_cookie(global: &GlobalOpts, args: &CookieArgs) -> Result<()
         CookieCommand::Set(set_args) => execute_set(global, set_args).await,
         CookieCommand::Delete(delete_args) => execute_delete(global, delete_args).await,
         CookieCommand::Clear => execute_clear(global).await,
+        CookieCommand::Export(export_args) => execute_export(global, export_args).await,
+        CookieCommand::Import(import_args) => execute_import(global, import_args).await,
     }
 }
 
@@ -131,32 +194,44 @@ async fn execute_list(global: &GlobalOpts, args: &CookieListArgs) -> Result<(),
 
 const DOMAIN_HINT: &str = "Use --domain <D> to set the cookie domain directly.";
 
-fn extract_host(url_str: &str) -> Result<String, AppError> {
-    let parsed = url::Url::parse(url_str).map_err(|e| AppError {
-        message: format!("--url value '{url_str}' is not a valid URL: {e}. {DOMAIN_HINT}"),
-        code: ExitCode::GeneralError,
-        custom_json: None,
-    })?;
+/// Resolve a cookie domain from its `url` and `domain` aliases, at most one
+/// of which may be given. `names` labels the two inputs in error messages.
+/// Shared by `cookie set` and `cookie import` so both validate alike.
+fn domain_from_aliases(
+    url: Option<&str>,
+    domain: Option<&str>,
+    names: [&str; 2],
+) -> Result<Option<String>, String> {
+    let [url_name, domain_name] = names;
+    match (url, domain) {
+        (Some(_), Some(_)) => Err(format!(
+            "{url_name} and {domain_name} are aliases for the same value; pass only one"
+        )),
+        (Some(url_str), None) => extract_host(url_str, url_name).map(Some),
+        (None, domain) => Ok(domain.map(str::to_string)),
+    }
+}
+
+fn extract_host(url_str: &str, url_name: &str) -> Result<String, String> {
+    let parsed = url::Url::parse(url_str)
+        .map_err(|e| format!("{url_name} value '{url_str}' is not a valid URL: {e}"))?;
     parsed
         .host_str()
         .map(str::to_string)
-        .ok_or_else(|| AppError {
-            message: format!("--url '{url_str}' has no host component. {DOMAIN_HINT}"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })
+        .ok_or_else(|| format!("{url_name} '{url_str}' has no host component"))
 }
 
 fn resolve_domain(args: &CookieSetArgs) -> Result<Option<String>, AppError> {
-    match (&args.url, &args.domain) {
-        (Some(_), Some(_)) => Err(AppError {
-            message: "--url and --domain are aliases for the same value; pass only one".to_string(),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        }),
-        (Some(url_str), None) => Ok(Some(extract_host(url_str)?)),
-        (None, maybe_domain) => Ok(maybe_domain.clone()),
-    }
+    domain_from_aliases(
+        args.url.as_deref(),
+        args.domain.as_deref(),
+        ["--url", "--domain"],
+    )
+    .map_err(|message| AppError {
+        message: format!("{message}. {DOMAIN_HINT}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
 }
 
 async fn execute_set(global: &GlobalOpts, args: &CookieSetArgs) -> Result<(), AppError> {
@@ -293,11 +368,340 @@ async fn execute_clear(global: &GlobalOpts) -> Result<(), AppError> {
     }
 }
 
+// =============================================================================
+// Export: write all cookies to a file
+// =============================================================================
+
+async fn execute_export(global: &GlobalOpts, args: &CookieExportArgs) -> Result<(), AppError> {
+    let (_client, mut managed) = setup_session(global).await?;
+    managed.ensure_domain("Network").await?;
+
+    let response = managed
+        .send_command("Network.getAllCookies", None)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Network.getAllCookies failed: {e}"),
+            code: ExitCode::ProtocolError,
+            custom_json: None,
+        })?;
+    let mut cookies = parse_cookies(&response);
+    if let Some(ref domain) = args.domain {
+        cookies.retain(|c| c.domain.contains(domain.as_str()));
+    }
+
+    let content = match args.format {
+        CookieFormat::Json => serde_json::to_string_pretty(&cookies).map_err(|e| AppError {
+            message: format!("Failed to serialize cookies: {e}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        })?,
+        CookieFormat::Netscape => to_netscape(&cookies),
+    };
+    // An export is a full credential dump.
+    session::write_private_file(&args.file, content.as_bytes()).map_err(|e| AppError {
+        message: format!("Failed to write cookie file {}: {e}", args.file.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+
+    let result = ExportResult {
+        file: args.file.display().to_string(),
+        format: format_name(args.format),
+        exported: cookies.len(),
+    };
+    if global.output.plain {
+        print_export_plain(&result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+/// Render cookies in the Netscape cookies.txt layout used by curl and wget.
+fn to_netscape(cookies: &[CookieInfo]) -> String {
+    let mut out = String::from("# Netscape HTTP Cookie File\n");
+    for c in cookies {
+        let prefix = if c.http_only { "#HttpOnly_" } else { "" };
+        let subdomains = netscape_bool(c.domain.starts_with('.'));
+        // Session cookies (expires <= 0) are written with expiry 0.
+        #[allow(clippy::cast_possible_truncation)]
+        let expires = if c.expires > 0.0 { c.expires as i64 } else { 0 };
+        let _ = writeln!(
+            out,
+            "{prefix}{}\t{subdomains}\t{}\t{}\t{expires}\t{}\t{}",
+            c.domain,
+            c.path,
+            netscape_bool(c.secure),
+            c.name,
+            c.value
+        );
+    }
+    out
+}
+
+fn netscape_bool(value: bool) -> &'static str {
+    if value { "TRUE" } else { "FALSE" }
+}
+
+fn format_name(format: CookieFormat) -> &'static str {
+    match format {
+        CookieFormat::Json => "json",
+        CookieFormat::Netscape => "netscape",
+    }
+}
+
+// =============================================================================
+// Import: bulk-set cookies from a file
+// =============================================================================
+
+/// A cookie read from an import file, in `cookie list` / Playwright field names.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportCookie {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) value: String,
+    #[serde(default)]
+    pub(crate) domain: String,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    #[serde(default = "default_cookie_path")]
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) expires: f64,
+    #[serde(default)]
+    pub(crate) http_only: bool,
+    #[serde(default)]
+    pub(crate) secure: bool,
+    #[serde(default)]
+    pub(crate) same_site: String,
+    #[serde(default)]
+    pub(crate) partition_key: Option<serde_json::Value>,
+    /// Set for cookies.txt entries whose include-subdomains column is FALSE.
+    #[serde(skip)]
+    pub(crate) host_only: bool,
+}
+
+fn default_cookie_path() -> String {
+    "/".to_string()
+}
+
+async fn execute_import(global: &GlobalOpts, args: &CookieImportArgs) -> Result<(), AppError> {
+    let content = std::fs::read_to_string(&args.file).map_err(|e| AppError {
+        message: format!("Failed to read cookie file {}: {e}", args.file.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    let format = args.format.unwrap_or_else(|| detect_format(&content));
+    let (cookies, mut failed) = match format {
+        CookieFormat::Json => (parse_json_cookies(&content)?, Vec::new()),
+        CookieFormat::Netscape => parse_netscape(&content),
+    };
+
+    let (_client, mut managed) = setup_session(global).await?;
+    managed.ensure_domain("Network").await?;
+    let (imported, set_failed) = set_cookies(&managed, cookies).await;
+    failed.extend(set_failed);
+
+    let result = ImportResult {
+        file: args.file.display().to_string(),
+        format: format_name(format),
+        imported,
+        failed,
+    };
+    if global.output.plain {
+        print_import_plain(&result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+/// Validate `cookies` and set them in the browser, returning how many were
+/// set and why the others were not. The Network domain must be enabled.
+pub(crate) async fn set_cookies(
+    managed: &ManagedSession,
+    cookies: Vec<ImportCookie>,
+) -> (usize, Vec<ImportFailure>) {
+    let mut failed = Vec::new();
+    let mut valid = Vec::new();
+    for cookie in cookies {
+        match cookie_param(&cookie) {
+            Ok(param) => valid.push((cookie, param)),
+            Err(error) => failed.push(import_failure(&cookie, error)),
+        }
+    }
+
+    let params: Vec<serde_json::Value> = valid.iter().map(|(_, p)| p.clone()).collect();
+    let mut imported = valid.len();
+    if !params.is_empty()
+        && managed
+            .send_command(
+                "Network.setCookies",
+                Some(serde_json::json!({ "cookies": params })),
+            )
+            .await
+            .is_err()
+    {
+        // Chrome rejects the whole batch if any cookie is invalid; retry one
+        // by one to find out which.
+        imported = 0;
+        for (cookie, param) in valid {
+            match managed.send_command("Network.setCookie", Some(param)).await {
+                Ok(response) if response["success"].as_bool() != Some(false) => imported += 1,
+                Ok(_) => failed.push(import_failure(&cookie, "rejected by the browser".into())),
+                Err(e) => failed.push(import_failure(&cookie, e.to_string())),
+            }
+        }
+    }
+    (imported, failed)
+}
+
+fn import_failure(cookie: &ImportCookie, error: String) -> ImportFailure {
+    ImportFailure {
+        name: cookie.name.clone(),
+        domain: cookie.domain.clone(),
+        line: None,
+        error,
+    }
+}
+
+/// JSON files start with `[` or `{`; anything else is treated as cookies.txt.
+fn detect_format(content: &str) -> CookieFormat {
+    match content.trim_start().chars().next() {
+        Some('[' | '{') => CookieFormat::Json,
+        _ => CookieFormat::Netscape,
+    }
+}
+
+/// Parse a cookie array or an object with a `cookies` array.
+fn parse_json_cookies(content: &str) -> Result<Vec<ImportCookie>, AppError> {
+    let invalid = |e: serde_json::Error| AppError {
+        message: format!("Invalid JSON cookie file: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    };
+    let value: serde_json::Value = serde_json::from_str(content).map_err(invalid)?;
+    let array = match value {
+        serde_json::Value::Object(mut map) => map.remove("cookies").unwrap_or_default(),
+        other => other,
+    };
+    serde_json::from_value(array).map_err(invalid)
+}
+
+/// Parse Netscape cookies.txt lines. Comments and blank lines are skipped;
+/// malformed lines are reported as failures with their line number.
+fn parse_netscape(content: &str) -> (Vec<ImportCookie>, Vec<ImportFailure>) {
+    let mut cookies = Vec::new();
+    let mut failed = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+            Some(rest) => (rest, true),
+            None if line.starts_with('#') || line.trim().is_empty() => continue,
+            None => (line, false),
+        };
+        match parse_netscape_line(line, http_only) {
+            Ok(cookie) => cookies.push(cookie),
+            Err(error) => failed.push(ImportFailure {
+                name: String::new(),
+                domain: String::new(),
+                line: Some(index + 1),
+                error,
+            }),
+        }
+    }
+    (cookies, failed)
+}
+
+fn parse_netscape_line(line: &str, http_only: bool) -> Result<ImportCookie, String> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [domain, subdomains, path, secure, expires, name, value] = fields[..] else {
+        return Err(format!(
+            "expected 7 tab-separated fields, found {}",
+            fields.len()
+        ));
+    };
+    let expires = expires
+        .parse()
+        .map_err(|_| format!("invalid expiry '{expires}'"))?;
+    Ok(ImportCookie {
+        name: name.to_string(),
+        value: value.to_string(),
+        domain: domain.to_string(),
+        url: None,
+        path: path.to_string(),
+        expires,
+        http_only,
+        secure: secure.eq_ignore_ascii_case("TRUE"),
+        same_site: String::new(),
+        partition_key: None,
+        host_only: !subdomains.eq_ignore_ascii_case("TRUE"),
+    })
+}
+
+/// Validate an imported cookie and build its `Network.CookieParam`.
+pub(crate) fn cookie_param(cookie: &ImportCookie) -> Result<serde_json::Value, String> {
+    if cookie.name.is_empty() {
+        return Err("cookie has no name".into());
+    }
+    let domain = domain_from_aliases(
+        cookie.url.as_deref(),
+        Some(cookie.domain.as_str()).filter(|d| !d.is_empty()),
+        ["url", "domain"],
+    )?
+    .ok_or("cookie has no domain")?;
+    let same_site = match cookie.same_site.to_ascii_lowercase().as_str() {
+        "" => None,
+        "strict" => Some("Strict"),
+        "lax" => Some("Lax"),
+        "none" => Some("None"),
+        other => {
+            return Err(format!(
+                "invalid SameSite '{other}' (expected Strict, Lax, or None)"
+            ));
+        }
+    };
+    if same_site == Some("None") && !cookie.secure {
+        return Err("SameSite=None cookies must be Secure".into());
+    }
+    if cookie.partition_key.is_some() && !cookie.secure {
+        return Err("partitioned cookies must be Secure".into());
+    }
+
+    let mut param = serde_json::json!({
+        "name": cookie.name,
+        "value": cookie.value,
+        "path": cookie.path,
+        "secure": cookie.secure,
+        "httpOnly": cookie.http_only,
+    });
+    // CDP widens any cookie given a `domain` to its subdomains; host-only
+    // cookies are set through a URL instead.
+    if cookie.host_only || !domain.starts_with('.') {
+        let scheme = if cookie.secure { "https" } else { "http" };
+        let host = domain.trim_start_matches('.');
+        param["url"] = serde_json::json!(format!("{scheme}://{host}{}", cookie.path));
+    } else {
+        param["domain"] = serde_json::json!(domain);
+    }
+    if let Some(same_site) = same_site {
+        param["sameSite"] = serde_json::Value::String(same_site.to_string());
+    }
+    if cookie.expires > 0.0 {
+        param["expires"] = serde_json::json!(cookie.expires);
+    }
+    if let Some(ref key) = cookie.partition_key {
+        param["partitionKey"] = key.clone();
+    }
+    Ok(param)
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
 
-fn parse_cookies(response: &serde_json::Value) -> Vec<CookieInfo> {
+pub(crate) fn parse_cookies(response: &serde_json::Value) -> Vec<CookieInfo> {
     let Some(cookies) = response["cookies"].as_array() else {
         return Vec::new();
     };
@@ -314,6 +718,7 @@ fn parse_cookies(response: &serde_json::Value) -> Vec<CookieInfo> {
             secure: c["secure"].as_bool().unwrap_or(false),
             same_site: c["sameSite"].as_str().unwrap_or("").to_string(),
             size: c["size"].as_u64().unwrap_or(0),
+            partition_key: c.get("partitionKey").cloned(),
         })
         .collect()
 }
@@ -356,6 +761,7 @@ mod tests {
             secure: true,
             same_site: "Lax".into(),
             size: 22,
+            partition_key: None,
         };
         let json: serde_json::Value = serde_json::to_value(&cookie).unwrap();
         assert_eq!(json["name"], "session_id");
@@ -380,6 +786,7 @@ mod tests {
             secure: false,
             same_site: String::new(),
             size: 0,
+            partition_key: None,
         };
         let json: serde_json::Value = serde_json::to_value(&cookie).unwrap();
         assert_eq!(json["name"], "test");
@@ -500,22 +907,39 @@ mod tests {
         print_clear_plain(&result);
     }
 
+    fn set_args(url: &str) -> CookieSetArgs {
+        CookieSetArgs {
+            name: "n".into(),
+            value: "v".into(),
+            domain: None,
+            path: "/".into(),
+            secure: false,
+            http_only: false,
+            same_site: None,
+            expires: None,
+            url: Some(url.into()),
+        }
+    }
+
     #[test]
     fn extract_host_from_https_url() {
         assert_eq!(
-            extract_host("https://example.com/path?x=1").unwrap(),
+            extract_host("https://example.com/path?x=1", "--url").unwrap(),
             "example.com"
         );
     }
 
     #[test]
     fn extract_host_from_http_url() {
-        assert_eq!(extract_host("http://foo.bar.baz/").unwrap(), "foo.bar.baz");
+        assert_eq!(
+            extract_host("http://foo.bar.baz/", "--url").unwrap(),
+            "foo.bar.baz"
+        );
     }
 
     #[test]
-    fn extract_host_rejects_malformed_url() {
-        let err = extract_host("not a url").expect_err("malformed URL must error");
+    fn resolve_domain_rejects_malformed_url() {
+        let err = resolve_domain(&set_args("not a url")).expect_err("malformed URL must error");
         assert!(
             err.message.contains("--domain"),
             "error must name --domain: {}",
@@ -524,8 +948,8 @@ mod tests {
     }
 
     #[test]
-    fn extract_host_rejects_url_without_host() {
-        let err = extract_host("file:///foo").expect_err("host-less URL must error");
+    fn resolve_domain_rejects_url_without_host() {
+        let err = resolve_domain(&set_args("file:///foo")).expect_err("host-less URL must error");
         assert!(
             err.message.contains("--domain"),
             "error must name --domain: {}",
@@ -572,4 +996,165 @@ mod tests {
             err.message
         );
     }
+
+    fn import_cookie(json: serde_json::Value) -> ImportCookie {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn netscape_round_trip() {
+        let cookies = vec![
+            CookieInfo {
+                name: "sid".into(),
+                value: "abc".into(),
+                domain: ".example.com".into(),
+                path: "/".into(),
+                expires: -1.0,
+                http_only: true,
+                secure: true,
+                same_site: "Strict".into(),
+                size: 6,
+                partition_key: None,
+            },
+            CookieInfo {
+                name: "pref".into(),
+                value: "dark".into(),
+                domain: "example.com".into(),
+                path: "/app".into(),
+                expires: 1_900_000_000.5,
+                http_only: false,
+                secure: false,
+                same_site: String::new(),
+                size: 8,
+                partition_key: None,
+            },
+        ];
+        let text = to_netscape(&cookies);
+        assert!(text.starts_with("# Netscape HTTP Cookie File\n"));
+        assert!(text.contains("#HttpOnly_.example.com\tTRUE\t/\tTRUE\t0\tsid\tabc\n"));
+        assert!(text.contains("example.com\tFALSE\t/app\tFALSE\t1900000000\tpref\tdark\n"));
+
+        let (parsed, failed) = parse_netscape(&text);
+        assert!(failed.is_empty());
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "sid");
+        assert!(parsed[0].http_only);
+        assert!(parsed[0].secure);
+        assert!(parsed[0].expires.abs() < f64::EPSILON);
+        assert_eq!(parsed[1].path, "/app");
+        assert!(!parsed[1].http_only);
+
+        // Cookie scope survives the round trip: the domain cookie keeps its
+        // domain, the host-only cookie is set through a URL.
+        assert!(!parsed[0].host_only);
+        let param = cookie_param(&parsed[0]).unwrap();
+        assert_eq!(param["domain"], ".example.com");
+        assert!(param.get("url").is_none());
+        assert!(parsed[1].host_only);
+        let param = cookie_param(&parsed[1]).unwrap();
+        assert_eq!(param["url"], "http://example.com/app");
+        assert!(param.get("domain").is_none());
+    }
+
+    #[test]
+    fn parse_netscape_reports_malformed_lines() {
+        let (parsed, failed) = parse_netscape(
+            "# comment\n\nnot\ta\tcookie\nexample.com\tFALSE\t/\tFALSE\tsoon\ta\t1\n\
+             example.com\tFALSE\t/\tFALSE\t0\tok\t1\n",
+        );
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, "ok");
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].line, Some(3));
+        assert!(failed[0].error.contains("7 tab-separated fields"));
+        assert_eq!(failed[1].line, Some(4));
+        assert!(failed[1].error.contains("invalid expiry"));
+    }
+
+    #[test]
+    fn host_only_flag_sets_cookie_by_url_even_with_dotted_domain() {
+        let (parsed, _) = parse_netscape(".example.com\tFALSE\t/\tTRUE\t0\ta\t1\n");
+        let param = cookie_param(&parsed[0]).unwrap();
+        assert_eq!(param["url"], "https://example.com/");
+        assert!(param.get("domain").is_none());
+    }
+
+    #[test]
+    fn detect_format_by_first_character() {
+        assert!(matches!(detect_format("  [{}]"), CookieFormat::Json));
+        assert!(matches!(
+            detect_format("{\"cookies\":[]}"),
+            CookieFormat::Json
+        ));
+        assert!(matches!(
+            detect_format("# Netscape HTTP Cookie File"),
+            CookieFormat::Netscape
+        ));
+    }
+
+    #[test]
+    fn parse_json_cookies_accepts_storage_state() {
+        let content = r#"{"cookies":[{"name":"a","value":"1","domain":"x.com"}],"origins":[]}"#;
+        let cookies = parse_json_cookies(content).unwrap();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].path, "/");
+    }
+
+    #[test]
+    fn cookie_param_normalizes_same_site() {
+        let param = cookie_param(&import_cookie(serde_json::json!({
+            "name": "a", "domain": "x.com", "sameSite": "lax", "expires": -1
+        })))
+        .unwrap();
+        assert_eq!(param["sameSite"], "Lax");
+        assert!(param.get("expires").is_none());
+    }
+
+    #[test]
+    fn cookie_param_rejects_invalid_attributes() {
+        let cases = [
+            (serde_json::json!({"name": "a"}), "no domain"),
+            (
+                serde_json::json!({"name": "a", "domain": "x.com", "sameSite": "Loose"}),
+                "invalid SameSite",
+            ),
+            (
+                serde_json::json!({"name": "a", "domain": "x.com", "sameSite": "None"}),
+                "must be Secure",
+            ),
+            (
+                serde_json::json!({"name": "a", "domain": "x.com",
+                    "partitionKey": {"topLevelSite": "https://x.com"}}),
+                "partitioned",
+            ),
+            (
+                serde_json::json!({"name": "a", "domain": "x.com", "url": "https://x.com/"}),
+                "aliases",
+            ),
+        ];
+        for (json, expected) in cases {
+            let err = cookie_param(&import_cookie(json.clone())).expect_err("must be rejected");
+            assert!(err.contains(expected), "{json}: {err}");
+        }
+    }
+
+    #[test]
+    fn cookie_param_rejects_url_without_host() {
+        let err = cookie_param(&import_cookie(serde_json::json!({
+            "name": "a", "url": "file:///foo"
+        })))
+        .expect_err("host-less URL must be rejected");
+        assert!(err.contains("no host component"), "{err}");
+    }
+
+    #[test]
+    fn cookie_param_resolves_domain_from_url() {
+        let param = cookie_param(&import_cookie(serde_json::json!({
+            "name": "a", "url": "https://app.example.com/login", "secure": true, "sameSite": "None"
+        })))
+        .unwrap();
+        assert_eq!(param["url"], "https://app.example.com/");
+        assert!(param.get("domain").is_none());
+        assert_eq!(param["sameSite"], "None");
+    }
 }
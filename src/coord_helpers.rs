@@ -176,6 +176,9 @@ async fn resolve_backend_node_id(
                 custom_json: None,
             })?
             .ok_or_else(AppError::no_snapshot_state)?;
+        if state.stale_uids.contains_key(target) {
+            return Err(crate::snapshot::stale_uid_error(&state, target));
+        }
         let backend_node_id = state
             .uid_map
             .get(target)
@@ -0,0 +1,1291 @@
+//! Background daemon that holds the Chrome CDP connection between CLI
+//! invocations.
+//!
+//! The daemon owns a single browser-level [`CdpClient`], attaches its own
+//! session to every page target, and buffers console, network, dialog and
+//! target events per tab. CLI commands connect to it over a Unix domain
+//! socket that speaks the same JSON-over-WebSocket protocol as Chrome, so the
+//! regular [`CdpClient`] works unchanged against either endpoint. Requests are
+//! relayed to Chrome; a small set of `Agentchrome.*` methods is answered by
+//! the daemon itself.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cdp::{CdpClient, CdpError, CdpEvent, UNIX_SOCKET_SCHEME};
+use crate::chrome::{ProbeResult, is_process_alive};
+use crate::connection::ManagedSession;
+use crate::session::{self, SessionError};
+
+/// Local method returning the events buffered for the caller's target.
+pub const METHOD_GET_BUFFERED_EVENTS: &str = "Agentchrome.getBufferedEvents";
+/// Local method returning daemon runtime status.
+pub const METHOD_STATUS: &str = "Agentchrome.status";
+/// Local method asking the daemon to exit.
+pub const METHOD_SHUTDOWN: &str = "Agentchrome.shutdown";
+/// Local method asking the daemon to re-read `network route` rules.
+pub const METHOD_RELOAD_ROUTES: &str = "Agentchrome.reloadRoutes";
+
+/// Maximum buffered console events per tab.
+pub const CONSOLE_BUFFER_CAPACITY: usize = 1000;
+/// Maximum buffered network events per tab (several events per request).
+pub const NETWORK_BUFFER_CAPACITY: usize = 5000;
+/// Maximum buffered dialog events per tab.
+pub const DIALOG_BUFFER_CAPACITY: usize = 100;
+/// Maximum buffered browser-level target events.
+pub const TARGET_BUFFER_CAPACITY: usize = 500;
+
+/// Domains the daemon enables on every page it attaches to.
+const BUFFERED_DOMAINS: &[&str] = &["Runtime", "Network", "Page", "Log"];
+
+/// Persisted record describing a running daemon: `~/.agentchrome/daemon.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DaemonRecord {
+    pub pid: u32,
+    pub socket: String,
+    pub ws_url: String,
+    pub port: u16,
+    pub started_at: String,
+}
+
+impl DaemonRecord {
+    /// The `ws+unix://` URL clients connect to.
+    #[must_use]
+    pub fn url(&self) -> String {
+        daemon_url(Path::new(&self.socket))
+    }
+}
+
+/// Event categories the daemon buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventCategory {
+    Console,
+    Network,
+    Dialog,
+    Target,
+}
+
+impl EventCategory {
+    /// Parse a category name as sent in `Agentchrome.getBufferedEvents`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "console" => Some(Self::Console),
+            "network" => Some(Self::Network),
+            "dialog" => Some(Self::Dialog),
+            "target" => Some(Self::Target),
+            _ => None,
+        }
+    }
+
+    /// The wire name of this category.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Console => "console",
+            Self::Network => "network",
+            Self::Dialog => "dialog",
+            Self::Target => "target",
+        }
+    }
+}
+
+/// Classify a CDP event method into a buffer category.
+///
+/// Returns `None` for events the daemon does not retain.
+#[must_use]
+pub fn categorize(method: &str) -> Option<EventCategory> {
+    match method {
+        "Runtime.consoleAPICalled" | "Runtime.exceptionThrown" | "Log.entryAdded" => {
+            Some(EventCategory::Console)
+        }
+        "Network.requestWillBeSent"
+        | "Network.responseReceived"
+        | "Network.loadingFinished"
+        | "Network.loadingFailed"
+        | "Page.frameNavigated"
+        | "Page.loadEventFired" => Some(EventCategory::Network),
+        "Page.javascriptDialogOpening" | "Page.javascriptDialogClosed" => {
+            Some(EventCategory::Dialog)
+        }
+        m if m.starts_with("Target.") => Some(EventCategory::Target),
+        _ => None,
+    }
+}
+
+/// A single buffered event as returned by `Agentchrome.getBufferedEvents`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BufferedEvent {
+    pub method: String,
+    pub params: serde_json::Value,
+}
+
+/// Fixed-capacity FIFO that drops the oldest event when full.
+#[derive(Debug)]
+pub struct EventBuffer {
+    capacity: usize,
+    events: VecDeque<BufferedEvent>,
+    dropped: u64,
+}
+
+impl EventBuffer {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Append an event, evicting the oldest one if the buffer is full.
+    pub fn push(&mut self, event: BufferedEvent) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Number of events evicted since the buffer was created.
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Snapshot of the buffered events, oldest first.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<BufferedEvent> {
+        self.events.iter().cloned().collect()
+    }
+}
+
+/// Per-tab event buffers.
+#[derive(Debug)]
+struct TargetBuffers {
+    console: EventBuffer,
+    network: EventBuffer,
+    dialog: EventBuffer,
+}
+
+impl Default for TargetBuffers {
+    fn default() -> Self {
+        Self {
+            console: EventBuffer::new(CONSOLE_BUFFER_CAPACITY),
+            network: EventBuffer::new(NETWORK_BUFFER_CAPACITY),
+            dialog: EventBuffer::new(DIALOG_BUFFER_CAPACITY),
+        }
+    }
+}
+
+impl TargetBuffers {
+    fn get(&self, category: EventCategory) -> Option<&EventBuffer> {
+        match category {
+            EventCategory::Console => Some(&self.console),
+            EventCategory::Network => Some(&self.network),
+            EventCategory::Dialog => Some(&self.dialog),
+            EventCategory::Target => None,
+        }
+    }
+
+    fn get_mut(&mut self, category: EventCategory) -> Option<&mut EventBuffer> {
+        match category {
+            EventCategory::Console => Some(&mut self.console),
+            EventCategory::Network => Some(&mut self.network),
+            EventCategory::Dialog => Some(&mut self.dialog),
+            EventCategory::Target => None,
+        }
+    }
+}
+
+// =============================================================================
+// Record file and socket paths
+// =============================================================================
+
+/// Returns the path to the daemon record: `daemon.json` next to the session file.
+///
+/// # Errors
+///
+/// Returns `SessionError::NoHomeDir` if the home directory cannot be determined.
+pub fn daemon_file_path() -> Result<PathBuf, SessionError> {
+    Ok(session::session_file_path()?.with_file_name("daemon.json"))
+}
+
+/// Longest path a Unix socket address holds (`sun_path` less its NUL
+/// terminator): 108 bytes on Linux, 104 on macOS and the BSDs.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MAX_SOCKET_PATH: usize = 107;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const MAX_SOCKET_PATH: usize = 103;
+
+/// Returns the path to the daemon socket: `daemon.sock` next to the session file.
+///
+/// # Errors
+///
+/// Returns `SessionError::NoHomeDir` if the home directory cannot be determined,
+/// or `SessionError::Io` if the path is too long to bind a Unix socket to
+/// (a long `--session` name under a deep home directory).
+pub fn daemon_socket_path() -> Result<PathBuf, SessionError> {
+    let path = socket_file_path()?;
+    check_socket_path(&path)?;
+    Ok(path)
+}
+
+fn socket_file_path() -> Result<PathBuf, SessionError> {
+    Ok(session::session_file_path()?.with_file_name("daemon.sock"))
+}
+
+fn check_socket_path(path: &Path) -> Result<(), SessionError> {
+    let len = path.as_os_str().len();
+    if len > MAX_SOCKET_PATH {
+        return Err(SessionError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "daemon socket path {} is {len} bytes, over the {MAX_SOCKET_PATH}-byte \
+                 Unix socket limit; use a shorter --session name",
+                path.display()
+            ),
+        )));
+    }
+    Ok(())
+}
+
+/// Build the `ws+unix://` URL for a daemon socket path.
+#[must_use]
+pub fn daemon_url(socket: &Path) -> String {
+    format!("{UNIX_SOCKET_SCHEME}{}", socket.display())
+}
+
+/// Whether `url` points at a daemon socket rather than Chrome itself.
+#[must_use]
+pub fn is_daemon_url(url: &str) -> bool {
+    url.starts_with(UNIX_SOCKET_SCHEME)
+}
+
+/// Write a daemon record to a specific path (atomic, `0o600` on Unix).
+///
+/// # Errors
+///
+/// Returns `SessionError::Io` on I/O failure.
+pub fn write_record_to(path: &Path, record: &DaemonRecord) -> Result<(), SessionError> {
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+    session::write_state_file(path, json.as_bytes())
+}
+
+/// Read a daemon record from a specific path. Returns `Ok(None)` if missing.
+///
+/// # Errors
+///
+/// Returns `SessionError::InvalidFormat` on malformed JSON, or
+/// `SessionError::Io` on other I/O errors.
+pub fn read_record_from(path: &Path) -> Result<Option<DaemonRecord>, SessionError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| SessionError::InvalidFormat(format!("{} at {}", e, path.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(SessionError::Io(e)),
+    }
+}
+
+/// Read the daemon record from its default location.
+///
+/// # Errors
+///
+/// See [`read_record_from`].
+pub fn read_record() -> Result<Option<DaemonRecord>, SessionError> {
+    read_record_from(&daemon_file_path()?)
+}
+
+/// Remove the daemon record and socket. Missing files are not an error.
+///
+/// # Errors
+///
+/// Returns `SessionError::Io` on I/O errors other than "not found".
+pub fn delete_record() -> Result<(), SessionError> {
+    session::delete_session_from(&daemon_file_path()?)?;
+    session::delete_session_from(&socket_file_path()?)
+}
+
+/// Return the record of a daemon that is alive and listening, if any.
+///
+/// A record whose process has exited or whose socket is gone is treated as
+/// stale and ignored.
+#[must_use]
+pub fn running_daemon() -> Option<DaemonRecord> {
+    let record = read_record().ok().flatten()?;
+    let alive = !matches!(is_process_alive(record.pid), ProbeResult::Dead);
+    (alive && Path::new(&record.socket).exists()).then_some(record)
+}
+
+// =============================================================================
+// Client helpers
+// =============================================================================
+
+/// Whether `client` is connected through the daemon.
+#[must_use]
+pub fn is_daemon_client(client: &CdpClient) -> bool {
+    is_daemon_url(client.url())
+}
+
+/// Fetch the events the daemon buffered for the tab behind `managed`.
+///
+/// # Errors
+///
+/// Returns `CdpError` if the request fails or the response is malformed.
+pub async fn buffered_events(
+    managed: &ManagedSession,
+    category: EventCategory,
+) -> Result<Vec<CdpEvent>, CdpError> {
+    let result = managed
+        .send_command(
+            METHOD_GET_BUFFERED_EVENTS,
+            Some(serde_json::json!({ "category": category.as_str() })),
+        )
+        .await?;
+    let events: Vec<BufferedEvent> = serde_json::from_value(result["events"].clone())
+        .map_err(|e| CdpError::InvalidResponse(format!("buffered events: {e}")))?;
+    let dropped = result["dropped"].as_u64().unwrap_or(0);
+    if dropped > 0 {
+        eprintln!(
+            "warning: daemon evicted {dropped} older {} events from its buffer",
+            category.as_str()
+        );
+    }
+    Ok(events
+        .into_iter()
+        .map(|e| CdpEvent {
+            method: e.method,
+            params: e.params,
+            session_id: Some(managed.session_id().to_string()),
+        })
+        .collect())
+}
+
+// =============================================================================
+// Daemon state
+// =============================================================================
+
+/// A session a client attached through the daemon.
+#[derive(Debug, Clone)]
+struct ClientSession {
+    client_id: u64,
+    target_id: String,
+}
+
+/// Shared routing and buffering state of a running daemon.
+#[derive(Debug)]
+struct DaemonState {
+    /// Sessions the daemon attached itself, keyed by session ID → target ID.
+    owned_sessions: HashMap<String, String>,
+    buffers: HashMap<String, TargetBuffers>,
+    target_events: EventBuffer,
+    clients: HashMap<u64, tokio::sync::mpsc::UnboundedSender<String>>,
+    client_sessions: HashMap<String, ClientSession>,
+}
+
+impl DaemonState {
+    fn new() -> Self {
+        Self {
+            owned_sessions: HashMap::new(),
+            buffers: HashMap::new(),
+            target_events: EventBuffer::new(TARGET_BUFFER_CAPACITY),
+            clients: HashMap::new(),
+            client_sessions: HashMap::new(),
+        }
+    }
+
+    fn owned_session_for(&self, target_id: &str) -> Option<String> {
+        self.owned_sessions
+            .iter()
+            .find(|(_, t)| t.as_str() == target_id)
+            .map(|(s, _)| s.clone())
+    }
+
+    fn forget_target(&mut self, target_id: &str) {
+        self.owned_sessions.retain(|_, t| t != target_id);
+        self.buffers.remove(target_id);
+    }
+
+    /// Route an event: buffer it for daemon-owned sessions, forward it to the
+    /// owning client for client sessions, and broadcast browser-level events.
+    /// Returns the target IDs that should be attached as a result.
+    fn route_event(&mut self, event: &CdpEvent) -> Vec<String> {
+        let mut to_attach = Vec::new();
+        if let Some(sid) = &event.session_id {
+            if let Some(target) = self.owned_sessions.get(sid).cloned() {
+                if let Some(category) = categorize(&event.method)
+                    && let Some(buffer) = self.buffers.entry(target).or_default().get_mut(category)
+                {
+                    buffer.push(BufferedEvent {
+                        method: event.method.clone(),
+                        params: event.params.clone(),
+                    });
+                }
+                return to_attach;
+            }
+            if let Some(cs) = self.client_sessions.get(sid) {
+                if let Some(tx) = self.clients.get(&cs.client_id) {
+                    let _ = tx.send(event_frame(event));
+                }
+                return to_attach;
+            }
+        } else {
+            match event.method.as_str() {
+                "Target.targetCreated" => {
+                    let info = &event.params["targetInfo"];
+                    if info["type"] == "page"
+                        && let Some(id) = info["targetId"].as_str()
+                    {
+                        to_attach.push(id.to_string());
+                    }
+                }
+                "Target.targetDestroyed" => {
+                    if let Some(id) = event.params["targetId"].as_str() {
+                        self.forget_target(id);
+                    }
+                }
+                "Target.detachedFromTarget" => {
+                    if let Some(sid) = event.params["sessionId"].as_str() {
+                        self.owned_sessions.remove(sid);
+                        self.client_sessions.remove(sid);
+                    }
+                }
+                _ => {}
+            }
+            if categorize(&event.method) == Some(EventCategory::Target) {
+                self.target_events.push(BufferedEvent {
+                    method: event.method.clone(),
+                    params: event.params.clone(),
+                });
+            }
+        }
+        let frame = event_frame(event);
+        for tx in self.clients.values() {
+            let _ = tx.send(frame.clone());
+        }
+        to_attach
+    }
+
+    fn buffered(&self, target_id: Option<&str>, category: EventCategory) -> serde_json::Value {
+        let buffer = match category {
+            EventCategory::Target => Some(&self.target_events),
+            _ => target_id
+                .and_then(|t| self.buffers.get(t))
+                .and_then(|b| b.get(category)),
+        };
+        buffer.map_or_else(
+            || serde_json::json!({ "events": [], "dropped": 0 }),
+            |b| serde_json::json!({ "events": b.to_vec(), "dropped": b.dropped() }),
+        )
+    }
+
+    fn status(&self) -> serde_json::Value {
+        let mut targets: Vec<serde_json::Value> = self
+            .buffers
+            .iter()
+            .map(|(id, b)| {
+                serde_json::json!({
+                    "target_id": id,
+                    "console": b.console.len(),
+                    "network": b.network.len(),
+                    "dialog": b.dialog.len(),
+                    "dropped": {
+                        "console": b.console.dropped(),
+                        "network": b.network.dropped(),
+                        "dialog": b.dialog.dropped(),
+                    },
+                })
+            })
+            .collect();
+        targets.sort_by(|a, b| a["target_id"].as_str().cmp(&b["target_id"].as_str()));
+        serde_json::json!({
+            "clients": self.clients.len(),
+            "targets": targets,
+            "target_events_dropped": self.target_events.dropped(),
+        })
+    }
+}
+
+/// Serialize an event into the frame format Chrome uses on the wire.
+fn event_frame(event: &CdpEvent) -> String {
+    let mut frame = serde_json::json!({
+        "method": event.method,
+        "params": event.params,
+    });
+    if let Some(sid) = &event.session_id {
+        frame["sessionId"] = serde_json::Value::String(sid.clone());
+    }
+    frame.to_string()
+}
+
+/// Serialize a command result into a response frame.
+fn response_frame(
+    id: &serde_json::Value,
+    session_id: Option<&str>,
+    result: Result<serde_json::Value, CdpError>,
+) -> String {
+    let mut frame = match result {
+        Ok(value) => serde_json::json!({ "id": id, "result": value }),
+        Err(CdpError::Protocol { code, message }) => {
+            serde_json::json!({ "id": id, "error": { "code": code, "message": message } })
+        }
+        Err(e) => {
+            serde_json::json!({ "id": id, "error": { "code": -32000, "message": e.to_string() } })
+        }
+    };
+    if let Some(sid) = session_id {
+        frame["sessionId"] = serde_json::Value::String(sid.to_string());
+    }
+    frame.to_string()
+}
+
+// =============================================================================
+// Server
+// =============================================================================
+
+#[cfg(unix)]
+pub use server::{serve, serve_pipe};
+
+#[cfg(unix)]
+mod server {
+    use std::path::Path;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::{Notify, mpsc};
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::{
+        BUFFERED_DOMAINS, ClientSession, DaemonRecord, DaemonState, EventCategory,
+        METHOD_GET_BUFFERED_EVENTS, METHOD_RELOAD_ROUTES, METHOD_SHUTDOWN, METHOD_STATUS,
+        response_frame, write_record_to,
+    };
+    use crate::cdp::{CdpClient, CdpConfig, CdpError, KeepAliveConfig, PIPE_URL};
+    use crate::chrome::PIPE_PORT;
+    use crate::error::{AppError, ExitCode};
+    use crate::route::{self, RouteTable};
+
+    /// How long Chrome may stay unreachable before the daemon exits.
+    const DISCONNECT_GRACE: Duration = Duration::from_secs(15);
+
+    /// Commands whose data only exists on the session that observed the
+    /// request, so they are redirected to the daemon's own session.
+    const REROUTED_METHODS: &[&str] = &["Network.getResponseBody", "Network.getRequestPostData"];
+
+    struct Daemon {
+        cdp: CdpClient,
+        state: Mutex<DaemonState>,
+        shutdown: Notify,
+        next_client_id: AtomicU64,
+        record: DaemonRecord,
+        /// `network route` rules enforced on every owned session.
+        routes: Mutex<Arc<RouteTable>>,
+    }
+
+    impl Daemon {
+        fn state(&self) -> MutexGuard<'_, DaemonState> {
+            self.state
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+        }
+
+        fn routes(&self) -> Arc<RouteTable> {
+            Arc::clone(
+                &self
+                    .routes
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner),
+            )
+        }
+    }
+
+    /// Run the daemon until it is asked to shut down or Chrome goes away.
+    ///
+    /// Binds `socket_path`, writes `record_path` once the socket is ready, and
+    /// removes both on exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError` if Chrome cannot be reached or the socket cannot be
+    /// bound.
+    pub async fn serve(
+        ws_url: &str,
+        port: u16,
+        socket_path: &Path,
+        record_path: &Path,
+    ) -> Result<(), AppError> {
+        let cdp = CdpClient::connect(ws_url, relay_config()).await?;
+        run(cdp, ws_url, port, socket_path, record_path).await
+    }
+
+    /// Like [`serve`], for a Chrome launched with `--remote-debugging-pipe`
+    /// whose pipe ends this process holds. The daemon is then the browser's
+    /// only connection: it records its own socket as the browser URL, exits
+    /// as soon as the pipe closes, and closes the browser when it stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError` if the descriptors are not pipes, Chrome does not
+    /// answer, or the socket cannot be bound.
+    pub async fn serve_pipe(
+        writer: std::os::fd::OwnedFd,
+        reader: std::os::fd::OwnedFd,
+        socket_path: &Path,
+        record_path: &Path,
+    ) -> Result<(), AppError> {
+        let cdp = CdpClient::connect_pipe(writer, reader, relay_config())?;
+        let url = super::daemon_url(socket_path);
+        run(cdp, &url, PIPE_PORT, socket_path, record_path).await
+    }
+
+    fn relay_config() -> CdpConfig {
+        CdpConfig {
+            // Clients enforce their own timeouts; the daemon just relays.
+            command_timeout: Duration::from_secs(600),
+            channel_capacity: 4096,
+            keepalive: KeepAliveConfig::default(),
+            ..CdpConfig::default()
+        }
+    }
+
+    async fn run(
+        cdp: CdpClient,
+        ws_url: &str,
+        port: u16,
+        socket_path: &Path,
+        record_path: &Path,
+    ) -> Result<(), AppError> {
+        let over_pipe = cdp.url() == PIPE_URL;
+        let events = cdp.subscribe_all().await?;
+
+        let _ = std::fs::remove_file(socket_path);
+        if let Some(parent) = socket_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| socket_error(socket_path, &e))?;
+        }
+        let listener =
+            UnixListener::bind(socket_path).map_err(|e| socket_error(socket_path, &e))?;
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| socket_error(socket_path, &e))?;
+        }
+
+        let record = DaemonRecord {
+            pid: std::process::id(),
+            socket: socket_path.display().to_string(),
+            ws_url: ws_url.to_string(),
+            port,
+            started_at: crate::session::now_iso8601(),
+        };
+        let daemon = Arc::new(Daemon {
+            cdp,
+            state: Mutex::new(DaemonState::new()),
+            shutdown: Notify::new(),
+            next_client_id: AtomicU64::new(1),
+            record: record.clone(),
+            routes: Mutex::new(Arc::new(RouteTable::load().unwrap_or_default())),
+        });
+
+        tokio::spawn(route_events(Arc::clone(&daemon), events));
+        discover_targets(&daemon).await?;
+        write_record_to(record_path, &record)?;
+
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .map_err(|e| socket_error(socket_path, &e))?;
+        let mut watchdog = tokio::time::interval(Duration::from_secs(1));
+        let mut disconnected_since: Option<tokio::time::Instant> = None;
+
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    if let Ok((stream, _)) = accepted {
+                        tokio::spawn(handle_client(Arc::clone(&daemon), stream));
+                    }
+                }
+                () = daemon.shutdown.notified() => break,
+                _ = terminate.recv() => break,
+                _ = tokio::signal::ctrl_c() => break,
+                _ = watchdog.tick() => {
+                    if daemon.cdp.is_connected() {
+                        if disconnected_since.take().is_some() {
+                            // The transport reconnected on a fresh socket: our
+                            // sessions are gone, so attach to every page again.
+                            daemon.state().owned_sessions.clear();
+                            let _ = discover_targets(&daemon).await;
+                        }
+                    } else {
+                        // A closed pipe never comes back.
+                        let grace = if over_pipe { Duration::ZERO } else { DISCONNECT_GRACE };
+                        let since = *disconnected_since.get_or_insert_with(tokio::time::Instant::now);
+                        if since.elapsed() >= grace {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Nothing else can reach a pipe-launched browser once we are gone.
+        if over_pipe && daemon.cdp.is_connected() {
+            let close = daemon.cdp.send_command("Browser.close", None);
+            let _ = tokio::time::timeout(Duration::from_secs(2), close).await;
+        }
+
+        let _ = std::fs::remove_file(socket_path);
+        if let Ok(Some(stored)) = super::read_record_from(record_path)
+            && stored.pid == record.pid
+        {
+            let _ = std::fs::remove_file(record_path);
+        }
+        Ok(())
+    }
+
+    fn socket_error(path: &Path, e: &std::io::Error) -> AppError {
+        AppError {
+            message: format!("daemon socket error: {}: {e}", path.display()),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }
+    }
+
+    /// Turn on target discovery; `Target.targetCreated` fires for every
+    /// existing target and drives [`attach_target`] from the event loop.
+    async fn discover_targets(daemon: &Arc<Daemon>) -> Result<(), CdpError> {
+        daemon
+            .cdp
+            .send_command(
+                "Target.setDiscoverTargets",
+                Some(serde_json::json!({ "discover": true })),
+            )
+            .await
+            .map(|_| ())
+    }
+
+    async fn attach_target(daemon: Arc<Daemon>, target_id: String) {
+        if daemon.state().owned_session_for(&target_id).is_some() {
+            return;
+        }
+        let Ok(session) = daemon.cdp.create_session(&target_id).await else {
+            return;
+        };
+        {
+            let mut state = daemon.state();
+            state
+                .owned_sessions
+                .insert(session.session_id().to_string(), target_id.clone());
+            state.buffers.entry(target_id).or_default();
+        }
+        if let Ok(mut paused_rx) = session.subscribe("Fetch.requestPaused").await {
+            let daemon = Arc::clone(&daemon);
+            let session = session.clone();
+            tokio::spawn(async move {
+                while let Some(event) = paused_rx.recv().await {
+                    let table = daemon.routes();
+                    let _ = route::handle_paused(&session, &table, &event.params).await;
+                }
+            });
+        }
+        if !daemon.routes().is_empty() {
+            let _ = route::enable_interception(&session).await;
+        }
+        for domain in BUFFERED_DOMAINS {
+            let _ = session
+                .send_command(&format!("{domain}.enable"), None)
+                .await;
+        }
+    }
+
+    /// Swap in the current rules file and turn interception on or off in
+    /// every owned session to match.
+    async fn reload_routes(daemon: &Daemon) -> Result<serde_json::Value, CdpError> {
+        let table = RouteTable::load().map_err(|e| CdpError::Internal(e.to_string()))?;
+        let enabled = !table.is_empty();
+        *daemon
+            .routes
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Arc::new(table);
+
+        let sessions: Vec<String> = daemon.state().owned_sessions.keys().cloned().collect();
+        for sid in &sessions {
+            let (method, params) = if enabled {
+                ("Fetch.enable", Some(route::interception_params()))
+            } else {
+                ("Fetch.disable", None)
+            };
+            daemon
+                .cdp
+                .send_command_with_session(method, params, Some(sid.clone()))
+                .await?;
+        }
+        Ok(serde_json::json!({ "intercepting": enabled, "sessions": sessions.len() }))
+    }
+
+    async fn route_events(
+        daemon: Arc<Daemon>,
+        mut events: mpsc::UnboundedReceiver<crate::cdp::CdpEvent>,
+    ) {
+        while let Some(event) = events.recv().await {
+            let to_attach = daemon.state().route_event(&event);
+            for target_id in to_attach {
+                tokio::spawn(attach_target(Arc::clone(&daemon), target_id));
+            }
+        }
+    }
+
+    async fn handle_client(daemon: Arc<Daemon>, stream: UnixStream) {
+        let Ok(ws) = tokio_tungstenite::accept_async(stream).await else {
+            return;
+        };
+        let (mut sink, mut source) = ws.split();
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
+        let client_id = daemon.next_client_id.fetch_add(1, Ordering::Relaxed);
+        daemon.state().clients.insert(client_id, out_tx.clone());
+
+        let writer = tokio::spawn(async move {
+            while let Some(text) = out_rx.recv().await {
+                if sink.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        // One relay task per client hands its commands to Chrome in the
+        // order they arrived; only separate clients run concurrently.
+        let (request_tx, mut request_rx) = mpsc::unbounded_channel::<String>();
+        let relay = {
+            let daemon = Arc::clone(&daemon);
+            let out_tx = out_tx.clone();
+            tokio::spawn(async move {
+                while let Some(text) = request_rx.recv().await {
+                    handle_request(&daemon, client_id, &out_tx, &text).await;
+                }
+            })
+        };
+
+        while let Some(Ok(msg)) = source.next().await {
+            match msg {
+                Message::Text(text) => {
+                    let _ = request_tx.send(text.to_string());
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+        drop(request_tx);
+        let _ = relay.await;
+
+        // Detach every session this client opened so Chrome does not keep
+        // them (and their enabled domains) alive after the CLI exits.
+        let orphaned: Vec<String> = {
+            let mut state = daemon.state();
+            state.clients.remove(&client_id);
+            let sessions: Vec<String> = state
+                .client_sessions
+                .iter()
+                .filter(|(_, cs)| cs.client_id == client_id)
+                .map(|(sid, _)| sid.clone())
+                .collect();
+            for sid in &sessions {
+                state.client_sessions.remove(sid);
+            }
+            sessions
+        };
+        for sid in orphaned {
+            let _ = daemon
+                .cdp
+                .send_command(
+                    "Target.detachFromTarget",
+                    Some(serde_json::json!({ "sessionId": sid })),
+                )
+                .await;
+        }
+        drop(out_tx);
+        let _ = writer.await;
+    }
+
+    /// Handle one client request. Relayed commands are queued on the
+    /// connection before this returns, but their responses are awaited in
+    /// the background: a command blocked on a dialog must not hold back the
+    /// `Page.handleJavaScriptDialog` that unblocks it.
+    async fn handle_request(
+        daemon: &Arc<Daemon>,
+        client_id: u64,
+        out_tx: &mpsc::UnboundedSender<String>,
+        text: &str,
+    ) {
+        let Ok(request) = serde_json::from_str::<serde_json::Value>(text) else {
+            return;
+        };
+        let id = request["id"].clone();
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        let params = request.get("params").cloned();
+        let session_id = request["sessionId"].as_str().map(String::from);
+
+        if method == METHOD_RELOAD_ROUTES {
+            let result = reload_routes(daemon).await;
+            let _ = out_tx.send(response_frame(&id, session_id.as_deref(), result));
+            return;
+        }
+
+        if method.starts_with("Agentchrome.") {
+            let result = handle_local(daemon, &method, params.as_ref(), session_id.as_deref());
+            let _ = out_tx.send(response_frame(&id, session_id.as_deref(), result));
+            return;
+        }
+
+        let routed_session = if REROUTED_METHODS.contains(&method.as_str()) {
+            let state = daemon.state();
+            session_id
+                .as_deref()
+                .and_then(|sid| state.client_sessions.get(sid))
+                .and_then(|cs| state.owned_session_for(&cs.target_id))
+                .or_else(|| session_id.clone())
+        } else {
+            session_id.clone()
+        };
+        let pending = match daemon
+            .cdp
+            .queue_command_with_session(&method, params.clone(), routed_session)
+            .await
+        {
+            Ok(pending) => pending,
+            Err(e) => {
+                let _ = out_tx.send(response_frame(&id, session_id.as_deref(), Err(e)));
+                return;
+            }
+        };
+
+        let daemon = Arc::clone(daemon);
+        let out_tx = out_tx.clone();
+        tokio::spawn(async move {
+            let result = pending.await;
+            if let Ok(value) = &result {
+                track_session(&daemon, client_id, &method, params.as_ref(), value);
+            }
+            let _ = out_tx.send(response_frame(&id, session_id.as_deref(), result));
+        });
+    }
+
+    /// Remember which client owns sessions it attaches so their events are
+    /// routed back to it and they are detached when it disconnects.
+    fn track_session(
+        daemon: &Daemon,
+        client_id: u64,
+        method: &str,
+        params: Option<&serde_json::Value>,
+        result: &serde_json::Value,
+    ) {
+        let mut state = daemon.state();
+        match method {
+            "Target.attachToTarget" => {
+                if let (Some(sid), Some(target)) = (
+                    result["sessionId"].as_str(),
+                    params.and_then(|p| p["targetId"].as_str()),
+                ) {
+                    state.client_sessions.insert(
+                        sid.to_string(),
+                        ClientSession {
+                            client_id,
+                            target_id: target.to_string(),
+                        },
+                    );
+                }
+            }
+            "Target.detachFromTarget" => {
+                if let Some(sid) = params.and_then(|p| p["sessionId"].as_str()) {
+                    state.client_sessions.remove(sid);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_local(
+        daemon: &Daemon,
+        method: &str,
+        params: Option<&serde_json::Value>,
+        session_id: Option<&str>,
+    ) -> Result<serde_json::Value, CdpError> {
+        match method {
+            METHOD_GET_BUFFERED_EVENTS => {
+                let name = params
+                    .and_then(|p| p["category"].as_str())
+                    .unwrap_or_default();
+                let category =
+                    EventCategory::from_name(name).ok_or_else(|| CdpError::Protocol {
+                        code: -32602,
+                        message: format!("unknown event category: '{name}'"),
+                    })?;
+                let state = daemon.state();
+                let target_id = params
+                    .and_then(|p| p["targetId"].as_str())
+                    .map(String::from)
+                    .or_else(|| {
+                        session_id
+                            .and_then(|sid| state.client_sessions.get(sid))
+                            .map(|cs| cs.target_id.clone())
+                    });
+                Ok(state.buffered(target_id.as_deref(), category))
+            }
+            METHOD_STATUS => {
+                let mut status = daemon.state().status();
+                status["pid"] = daemon.record.pid.into();
+                status["ws_url"] = daemon.record.ws_url.clone().into();
+                status["started_at"] = daemon.record.started_at.clone().into();
+                Ok(status)
+            }
+            METHOD_SHUTDOWN => {
+                daemon.shutdown.notify_one();
+                Ok(serde_json::json!({}))
+            }
+            _ => Err(CdpError::Protocol {
+                code: -32601,
+                message: format!("'{method}' wasn't found"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_socket_path_rejects_paths_over_the_limit() {
+        let fits = PathBuf::from(format!("/{}", "a".repeat(MAX_SOCKET_PATH - 1)));
+        assert!(check_socket_path(&fits).is_ok());
+
+        let long = PathBuf::from(format!("/{}", "a".repeat(MAX_SOCKET_PATH)));
+        let err = check_socket_path(&long).unwrap_err().to_string();
+        assert!(err.contains("shorter --session name"), "{err}");
+    }
+
+    fn event(method: &str, params: serde_json::Value, session: Option<&str>) -> CdpEvent {
+        CdpEvent {
+            method: method.to_string(),
+            params,
+            session_id: session.map(String::from),
+        }
+    }
+
+    #[test]
+    fn categorize_known_events() {
+        assert_eq!(
+            categorize("Runtime.consoleAPICalled"),
+            Some(EventCategory::Console)
+        );
+        assert_eq!(categorize("Log.entryAdded"), Some(EventCategory::Console));
+        assert_eq!(
+            categorize("Network.loadingFailed"),
+            Some(EventCategory::Network)
+        );
+        assert_eq!(
+            categorize("Page.frameNavigated"),
+            Some(EventCategory::Network)
+        );
+        assert_eq!(
+            categorize("Page.javascriptDialogOpening"),
+            Some(EventCategory::Dialog)
+        );
+        assert_eq!(
+            categorize("Target.targetCreated"),
+            Some(EventCategory::Target)
+        );
+        assert_eq!(categorize("Network.dataReceived"), None);
+        assert_eq!(categorize("DOM.documentUpdated"), None);
+    }
+
+    #[test]
+    fn category_names_round_trip() {
+        for c in [
+            EventCategory::Console,
+            EventCategory::Network,
+            EventCategory::Dialog,
+            EventCategory::Target,
+        ] {
+            assert_eq!(EventCategory::from_name(c.as_str()), Some(c));
+        }
+        assert_eq!(EventCategory::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn event_buffer_evicts_oldest() {
+        let mut buffer = EventBuffer::new(2);
+        for i in 0..3 {
+            buffer.push(BufferedEvent {
+                method: format!("m{i}"),
+                params: serde_json::Value::Null,
+            });
+        }
+        let methods: Vec<String> = buffer.to_vec().into_iter().map(|e| e.method).collect();
+        assert_eq!(methods, vec!["m1", "m2"]);
+        assert_eq!(buffer.dropped(), 1);
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let dir = std::env::temp_dir().join(format!("agentchrome-daemon-{}", std::process::id()));
+        let path = dir.join("daemon.json");
+        let record = DaemonRecord {
+            pid: 42,
+            socket: "/tmp/daemon.sock".into(),
+            ws_url: "ws://127.0.0.1:9222/devtools/browser/abc".into(),
+            port: 9222,
+            started_at: "2026-01-01T00:00:00Z".into(),
+        };
+        write_record_to(&path, &record).unwrap();
+        assert_eq!(read_record_from(&path).unwrap(), Some(record.clone()));
+        assert_eq!(record.url(), "ws+unix:///tmp/daemon.sock");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(read_record_from(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn owned_session_events_are_buffered_not_broadcast() {
+        let mut state = DaemonState::new();
+        state.owned_sessions.insert("S1".into(), "T1".into());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        state.clients.insert(1, tx);
+
+        state.route_event(&event(
+            "Runtime.consoleAPICalled",
+            serde_json::json!({"type": "log"}),
+            Some("S1"),
+        ));
+        assert!(rx.try_recv().is_err());
+        let buffered = state.buffered(Some("T1"), EventCategory::Console);
+        assert_eq!(buffered["events"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn status_reports_evicted_events() {
+        let mut state = DaemonState::new();
+        state.owned_sessions.insert("S1".into(), "T1".into());
+        for _ in 0..=DIALOG_BUFFER_CAPACITY {
+            state.route_event(&event(
+                "Page.javascriptDialogOpening",
+                serde_json::json!({"type": "alert"}),
+                Some("S1"),
+            ));
+        }
+        let status = state.status();
+        assert_eq!(status["targets"][0]["dialog"], DIALOG_BUFFER_CAPACITY);
+        assert_eq!(status["targets"][0]["dropped"]["dialog"], 1);
+        assert_eq!(status["targets"][0]["dropped"]["console"], 0);
+        let buffered = state.buffered(Some("T1"), EventCategory::Dialog);
+        assert_eq!(buffered["dropped"], 1);
+    }
+
+    #[test]
+    fn client_session_events_go_to_owner_only() {
+        let mut state = DaemonState::new();
+        let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+        let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+        state.clients.insert(1, tx1);
+        state.clients.insert(2, tx2);
+        state.client_sessions.insert(
+            "C1".into(),
+            ClientSession {
+                client_id: 1,
+                target_id: "T1".into(),
+            },
+        );
+
+        state.route_event(&event(
+            "Page.loadEventFired",
+            serde_json::json!({}),
+            Some("C1"),
+        ));
+        let frame: serde_json::Value = serde_json::from_str(&rx1.try_recv().unwrap()).unwrap();
+        assert_eq!(frame["sessionId"], "C1");
+        assert!(rx2.try_recv().is_err());
+    }
+
+    #[test]
+    fn target_created_requests_attach_for_pages_only() {
+        let mut state = DaemonState::new();
+        let page = state.route_event(&event(
+            "Target.targetCreated",
+            serde_json::json!({"targetInfo": {"targetId": "T1", "type": "page"}}),
+            None,
+        ));
+        let worker = state.route_event(&event(
+            "Target.targetCreated",
+            serde_json::json!({"targetInfo": {"targetId": "W1", "type": "service_worker"}}),
+            None,
+        ));
+        assert_eq!(page, vec!["T1".to_string()]);
+        assert!(worker.is_empty());
+        assert_eq!(state.target_events.len(), 2);
+    }
+
+    #[test]
+    fn target_destroyed_drops_buffers() {
+        let mut state = DaemonState::new();
+        state.owned_sessions.insert("S1".into(), "T1".into());
+        state.buffers.entry("T1".into()).or_default();
+        state.route_event(&event(
+            "Target.targetDestroyed",
+            serde_json::json!({"targetId": "T1"}),
+            None,
+        ));
+        assert!(state.owned_sessions.is_empty());
+        assert!(state.buffers.is_empty());
+    }
+
+    #[test]
+    fn response_frame_maps_errors() {
+        let ok = response_frame(
+            &serde_json::json!(3),
+            Some("S"),
+            Ok(serde_json::json!({"a": 1})),
+        );
+        let ok: serde_json::Value = serde_json::from_str(&ok).unwrap();
+        assert_eq!(ok["id"], 3);
+        assert_eq!(ok["result"]["a"], 1);
+        assert_eq!(ok["sessionId"], "S");
+
+        let err = response_frame(
+            &serde_json::json!(4),
+            None,
+            Err(CdpError::Protocol {
+                code: -32601,
+                message: "nope".into(),
+            }),
+        );
+        let err: serde_json::Value = serde_json::from_str(&err).unwrap();
+        assert_eq!(err["error"]["code"], -32601);
+        assert!(err.get("sessionId").is_none());
+    }
+
+    #[test]
+    fn daemon_url_detection() {
+        assert!(is_daemon_url("ws+unix:///tmp/x.sock"));
+        assert!(!is_daemon_url("ws://127.0.0.1:9222/devtools/browser/x"));
+    }
+}
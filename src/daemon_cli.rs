@@ -0,0 +1,470 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use agentchrome::cdp::{CdpClient, CdpConfig};
+use agentchrome::connection::{ReconnectPolicy, resolve_connection_with_reconnect};
+use agentchrome::daemon::{self, DaemonRecord};
+use agentchrome::error::{AppError, ExitCode};
+
+use crate::cli::{DaemonArgs, DaemonCommand, DaemonStartArgs, GlobalOpts};
+use crate::output::print_output;
+
+/// How long `daemon start` waits for the background process to become ready.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long `daemon stop` waits for the process to exit before signalling it.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct DaemonStatus {
+    running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    socket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ws_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clients: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    targets: Option<serde_json::Value>,
+}
+
+impl DaemonStatus {
+    fn not_running() -> Self {
+        Self {
+            running: false,
+            pid: None,
+            socket: None,
+            ws_url: None,
+            port: None,
+            started_at: None,
+            clients: None,
+            targets: None,
+        }
+    }
+
+    fn from_record(record: DaemonRecord) -> Self {
+        Self {
+            running: true,
+            pid: Some(record.pid),
+            socket: Some(record.socket),
+            ws_url: Some(record.ws_url),
+            port: Some(record.port),
+            started_at: Some(record.started_at),
+            clients: None,
+            targets: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StopResult {
+    stopped: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+}
+
+// =============================================================================
+// Output formatting
+// =============================================================================
+
+fn print_status_plain(status: &DaemonStatus) {
+    if !status.running {
+        println!("Daemon not running");
+        return;
+    }
+    println!("Daemon running (pid {})", status.pid.unwrap_or_default());
+    if let Some(ws_url) = &status.ws_url {
+        println!("  browser: {ws_url}");
+    }
+    if let Some(socket) = &status.socket {
+        println!("  socket:  {socket}");
+    }
+    if let Some(clients) = status.clients {
+        println!("  clients: {clients}");
+    }
+    if let Some(targets) = status.targets.as_ref().and_then(|t| t.as_array()) {
+        for t in targets {
+            println!(
+                "  tab {}: {} console, {} network, {} dialog",
+                t["target_id"].as_str().unwrap_or("?"),
+                t["console"],
+                t["network"],
+                t["dialog"]
+            );
+        }
+    }
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `daemon` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the daemon cannot be started, reached, or stopped.
+pub async fn execute_daemon(global: &GlobalOpts, args: &DaemonArgs) -> Result<(), AppError> {
+    match &args.command {
+        DaemonCommand::Start(start_args) => execute_start(global, start_args).await,
+        DaemonCommand::Stop => execute_stop(global).await,
+        DaemonCommand::Status => execute_status(global).await,
+    }
+}
+
+// =============================================================================
+// Start
+// =============================================================================
+
+/// Fail if a daemon is already running for the current session.
+///
+/// # Errors
+///
+/// Returns `AppError` naming the running daemon's PID.
+pub fn ensure_not_running() -> Result<(), AppError> {
+    match daemon::running_daemon() {
+        Some(record) => Err(AppError {
+            message: format!(
+                "Daemon already running (pid {}). Run 'agentchrome daemon stop' first.",
+                record.pid
+            ),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }),
+        None => Ok(()),
+    }
+}
+
+async fn execute_start(global: &GlobalOpts, args: &DaemonStartArgs) -> Result<(), AppError> {
+    ensure_not_running()?;
+
+    if args.pipe {
+        return run_pipe_foreground().await;
+    }
+
+    let resolved = resolve_connection_with_reconnect(
+        &global.host,
+        global.port,
+        global.ws_url.as_deref(),
+        &ReconnectPolicy::default(),
+    )
+    .await?;
+
+    if args.foreground {
+        return run_foreground(&resolved.ws_url, resolved.port).await;
+    }
+
+    let record = spawn_background(global, Upstream::WebSocket(&resolved.ws_url)).await?;
+    let status = DaemonStatus::from_record(record);
+    if global.output.plain {
+        print_status_plain(&status);
+        return Ok(());
+    }
+    print_output(&status, &global.output)
+}
+
+#[cfg(unix)]
+async fn run_foreground(ws_url: &str, port: u16) -> Result<(), AppError> {
+    let socket = daemon::daemon_socket_path()?;
+    let record_path = daemon::daemon_file_path()?;
+    daemon::serve(ws_url, port, &socket, &record_path).await
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unused_async)]
+async fn run_foreground(_ws_url: &str, _port: u16) -> Result<(), AppError> {
+    Err(unsupported_platform())
+}
+
+/// Serve a pipe-launched Chrome whose debugging pipe `connect --launch --pipe`
+/// placed on descriptors 3 and 4 of this process.
+#[cfg(unix)]
+async fn run_pipe_foreground() -> Result<(), AppError> {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let socket = daemon::daemon_socket_path()?;
+    let record_path = daemon::daemon_file_path()?;
+    // SAFETY: the launching process maps the pipe onto descriptors 3 and 4
+    // before exec (see `pass_pipe_fds`), and nothing else in this process
+    // opens or owns them. `serve_pipe` rejects descriptors that are not pipes.
+    let (writer, reader) = unsafe { (OwnedFd::from_raw_fd(3), OwnedFd::from_raw_fd(4)) };
+    daemon::serve_pipe(writer, reader, &socket, &record_path).await
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unused_async)]
+async fn run_pipe_foreground() -> Result<(), AppError> {
+    Err(unsupported_platform())
+}
+
+/// Where a spawned daemon finds Chrome.
+pub enum Upstream<'a> {
+    /// The browser-level WebSocket URL.
+    WebSocket(&'a str),
+    /// The debugging pipe of a Chrome this process launched.
+    #[cfg(unix)]
+    Pipe(&'a agentchrome::chrome::DebugPipe),
+}
+
+/// Re-run this binary as `daemon start --foreground` in a new session and wait
+/// until it has written its record file.
+///
+/// # Errors
+///
+/// Returns `AppError` if the process cannot be spawned, exits early, or does
+/// not become ready within the startup timeout.
+#[cfg(unix)]
+pub async fn spawn_background(
+    global: &GlobalOpts,
+    upstream: Upstream<'_>,
+) -> Result<DaemonRecord, AppError> {
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+
+    // Fail here rather than in the detached child, whose errors only reach
+    // the log.
+    daemon::daemon_socket_path()?;
+    let exe = std::env::current_exe().map_err(|e| start_failed(&e.to_string()))?;
+    let log_path = daemon::daemon_file_path()?.with_file_name("daemon.log");
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| start_failed(&e.to_string()))?;
+    }
+    let log = std::fs::File::create(&log_path).map_err(|e| start_failed(&e.to_string()))?;
+
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(["--host", &global.host])
+        .args(["daemon", "start", "--foreground"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::from(log));
+    if let Some(config) = &global.config {
+        cmd.arg("--config").arg(config);
+    }
+    // The child must resolve the same session directory for its socket and
+    // record, even when the session came from the command line.
+    cmd.args(["--session", agentchrome::session::session_name()]);
+    match upstream {
+        Upstream::WebSocket(ws_url) => {
+            cmd.args(["--ws-url", ws_url]);
+        }
+        Upstream::Pipe(pipe) => {
+            use std::os::fd::AsRawFd;
+            agentchrome::cdp::pass_pipe_fds(
+                &mut cmd,
+                pipe.writer.as_raw_fd(),
+                pipe.reader.as_raw_fd(),
+            );
+            cmd.arg("--pipe");
+        }
+    }
+    // SAFETY: `pre_exec` runs in the child after fork and before exec.
+    // The closure only calls `setsid`, an async-signal-safe libc function,
+    // and returns an OS error if it fails.
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = cmd.spawn().map_err(|e| start_failed(&e.to_string()))?;
+
+    let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        if let Some(record) = daemon::running_daemon()
+            && record.pid == child.id()
+        {
+            return Ok(record);
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            let detail = std::fs::read_to_string(&log_path).unwrap_or_default();
+            return Err(start_failed(&format!(
+                "daemon exited with {status}: {}",
+                detail.trim()
+            )));
+        }
+        if tokio::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            return Err(AppError {
+                message: format!(
+                    "Daemon did not become ready within {}s (log: {})",
+                    STARTUP_TIMEOUT.as_secs(),
+                    log_path.display()
+                ),
+                code: ExitCode::TimeoutError,
+                custom_json: None,
+            });
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unused_async)]
+pub async fn spawn_background(
+    _global: &GlobalOpts,
+    _upstream: Upstream<'_>,
+) -> Result<DaemonRecord, AppError> {
+    Err(unsupported_platform())
+}
+
+fn start_failed(detail: &str) -> AppError {
+    AppError {
+        message: format!("Failed to start daemon: {detail}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
+
+#[cfg(not(unix))]
+fn unsupported_platform() -> AppError {
+    AppError {
+        message: "The daemon requires Unix domain sockets and is not supported on this platform"
+            .into(),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
+
+// =============================================================================
+// Stop / status
+// =============================================================================
+
+async fn connect_daemon(record: &DaemonRecord) -> Result<CdpClient, AppError> {
+    let config = CdpConfig {
+        connect_timeout: Duration::from_secs(2),
+        command_timeout: Duration::from_secs(5),
+        ..CdpConfig::default()
+    };
+    Ok(CdpClient::connect(&record.url(), config).await?)
+}
+
+async fn execute_stop(global: &GlobalOpts) -> Result<(), AppError> {
+    let Some(record) = daemon::running_daemon() else {
+        // Clean up a stale record left by a daemon that crashed.
+        daemon::delete_record()?;
+        return print_output(
+            &StopResult {
+                stopped: false,
+                pid: None,
+            },
+            &global.output,
+        );
+    };
+
+    if let Ok(client) = connect_daemon(&record).await {
+        let _ = client.send_command(daemon::METHOD_SHUTDOWN, None).await;
+    }
+    if !wait_for_exit(record.pid).await {
+        terminate(record.pid);
+        wait_for_exit(record.pid).await;
+    }
+    daemon::delete_record()?;
+
+    let result = StopResult {
+        stopped: true,
+        pid: Some(record.pid),
+    };
+    if global.output.plain {
+        println!("Daemon stopped (pid {})", record.pid);
+        return Ok(());
+    }
+    print_output(&result, &global.output)
+}
+
+pub(crate) async fn wait_for_exit(pid: u32) -> bool {
+    let deadline = tokio::time::Instant::now() + SHUTDOWN_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        if matches!(
+            agentchrome::chrome::is_process_alive(pid),
+            agentchrome::chrome::ProbeResult::Dead
+        ) {
+            return true;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    false
+}
+
+pub(crate) fn terminate(pid: u32) {
+    #[cfg(unix)]
+    {
+        // PID values are always within i32 range on all supported platforms.
+        #[allow(clippy::cast_possible_wrap)]
+        let pid_i32 = pid as i32;
+        // SAFETY: libc::kill only sends a signal to the given pid.
+        unsafe { libc::kill(pid_i32, libc::SIGTERM) };
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+    }
+}
+
+async fn execute_status(global: &GlobalOpts) -> Result<(), AppError> {
+    let status = match daemon::running_daemon() {
+        Some(record) => {
+            let live = match connect_daemon(&record).await {
+                Ok(client) => client.send_command(daemon::METHOD_STATUS, None).await.ok(),
+                Err(_) => None,
+            };
+            let mut status = DaemonStatus::from_record(record);
+            if let Some(live) = live {
+                status.clients = live["clients"].as_u64();
+                status.targets = Some(live["targets"].clone());
+            }
+            status
+        }
+        None => DaemonStatus::not_running(),
+    };
+
+    if global.output.plain {
+        print_status_plain(&status);
+        return Ok(());
+    }
+    print_output(&status, &global.output)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_running_status_serializes_flat() {
+        let json = serde_json::to_value(DaemonStatus::not_running()).unwrap();
+        assert_eq!(json, serde_json::json!({"running": false}));
+    }
+
+    #[test]
+    fn status_from_record_omits_live_fields() {
+        let status = DaemonStatus::from_record(DaemonRecord {
+            pid: 7,
+            socket: "/tmp/d.sock".into(),
+            ws_url: "ws://127.0.0.1:9222/devtools/browser/x".into(),
+            port: 9222,
+            started_at: "2026-01-01T00:00:00Z".into(),
+        });
+        let json = serde_json::to_value(status).unwrap();
+        assert_eq!(json["running"], true);
+        assert_eq!(json["pid"], 7);
+        assert!(json.get("clients").is_none());
+    }
+}
@@ -262,7 +262,8 @@ pub(crate) const SUGGESTION_SHADOW_DOM: &str = "Shadow DOM hosts encapsulate the
      Standard CSS selectors do not pierce shadow boundaries.";
 
 pub(crate) const SUGGESTION_CANVAS: &str = "Canvas-rendered UI is not accessible via the DOM or accessibility tree. \
-     Use 'interact click-at' with coordinate targeting; the 'page snapshot' accessibility tree \
+     Use 'interact click-at' with coordinate targeting, reading positions from \
+     'page screenshot --annotate grid'; the 'page snapshot' accessibility tree \
      will be sparse for canvas content.";
 
 pub(crate) const SUGGESTION_MEDIA: &str = "Media elements may gate page flow. Use 'agentchrome media play 0' to unblock audio/video \
@@ -361,15 +361,11 @@ async fn resolve_node(
     // UID resolution
     if snapshot::is_uid(target) {
         let state = snapshot::read_snapshot_state()?.ok_or_else(AppError::no_snapshot_state)?;
-        let backend_node_id = state
-            .uid_map
-            .get(target)
-            .copied()
-            .ok_or_else(|| AppError::uid_not_found(target))?;
+        let backend_node_id = snapshot::lookup_uid(&state, target)?;
 
         let node_id = push_backend_node_to_frontend(session, backend_node_id, target)
             .await
-            .map_err(|_| AppError::stale_uid(target))?;
+            .map_err(|_| snapshot::stale_uid_error(&state, target))?;
         return Ok(ResolvedNode {
             node_id,
             backend_node_id,
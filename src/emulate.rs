@@ -100,21 +100,10 @@ pub struct EmulateState {
     pub baseline_viewport: Option<ViewportState>,
 }
 
-/// Returns the path to the emulation state file: `~/.agentchrome/emulate-state.json`.
+/// Returns the path to the emulation state file: `emulate-state.json` in the
+/// selected session's state directory.
 fn emulate_state_path() -> Result<PathBuf, AppError> {
-    #[cfg(unix)]
-    let key = "HOME";
-    #[cfg(windows)]
-    let key = "USERPROFILE";
-
-    let home = std::env::var(key)
-        .map(PathBuf::from)
-        .map_err(|_| AppError {
-            message: "could not determine home directory".to_string(),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
-    Ok(home.join(".agentchrome").join("emulate-state.json"))
+    Ok(agentchrome::session::session_file_path()?.with_file_name("emulate-state.json"))
 }
 
 /// Write emulation state to the given path (atomic write + `0o600` permissions on Unix).
@@ -167,6 +167,65 @@ impl AppError {
         }
     }
 
+    #[must_use]
+    pub fn pdf_failed(description: &str) -> Self {
+        Self {
+            message: format!("PDF export failed: {description}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }
+    }
+
+    #[must_use]
+    pub fn archive_failed(description: &str) -> Self {
+        Self {
+            message: format!("Page archive failed: {description}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }
+    }
+
+    #[must_use]
+    pub fn table_extraction_failed(description: &str) -> Self {
+        Self {
+            message: format!("Table extraction failed: {description}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }
+    }
+
+    #[must_use]
+    pub fn extraction_failed(description: &str) -> Self {
+        Self {
+            message: format!("Extraction failed: {description}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        }
+    }
+
+    /// A `page extract` run whose required fields were missing or invalid.
+    /// `report` is the extraction output (`data` and `errors`), echoed in the
+    /// error JSON.
+    #[must_use]
+    pub fn extraction_invalid(failed_fields: usize, report: &serde_json::Value) -> Self {
+        let message = format!("{failed_fields} required field(s) missing or invalid");
+        let mut custom = serde_json::json!({
+            "error": message,
+            "code": ExitCode::GeneralError as u8,
+            "kind": "extraction_invalid",
+        });
+        if let (Some(obj), Some(fields)) = (custom.as_object_mut(), report.as_object()) {
+            for (key, value) in fields {
+                obj.insert(key.clone(), value.clone());
+            }
+        }
+        Self {
+            message,
+            code: ExitCode::GeneralError,
+            custom_json: Some(custom.to_string()),
+        }
+    }
+
     #[must_use]
     pub fn uid_not_found(uid: &str) -> Self {
         Self {
@@ -582,6 +641,79 @@ impl AppError {
         }
     }
 
+    /// Stale UID error that names the element's last known role and name.
+    #[must_use]
+    pub fn stale_uid_last_known(uid: &str, role: &str, name: &str) -> Self {
+        let message = format!(
+            "UID '{uid}' refers to an element that no longer exists (last seen as {role} \
+             \"{name}\"). Run 'agentchrome page snapshot' to refresh."
+        );
+        let custom = serde_json::json!({
+            "error": message,
+            "code": ExitCode::GeneralError as u8,
+            "kind": "stale_uid",
+            "uid": uid,
+            "last_known": { "role": role, "name": name },
+        });
+        Self {
+            message,
+            code: ExitCode::GeneralError,
+            custom_json: Some(custom.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn screenshot_mismatch(
+        baseline: &str,
+        mismatch_ratio: f64,
+        threshold: f64,
+        report: &serde_json::Value,
+    ) -> Self {
+        let message = format!(
+            "Screenshot differs from baseline {baseline}: mismatch ratio {mismatch_ratio:.4} \
+             exceeds threshold {threshold}"
+        );
+        let mut custom = serde_json::json!({
+            "error": message,
+            "code": ExitCode::GeneralError as u8,
+            "kind": "screenshot_mismatch",
+        });
+        if let (Some(obj), Some(fields)) = (custom.as_object_mut(), report.as_object()) {
+            for (key, value) in fields {
+                obj.insert(key.clone(), value.clone());
+            }
+        }
+        Self {
+            message,
+            code: ExitCode::GeneralError,
+            custom_json: Some(custom.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn snapshot_mismatch(
+        golden: &str,
+        compared_nodes: usize,
+        mismatches: &serde_json::Value,
+    ) -> Self {
+        let count = mismatches.as_array().map_or(0, Vec::len);
+        let message = format!("Snapshot does not match golden file {golden}: {count} mismatch(es)");
+        let custom = serde_json::json!({
+            "error": message,
+            "code": ExitCode::GeneralError as u8,
+            "kind": "snapshot_mismatch",
+            "golden": golden,
+            "passed": false,
+            "compared_nodes": compared_nodes,
+            "mismatches": mismatches,
+        });
+        Self {
+            message,
+            code: ExitCode::GeneralError,
+            custom_json: Some(custom.to_string()),
+        }
+    }
+
     #[must_use]
     pub fn element_not_scrollable(descriptor: &str) -> Self {
         Self {
@@ -836,6 +968,53 @@ mod tests {
         assert!(matches!(err.code, ExitCode::GeneralError));
     }
 
+    #[test]
+    fn pdf_failed_error() {
+        let err = AppError::pdf_failed("printing is not available");
+        assert!(err.message.contains("PDF export failed"));
+        assert!(err.message.contains("printing is not available"));
+        assert!(matches!(err.code, ExitCode::GeneralError));
+    }
+
+    #[test]
+    fn archive_failed_error() {
+        let err = AppError::archive_failed("response missing 'data'");
+        assert!(err.message.contains("Page archive failed"));
+        assert!(err.message.contains("response missing 'data'"));
+        assert!(matches!(err.code, ExitCode::GeneralError));
+    }
+
+    #[test]
+    fn table_extraction_failed_error() {
+        let err = AppError::table_extraction_failed("ReferenceError: x is not defined");
+        assert!(err.message.contains("Table extraction failed"));
+        assert!(err.message.contains("ReferenceError"));
+        assert!(matches!(err.code, ExitCode::GeneralError));
+    }
+
+    #[test]
+    fn extraction_failed_error() {
+        let err = AppError::extraction_failed("invalid schema schema.json: expected value");
+        assert!(err.message.contains("Extraction failed"));
+        assert!(err.message.contains("schema.json"));
+        assert!(err.custom_json.is_none());
+    }
+
+    #[test]
+    fn extraction_invalid_error_carries_report() {
+        let report = serde_json::json!({
+            "data": { "price": null },
+            "errors": [{ "field": "price", "error": "no element matches '.price'", "required": true }],
+        });
+        let err = AppError::extraction_invalid(1, &report);
+        assert!(err.message.contains("1 required field(s)"));
+        let json: serde_json::Value =
+            serde_json::from_str(err.custom_json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["kind"], "extraction_invalid");
+        assert_eq!(json["errors"][0]["field"], "price");
+        assert!(json["data"]["price"].is_null());
+    }
+
     #[test]
     fn uid_not_found_error() {
         let err = AppError::uid_not_found("s99");
@@ -844,6 +1023,52 @@ mod tests {
         assert!(matches!(err.code, ExitCode::GeneralError));
     }
 
+    #[test]
+    fn stale_uid_last_known_error() {
+        let err = AppError::stale_uid_last_known("s12", "button", "Save");
+        assert!(matches!(err.code, ExitCode::GeneralError));
+        assert!(err.message.contains("s12"));
+        assert!(err.message.contains("button \"Save\""));
+
+        let json: serde_json::Value =
+            serde_json::from_str(err.custom_json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["kind"], "stale_uid");
+        assert_eq!(json["uid"], "s12");
+        assert_eq!(json["last_known"]["role"], "button");
+        assert_eq!(json["last_known"]["name"], "Save");
+    }
+
+    #[test]
+    fn screenshot_mismatch_error() {
+        let report = serde_json::json!({ "baseline": "home.png", "mismatched_pixels": 42 });
+        let err = AppError::screenshot_mismatch("home.png", 0.25, 0.1, &report);
+        assert!(matches!(err.code, ExitCode::GeneralError));
+        assert!(err.message.contains("home.png"));
+        assert!(err.message.contains("0.2500"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(err.custom_json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["kind"], "screenshot_mismatch");
+        assert_eq!(json["mismatched_pixels"], 42);
+        assert_eq!(json["code"], 1);
+    }
+
+    #[test]
+    fn snapshot_mismatch_error() {
+        let mismatches = serde_json::json!([{ "path": "RootWebArea > button", "kind": "missing" }]);
+        let err = AppError::snapshot_mismatch("golden.yaml", 7, &mismatches);
+        assert!(matches!(err.code, ExitCode::GeneralError));
+        assert!(err.message.contains("golden.yaml"));
+        assert!(err.message.contains("1 mismatch"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(err.custom_json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["kind"], "snapshot_mismatch");
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["compared_nodes"], 7);
+        assert_eq!(json["mismatches"][0]["kind"], "missing");
+    }
+
     #[test]
     fn invalid_clip_error() {
         let err = AppError::invalid_clip("abc");
@@ -153,6 +153,7 @@ mod tests {
             page_id: None,
             auto_dismiss_dialogs: false,
             config: None,
+            session: None,
             keepalive_interval: None,
             no_keepalive: false,
             output: OutputFormat {
@@ -71,6 +71,49 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                     description: "Disconnect and remove the session file".into(),
                     flags: Some(vec!["--disconnect".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome connect --launch --headless --pipe".into(),
+                    description: "Launch Chrome over a debugging pipe instead of a TCP port"
+                        .into(),
+                    flags: Some(vec!["--launch".into(), "--pipe".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome --session agent-b connect --launch --headless".into(),
+                    description: "Launch a second Chrome under its own named session".into(),
+                    flags: Some(vec!["--session".into(), "--launch".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome connect --list-sessions".into(),
+                    description: "List named sessions and their connections".into(),
+                    flags: Some(vec!["--list-sessions".into()]),
+                },
+            ],
+        },
+        CommandGroupSummary {
+            command: "daemon".into(),
+            description: "Background process that keeps the CDP connection and event history alive"
+                .into(),
+            examples: vec![
+                ExampleEntry {
+                    cmd: "agentchrome daemon start".into(),
+                    description: "Start the daemon for the current session".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome daemon start --foreground".into(),
+                    description: "Run the daemon in the current process".into(),
+                    flags: Some(vec!["--foreground".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome daemon status".into(),
+                    description: "Show daemon status and buffered event counts".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome daemon stop".into(),
+                    description: "Stop the running daemon".into(),
+                    flags: None,
+                },
             ],
         },
         CommandGroupSummary {
@@ -104,6 +147,33 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                 },
             ],
         },
+        CommandGroupSummary {
+            command: "context".into(),
+            description: "Isolated browser contexts (create, list, dispose)".into(),
+            examples: vec![
+                ExampleEntry {
+                    cmd: "agentchrome context create".into(),
+                    description: "Create a browser context with its own cookies and storage"
+                        .into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome tabs create https://example.com --context CTX123".into(),
+                    description: "Open a tab inside a browser context".into(),
+                    flags: Some(vec!["--context".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome context list".into(),
+                    description: "List browser contexts and the tabs in each".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome context dispose CTX123".into(),
+                    description: "Dispose a context and close its tabs".into(),
+                    flags: None,
+                },
+            ],
+        },
         CommandGroupSummary {
             command: "navigate".into(),
             description: "URL navigation and history".into(),
@@ -151,11 +221,65 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                         .into(),
                     flags: Some(vec!["--compact".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome page snapshot --diff".into(),
+                    description: "Show only nodes added, removed, or changed since the last snapshot"
+                        .into(),
+                    flags: Some(vec!["--diff".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page snapshot --assert golden.yaml --ignore-order".into(),
+                    description:
+                        "Fail when the page structure differs from a golden recorded with --update-golden"
+                            .into(),
+                    flags: Some(vec!["--assert".into(), "--ignore-order".into()]),
+                },
                 ExampleEntry {
                     cmd: "agentchrome page screenshot --full-page --file page.png".into(),
                     description: "Take a full-page screenshot".into(),
                     flags: Some(vec!["--full-page".into(), "--file".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome page screenshot --compare baseline.png --diff-out diff.png"
+                        .into(),
+                    description: "Fail when the viewport differs from a baseline PNG".into(),
+                    flags: Some(vec!["--compare".into(), "--diff-out".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page screenshot --annotate uids --file marked.png".into(),
+                    description: "Label interactive elements with their snapshot UIDs".into(),
+                    flags: Some(vec!["--annotate".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page pdf --file report.pdf --paper a4 --print-background"
+                        .into(),
+                    description: "Print the page to an A4 PDF with backgrounds".into(),
+                    flags: Some(vec!["--file".into(), "--paper".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page archive --bundle ./bug-report".into(),
+                    description:
+                        "Save MHTML, snapshot, screenshot, console, and network for a bug report"
+                            .into(),
+                    flags: Some(vec!["--bundle".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page record start --dir frames/ --max-fps 5".into(),
+                    description: "Record the tab; 'page record stop' assembles an APNG and timeline"
+                        .into(),
+                    flags: Some(vec!["--dir".into(), "--max-fps".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page tables --selector \"#prices\" --format csv".into(),
+                    description: "Extract a data table as CSV, expanding colspan/rowspan".into(),
+                    flags: Some(vec!["--selector".into(), "--format".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome page extract --schema product.json".into(),
+                    description: "Extract typed fields and lists described by a JSON schema"
+                        .into(),
+                    flags: Some(vec!["--schema".into()]),
+                },
                 ExampleEntry {
                     cmd: "agentchrome page find \"Sign in\"".into(),
                     description: "Find elements by text".into(),
@@ -389,6 +513,16 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                     description: "Execute JavaScript in a Service Worker".into(),
                     flags: Some(vec!["--worker".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome js inject add --file shim.js".into(),
+                    description: "Run a shim in every document the tab loads".into(),
+                    flags: Some(vec!["--file".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome js inject list".into(),
+                    description: "List the tab's preload scripts".into(),
+                    flags: None,
+                },
             ],
         },
         CommandGroupSummary {
@@ -402,9 +536,20 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                 },
                 ExampleEntry {
                     cmd: "agentchrome console read --errors-only".into(),
-                    description: "Show only error messages".into(),
+                    description: "Show only errors and uncaught exceptions".into(),
                     flags: Some(vec!["--errors-only".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome console read --type exception".into(),
+                    description: "List uncaught exceptions with their stack traces".into(),
+                    flags: Some(vec!["--type".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome console read --source log".into(),
+                    description: "List browser Log entries (failed loads, CSP violations, deprecations)"
+                        .into(),
+                    flags: Some(vec!["--source".into()]),
+                },
                 ExampleEntry {
                     cmd: "agentchrome console follow".into(),
                     description: "Stream console messages in real time".into(),
@@ -452,6 +597,56 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                     description: "List network requests from a specific frame".into(),
                     flags: Some(vec!["--frame".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome network har --file session.har --include-bodies".into(),
+                    description: "Export captured traffic as a HAR 1.2 file".into(),
+                    flags: Some(vec!["--file".into(), "--include-bodies".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome network replay --har recording.har --timeout 30000".into(),
+                    description: "Serve requests from a recorded HAR file instead of the network"
+                        .into(),
+                    flags: Some(vec!["--har".into(), "--timeout".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome network route add --url-pattern '*/api/*' --status 500"
+                        .into(),
+                    description: "Mock matching requests with an error response".into(),
+                    flags: Some(vec!["--url-pattern".into(), "--status".into()]),
+                },
+            ],
+        },
+        CommandGroupSummary {
+            command: "cdp".into(),
+            description: "Raw Chrome DevTools Protocol passthrough (send, listen)".into(),
+            examples: vec![
+                ExampleEntry {
+                    cmd: "agentchrome cdp send Security.enable".into(),
+                    description: "Call a CDP method on the current tab".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: r#"agentchrome cdp send Animation.setPlaybackRate '{"playbackRate":0.5}'"#
+                        .into(),
+                    description: "Call a CDP method with JSON parameters".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome cdp send --browser Browser.getVersion".into(),
+                    description: "Call a method on the browser-level connection".into(),
+                    flags: Some(vec!["--browser".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome cdp listen Animation.animationStarted --count 5".into(),
+                    description: "Stream the next five matching events as NDJSON".into(),
+                    flags: Some(vec!["--count".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome cdp listen Security.visibleSecurityStateChanged --timeout 10000"
+                        .into(),
+                    description: "Stream events for ten seconds".into(),
+                    flags: Some(vec!["--timeout".into()]),
+                },
             ],
         },
         CommandGroupSummary {
@@ -473,6 +668,11 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                     description: "Click and wait for network idle (for SPA navigation)".into(),
                     flags: Some(vec!["--wait-until".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome interact click s5 --include-snapshot-diff".into(),
+                    description: "Click and return only the accessibility nodes that changed".into(),
+                    flags: Some(vec!["--include-snapshot-diff".into()]),
+                },
                 ExampleEntry {
                     cmd: "agentchrome interact type \"Hello, world!\"".into(),
                     description: "Type text into the focused element".into(),
@@ -581,6 +781,12 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                     description: "Fill a form field inside an iframe".into(),
                     flags: Some(vec!["--frame".into()]),
                 },
+                ExampleEntry {
+                    cmd: "agentchrome form submit s3 --include-snapshot-diff".into(),
+                    description: "Submit and return only the accessibility nodes that changed"
+                        .into(),
+                    flags: Some(vec!["--include-snapshot-diff".into()]),
+                },
             ],
         },
         CommandGroupSummary {
@@ -645,6 +851,70 @@ pub fn all_examples() -> Vec<CommandGroupSummary> {
                 },
             ],
         },
+        CommandGroupSummary {
+            command: "storage".into(),
+            description: "Web storage management (localStorage, sessionStorage, IndexedDB)".into(),
+            examples: vec![
+                ExampleEntry {
+                    cmd: "agentchrome storage local list".into(),
+                    description: "List localStorage for the current page's origin".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage session get cart".into(),
+                    description: "Read a single sessionStorage value".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage local set theme dark --origin https://example.com"
+                        .into(),
+                    description: "Seed a localStorage value for a specific origin".into(),
+                    flags: Some(vec!["--origin".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage --frame 1 local list".into(),
+                    description: "List an iframe's localStorage, even cross-origin".into(),
+                    flags: Some(vec!["--frame".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage session clear".into(),
+                    description: "Clear sessionStorage".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage idb stores app-db".into(),
+                    description: "Describe the object stores of an IndexedDB database".into(),
+                    flags: None,
+                },
+                ExampleEntry {
+                    cmd: "agentchrome storage idb dump app-db todos --key-range 10..20".into(),
+                    description: "Dump IndexedDB records with keys 10 through 20".into(),
+                    flags: Some(vec!["--key-range".into()]),
+                },
+            ],
+        },
+        CommandGroupSummary {
+            command: "state".into(),
+            description: "Save and restore authentication state (cookies and web storage)".into(),
+            examples: vec![
+                ExampleEntry {
+                    cmd: "agentchrome state save --file auth.json".into(),
+                    description: "Save cookies and storage of the page's origins".into(),
+                    flags: Some(vec!["--file".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome state save --file auth.json --origins https://app.example.com"
+                        .into(),
+                    description: "Save storage for specific origins only".into(),
+                    flags: Some(vec!["--file".into(), "--origins".into()]),
+                },
+                ExampleEntry {
+                    cmd: "agentchrome state load --file auth.json".into(),
+                    description: "Restore a saved login before navigating".into(),
+                    flags: Some(vec!["--file".into()]),
+                },
+            ],
+        },
         CommandGroupSummary {
             command: "dialog".into(),
             description: "Browser dialog handling (alert, confirm, prompt, beforeunload)".into(),
@@ -23,6 +23,8 @@ struct FillResult {
     value: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -31,7 +33,10 @@ enum FillManyOutput {
     Plain(Vec<FillResult>),
     WithSnapshot {
         results: Vec<FillResult>,
-        snapshot: serde_json::Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        snapshot: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        snapshot_diff: Option<serde_json::Value>,
     },
 }
 
@@ -40,6 +45,8 @@ struct ClearResult {
     cleared: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -49,6 +56,8 @@ struct UploadResult {
     size: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -58,6 +67,8 @@ struct SubmitResult {
     url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 /// JSON input for fill-many: each entry has a target (UID or CSS selector) and value.
@@ -113,13 +124,7 @@ async fn resolve_target_to_backend_node_id(
     target: &str,
 ) -> Result<i64, AppError> {
     if snapshot::is_uid(target) {
-        let state = snapshot::read_snapshot_state()?.ok_or_else(AppError::no_snapshot_state)?;
-        let backend_node_id = state
-            .uid_map
-            .get(target)
-            .copied()
-            .ok_or_else(|| AppError::uid_not_found(target))?;
-        Ok(backend_node_id)
+        snapshot::resolve_uid(session, target).await
     } else if snapshot::is_css_selector(target) {
         let selector = &target[4..];
 
@@ -156,53 +161,6 @@ async fn resolve_target_to_backend_node_id(
     }
 }
 
-// =============================================================================
-// Snapshot refresh helper
-// =============================================================================
-
-/// Take a fresh snapshot and write it to snapshot state.
-async fn take_snapshot(
-    session: &mut ManagedSession,
-    url: &str,
-    compact: bool,
-) -> Result<serde_json::Value, AppError> {
-    session.ensure_domain("Accessibility").await?;
-
-    let response = session
-        .send_command("Accessibility.getFullAXTree", None)
-        .await?;
-
-    let nodes = response["nodes"]
-        .as_array()
-        .ok_or_else(|| AppError::snapshot_failed("missing nodes array"))?;
-
-    let build_result = snapshot::build_tree(nodes, false);
-
-    let state = snapshot::SnapshotState {
-        url: url.to_string(),
-        timestamp: agentchrome::session::now_iso8601(),
-        uid_map: build_result.uid_map,
-        frame_index: None,
-        frame_id: None,
-        aggregate: false,
-        frame_uid_ranges: Vec::new(),
-        frame_ids: Vec::new(),
-    };
-    snapshot::write_snapshot_state(&state)?;
-
-    // Apply compact filtering if requested
-    let root = if compact {
-        snapshot::compact_tree(&build_result.root)
-    } else {
-        build_result.root
-    };
-
-    let snapshot_json = serde_json::to_value(&root)
-        .map_err(|e| AppError::snapshot_failed(&format!("failed to serialize snapshot: {e}")))?;
-
-    Ok(snapshot_json)
-}
-
 // =============================================================================
 // Fill JavaScript
 // =============================================================================
@@ -735,17 +693,25 @@ async fn execute_fill(
     .await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        crate::interact::take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = FillResult {
         filled: args.target.clone(),
         value: args.value.clone(),
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -819,14 +785,26 @@ async fn execute_fill_many(
             filled: entry.target.clone(),
             value: entry.value.clone(),
             snapshot: None,
+            snapshot_diff: None,
         });
     }
 
     // Take snapshot once after all fills if requested
-    if args.include_snapshot {
+    if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        let snapshot = take_snapshot(&mut managed, &url, args.compact).await?;
-        let output = FillManyOutput::WithSnapshot { results, snapshot };
+        let (snapshot, snapshot_diff) = crate::interact::take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?;
+        let output = FillManyOutput::WithSnapshot {
+            results,
+            snapshot,
+            snapshot_diff,
+        };
         if global.output.plain {
             if let FillManyOutput::WithSnapshot { results, .. } = &output {
                 print_fill_many_plain(results);
@@ -889,16 +867,24 @@ async fn execute_clear(
     clear_element(effective, &args.target).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        crate::interact::take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = ClearResult {
         cleared: args.target.clone(),
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -1042,11 +1028,18 @@ async fn execute_upload(
         .map_err(|e| AppError::interaction_failed("dispatch_change", &e.to_string()))?;
 
     // --- Optionally take snapshot ---
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        crate::interact::take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // --- Build and print result ---
@@ -1055,6 +1048,7 @@ async fn execute_upload(
         files: resolved_paths,
         size: total_size,
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -1191,21 +1185,29 @@ async fn execute_submit(
     };
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let current_url = if let Some(u) = &url {
             u.clone()
         } else {
             pre_url
         };
-        Some(take_snapshot(&mut managed, &current_url, args.compact).await?)
+        crate::interact::take_snapshot(
+            &mut managed,
+            &current_url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = SubmitResult {
         submitted: args.target.clone(),
         url,
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -1318,6 +1320,7 @@ mod tests {
             filled: "s1".to_string(),
             value: "John".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["filled"], "s1");
@@ -1331,6 +1334,7 @@ mod tests {
             filled: "s1".to_string(),
             value: "John".to_string(),
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["filled"], "s1");
@@ -1344,6 +1348,7 @@ mod tests {
             filled: "css:#email".to_string(),
             value: "user@example.com".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["filled"], "css:#email");
@@ -1361,11 +1366,13 @@ mod tests {
                 filled: "s1".to_string(),
                 value: "John".to_string(),
                 snapshot: None,
+                snapshot_diff: None,
             },
             FillResult {
                 filled: "s2".to_string(),
                 value: "Doe".to_string(),
                 snapshot: None,
+                snapshot_diff: None,
             },
         ]);
         let json: serde_json::Value = serde_json::to_value(&output).unwrap();
@@ -1377,6 +1384,19 @@ mod tests {
         assert_eq!(arr[1]["value"], "Doe");
     }
 
+    #[test]
+    fn submit_result_with_snapshot_diff_omits_tree() {
+        let result = SubmitResult {
+            submitted: "s3".to_string(),
+            url: None,
+            snapshot: None,
+            snapshot_diff: Some(serde_json::json!({"added": [], "removed": [], "changed": []})),
+        };
+        let json: serde_json::Value = serde_json::to_value(&result).unwrap();
+        assert!(json.get("snapshot").is_none());
+        assert!(json["snapshot_diff"]["added"].is_array());
+    }
+
     #[test]
     fn fill_many_output_with_snapshot_serialization() {
         let output = FillManyOutput::WithSnapshot {
@@ -1384,8 +1404,10 @@ mod tests {
                 filled: "s1".to_string(),
                 value: "John".to_string(),
                 snapshot: None,
+                snapshot_diff: None,
             }],
-            snapshot: serde_json::json!({"role": "document"}),
+            snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&output).unwrap();
         assert!(json.get("results").is_some());
@@ -1404,6 +1426,7 @@ mod tests {
         let result = ClearResult {
             cleared: "s1".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["cleared"], "s1");
@@ -1415,6 +1438,7 @@ mod tests {
         let result = ClearResult {
             cleared: "s1".to_string(),
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["cleared"], "s1");
@@ -1472,6 +1496,7 @@ mod tests {
             filled: "s1".to_string(),
             value: "test".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         // Would print "Filled s1 = test"
         print_fill_plain(&result);
@@ -1482,6 +1507,7 @@ mod tests {
         let result = ClearResult {
             cleared: "s1".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         // Would print "Cleared s1"
         print_clear_plain(&result);
@@ -1498,6 +1524,7 @@ mod tests {
             files: vec!["/tmp/photo.jpg".to_string()],
             size: 24576,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["uploaded"], "s5");
@@ -1514,6 +1541,7 @@ mod tests {
             files: vec!["/tmp/photo.jpg".to_string()],
             size: 24576,
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["uploaded"], "s5");
@@ -1528,6 +1556,7 @@ mod tests {
             files: vec!["/tmp/doc1.pdf".to_string(), "/tmp/doc2.pdf".to_string()],
             size: 102_400,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["uploaded"], "s3");
@@ -1544,6 +1573,7 @@ mod tests {
             files: vec!["/tmp/document.pdf".to_string()],
             size: 51200,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["uploaded"], "css:#file-upload");
@@ -1556,6 +1586,7 @@ mod tests {
             files: vec!["/tmp/photo.jpg".to_string()],
             size: 24576,
             snapshot: None,
+            snapshot_diff: None,
         };
         // Would print "Uploaded s5 (24576 bytes): /tmp/photo.jpg"
         print_upload_plain(&result);
@@ -1571,6 +1602,7 @@ mod tests {
             submitted: "s3".to_string(),
             url: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["submitted"], "s3");
@@ -1584,6 +1616,7 @@ mod tests {
             submitted: "s3".to_string(),
             url: Some("https://example.com/dashboard".to_string()),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["submitted"], "s3");
@@ -1597,6 +1630,7 @@ mod tests {
             submitted: "s3".to_string(),
             url: None,
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["submitted"], "s3");
@@ -1610,6 +1644,7 @@ mod tests {
             submitted: "css:#login-form".to_string(),
             url: Some("https://example.com/home".to_string()),
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["submitted"], "css:#login-form");
@@ -1623,6 +1658,7 @@ mod tests {
             submitted: "s3".to_string(),
             url: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         print_submit_plain(&result);
     }
@@ -1633,6 +1669,7 @@ mod tests {
             submitted: "s3".to_string(),
             url: Some("https://example.com".to_string()),
             snapshot: None,
+            snapshot_diff: None,
         };
         print_submit_plain(&result);
     }
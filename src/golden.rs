@@ -0,0 +1,448 @@
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use agentchrome::error::{AppError, ExitCode};
+
+use crate::snapshot::SnapshotNode;
+
+/// Above this many sibling pairs, ordered comparison falls back to pairing
+/// children positionally instead of running the alignment table.
+const MAX_ALIGNMENT_CELLS: usize = 1_000_000;
+
+// =============================================================================
+// Golden file format
+// =============================================================================
+
+/// On-disk golden snapshot written by `page snapshot --update-golden`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoldenFile {
+    /// Page the golden was recorded from (informational; never compared).
+    #[serde(default)]
+    pub url: String,
+    pub tree: GoldenNode,
+}
+
+/// One node of the compacted accessibility tree, without UIDs or properties.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenNode {
+    pub role: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<GoldenNode>,
+}
+
+impl From<&SnapshotNode> for GoldenNode {
+    fn from(node: &SnapshotNode) -> Self {
+        Self {
+            role: node.role.clone(),
+            name: node.name.clone(),
+            children: node.children.iter().map(Self::from).collect(),
+        }
+    }
+}
+
+impl GoldenNode {
+    /// Number of nodes in this subtree, including itself.
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(Self::count).sum::<usize>()
+    }
+}
+
+/// Read and parse a golden file.
+///
+/// # Errors
+///
+/// Returns `AppError` if the file cannot be read or is not a valid golden file.
+pub fn read_golden(path: &Path) -> Result<GoldenFile, AppError> {
+    let yaml = std::fs::read_to_string(path).map_err(|e| AppError {
+        message: format!("Failed to read golden file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    serde_yaml::from_str(&yaml).map_err(|e| AppError {
+        message: format!("Invalid golden file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+/// Serialize a golden file as YAML and write it to `path`.
+///
+/// # Errors
+///
+/// Returns `AppError` if serialization or the write fails.
+pub fn write_golden(path: &Path, golden: &GoldenFile) -> Result<(), AppError> {
+    let yaml = serde_yaml::to_string(golden).map_err(|e| AppError {
+        message: format!("serialization error: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    std::fs::write(path, yaml).map_err(|e| AppError {
+        message: format!("Failed to write golden file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+// =============================================================================
+// Comparison
+// =============================================================================
+
+/// Tolerances applied when comparing a snapshot against a golden.
+#[derive(Debug, Default)]
+pub struct CompareOptions {
+    /// Names matching any of these patterns (on either side) are not compared.
+    pub ignore_names: Vec<Regex>,
+    /// Match children regardless of their order among siblings.
+    pub ignore_order: bool,
+}
+
+impl CompareOptions {
+    /// Compile `--ignore-name` patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError` naming the first pattern that is not a valid regex.
+    pub fn new(ignore_names: &[String], ignore_order: bool) -> Result<Self, AppError> {
+        let ignore_names = ignore_names
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| AppError {
+                    message: format!("Invalid --ignore-name pattern '{pattern}': {e}"),
+                    code: ExitCode::GeneralError,
+                    custom_json: None,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            ignore_names,
+            ignore_order,
+        })
+    }
+
+    fn names_match(&self, expected: &str, actual: &str) -> bool {
+        expected == actual
+            || self
+                .ignore_names
+                .iter()
+                .any(|re| re.is_match(expected) || re.is_match(actual))
+    }
+}
+
+/// What went wrong at one position of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    /// Present in the golden, absent from the page.
+    Missing,
+    /// Present on the page, absent from the golden.
+    Unexpected,
+    NameMismatch,
+    RoleMismatch,
+}
+
+/// Role and name of a node named in a mismatch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeRef {
+    pub role: String,
+    pub name: String,
+}
+
+impl From<&GoldenNode> for NodeRef {
+    fn from(node: &GoldenNode) -> Self {
+        Self {
+            role: node.role.clone(),
+            name: node.name.clone(),
+        }
+    }
+}
+
+/// A single difference between the golden and the current tree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mismatch {
+    /// Position in the tree, e.g. `RootWebArea > main > button[2]`.
+    pub path: String,
+    pub kind: MismatchKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<NodeRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<NodeRef>,
+}
+
+/// Result of comparing a tree against a golden.
+#[derive(Debug, Default)]
+pub struct Comparison {
+    /// Number of golden nodes that were compared.
+    pub compared_nodes: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Compare the current compacted tree against the golden tree.
+pub fn compare(expected: &GoldenNode, actual: &GoldenNode, opts: &CompareOptions) -> Comparison {
+    let mut out = Comparison::default();
+    compare_node(expected, actual, &expected.role, opts, &mut out);
+    out
+}
+
+fn compare_node(
+    expected: &GoldenNode,
+    actual: &GoldenNode,
+    path: &str,
+    opts: &CompareOptions,
+    out: &mut Comparison,
+) {
+    out.compared_nodes += 1;
+    if expected.role != actual.role {
+        // Different kinds of node: the subtrees are not comparable.
+        out.compared_nodes += expected.count() - 1;
+        out.mismatches.push(Mismatch {
+            path: path.to_string(),
+            kind: MismatchKind::RoleMismatch,
+            expected: Some(expected.into()),
+            actual: Some(actual.into()),
+        });
+        return;
+    }
+    if !opts.names_match(&expected.name, &actual.name) {
+        out.mismatches.push(Mismatch {
+            path: path.to_string(),
+            kind: MismatchKind::NameMismatch,
+            expected: Some(expected.into()),
+            actual: Some(actual.into()),
+        });
+    }
+
+    let pairs = if opts.ignore_order {
+        match_unordered(&expected.children, &actual.children, opts)
+    } else {
+        match_ordered(&expected.children, &actual.children, opts)
+    };
+
+    let expected_paths = child_paths(path, &expected.children);
+    let actual_paths = child_paths(path, &actual.children);
+    let mut actual_used = vec![false; actual.children.len()];
+    for (i, child) in expected.children.iter().enumerate() {
+        if let Some(j) = pairs[i] {
+            actual_used[j] = true;
+            compare_node(child, &actual.children[j], &expected_paths[i], opts, out);
+        } else {
+            out.compared_nodes += child.count();
+            out.mismatches.push(Mismatch {
+                path: expected_paths[i].clone(),
+                kind: MismatchKind::Missing,
+                expected: Some(child.into()),
+                actual: None,
+            });
+        }
+    }
+    for (j, child) in actual.children.iter().enumerate() {
+        if !actual_used[j] {
+            out.mismatches.push(Mismatch {
+                path: actual_paths[j].clone(),
+                kind: MismatchKind::Unexpected,
+                expected: None,
+                actual: Some(child.into()),
+            });
+        }
+    }
+}
+
+/// Path of each child: `parent > role[n]`, where `n` counts same-role siblings.
+fn child_paths(parent: &str, children: &[GoldenNode]) -> Vec<String> {
+    let mut totals: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for child in children {
+        *totals.entry(child.role.as_str()).or_default() += 1;
+    }
+    let mut seen: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    children
+        .iter()
+        .map(|child| {
+            let n = seen.entry(child.role.as_str()).or_default();
+            *n += 1;
+            if totals[child.role.as_str()] > 1 {
+                format!("{parent} > {}[{n}]", child.role)
+            } else {
+                format!("{parent} > {}", child.role)
+            }
+        })
+        .collect()
+}
+
+/// Pair siblings in order: an alignment that prefers same role and name, then
+/// same role alone. Returns, for each expected child, its actual counterpart.
+fn match_ordered(
+    expected: &[GoldenNode],
+    actual: &[GoldenNode],
+    opts: &CompareOptions,
+) -> Vec<Option<usize>> {
+    let (rows, cols) = (expected.len(), actual.len());
+    let score = |i: usize, j: usize| -> usize {
+        let (e, a) = (&expected[i], &actual[j]);
+        if e.role != a.role {
+            0
+        } else if opts.names_match(&e.name, &a.name) {
+            2
+        } else {
+            1
+        }
+    };
+
+    if rows.saturating_mul(cols) > MAX_ALIGNMENT_CELLS {
+        return (0..rows)
+            .map(|i| (i < cols && score(i, i) > 0).then_some(i))
+            .collect();
+    }
+
+    // table[i][j] = best score aligning expected[i..] with actual[j..].
+    let mut table = vec![vec![0usize; cols + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            let s = score(i, j);
+            let take = if s > 0 { s + table[i + 1][j + 1] } else { 0 };
+            table[i][j] = take.max(table[i + 1][j]).max(table[i][j + 1]);
+        }
+    }
+
+    let mut pairs = vec![None; rows];
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        let s = score(i, j);
+        if s > 0 && table[i][j] == s + table[i + 1][j + 1] {
+            pairs[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if table[i][j] == table[i + 1][j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Pair siblings regardless of order: exact role and name first, then role alone.
+fn match_unordered(
+    expected: &[GoldenNode],
+    actual: &[GoldenNode],
+    opts: &CompareOptions,
+) -> Vec<Option<usize>> {
+    let mut pairs = vec![None; expected.len()];
+    let mut used = vec![false; actual.len()];
+    for same_name in [true, false] {
+        for (i, e) in expected.iter().enumerate() {
+            if pairs[i].is_some() {
+                continue;
+            }
+            let found = actual.iter().enumerate().position(|(j, a)| {
+                !used[j] && a.role == e.role && (!same_name || opts.names_match(&e.name, &a.name))
+            });
+            if let Some(j) = found {
+                used[j] = true;
+                pairs[i] = Some(j);
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, name: &str, children: Vec<GoldenNode>) -> GoldenNode {
+        GoldenNode {
+            role: role.to_string(),
+            name: name.to_string(),
+            children,
+        }
+    }
+
+    fn page(children: Vec<GoldenNode>) -> GoldenNode {
+        node("RootWebArea", "Home", vec![node("main", "", children)])
+    }
+
+    fn kinds(cmp: &Comparison) -> Vec<(String, MismatchKind)> {
+        cmp.mismatches
+            .iter()
+            .map(|m| (m.path.clone(), m.kind))
+            .collect()
+    }
+
+    #[test]
+    fn identical_trees_have_no_mismatches() {
+        let tree = page(vec![
+            node("button", "Save", vec![]),
+            node("link", "Help", vec![]),
+        ]);
+        let cmp = compare(&tree, &tree.clone(), &CompareOptions::default());
+        assert!(cmp.mismatches.is_empty());
+        assert_eq!(cmp.compared_nodes, 4);
+    }
+
+    #[test]
+    fn reports_missing_unexpected_and_renamed_nodes() {
+        let expected = page(vec![
+            node("button", "Save", vec![]),
+            node("button", "Cancel", vec![]),
+            node("link", "Help", vec![]),
+        ]);
+        let actual = page(vec![
+            node("button", "Cancel", vec![]),
+            node("link", "Support", vec![]),
+            node("checkbox", "Agree", vec![]),
+        ]);
+        let cmp = compare(&expected, &actual, &CompareOptions::default());
+        assert_eq!(
+            kinds(&cmp),
+            vec![
+                (
+                    "RootWebArea > main > button[1]".to_string(),
+                    MismatchKind::Missing
+                ),
+                (
+                    "RootWebArea > main > link".to_string(),
+                    MismatchKind::NameMismatch
+                ),
+                (
+                    "RootWebArea > main > checkbox".to_string(),
+                    MismatchKind::Unexpected
+                ),
+            ]
+        );
+        assert_eq!(cmp.mismatches[1].actual.as_ref().unwrap().name, "Support");
+    }
+
+    #[test]
+    fn ignore_name_patterns_tolerate_dynamic_text() {
+        let expected = page(vec![node("status", "3 items in cart", vec![])]);
+        let actual = page(vec![node("status", "5 items in cart", vec![])]);
+        let opts = CompareOptions::new(&[r"^\d+ items".to_string()], false).unwrap();
+        assert!(compare(&expected, &actual, &opts).mismatches.is_empty());
+        assert!(CompareOptions::new(&["(".to_string()], false).is_err());
+    }
+
+    #[test]
+    fn ignore_order_matches_reordered_siblings() {
+        let expected = page(vec![node("link", "A", vec![]), node("button", "B", vec![])]);
+        let actual = page(vec![node("button", "B", vec![]), node("link", "A", vec![])]);
+        let ordered = compare(&expected, &actual, &CompareOptions::default());
+        assert_eq!(ordered.mismatches.len(), 2);
+        let opts = CompareOptions::new(&[], true).unwrap();
+        assert!(compare(&expected, &actual, &opts).mismatches.is_empty());
+    }
+
+    #[test]
+    fn golden_round_trips_through_yaml() {
+        let golden = GoldenFile {
+            url: "https://example.com/".to_string(),
+            tree: page(vec![node("button", "Save", vec![])]),
+        };
+        let yaml = serde_yaml::to_string(&golden).unwrap();
+        assert!(!yaml.contains("name: ''"));
+        let parsed: GoldenFile = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.tree, golden.tree);
+    }
+}
@@ -0,0 +1,499 @@
+//! HTTP Archive (HAR 1.2) documents.
+//!
+//! `agentchrome network har` writes captured traffic in this format so it can
+//! be opened in Chrome `DevTools` or any other HAR viewer. Only the fields the
+//! spec requires plus the handful agentchrome can fill in are modelled;
+//! unknown fields are ignored on read, and fields agentchrome does not need
+//! for `network replay` default when missing so archives from other tools
+//! load. Custom fields use the `_` prefix the spec reserves for them, with the
+//! same names `DevTools` uses.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AppError, ExitCode};
+
+/// HAR format version written by agentchrome.
+pub const HAR_VERSION: &str = "1.2";
+
+/// Root of a HAR document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+/// The `log` object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Log {
+    pub version: String,
+    pub creator: Creator,
+    #[serde(default)]
+    pub pages: Vec<Page>,
+    pub entries: Vec<Entry>,
+}
+
+/// Application that produced the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Creator {
+    pub name: String,
+    pub version: String,
+}
+
+impl Default for Creator {
+    fn default() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// A page (one top-level navigation) that entries are grouped under.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page {
+    pub started_date_time: String,
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub page_timings: PageTimings,
+}
+
+/// Page load timings. agentchrome does not track them, so both are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageTimings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_content_load: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<f64>,
+}
+
+/// One request/response pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pageref: Option<String>,
+    #[serde(default)]
+    pub started_date_time: String,
+    /// Total elapsed time in milliseconds.
+    #[serde(default)]
+    pub time: f64,
+    pub request: Request,
+    pub response: Response,
+    #[serde(default)]
+    pub cache: Value,
+    #[serde(default)]
+    pub timings: Timings,
+    #[serde(
+        rename = "_resourceType",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_type: Option<String>,
+    /// Network error for requests that never got a response.
+    #[serde(rename = "_error", default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Request half of an entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<Cookie>,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    #[serde(default)]
+    pub query_string: Vec<NameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<PostData>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+/// Response half of an entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<Cookie>,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    #[serde(default)]
+    pub content: Content,
+    #[serde(rename = "redirectURL", default)]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+/// A header or query string parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// A cookie. agentchrome leaves the cookie lists empty; the raw `Cookie` and
+/// `Set-Cookie` headers are kept in `headers`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+}
+
+/// Posted request body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostData {
+    #[serde(default)]
+    pub mime_type: String,
+    pub text: String,
+}
+
+/// Response body details.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Content {
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// `"base64"` when `text` holds base64-encoded binary data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+/// Timing phases in milliseconds. `-1` marks a phase that does not apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+    pub ssl: f64,
+}
+
+impl Timings {
+    /// Timings for an entry whose phases are unknown.
+    #[must_use]
+    pub fn unknown() -> Self {
+        Self {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: 0.0,
+            receive: 0.0,
+            ssl: -1.0,
+        }
+    }
+
+    /// Entry time: the sum of all applicable phases. `ssl` is already part
+    /// of `connect`, so it is not counted twice.
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        [
+            self.blocked,
+            self.dns,
+            self.connect,
+            self.send,
+            self.wait,
+            self.receive,
+        ]
+        .iter()
+        .filter(|t| **t > 0.0)
+        .sum()
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::unknown()
+    }
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+impl Har {
+    /// Start an empty log attributed to agentchrome.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            log: Log {
+                version: HAR_VERSION.to_string(),
+                creator: Creator::default(),
+                pages: Vec::new(),
+                entries: Vec::new(),
+            },
+        }
+    }
+}
+
+impl Default for Har {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert a CDP `Network.Headers` object into HAR headers.
+///
+/// Chrome folds repeated headers (e.g. several `Set-Cookie` lines) into one
+/// value separated by newlines; each line becomes its own HAR header. Names
+/// are sorted so the output is stable.
+#[must_use]
+pub fn headers_from_cdp(headers: &Value) -> Vec<NameValue> {
+    let Some(object) = headers.as_object() else {
+        return Vec::new();
+    };
+    let mut names: Vec<&String> = object.keys().collect();
+    names.sort();
+    let mut out = Vec::new();
+    for name in names {
+        let value = match &object[name] {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        for line in value.split('\n') {
+            out.push(NameValue {
+                name: name.clone(),
+                value: line.to_string(),
+            });
+        }
+    }
+    out
+}
+
+/// Decoded query string parameters of `url`, in order.
+#[must_use]
+pub fn query_string(url: &str) -> Vec<NameValue> {
+    url::Url::parse(url)
+        .map(|parsed| {
+            parsed
+                .query_pairs()
+                .map(|(name, value)| NameValue {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Case-insensitive header lookup.
+#[must_use]
+pub fn header_value<'a>(headers: &'a [NameValue], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Read a HAR document from `path`.
+///
+/// # Errors
+///
+/// Returns `AppError` if the file is missing or is not a HAR log.
+pub fn read_har(path: &Path) -> Result<Har, AppError> {
+    let display = path.display().to_string();
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(AppError::file_not_found(&display));
+        }
+        Err(_) => return Err(AppError::file_not_readable(&display)),
+    };
+    serde_json::from_str(&contents).map_err(|e| AppError {
+        message: format!("Invalid HAR file {display}: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+/// Write `har` to `path` as pretty-printed JSON.
+///
+/// # Errors
+///
+/// Returns `AppError` if the file cannot be written.
+pub fn write_har(path: &Path, har: &Har) -> Result<(), AppError> {
+    let json = serde_json::to_vec_pretty(har).map_err(|e| AppError {
+        message: format!("Failed to serialize HAR: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    std::fs::write(path, json).map_err(|e| AppError {
+        message: format!("Failed to write HAR file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn headers_split_folded_values_and_sort_names() {
+        let headers = headers_from_cdp(&json!({
+            "set-cookie": "a=1\nb=2",
+            "content-type": "text/html",
+        }));
+        let pairs: Vec<_> = headers
+            .iter()
+            .map(|h| (h.name.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("content-type", "text/html"),
+                ("set-cookie", "a=1"),
+                ("set-cookie", "b=2"),
+            ]
+        );
+        assert_eq!(header_value(&headers, "Content-Type"), Some("text/html"));
+        assert!(headers_from_cdp(&Value::Null).is_empty());
+    }
+
+    #[test]
+    fn query_string_is_decoded() {
+        let params = query_string("https://example.com/search?q=a%20b&page=2");
+        assert_eq!(
+            params,
+            [
+                NameValue {
+                    name: "q".into(),
+                    value: "a b".into()
+                },
+                NameValue {
+                    name: "page".into(),
+                    value: "2".into()
+                },
+            ]
+        );
+        assert!(query_string("not a url").is_empty());
+    }
+
+    #[test]
+    fn timings_total_skips_inapplicable_phases() {
+        let timings = Timings {
+            blocked: -1.0,
+            dns: 2.0,
+            connect: 10.0,
+            send: 1.0,
+            wait: 20.0,
+            receive: 5.0,
+            ssl: 6.0,
+        };
+        assert!((timings.total() - 38.0).abs() < f64::EPSILON);
+        assert!(Timings::unknown().total().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn read_har_accepts_minimal_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("min.har");
+        std::fs::write(
+            &path,
+            r#"{"log":{"version":"1.2","creator":{"name":"x","version":"1"},"entries":[
+                {"request":{"method":"GET","url":"https://example.com/"},
+                 "response":{"status":204}}]}}"#,
+        )
+        .unwrap();
+
+        let har = read_har(&path).unwrap();
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.response.status, 204);
+        assert_eq!(entry.response.body_size, -1);
+        assert!(entry.response.content.text.is_none());
+        assert_eq!(entry.timings, Timings::unknown());
+    }
+
+    #[test]
+    fn read_har_reports_missing_and_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = read_har(&dir.path().join("nope.har")).unwrap_err();
+        assert!(missing.message.contains("not found"), "{}", missing.message);
+
+        let path = dir.path().join("bad.har");
+        std::fs::write(&path, "{}").unwrap();
+        let invalid = read_har(&path).unwrap_err();
+        assert!(invalid.message.contains("Invalid HAR file"));
+    }
+
+    #[test]
+    fn har_serializes_spec_field_names() {
+        let mut har = Har::new();
+        har.log.entries.push(Entry {
+            pageref: Some("page_1".into()),
+            started_date_time: "2024-01-01T00:00:00.000Z".into(),
+            time: 0.0,
+            request: Request {
+                method: "GET".into(),
+                url: "https://example.com/".into(),
+                http_version: "HTTP/1.1".into(),
+                cookies: vec![],
+                headers: vec![],
+                query_string: vec![],
+                post_data: None,
+                headers_size: -1,
+                body_size: 0,
+            },
+            response: Response {
+                status: 301,
+                status_text: "Moved".into(),
+                http_version: "HTTP/1.1".into(),
+                cookies: vec![],
+                headers: vec![],
+                content: Content {
+                    size: 0,
+                    mime_type: String::new(),
+                    text: None,
+                    encoding: None,
+                },
+                redirect_url: "https://example.com/new".into(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: json!({}),
+            timings: Timings::unknown(),
+            resource_type: Some("document".into()),
+            error: None,
+        });
+        let value = serde_json::to_value(&har).unwrap();
+        assert_eq!(value["log"]["version"], "1.2");
+        assert_eq!(value["log"]["creator"]["name"], "agentchrome");
+        let entry = &value["log"]["entries"][0];
+        assert_eq!(entry["startedDateTime"], "2024-01-01T00:00:00.000Z");
+        assert_eq!(entry["response"]["redirectURL"], "https://example.com/new");
+        assert_eq!(entry["request"]["httpVersion"], "HTTP/1.1");
+        assert_eq!(entry["_resourceType"], "document");
+        assert!(entry.get("_error").is_none());
+
+        let back: Har = serde_json::from_value(value).unwrap();
+        assert_eq!(back, har);
+    }
+}
@@ -0,0 +1,375 @@
+//! Pixel comparison for `page screenshot --compare`.
+//!
+//! Colour differences are measured in YIQ space and pixels that only differ
+//! because of anti-aliasing are detected from their neighbourhood and
+//! tolerated, following the approach popularised by pixelmatch.
+
+use std::io::Cursor;
+
+/// Per-pixel colour tolerance (0–1) applied before a pixel counts as different.
+const COLOR_TOLERANCE: f64 = 0.1;
+
+/// Largest possible YIQ delta between two colours.
+const MAX_YIQ_DELTA: f64 = 35215.0;
+
+/// Colours used in the highlighted diff image.
+const DIFF_COLOR: [u8; 3] = [255, 0, 0];
+const ANTI_ALIASED_COLOR: [u8; 3] = [255, 255, 0];
+
+/// Opacity of the faded, greyscale original drawn under the highlights.
+const BACKGROUND_ALPHA: f64 = 0.1;
+
+/// An 8-bit RGBA image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+    fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height
+    }
+}
+
+/// Decode a PNG into RGBA pixels, expanding palette, greyscale, and 16-bit images.
+///
+/// # Errors
+///
+/// Returns a description of the decoding failure.
+pub fn decode_png(bytes: &[u8]) -> Result<RgbaImage, String> {
+    let mut decoder = png::Decoder::new(Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let size = reader
+        .output_buffer_size()
+        .ok_or_else(|| "image is too large to decode".to_string())?;
+    let mut buf = vec![0; size];
+    let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    buf.truncate(info.buffer_size());
+
+    let data = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => return Err("unexpanded palette image".to_string()),
+    };
+    Ok(RgbaImage {
+        width: info.width,
+        height: info.height,
+        data,
+    })
+}
+
+/// Encode RGBA pixels as a PNG.
+///
+/// # Errors
+///
+/// Returns a description of the encoding failure.
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer
+        .write_image_data(&image.data)
+        .map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+/// Outcome of comparing two images.
+#[derive(Debug)]
+pub struct ImageComparison {
+    /// Pixels whose colour differs beyond tolerance (including pixels outside
+    /// the overlap when the dimensions differ).
+    pub mismatched_pixels: u64,
+    /// Differing pixels that were attributed to anti-aliasing and tolerated.
+    pub anti_aliased_pixels: u64,
+    /// Pixels in the compared area (the larger width by the larger height).
+    pub total_pixels: u64,
+    /// Greyscale copy of the actual image with mismatches in red and
+    /// anti-aliasing in yellow.
+    pub diff: RgbaImage,
+}
+
+impl ImageComparison {
+    /// Fraction of compared pixels that mismatched (0.0–1.0).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mismatch_ratio(&self) -> f64 {
+        if self.total_pixels == 0 {
+            0.0
+        } else {
+            self.mismatched_pixels as f64 / self.total_pixels as f64
+        }
+    }
+}
+
+/// Compare `actual` against `expected` pixel by pixel.
+pub fn compare(expected: &RgbaImage, actual: &RgbaImage) -> ImageComparison {
+    let width = expected.width.max(actual.width);
+    let height = expected.height.max(actual.height);
+    let max_delta = MAX_YIQ_DELTA * COLOR_TOLERANCE * COLOR_TOLERANCE;
+
+    let mut diff = RgbaImage {
+        width,
+        height,
+        data: vec![0; width as usize * height as usize * 4],
+    };
+    let mut mismatched_pixels = 0;
+    let mut anti_aliased_pixels = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let color = if !expected.contains(x, y) || !actual.contains(x, y) {
+                mismatched_pixels += 1;
+                DIFF_COLOR
+            } else {
+                let (a, b) = (expected.pixel(x, y), actual.pixel(x, y));
+                if color_delta(a, b, false).abs() <= max_delta {
+                    faded(b)
+                } else if anti_aliased(expected, x, y, actual)
+                    || anti_aliased(actual, x, y, expected)
+                {
+                    anti_aliased_pixels += 1;
+                    ANTI_ALIASED_COLOR
+                } else {
+                    mismatched_pixels += 1;
+                    DIFF_COLOR
+                }
+            };
+            let i = (y as usize * width as usize + x as usize) * 4;
+            diff.data[i..i + 3].copy_from_slice(&color);
+            diff.data[i + 3] = 255;
+        }
+    }
+
+    ImageComparison {
+        mismatched_pixels,
+        anti_aliased_pixels,
+        total_pixels: u64::from(width) * u64::from(height),
+        diff,
+    }
+}
+
+/// Greyscale, mostly-white rendering of an unchanged pixel.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn faded(pixel: [u8; 4]) -> [u8; 3] {
+    let [r, g, b] = blend_white(pixel);
+    let luma = rgb_to_y(r, g, b);
+    let v = (255.0 + (luma - 255.0) * BACKGROUND_ALPHA).clamp(0.0, 255.0) as u8;
+    [v, v, v]
+}
+
+/// Composite a pixel over white so transparency compares like the page renders.
+fn blend_white(pixel: [u8; 4]) -> [f64; 3] {
+    let alpha = f64::from(pixel[3]) / 255.0;
+    let blend = |c: u8| 255.0 + (f64::from(c) - 255.0) * alpha;
+    [blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]
+}
+
+fn rgb_to_y(r: f64, g: f64, b: f64) -> f64 {
+    r * 0.298_895_31 + g * 0.586_622_47 + b * 0.114_482_23
+}
+
+fn rgb_to_i(r: f64, g: f64, b: f64) -> f64 {
+    r * 0.595_977_99 - g * 0.274_176_10 - b * 0.321_801_89
+}
+
+fn rgb_to_q(r: f64, g: f64, b: f64) -> f64 {
+    r * 0.211_470_17 - g * 0.522_617_11 + b * 0.311_146_94
+}
+
+/// Perceptual colour difference; negative when `second` is lighter than `first`.
+/// With `brightness_only`, returns just the luma difference.
+fn color_delta(first: [u8; 4], second: [u8; 4], brightness_only: bool) -> f64 {
+    if first == second {
+        return 0.0;
+    }
+    let [r1, g1, b1] = blend_white(first);
+    let [r2, g2, b2] = blend_white(second);
+    let (y1, y2) = (rgb_to_y(r1, g1, b1), rgb_to_y(r2, g2, b2));
+    let luma = y1 - y2;
+    if brightness_only {
+        return luma;
+    }
+    let in_phase = rgb_to_i(r1, g1, b1) - rgb_to_i(r2, g2, b2);
+    let quadrature = rgb_to_q(r1, g1, b1) - rgb_to_q(r2, g2, b2);
+    let delta =
+        0.5053 * luma * luma + 0.299 * in_phase * in_phase + 0.1957 * quadrature * quadrature;
+    if y1 > y2 { -delta } else { delta }
+}
+
+/// The 3×3 neighbourhood of `(x, y)` clipped to the image, and whether the
+/// pixel sits on the image edge.
+fn neighbourhood(image: &RgbaImage, x: u32, y: u32) -> (Vec<(u32, u32)>, bool) {
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x2, y2) = ((x + 1).min(image.width - 1), (y + 1).min(image.height - 1));
+    let on_edge = x == x0 || x == x2 || y == y0 || y == y2;
+    let mut points = Vec::with_capacity(8);
+    for ny in y0..=y2 {
+        for nx in x0..=x2 {
+            if (nx, ny) != (x, y) {
+                points.push((nx, ny));
+            }
+        }
+    }
+    (points, on_edge)
+}
+
+/// Whether the pixel at `(x, y)` in `image` looks like an anti-aliased edge:
+/// it lies between a darker and a lighter neighbour that are each part of a
+/// solid area in both images.
+fn anti_aliased(image: &RgbaImage, x: u32, y: u32, other: &RgbaImage) -> bool {
+    let center = image.pixel(x, y);
+    let (points, on_edge) = neighbourhood(image, x, y);
+    let mut zeroes = usize::from(on_edge);
+    let (mut min, mut max) = (0.0_f64, 0.0_f64);
+    let (mut brighter, mut darker) = ((0, 0), (0, 0));
+
+    for (nx, ny) in points {
+        let delta = color_delta(center, image.pixel(nx, ny), true);
+        if delta == 0.0 {
+            zeroes += 1;
+            if zeroes > 2 {
+                return false;
+            }
+        } else if delta < min {
+            min = delta;
+            brighter = (nx, ny);
+        } else if delta > max {
+            max = delta;
+            darker = (nx, ny);
+        }
+    }
+    if min == 0.0 || max == 0.0 {
+        return false;
+    }
+
+    let solid_in_both = |(px, py): (u32, u32)| {
+        other.contains(px, py)
+            && has_many_siblings(image, px, py)
+            && has_many_siblings(other, px, py)
+    };
+    solid_in_both(darker) || solid_in_both(brighter)
+}
+
+/// Whether at least three neighbours of `(x, y)` share its exact colour.
+fn has_many_siblings(image: &RgbaImage, x: u32, y: u32) -> bool {
+    let center = image.pixel(x, y);
+    let (points, on_edge) = neighbourhood(image, x, y);
+    let mut zeroes = usize::from(on_edge);
+    for (nx, ny) in points {
+        if image.pixel(nx, ny) == center {
+            zeroes += 1;
+            if zeroes > 2 {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            data: rgba.repeat(width as usize * height as usize),
+        }
+    }
+
+    fn set(image: &mut RgbaImage, x: u32, y: u32, rgba: [u8; 4]) {
+        let i = (y as usize * image.width as usize + x as usize) * 4;
+        image.data[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    #[test]
+    fn identical_images_match() {
+        let image = solid(8, 8, [10, 20, 30, 255]);
+        let cmp = compare(&image, &image.clone());
+        assert_eq!(cmp.mismatched_pixels, 0);
+        assert_eq!(cmp.total_pixels, 64);
+        assert!(cmp.mismatch_ratio().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn changed_block_is_counted_and_highlighted() {
+        let expected = solid(10, 10, [255, 255, 255, 255]);
+        let mut actual = expected.clone();
+        for y in 2..4 {
+            for x in 2..4 {
+                set(&mut actual, x, y, [0, 0, 0, 255]);
+            }
+        }
+        let cmp = compare(&expected, &actual);
+        assert_eq!(cmp.mismatched_pixels, 4);
+        assert!((cmp.mismatch_ratio() - 0.04).abs() < 1e-9);
+        assert_eq!(cmp.diff.pixel(2, 2), [255, 0, 0, 255]);
+        assert_ne!(cmp.diff.pixel(0, 0), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn slight_colour_shift_is_within_tolerance() {
+        let expected = solid(4, 4, [200, 200, 200, 255]);
+        let actual = solid(4, 4, [202, 201, 200, 255]);
+        assert_eq!(compare(&expected, &actual).mismatched_pixels, 0);
+    }
+
+    #[test]
+    fn anti_aliased_edge_pixel_is_tolerated() {
+        // Left half black, right half white; the actual image softens one
+        // pixel on the boundary to grey.
+        let mut expected = solid(6, 6, [255, 255, 255, 255]);
+        for y in 0..6 {
+            for x in 0..3 {
+                set(&mut expected, x, y, [0, 0, 0, 255]);
+            }
+        }
+        let mut actual = expected.clone();
+        set(&mut actual, 3, 3, [128, 128, 128, 255]);
+        let cmp = compare(&expected, &actual);
+        assert_eq!(cmp.mismatched_pixels, 0);
+        assert_eq!(cmp.anti_aliased_pixels, 1);
+    }
+
+    #[test]
+    fn size_difference_counts_uncovered_pixels() {
+        let expected = solid(4, 4, [0, 0, 0, 255]);
+        let actual = solid(4, 5, [0, 0, 0, 255]);
+        let cmp = compare(&expected, &actual);
+        assert_eq!(cmp.total_pixels, 20);
+        assert_eq!(cmp.mismatched_pixels, 4);
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let mut image = solid(3, 2, [1, 2, 3, 255]);
+        set(&mut image, 1, 1, [200, 100, 50, 128]);
+        let bytes = encode_png(&image).unwrap();
+        assert_eq!(decode_png(&bytes).unwrap(), image);
+        assert!(decode_png(b"not a png").is_err());
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use agentchrome::cdp::CdpClient;
+use agentchrome::connection::{ManagedSession, resolve_target};
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::session::{self, SessionError};
+
+use crate::cli::{
+    GlobalOpts, InjectWorld, JsInjectAddArgs, JsInjectArgs, JsInjectCommand, JsInjectRemoveArgs,
+};
+use crate::output::{connect_from_global, print_output};
+
+/// World name used for scripts added with `--world isolated`.
+const ISOLATED_WORLD_NAME: &str = "agentchrome_inject";
+
+// =============================================================================
+// Preload script persistence
+// =============================================================================
+
+/// A preload script saved by `js inject add`. The source is captured when the
+/// script is added, so later edits to the file need another `add`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InjectedScript {
+    pub name: String,
+    /// `main` or `isolated`.
+    pub world: String,
+    pub file: String,
+    pub source: String,
+}
+
+/// Preload scripts keyed by tab (target) ID.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InjectState {
+    #[serde(default)]
+    pub tabs: BTreeMap<String, Vec<InjectedScript>>,
+}
+
+impl InjectState {
+    /// Add `script` to `tab`, replacing any script with the same name.
+    fn add(&mut self, tab: &str, script: InjectedScript) {
+        let scripts = self.tabs.entry(tab.to_string()).or_default();
+        match scripts.iter_mut().find(|s| s.name == script.name) {
+            Some(existing) => *existing = script,
+            None => scripts.push(script),
+        }
+    }
+
+    /// Remove the named script (or every script when `name` is `None`) from
+    /// `tab`, returning the names removed.
+    fn remove(&mut self, tab: &str, name: Option<&str>) -> Vec<String> {
+        let Some(scripts) = self.tabs.get_mut(tab) else {
+            return Vec::new();
+        };
+        let mut removed = Vec::new();
+        scripts.retain(|s| {
+            let matches = name.is_none_or(|n| s.name == n);
+            if matches {
+                removed.push(s.name.clone());
+            }
+            !matches
+        });
+        if scripts.is_empty() {
+            self.tabs.remove(tab);
+        }
+        removed
+    }
+
+    /// Drop the scripts of tabs that are no longer open.
+    fn prune_closed_tabs(&mut self, targets: &serde_json::Value) {
+        let open: Vec<&str> = targets["targetInfos"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t["targetId"].as_str())
+            .collect();
+        self.tabs.retain(|tab, _| open.contains(&tab.as_str()));
+    }
+}
+
+/// Returns the path to the preload script file: `inject-scripts.json` in the
+/// selected session's state directory.
+fn inject_state_path() -> Result<PathBuf, SessionError> {
+    Ok(session::session_file_path()?.with_file_name("inject-scripts.json"))
+}
+
+/// Write preload scripts to the given path (atomic, owner-only permissions).
+fn write_inject_state_to(path: &Path, state: &InjectState) -> Result<(), SessionError> {
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+    session::write_state_file(path, json.as_bytes())
+}
+
+/// Read preload scripts from the given path. A missing file means no scripts.
+fn read_inject_state_from(path: &Path) -> Result<InjectState, SessionError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| SessionError::InvalidFormat(format!("{} at {}", e, path.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(InjectState::default()),
+        Err(e) => Err(SessionError::Io(e)),
+    }
+}
+
+fn read_inject_state() -> Result<InjectState, SessionError> {
+    read_inject_state_from(&inject_state_path()?)
+}
+
+fn write_inject_state(state: &InjectState) -> Result<(), SessionError> {
+    write_inject_state_to(&inject_state_path()?, state)
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+fn world_name(world: InjectWorld) -> &'static str {
+    match world {
+        InjectWorld::Main => "main",
+        InjectWorld::Isolated => "isolated",
+    }
+}
+
+/// `Page.addScriptToEvaluateOnNewDocument` parameters for `script`.
+fn registration_params(script: &InjectedScript, run_immediately: bool) -> serde_json::Value {
+    let mut params = serde_json::json!({ "source": script.source });
+    if script.world == "isolated" {
+        params["worldName"] = ISOLATED_WORLD_NAME.into();
+    }
+    if run_immediately {
+        params["runImmediately"] = true.into();
+    }
+    params
+}
+
+/// Re-register the preload scripts saved for `target_id` on a new session.
+///
+/// Registrations belong to the CDP session that made them, so every
+/// connection has to repeat them, like `apply_emulate_state()` does for
+/// emulation overrides.
+///
+/// # Errors
+///
+/// Returns `AppError` if the state file is unreadable or Chrome rejects a
+/// script.
+pub async fn apply_inject_scripts(
+    managed: &ManagedSession,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let state = read_inject_state()?;
+    let Some(scripts) = state.tabs.get(target_id) else {
+        return Ok(());
+    };
+    for script in scripts {
+        managed
+            .send_command(
+                "Page.addScriptToEvaluateOnNewDocument",
+                Some(registration_params(script, false)),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct AddResult {
+    name: String,
+    world: String,
+    file: String,
+    tab: String,
+}
+
+#[derive(Serialize)]
+struct ScriptInfo {
+    name: String,
+    world: String,
+    file: String,
+    bytes: usize,
+}
+
+#[derive(Serialize)]
+struct RemoveResult {
+    removed: Vec<String>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Connect and resolve the tab selected by `--tab`/`--page-id`.
+async fn resolve_tab(global: &GlobalOpts) -> Result<(CdpClient, String), AppError> {
+    let conn = connect_from_global(global).await?;
+    let target = resolve_target(
+        &conn.resolved.host,
+        conn.resolved.port,
+        global.tab.as_deref(),
+        global.page_id.as_deref(),
+    )
+    .await?;
+    Ok((conn.client, target.id))
+}
+
+/// Read the saved scripts, forgetting tabs Chrome no longer has.
+async fn read_pruned_state(client: &CdpClient) -> Result<InjectState, AppError> {
+    let mut state = read_inject_state()?;
+    if !state.tabs.is_empty() {
+        let targets = client.send_command("Target.getTargets", None).await?;
+        state.prune_closed_tabs(&targets);
+    }
+    Ok(state)
+}
+
+/// Default script name: the file name without its extension.
+fn default_name(file: &Path) -> String {
+    file.file_stem().map_or_else(
+        || "script".to_string(),
+        |s| s.to_string_lossy().into_owned(),
+    )
+}
+
+fn script_not_found(name: &str) -> AppError {
+    AppError {
+        message: format!(
+            "No injected script named '{name}' on this tab. Run 'agentchrome js inject list'."
+        ),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `js inject` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the connection fails, the script file cannot be
+/// read, or the state file cannot be read or written.
+pub async fn execute_inject(global: &GlobalOpts, args: &JsInjectArgs) -> Result<(), AppError> {
+    match &args.command {
+        JsInjectCommand::Add(add_args) => execute_add(global, add_args).await,
+        JsInjectCommand::List => execute_list(global).await,
+        JsInjectCommand::Remove(remove_args) => execute_remove(global, remove_args).await,
+    }
+}
+
+async fn execute_add(global: &GlobalOpts, args: &JsInjectAddArgs) -> Result<(), AppError> {
+    let file = args.file.display().to_string();
+    let source = std::fs::read_to_string(&args.file).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            AppError::script_file_not_found(&file)
+        } else {
+            AppError::script_file_read_failed(&file, &e.to_string())
+        }
+    })?;
+    let script = InjectedScript {
+        name: args
+            .name
+            .clone()
+            .unwrap_or_else(|| default_name(&args.file)),
+        world: world_name(args.world).to_string(),
+        file,
+        source,
+    };
+
+    let (client, tab) = resolve_tab(global).await?;
+    let managed = ManagedSession::new(client.create_session(&tab).await?);
+    // Also run the script in the current document, so the shim is in place
+    // without a reload.
+    managed
+        .send_command(
+            "Page.addScriptToEvaluateOnNewDocument",
+            Some(registration_params(&script, true)),
+        )
+        .await?;
+
+    let mut state = read_pruned_state(&client).await?;
+    let result = AddResult {
+        name: script.name.clone(),
+        world: script.world.clone(),
+        file: script.file.clone(),
+        tab: tab.clone(),
+    };
+    state.add(&tab, script);
+    write_inject_state(&state)?;
+
+    if global.output.plain {
+        println!(
+            "Injected {} ({} world) into {tab}",
+            result.name, result.world
+        );
+        return Ok(());
+    }
+    print_output(&result, &global.output)
+}
+
+async fn execute_list(global: &GlobalOpts) -> Result<(), AppError> {
+    let (client, tab) = resolve_tab(global).await?;
+    let state = read_pruned_state(&client).await?;
+
+    let scripts: Vec<ScriptInfo> = state
+        .tabs
+        .get(&tab)
+        .into_iter()
+        .flatten()
+        .map(|s| ScriptInfo {
+            name: s.name.clone(),
+            world: s.world.clone(),
+            file: s.file.clone(),
+            bytes: s.source.len(),
+        })
+        .collect();
+
+    if global.output.plain {
+        if scripts.is_empty() {
+            println!("No injected scripts");
+        }
+        for s in &scripts {
+            println!("{}  {}  {}", s.name, s.world, s.file);
+        }
+        return Ok(());
+    }
+    print_output(&scripts, &global.output)
+}
+
+async fn execute_remove(global: &GlobalOpts, args: &JsInjectRemoveArgs) -> Result<(), AppError> {
+    let (client, tab) = resolve_tab(global).await?;
+    let mut state = read_pruned_state(&client).await?;
+
+    let removed = state.remove(&tab, args.name.as_deref());
+    if let Some(name) = &args.name
+        && removed.is_empty()
+    {
+        return Err(script_not_found(name));
+    }
+    write_inject_state(&state)?;
+
+    if global.output.plain {
+        println!("Removed {} script(s)", removed.len());
+        return Ok(());
+    }
+    print_output(&RemoveResult { removed }, &global.output)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(name: &str, world: &str) -> InjectedScript {
+        InjectedScript {
+            name: name.into(),
+            world: world.into(),
+            file: format!("{name}.js"),
+            source: format!("window.{name} = true;"),
+        }
+    }
+
+    #[test]
+    fn add_replaces_script_with_same_name() {
+        let mut state = InjectState::default();
+        state.add("T1", script("shim", "main"));
+        state.add("T1", script("flags", "main"));
+        state.add("T1", script("shim", "isolated"));
+
+        let scripts = &state.tabs["T1"];
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "shim");
+        assert_eq!(scripts[0].world, "isolated");
+    }
+
+    #[test]
+    fn remove_by_name_and_all() {
+        let mut state = InjectState::default();
+        state.add("T1", script("shim", "main"));
+        state.add("T1", script("flags", "main"));
+
+        assert!(state.remove("T1", Some("missing")).is_empty());
+        assert_eq!(state.remove("T1", Some("shim")), ["shim"]);
+        assert_eq!(state.remove("T1", None), ["flags"]);
+        assert!(!state.tabs.contains_key("T1"));
+        assert!(state.remove("T2", None).is_empty());
+    }
+
+    #[test]
+    fn prune_drops_closed_tabs() {
+        let mut state = InjectState::default();
+        state.add("T1", script("shim", "main"));
+        state.add("T2", script("shim", "main"));
+        state.prune_closed_tabs(&serde_json::json!({
+            "targetInfos": [{"targetId": "T2", "type": "page"}]
+        }));
+        assert_eq!(state.tabs.keys().collect::<Vec<_>>(), ["T2"]);
+    }
+
+    #[test]
+    fn registration_params_select_world() {
+        let main = registration_params(&script("shim", "main"), false);
+        assert_eq!(main, serde_json::json!({ "source": "window.shim = true;" }));
+
+        let isolated = registration_params(&script("shim", "isolated"), true);
+        assert_eq!(isolated["worldName"], ISOLATED_WORLD_NAME);
+        assert_eq!(isolated["runImmediately"], true);
+    }
+
+    #[test]
+    fn inject_state_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("agentchrome-test-inject-{}", std::process::id()));
+        let path = dir.join("inject-scripts.json");
+        assert!(read_inject_state_from(&path).unwrap().tabs.is_empty());
+
+        let mut state = InjectState::default();
+        state.add("T1", script("shim", "main"));
+        write_inject_state_to(&path, &state).unwrap();
+        let read = read_inject_state_from(&path).unwrap();
+        assert_eq!(read.tabs["T1"], [script("shim", "main")]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn default_name_strips_extension() {
+        assert_eq!(
+            default_name(Path::new("/tmp/shims/mock-fetch.js")),
+            "mock-fetch"
+        );
+    }
+}
@@ -45,6 +45,8 @@ struct ClickResult {
     right_click: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -60,6 +62,8 @@ struct ClickAtResult {
     right_click: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -67,6 +71,8 @@ struct HoverResult {
     hovered: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -74,6 +80,8 @@ struct DragResult {
     dragged: DragTargets,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -89,6 +97,8 @@ struct DragAtResult {
     steps: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -98,6 +108,8 @@ struct MouseDownAtResult {
     button: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -107,6 +119,8 @@ struct MouseUpAtResult {
     button: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -115,6 +129,8 @@ struct TypeResult {
     length: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -124,6 +140,8 @@ struct KeyResult {
     repeat: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -132,6 +150,8 @@ struct ScrollResult {
     position: Coords,
     #[serde(skip_serializing_if = "Option::is_none")]
     snapshot: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_diff: Option<serde_json::Value>,
 }
 
 // =============================================================================
@@ -252,24 +272,16 @@ fn print_scroll_plain(result: &ScrollResult, mode: &str) {
 
 /// Resolve a target (UID or CSS selector) to a backend DOM node ID.
 ///
-/// For UIDs: reads snapshot state and looks up the backendDOMNodeId.
+/// For UIDs: reads snapshot state, looks up the backendDOMNodeId, and fails with a
+/// stale-uid error if the element has left the document.
 /// For CSS selectors: queries the DOM and resolves the node.
 async fn resolve_target_to_backend_node_id(
     session: &ManagedSession,
     target: &str,
 ) -> Result<i64, AppError> {
     if snapshot::is_uid(target) {
-        // Read snapshot state
-        let state = snapshot::read_snapshot_state()?.ok_or_else(AppError::no_snapshot_state)?;
-
-        // Lookup UID in the map
-        let backend_node_id = state
-            .uid_map
-            .get(target)
-            .copied()
-            .ok_or_else(|| AppError::uid_not_found(target))?;
-
-        Ok(backend_node_id)
+        // Look up the UID in snapshot state and make sure its element still exists
+        snapshot::resolve_uid(session, target).await
     } else if snapshot::is_css_selector(target) {
         // Strip 'css:' prefix
         let selector = &target[4..];
@@ -1300,12 +1312,15 @@ async fn get_current_url(managed: &ManagedSession) -> Result<String, AppError> {
 
 /// Take a fresh snapshot and write it to snapshot state.
 ///
-/// Returns the snapshot tree as a JSON value.
-async fn take_snapshot(
+/// Returns the snapshot tree (when `include_tree`) and the diff against the
+/// previous snapshot state (when `include_diff`) as JSON values.
+pub(crate) async fn take_snapshot(
     session: &mut ManagedSession,
     url: &str,
     compact: bool,
-) -> Result<serde_json::Value, AppError> {
+    include_tree: bool,
+    include_diff: bool,
+) -> Result<(Option<serde_json::Value>, Option<serde_json::Value>), AppError> {
     // Enable Accessibility domain
     session.ensure_domain("Accessibility").await?;
 
@@ -1319,7 +1334,11 @@ async fn take_snapshot(
         .ok_or_else(|| AppError::snapshot_failed("missing nodes array"))?;
 
     // Build tree
-    let build_result = snapshot::build_tree(nodes, false);
+    // Read the previous state: UIDs carry forward from it, and it is the diff baseline
+    let previous = snapshot::read_snapshot_state().ok().flatten();
+    let document = snapshot::document_id(session).await;
+    let uids = snapshot::UidAssigner::carried_from(previous.as_ref(), None, document.as_deref());
+    let build_result = snapshot::build_tree_with_uids(nodes, false, uids);
 
     // Write snapshot state
     let state = snapshot::SnapshotState {
@@ -1331,9 +1350,18 @@ async fn take_snapshot(
         aggregate: false,
         frame_uid_ranges: Vec::new(),
         frame_ids: Vec::new(),
+        nodes: build_result.nodes,
+        stale_uids: std::collections::HashMap::new(),
+        last_uid: 0,
+        document,
     };
     snapshot::write_snapshot_state(&state)?;
 
+    let diff = include_diff.then(|| snapshot::diff_against_state(previous.as_ref(), &state).1);
+    if !include_tree {
+        return Ok((None, diff));
+    }
+
     // Apply compact filtering if requested
     let root = if compact {
         snapshot::compact_tree(&build_result.root)
@@ -1345,7 +1373,7 @@ async fn take_snapshot(
     let snapshot_json = serde_json::to_value(&root)
         .map_err(|e| AppError::snapshot_failed(&format!("failed to serialize snapshot: {e}")))?;
 
-    Ok(snapshot_json)
+    Ok((Some(snapshot_json), diff))
 }
 
 // =============================================================================
@@ -1719,11 +1747,18 @@ async fn execute_scroll(
     };
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(effective).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = ScrollResult {
@@ -1736,6 +1771,7 @@ async fn execute_scroll(
             y: final_y,
         },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -1926,10 +1962,17 @@ async fn execute_click(
     };
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result
@@ -1940,6 +1983,7 @@ async fn execute_click(
         double_click: if args.double { Some(true) } else { None },
         right_click: if args.right { Some(true) } else { None },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2137,7 +2181,7 @@ async fn execute_click_at(
     }
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let snap_url = if opened_dialog {
             None
         } else if let Some(ref u) = url {
@@ -2146,12 +2190,19 @@ async fn execute_click_at(
             Some(get_current_url(&managed).await?)
         };
         if let Some(snap_url) = snap_url {
-            Some(take_snapshot(&mut managed, &snap_url, args.compact).await?)
+            take_snapshot(
+                &mut managed,
+                &snap_url,
+                args.compact,
+                args.include_snapshot,
+                args.include_snapshot_diff,
+            )
+            .await?
         } else {
-            None
+            (None, None)
         }
     } else {
-        None
+        (None, None)
     };
 
     // Build result — `clicked_at` reports resolved page-global coords when --relative-to is
@@ -2166,6 +2217,7 @@ async fn execute_click_at(
         double_click: if args.double { Some(true) } else { None },
         right_click: if args.right { Some(true) } else { None },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2220,17 +2272,25 @@ async fn execute_hover(
     dispatch_hover(&mut managed, x, y).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result
     let result = HoverResult {
         hovered: args.target.clone(),
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2290,11 +2350,18 @@ async fn execute_drag(
     dispatch_drag(&mut managed, from_x, from_y, to_x, to_y).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result
@@ -2304,6 +2371,7 @@ async fn execute_drag(
             to: args.to.clone(),
         },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2410,11 +2478,18 @@ async fn execute_drag_at(
     dispatch_drag_interpolated(&mut managed, from_x, from_y, to_x, to_y, steps).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result — from/to report resolved page-global coords when --relative-to is present
@@ -2431,6 +2506,7 @@ async fn execute_drag_at(
         },
         steps: if steps > 1 { Some(steps) } else { None },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2500,11 +2576,18 @@ async fn execute_mousedown_at(
     dispatch_mousedown(&mut managed, x, y, button).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result — mousedown_at reports resolved page-global coords when --relative-to is used
@@ -2512,6 +2595,7 @@ async fn execute_mousedown_at(
         mousedown_at: Coords { x: out_x, y: out_y },
         button: mouse_button_for_output(args.button.as_ref()),
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2581,11 +2665,18 @@ async fn execute_mouseup_at(
     dispatch_mouseup(&mut managed, x, y, button).await?;
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     // Build result — mouseup_at reports resolved page-global coords when --relative-to is used
@@ -2593,6 +2684,7 @@ async fn execute_mouseup_at(
         mouseup_at: Coords { x: out_x, y: out_y },
         button: mouse_button_for_output(args.button.as_ref()),
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2634,18 +2726,26 @@ async fn execute_type(
     }
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         managed.ensure_domain("Runtime").await?;
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = TypeResult {
         typed: text.clone(),
         length,
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2686,12 +2786,19 @@ async fn execute_key(
     }
 
     // Take snapshot if requested
-    let snapshot = if args.include_snapshot {
+    let (snapshot, snapshot_diff) = if args.include_snapshot || args.include_snapshot_diff {
         managed.ensure_domain("Runtime").await?;
         let url = get_current_url(&managed).await?;
-        Some(take_snapshot(&mut managed, &url, args.compact).await?)
+        take_snapshot(
+            &mut managed,
+            &url,
+            args.compact,
+            args.include_snapshot,
+            args.include_snapshot_diff,
+        )
+        .await?
     } else {
-        None
+        (None, None)
     };
 
     let result = KeyResult {
@@ -2702,6 +2809,7 @@ async fn execute_key(
             None
         },
         snapshot,
+        snapshot_diff,
     };
 
     if global.output.plain {
@@ -2814,6 +2922,7 @@ mod tests {
             double_click: None,
             right_click: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["clicked"], "s1");
@@ -2833,6 +2942,7 @@ mod tests {
             double_click: Some(true),
             right_click: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["double_click"], true);
@@ -2848,6 +2958,7 @@ mod tests {
             double_click: None,
             right_click: Some(true),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["right_click"], true);
@@ -2863,6 +2974,7 @@ mod tests {
             double_click: None,
             right_click: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["clicked_at"]["x"], 100.0);
@@ -2875,6 +2987,7 @@ mod tests {
         let result = HoverResult {
             hovered: "s3".to_string(),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["hovered"], "s3");
@@ -2889,6 +3002,7 @@ mod tests {
                 to: "s2".to_string(),
             },
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["dragged"]["from"], "s1");
@@ -2905,6 +3019,7 @@ mod tests {
             },
             steps: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["dragged_at"]["from"]["x"], 100.0);
@@ -2924,6 +3039,7 @@ mod tests {
             },
             steps: Some(5),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["steps"], 5);
@@ -2935,6 +3051,7 @@ mod tests {
             mousedown_at: Coords { x: 150.0, y: 250.0 },
             button: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["mousedown_at"]["x"], 150.0);
@@ -2949,6 +3066,7 @@ mod tests {
             mousedown_at: Coords { x: 100.0, y: 200.0 },
             button: Some("right".to_string()),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["button"], "right");
@@ -2960,6 +3078,7 @@ mod tests {
             mouseup_at: Coords { x: 300.0, y: 400.0 },
             button: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["mouseup_at"]["x"], 300.0);
@@ -2974,6 +3093,7 @@ mod tests {
             mouseup_at: Coords { x: 100.0, y: 200.0 },
             button: Some("middle".to_string()),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["button"], "middle");
@@ -3356,6 +3476,7 @@ mod tests {
             typed: "Hello".to_string(),
             length: 5,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["typed"], "Hello");
@@ -3369,6 +3490,7 @@ mod tests {
             typed: "test".to_string(),
             length: 4,
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["typed"], "test");
@@ -3382,6 +3504,7 @@ mod tests {
             pressed: "Enter".to_string(),
             repeat: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["pressed"], "Enter");
@@ -3395,6 +3518,7 @@ mod tests {
             pressed: "ArrowDown".to_string(),
             repeat: Some(5),
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["pressed"], "ArrowDown");
@@ -3407,6 +3531,7 @@ mod tests {
             pressed: "Tab".to_string(),
             repeat: None,
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["pressed"], "Tab");
@@ -3420,6 +3545,7 @@ mod tests {
             pressed: "Control+A".to_string(),
             repeat: None,
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["pressed"], "Control+A");
@@ -3435,6 +3561,7 @@ mod tests {
             scrolled: Coords { x: 0.0, y: 600.0 },
             position: Coords { x: 0.0, y: 600.0 },
             snapshot: None,
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["scrolled"]["x"], 0.0);
@@ -3450,6 +3577,7 @@ mod tests {
             scrolled: Coords { x: 0.0, y: 300.0 },
             position: Coords { x: 0.0, y: 300.0 },
             snapshot: Some(serde_json::json!({"role": "document"})),
+            snapshot_diff: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["scrolled"]["y"], 300.0);
@@ -3464,6 +3592,7 @@ mod tests {
             scrolled: Coords { x: 200.0, y: 0.0 },
             position: Coords { x: 200.0, y: 100.0 },
             snapshot: None,
+            snapshot_diff: None,
         };
         let json_str = serde_json::to_string(&result).unwrap();
         assert!(!json_str.contains("snapshot"));
@@ -6,6 +6,7 @@ use agentchrome::error::{AppError, ExitCode};
 
 use crate::cli::{GlobalOpts, JsArgs, JsCommand, JsExecArgs};
 use crate::emulate::apply_emulate_state;
+use crate::inject::apply_inject_scripts;
 use crate::output::connect_from_global_with_timeout;
 
 // =============================================================================
@@ -48,6 +49,7 @@ struct JsExecError {
 pub async fn execute_js(global: &GlobalOpts, args: &JsArgs) -> Result<(), AppError> {
     match &args.command {
         JsCommand::Exec(exec_args) => execute_exec(global, exec_args, args.frame.as_deref()).await,
+        JsCommand::Inject(inject_args) => crate::inject::execute_inject(global, inject_args).await,
     }
 }
 
@@ -74,6 +76,7 @@ async fn setup_session(
     let session = conn.client.create_session(&target.id).await?;
     let mut managed = ManagedSession::new(session);
     apply_emulate_state(&mut managed).await?;
+    apply_inject_scripts(&managed, &target.id).await?;
     managed.install_dialog_interceptors().await;
 
     Ok((conn.client, managed))
@@ -644,10 +647,16 @@ async fn execute_in_worker(
 /// Propagates `AppError` from the underlying js logic.
 pub async fn run_from_session(
     managed: &mut agentchrome::connection::ManagedSession,
-    _global: &GlobalOpts,
+    global: &GlobalOpts,
     args: &JsArgs,
 ) -> Result<serde_json::Value, AppError> {
-    let JsCommand::Exec(exec_args) = &args.command;
+    let exec_args = match &args.command {
+        JsCommand::Exec(exec_args) => exec_args,
+        JsCommand::Inject(inject_args) => {
+            crate::inject::execute_inject(global, inject_args).await?;
+            return Ok(serde_json::json!({"executed": true}));
+        }
+    };
 
     let code = resolve_code(exec_args)?;
 
@@ -696,10 +705,7 @@ async fn execute_with_uid_on_session(
             custom_json: None,
         })?;
 
-    let backend_node_id = state
-        .uid_map
-        .get(uid)
-        .ok_or_else(|| AppError::uid_not_found(uid))?;
+    let backend_node_id = crate::snapshot::lookup_uid(&state, uid)?;
 
     let resolve_result = session
         .send_command(
@@ -707,19 +713,11 @@ async fn execute_with_uid_on_session(
             Some(serde_json::json!({ "backendNodeId": backend_node_id })),
         )
         .await
-        .map_err(|e| AppError {
-            message: format!("Failed to resolve UID '{uid}': {e}"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+        .map_err(|_| crate::snapshot::stale_uid_error(&state, uid))?;
 
     let object_id = resolve_result["object"]["objectId"]
         .as_str()
-        .ok_or_else(|| AppError {
-            message: format!("UID '{uid}' could not be resolved to a DOM object"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+        .ok_or_else(|| crate::snapshot::stale_uid_error(&state, uid))?;
 
     let params = serde_json::json!({
         "functionDeclaration": code,
@@ -770,10 +768,7 @@ async fn execute_with_uid(
             custom_json: None,
         })?;
 
-    let backend_node_id = state
-        .uid_map
-        .get(uid)
-        .ok_or_else(|| AppError::uid_not_found(uid))?;
+    let backend_node_id = crate::snapshot::lookup_uid(&state, uid)?;
 
     // Enable DOM domain for resolveNode
     managed.ensure_domain("DOM").await?;
@@ -785,19 +780,11 @@ async fn execute_with_uid(
             Some(serde_json::json!({ "backendNodeId": backend_node_id })),
         )
         .await
-        .map_err(|e| AppError {
-            message: format!("Failed to resolve UID '{uid}': {e}"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+        .map_err(|_| crate::snapshot::stale_uid_error(&state, uid))?;
 
     let object_id = resolve_result["object"]["objectId"]
         .as_str()
-        .ok_or_else(|| AppError {
-            message: format!("UID '{uid}' could not be resolved to a DOM object"),
-            code: ExitCode::GeneralError,
-            custom_json: None,
-        })?;
+        .ok_or_else(|| crate::snapshot::stale_uid_error(&state, uid))?;
 
     // Call the function on the resolved element
     let params = serde_json::json!({
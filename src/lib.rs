@@ -10,10 +10,13 @@ pub mod chrome;
 pub mod config;
 pub mod connection;
 pub mod coords;
+pub mod daemon;
 pub mod error;
 pub mod examples_data;
 pub mod frame;
+pub mod har;
 pub mod man_enrichment;
+pub mod route;
 pub mod session;
 
 /// Returns the clap `Command` definition for man page and completion generation.
@@ -1,9 +1,12 @@
 mod audit;
 mod capabilities_cli;
+mod cdp_cli;
 mod cli;
 mod console;
+mod context;
 mod cookie;
 mod coord_helpers;
+mod daemon_cli;
 mod script;
 // Re-export the library's coords module so `crate::coords` works in shared code (cli/mod.rs).
 mod coords {
@@ -15,6 +18,9 @@ mod dom;
 mod emulate;
 mod examples;
 mod form;
+mod golden;
+mod image_diff;
+mod inject;
 mod interact;
 mod js;
 mod markdown;
@@ -24,9 +30,12 @@ mod network;
 mod output;
 mod page;
 mod perf;
+mod route_cli;
 mod skill;
 mod skill_check;
 mod snapshot;
+mod state;
+mod storage;
 mod tabs;
 
 use std::io::Write as _;
@@ -170,7 +179,49 @@ fn resolve_subcommand_path(argv: &[String]) -> Option<String> {
     }
 }
 
+/// Start a `page record` timeline marker for `command` if it is an
+/// interaction and a recording is running. `argv` is the full invocation,
+/// program name first. Shared by top-level commands and `script run` steps.
+fn command_marker(command: &Command, argv: &[String]) -> Option<page::record::CommandMarker> {
+    if !matches!(command, Command::Interact(_) | Command::Form(_)) {
+        return None;
+    }
+    let path = resolve_subcommand_path(argv).unwrap_or_default();
+    page::record::CommandMarker::start(path, redact_marker_args(command, argv))
+}
+
+/// The marker's argument list: `argv` without the program name, with typed
+/// text and form values replaced by `<redacted>` so secrets never reach the
+/// recording directory. Command names, targets and flags are kept.
+fn redact_marker_args(command: &Command, argv: &[String]) -> Vec<String> {
+    let secret = match command {
+        Command::Interact(interact) => match &interact.command {
+            cli::InteractCommand::Type(a) => Some(a.text.as_str()),
+            _ => None,
+        },
+        Command::Form(form) => match &form.command {
+            cli::FormCommand::Fill(a) => Some(a.value.as_str()),
+            cli::FormCommand::FillMany(a) => a.input.as_deref(),
+            _ => None,
+        },
+        _ => None,
+    };
+    argv.iter()
+        .skip(1)
+        .map(|arg| match secret {
+            Some(secret) if arg == secret => "<redacted>".to_string(),
+            _ => arg.clone(),
+        })
+        .collect()
+}
+
 async fn run(cli: &Cli) -> Result<(), AppError> {
+    // Every state file path resolves through the selected session, so pick it
+    // before anything touches disk.
+    if let Some(name) = &cli.global.session {
+        session::select_session(name);
+    }
+
     // Load config file (if any) and apply defaults to global opts
     let (config_path, config_file) = config::load_config(cli.global.config.as_deref());
     if should_emit_stale_notice_for_command(&cli.command) {
@@ -178,13 +229,19 @@ async fn run(cli: &Cli) -> Result<(), AppError> {
     }
     let global = apply_config_defaults(&cli.global, &config_file);
 
-    match &cli.command {
+    // Interactions run during `page record` are marked on its timeline.
+    let marker = command_marker(&cli.command, &std::env::args().collect::<Vec<_>>());
+
+    let result = match &cli.command {
         Command::Config(args) => {
             let resolved = build_resolved_config(&global, &config_file, config_path);
             execute_config(&args.command, &resolved, cli.global.config.as_deref())
         }
         Command::Connect(args) => execute_connect(&global, args).await,
+        Command::Daemon(args) => daemon_cli::execute_daemon(&global, args).await,
+        Command::Cdp(args) => cdp_cli::execute_cdp(&global, args).await,
         Command::Tabs(args) => tabs::execute_tabs(&global, args).await,
+        Command::Context(args) => context::execute_context(&global, args).await,
         Command::Navigate(args) => navigate::execute_navigate(&global, args).await,
         Command::Page(args) => page::execute_page(&global, args).await,
         Command::Dom(args) => dom::execute_dom(&global, args).await,
@@ -197,6 +254,8 @@ async fn run(cli: &Cli) -> Result<(), AppError> {
         Command::Emulate(args) => emulate::execute_emulate(&global, args).await,
         Command::Perf(args) => perf::execute_perf(&global, args).await,
         Command::Cookie(args) => cookie::execute_cookie(&global, args).await,
+        Command::Storage(args) => storage::execute_storage(&global, args).await,
+        Command::State(args) => state::execute_state(&global, args).await,
         Command::Dialog(args) => dialog::execute_dialog(&global, args).await,
         Command::Audit(args) => audit::execute_audit(&global, args).await,
         Command::Diagnose(args) => diagnose::execute_diagnose(&global, args).await,
@@ -207,7 +266,11 @@ async fn run(cli: &Cli) -> Result<(), AppError> {
         Command::Completions(args) => execute_completions(args),
         Command::Man(args) => execute_man(args),
         Command::Script(args) => execute_script(&global, args).await,
+    };
+    if let Some(marker) = marker {
+        marker.finish(result.is_ok());
     }
+    result
 }
 
 fn should_emit_stale_notice_for_command(command: &Command) -> bool {
@@ -295,6 +358,7 @@ fn apply_config_defaults(cli_global: &GlobalOpts, config: &config::ConfigFile) -
         page_id: cli_global.page_id.clone(),
         auto_dismiss_dialogs: cli_global.auto_dismiss_dialogs,
         config: cli_global.config.clone(),
+        session: cli_global.session.clone(),
         keepalive_interval: cli_global
             .keepalive_interval
             .or(config.keepalive.interval_ms),
@@ -463,6 +527,23 @@ struct KeepaliveStatus {
     enabled: bool,
 }
 
+#[derive(Serialize)]
+struct SessionListEntry {
+    name: String,
+    /// `true` for the session selected by `--session`/`AGENTCHROME_SESSION`.
+    current: bool,
+    /// `true` when the session has a session file (i.e. a recorded connection).
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ws_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+}
+
 #[derive(Serialize)]
 struct DisconnectInfo {
     disconnected: bool,
@@ -527,6 +608,7 @@ fn save_session(info: &ConnectionInfo) {
             timestamp: session::now_iso8601(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         },
     };
     if let Err(e) = session::write_session(&data) {
@@ -545,6 +627,10 @@ async fn execute_connect(global: &GlobalOpts, args: &ConnectArgs) -> Result<(),
         return execute_disconnect();
     }
 
+    if args.list_sessions {
+        return execute_list_sessions(global);
+    }
+
     let timeout = Duration::from_millis(global.timeout.unwrap_or(30_000));
 
     warn_if_remote_host(&global.host);
@@ -564,6 +650,9 @@ async fn execute_connect(global: &GlobalOpts, args: &ConnectArgs) -> Result<(),
 
     // Strategy 2: Explicit --launch
     if args.launch {
+        if args.pipe {
+            return execute_launch_pipe(global, args, timeout).await;
+        }
         return execute_launch(args, timeout).await;
     }
 
@@ -639,6 +728,7 @@ async fn execute_launch(args: &ConnectArgs, timeout: Duration) -> Result<(), App
             headless: args.headless,
             extra_args: args.chrome_arg.clone(),
             user_data_dir: None,
+            pipe: false,
         };
 
         match launch_chrome(config, timeout).await {
@@ -670,6 +760,64 @@ async fn execute_launch(args: &ConnectArgs, timeout: Duration) -> Result<(), App
         .into())
 }
 
+/// Launch Chrome with `--remote-debugging-pipe` and hand the pipe to a
+/// background daemon, which then serves every later command.
+#[cfg(unix)]
+async fn execute_launch_pipe(
+    global: &GlobalOpts,
+    args: &ConnectArgs,
+    timeout: Duration,
+) -> Result<(), AppError> {
+    daemon_cli::ensure_not_running()?;
+
+    let executable = match &args.chrome_path {
+        Some(path) => path.clone(),
+        None => find_chrome_executable(convert_channel(args.channel))?,
+    };
+    let config = LaunchConfig {
+        executable,
+        port: chrome::PIPE_PORT,
+        headless: args.headless,
+        extra_args: args.chrome_arg.clone(),
+        user_data_dir: None,
+        pipe: true,
+    };
+
+    // Until the daemon is up, dropping `process` kills Chrome.
+    let mut process = launch_chrome(config, timeout).await?;
+    let pipe = process.take_pipe().ok_or_else(|| {
+        AppError::from(chrome::ChromeError::LaunchFailed(
+            "Chrome was launched without a debugging pipe".into(),
+        ))
+    })?;
+    let record = daemon_cli::spawn_background(global, daemon_cli::Upstream::Pipe(&pipe)).await?;
+    // The daemon holds its own copies of the pipe ends.
+    drop(pipe);
+    let (pid, port) = process.detach();
+
+    let info = ConnectionInfo {
+        ws_url: record.url(),
+        port,
+        pid: Some(pid),
+    };
+    save_session(&info);
+    print_json(&info)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unused_async)]
+async fn execute_launch_pipe(
+    _global: &GlobalOpts,
+    _args: &ConnectArgs,
+    _timeout: Duration,
+) -> Result<(), AppError> {
+    Err(chrome::ChromeError::LaunchFailed(
+        "--remote-debugging-pipe is only supported on Unix".into(),
+    )
+    .into())
+}
+
 async fn execute_status(global: &GlobalOpts) -> Result<(), AppError> {
     // `--status` exits 0 whether or not a session exists so scripts can poll
     // it as a discovery probe without conflating "no session" with an error.
@@ -755,6 +903,49 @@ fn format_plain_status(status: &StatusInfo) -> String {
     out
 }
 
+fn execute_list_sessions(global: &GlobalOpts) -> Result<(), AppError> {
+    let current = session::session_name();
+    let entries: Vec<SessionListEntry> = session::list_sessions()?
+        .into_iter()
+        .map(|name| {
+            // A corrupt session file should not hide the other sessions.
+            let data = session::read_named_session(&name).ok().flatten();
+            SessionListEntry {
+                current: name == current,
+                active: data.is_some(),
+                ws_url: data.as_ref().map(|d| d.ws_url.clone()),
+                port: data.as_ref().map(|d| d.port),
+                pid: data.as_ref().and_then(|d| d.pid),
+                timestamp: data.map(|d| d.timestamp),
+                name,
+            }
+        })
+        .collect();
+
+    if global.output.plain {
+        print!("{}", format_plain_sessions(&entries));
+        return Ok(());
+    }
+    output::print_output(&entries, &global.output)
+}
+
+fn format_plain_sessions(entries: &[SessionListEntry]) -> String {
+    use std::fmt::Write;
+    if entries.is_empty() {
+        return "No sessions\n".to_string();
+    }
+    let mut out = String::new();
+    for entry in entries {
+        let marker = if entry.current { "*" } else { " " };
+        let connection = match (&entry.port, &entry.ws_url) {
+            (Some(port), Some(ws_url)) => format!("port {port}  {ws_url}"),
+            _ => "not connected".to_string(),
+        };
+        let _ = writeln!(out, "{marker} {:<20} {connection}", entry.name);
+    }
+    out
+}
+
 fn execute_disconnect() -> Result<(), AppError> {
     let session_data = session::read_session()?;
     let mut killed_pid = None;
@@ -887,6 +1078,12 @@ async fn execute_script(global: &GlobalOpts, args: &cli::ScriptArgs) -> Result<(
     .await?;
     let session = opts_connection.client.create_session(&target.id).await?;
     let mut managed = agentchrome::connection::ManagedSession::new(session);
+    // A running daemon already enforces routes in its own session.
+    let _routes = if agentchrome::daemon::is_daemon_client(&opts_connection.client) {
+        None
+    } else {
+        managed.spawn_route_handler().await?
+    };
 
     let run_opts = RunOptions {
         fail_fast: run_args.fail_fast,
@@ -949,6 +1146,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn marker_args_redact_fill_and_type_values() {
+        let a = argv(&["agentchrome", "form", "fill", "s5", "hunter2"]);
+        let cli = Cli::try_parse_from(&a).unwrap();
+        assert_eq!(
+            redact_marker_args(&cli.command, &a),
+            ["form", "fill", "s5", "<redacted>"]
+        );
+
+        let a = argv(&["agentchrome", "interact", "type", "secret", "--delay", "5"]);
+        let cli = Cli::try_parse_from(&a).unwrap();
+        assert_eq!(
+            redact_marker_args(&cli.command, &a),
+            ["interact", "type", "<redacted>", "--delay", "5"]
+        );
+
+        let a = argv(&["agentchrome", "interact", "click", "s6"]);
+        let cli = Cli::try_parse_from(&a).unwrap();
+        assert_eq!(
+            redact_marker_args(&cli.command, &a),
+            ["interact", "click", "s6"]
+        );
+    }
+
     #[test]
     fn resolve_subcommand_path_returns_none_when_no_subcommand() {
         let a = argv(&["agentchrome", "--help"]);
@@ -671,7 +671,7 @@ fn unwrap_layout_tables(root: &NodeRef) -> usize {
     removed
 }
 
-fn is_layout_table(table: &NodeRef) -> bool {
+pub(crate) fn is_layout_table(table: &NodeRef) -> bool {
     if count_matches(table, "th") > 0 {
         return false;
     }
@@ -774,7 +774,7 @@ fn extract_title(html: &str) -> Option<String> {
         .filter(|title| !title.is_empty())
 }
 
-fn normalized_text(value: &str) -> String {
+pub(crate) fn normalized_text(value: &str) -> String {
     value.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
@@ -5,14 +5,16 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use agentchrome::cdp::CdpClient;
+use agentchrome::cdp::{CdpClient, CdpEvent};
 use agentchrome::connection::ManagedSession;
 use agentchrome::error::{AppError, ExitCode};
+use agentchrome::har;
 
 use crate::cli::{
-    GlobalOpts, NetworkArgs, NetworkCommand, NetworkFollowArgs, NetworkGetArgs, NetworkListArgs,
+    GlobalOpts, NetworkArgs, NetworkCommand, NetworkFollowArgs, NetworkGetArgs, NetworkHarArgs,
+    NetworkListArgs, NetworkReplayArgs, ReplayNotFound,
 };
-use crate::output::connect_from_global;
+use crate::output::{connect_from_global, print_output};
 
 // =============================================================================
 // Output types
@@ -105,6 +107,31 @@ struct NetworkStreamEvent {
     response_headers: Option<serde_json::Value>,
 }
 
+/// Result of `network har`.
+#[derive(Debug, Serialize)]
+struct HarFileResult {
+    file: String,
+    entries: usize,
+    pages: usize,
+}
+
+/// A request `network replay` had no archived response for.
+#[derive(Debug, Serialize)]
+struct UnmatchedRequest {
+    method: String,
+    url: String,
+}
+
+/// Result of `network replay`.
+#[derive(Debug, Serialize)]
+struct ReplaySummary {
+    har: String,
+    entries: usize,
+    served: usize,
+    not_found: &'static str,
+    unmatched: Vec<UnmatchedRequest>,
+}
+
 /// Raw collected event data before correlation.
 struct RawNetworkEvent {
     params: serde_json::Value,
@@ -138,6 +165,9 @@ struct NetworkRequestBuilder {
     status_text: String,
     response_headers: serde_json::Value,
     mime_type: Option<String>,
+    /// Negotiated protocol reported with the response (e.g. `http/1.1`, `h2`).
+    #[serde(default)]
+    protocol: Option<String>,
     encoded_data_length: Option<u64>,
     timing: Option<serde_json::Value>,
     redirect_chain: Vec<RedirectEntry>,
@@ -311,6 +341,16 @@ fn filter_by_type(
         .collect()
 }
 
+/// Whether `url` matches a `--url` filter (substring).
+fn url_matches(url: &str, pattern: &str) -> bool {
+    url.contains(pattern)
+}
+
+/// Whether `method` matches a `--method` filter (case-insensitive).
+fn method_matches(method: &str, filter: &str) -> bool {
+    method.eq_ignore_ascii_case(filter)
+}
+
 /// Filter requests by URL substring.
 fn filter_by_url(
     requests: Vec<NetworkRequestSummary>,
@@ -318,7 +358,7 @@ fn filter_by_url(
 ) -> Vec<NetworkRequestSummary> {
     requests
         .into_iter()
-        .filter(|r| r.url.contains(pattern))
+        .filter(|r| url_matches(&r.url, pattern))
         .collect()
 }
 
@@ -338,10 +378,9 @@ fn filter_by_method(
     requests: Vec<NetworkRequestSummary>,
     method: &str,
 ) -> Vec<NetworkRequestSummary> {
-    let upper = method.to_uppercase();
     requests
         .into_iter()
-        .filter(|r| r.method.to_uppercase() == upper)
+        .filter(|r| method_matches(&r.method, method))
         .collect()
 }
 
@@ -412,6 +451,34 @@ fn extract_timing(timing: &serde_json::Value) -> TimingInfo {
     }
 }
 
+/// Full timing breakdown for a request, including the download phase derived
+/// from the `Network.loadingFinished` timestamp.
+fn timing_for_builder(builder: &NetworkRequestBuilder) -> TimingInfo {
+    let Some(t) = builder.timing.as_ref() else {
+        return TimingInfo {
+            dns_ms: 0.0,
+            connect_ms: 0.0,
+            tls_ms: 0.0,
+            ttfb_ms: 0.0,
+            download_ms: 0.0,
+        };
+    };
+    let mut ti = extract_timing(t);
+    // Calculate download time from timing + loading finished
+    if let Some(end_ts) = builder.loading_finished_timestamp {
+        let request_time = t["requestTime"].as_f64().unwrap_or(0.0);
+        let receive_headers_end = t["receiveHeadersEnd"].as_f64().unwrap_or(0.0);
+        if request_time > 0.0 && receive_headers_end > 0.0 {
+            let headers_done = request_time + receive_headers_end / 1000.0;
+            ti.download_ms = (end_ts - headers_done) * 1000.0;
+            if ti.download_ms < 0.0 {
+                ti.download_ms = 0.0;
+            }
+        }
+    }
+    ti
+}
+
 /// Check if a MIME type represents a binary resource.
 fn is_binary_mime(mime: &str) -> bool {
     let lower = mime.to_lowercase();
@@ -452,6 +519,41 @@ fn save_binary_body_to_file(path: &Path, base64_content: &str) -> Result<(), App
     })
 }
 
+/// Fetch the posted body of a POST/PUT/PATCH request, if Chrome still has it.
+async fn fetch_request_body(
+    managed: &ManagedSession,
+    builder: &NetworkRequestBuilder,
+) -> Option<String> {
+    if !matches!(builder.method.as_str(), "POST" | "PUT" | "PATCH") {
+        return None;
+    }
+    managed
+        .send_command(
+            "Network.getRequestPostData",
+            Some(serde_json::json!({ "requestId": &builder.cdp_request_id })),
+        )
+        .await
+        .ok()
+        .and_then(|result| result["postData"].as_str().map(String::from))
+}
+
+/// Fetch a response body as `(body, base64_encoded)`, if Chrome still has it.
+async fn fetch_response_body(
+    managed: &ManagedSession,
+    builder: &NetworkRequestBuilder,
+) -> Option<(String, bool)> {
+    let result = managed
+        .send_command(
+            "Network.getResponseBody",
+            Some(serde_json::json!({ "requestId": &builder.cdp_request_id })),
+        )
+        .await
+        .ok()?;
+    let base64_encoded = result["base64Encoded"].as_bool().unwrap_or(false);
+    let body = result["body"].as_str().unwrap_or("").to_string();
+    Some((body, base64_encoded))
+}
+
 // =============================================================================
 // Event collection and correlation
 // =============================================================================
@@ -507,6 +609,7 @@ async fn setup_network_session(
     let session = conn.client.create_session(&target_id).await?;
     let mut managed = ManagedSession::new(session);
     crate::emulate::apply_emulate_state(&mut managed).await?;
+    crate::inject::apply_inject_scripts(&managed, &target_id).await?;
 
     Ok((
         conn.client,
@@ -648,6 +751,75 @@ fn lookup_snapshot_request(
         })
 }
 
+/// Collect network requests for the current tab.
+///
+/// Through the daemon this reads its passive event history; otherwise it
+/// falls back to reloading the page via [`collect_and_correlate`].
+async fn collect_requests(
+    client: &CdpClient,
+    managed: &mut ManagedSession,
+    include_preserved: bool,
+    timeout_ms: Option<u64>,
+) -> Result<(Vec<NetworkRequestBuilder>, u32), AppError> {
+    if agentchrome::daemon::is_daemon_client(client) {
+        let events = agentchrome::daemon::buffered_events(
+            managed,
+            agentchrome::daemon::EventCategory::Network,
+        )
+        .await?;
+        let (raw_events, current_nav_id) = raw_events_from_buffer(events);
+        let builders = correlate_raw_events(&raw_events, include_preserved, current_nav_id, None);
+        return Ok((builders, current_nav_id));
+    }
+    collect_and_correlate(managed, include_preserved, timeout_ms).await
+}
+
+/// Summaries of the current tab's requests, as `network list` prints them
+/// before filtering. Without the daemon this reloads the page.
+///
+/// # Errors
+///
+/// Returns `AppError` if the network events cannot be collected.
+pub(crate) async fn summarize_requests(
+    client: &CdpClient,
+    managed: &mut ManagedSession,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<NetworkRequestSummary>, AppError> {
+    let (builders, _nav_id) = collect_requests(client, managed, false, timeout_ms).await?;
+    let mut requests: Vec<NetworkRequestSummary> =
+        builders.iter().map(builder_to_summary).collect();
+    requests.sort_by_key(|r| r.id);
+    Ok(requests)
+}
+
+/// Convert buffered daemon events into correlatable network events, counting
+/// main-frame navigations so `--include-preserved` keeps its meaning.
+fn raw_events_from_buffer(events: Vec<CdpEvent>) -> (Vec<RawNetworkEvent>, u32) {
+    let mut raw_events = Vec::new();
+    let mut current_nav_id: u32 = 0;
+    for event in events {
+        let event_type = match event.method.as_str() {
+            "Network.requestWillBeSent" => NetworkEventType::RequestWillBeSent,
+            "Network.responseReceived" => NetworkEventType::ResponseReceived,
+            "Network.loadingFinished" => NetworkEventType::LoadingFinished,
+            "Network.loadingFailed" => NetworkEventType::LoadingFailed,
+            "Page.frameNavigated" => {
+                if event.params["frame"]["parentId"].is_null() {
+                    current_nav_id += 1;
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        raw_events.push(RawNetworkEvent {
+            params: event.params,
+            event_type,
+            navigation_id: current_nav_id,
+        });
+    }
+    (raw_events, current_nav_id)
+}
+
 /// Collect network events by reloading the page and capturing the resulting traffic.
 ///
 /// After enabling the Network and Page domains and subscribing to events, this
@@ -916,6 +1088,7 @@ fn builder_from_request_event(
         status_text: String::new(),
         response_headers: serde_json::Value::Null,
         mime_type: None,
+        protocol: None,
         encoded_data_length: None,
         timing: None,
         redirect_chain: Vec::new(),
@@ -964,6 +1137,7 @@ fn apply_response_event(builder: &mut NetworkRequestBuilder, params: &serde_json
         .to_string();
     builder.response_headers = params["response"]["headers"].clone();
     builder.mime_type = params["response"]["mimeType"].as_str().map(String::from);
+    builder.protocol = params["response"]["protocol"].as_str().map(String::from);
     builder.timing = Some(params["response"]["timing"].clone());
 }
 
@@ -1105,6 +1279,11 @@ pub async fn execute_network(global: &GlobalOpts, args: &NetworkArgs) -> Result<
         NetworkCommand::List(list_args) => execute_list(global, list_args).await,
         NetworkCommand::Get(get_args) => execute_get(global, get_args).await,
         NetworkCommand::Follow(follow_args) => execute_follow(global, follow_args).await,
+        NetworkCommand::Har(har_args) => execute_har(global, har_args).await,
+        NetworkCommand::Replay(replay_args) => execute_replay(global, replay_args).await,
+        NetworkCommand::Route(route_args) => {
+            crate::route_cli::execute_route(global, route_args).await
+        }
     }
 }
 
@@ -1139,8 +1318,13 @@ async fn execute_list(global: &GlobalOpts, args: &NetworkListArgs) -> Result<(),
         None
     };
 
-    let (builders, _nav_id) =
-        collect_and_correlate(&mut managed, args.include_preserved, global.timeout).await?;
+    let (builders, _nav_id) = collect_requests(
+        &client,
+        &mut managed,
+        args.include_preserved,
+        global.timeout,
+    )
+    .await?;
     if let Err(e) = write_network_snapshot(&context, &builders) {
         eprintln!(
             "warning: could not persist network list snapshot: {}",
@@ -1227,7 +1411,7 @@ async fn execute_list(global: &GlobalOpts, args: &NetworkListArgs) -> Result<(),
 
 #[allow(clippy::too_many_lines)]
 async fn execute_get(global: &GlobalOpts, args: &NetworkGetArgs) -> Result<(), AppError> {
-    let (_client, mut managed, context) = setup_network_session(global).await?;
+    let (client, mut managed, context) = setup_network_session(global).await?;
 
     if global.auto_dismiss_dialogs {
         let _dismiss = managed.spawn_auto_dismiss().await?;
@@ -1244,7 +1428,7 @@ async fn execute_get(global: &GlobalOpts, args: &NetworkGetArgs) -> Result<(), A
         }
         SnapshotLookup::MissingOrStale => {
             let (builders, _nav_id) =
-                collect_and_correlate(&mut managed, true, global.timeout).await?;
+                collect_requests(&client, &mut managed, true, global.timeout).await?;
             let builder = builders
                 .iter()
                 .find(|b| b.assigned_id == target_id)
@@ -1277,56 +1461,36 @@ async fn execute_get(global: &GlobalOpts, args: &NetworkGetArgs) -> Result<(), A
     }
 
     // Fetch request body for POST/PUT
-    let request_body =
-        if builder.method == "POST" || builder.method == "PUT" || builder.method == "PATCH" {
-            match managed
-                .send_command(
-                    "Network.getRequestPostData",
-                    Some(serde_json::json!({ "requestId": &builder.cdp_request_id })),
-                )
-                .await
-            {
-                Ok(result) => result["postData"].as_str().map(String::from),
-                Err(_) => None,
-            }
-        } else {
-            None
-        };
+    let request_body = fetch_request_body(&managed, builder).await;
 
     // Fetch response body
-    let (response_body, is_binary, is_truncated) = match managed
-        .send_command(
-            "Network.getResponseBody",
-            Some(serde_json::json!({ "requestId": &builder.cdp_request_id })),
-        )
-        .await
-    {
-        Ok(result) => {
-            let base64_encoded = result["base64Encoded"].as_bool().unwrap_or(false);
-            let body_str = result["body"].as_str().unwrap_or("");
-
-            if base64_encoded {
-                // Binary content — save to file if requested, don't inline
-                if let Some(ref save_path) = args.save_response {
-                    save_binary_body_to_file(save_path, body_str)?;
-                }
-                (None, true, false)
-            } else if body_str.len() > MAX_INLINE_BODY_SIZE {
-                // Save full body to file if requested
-                if let Some(ref save_path) = args.save_response {
-                    save_body_to_file(save_path, body_str)?;
-                }
-                let truncated = body_str[..MAX_INLINE_BODY_SIZE].to_string();
-                (Some(truncated), false, true)
-            } else {
-                if let Some(ref save_path) = args.save_response {
-                    save_body_to_file(save_path, body_str)?;
+    let (response_body, is_binary, is_truncated) =
+        match fetch_response_body(&managed, builder).await {
+            Some((body, base64_encoded)) => {
+                let body_str = body.as_str();
+
+                if base64_encoded {
+                    // Binary content — save to file if requested, don't inline
+                    if let Some(ref save_path) = args.save_response {
+                        save_binary_body_to_file(save_path, body_str)?;
+                    }
+                    (None, true, false)
+                } else if body_str.len() > MAX_INLINE_BODY_SIZE {
+                    // Save full body to file if requested
+                    if let Some(ref save_path) = args.save_response {
+                        save_body_to_file(save_path, body_str)?;
+                    }
+                    let truncated = body_str[..MAX_INLINE_BODY_SIZE].to_string();
+                    (Some(truncated), false, true)
+                } else {
+                    if let Some(ref save_path) = args.save_response {
+                        save_body_to_file(save_path, body_str)?;
+                    }
+                    (Some(body_str.to_string()), false, false)
                 }
-                (Some(body_str.to_string()), false, false)
             }
-        }
-        Err(_) => (None, false, false),
-    };
+            None => (None, false, false),
+        };
 
     // Save request body if requested
     if let Some(ref save_path) = args.save_request
@@ -1335,32 +1499,7 @@ async fn execute_get(global: &GlobalOpts, args: &NetworkGetArgs) -> Result<(), A
         save_body_to_file(save_path, body)?;
     }
 
-    // Build timing info
-    let timing = builder.timing.as_ref().map_or_else(
-        || TimingInfo {
-            dns_ms: 0.0,
-            connect_ms: 0.0,
-            tls_ms: 0.0,
-            ttfb_ms: 0.0,
-            download_ms: 0.0,
-        },
-        |t| {
-            let mut ti = extract_timing(t);
-            // Calculate download time from timing + loading finished
-            if let Some(end_ts) = builder.loading_finished_timestamp {
-                let request_time = t["requestTime"].as_f64().unwrap_or(0.0);
-                let receive_headers_end = t["receiveHeadersEnd"].as_f64().unwrap_or(0.0);
-                if request_time > 0.0 && receive_headers_end > 0.0 {
-                    let headers_done = request_time + receive_headers_end / 1000.0;
-                    ti.download_ms = (end_ts - headers_done) * 1000.0;
-                    if ti.download_ms < 0.0 {
-                        ti.download_ms = 0.0;
-                    }
-                }
-            }
-            ti
-        },
-    );
+    let timing = timing_for_builder(builder);
 
     let duration_ms = builder
         .loading_finished_timestamp
@@ -1410,18 +1549,630 @@ async fn execute_get(global: &GlobalOpts, args: &NetworkGetArgs) -> Result<(), A
     })
 }
 
+// =============================================================================
+// HAR export
+// =============================================================================
+
+/// Bodies fetched for one request before it is converted to HAR entries.
+#[derive(Default)]
+struct HarBodies {
+    request: Option<String>,
+    /// Response body and whether it is base64-encoded.
+    response: Option<(String, bool)>,
+}
+
+/// HAR spells protocol versions like the status line (`HTTP/1.1`); newer
+/// protocols keep the ALPN name Chrome reports (`h2`, `h3`).
+fn har_http_version(protocol: Option<&str>) -> String {
+    match protocol {
+        Some(p) if p.starts_with("http/") => p.to_uppercase(),
+        Some(p) => p.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Convert the CDP `ResourceTiming` of a request into HAR phases.
+fn har_timings(builder: &NetworkRequestBuilder) -> har::Timings {
+    let Some(t) = builder.timing.as_ref().filter(|t| t.is_object()) else {
+        // No phase breakdown (cached or failed request): attribute the whole
+        // duration to waiting so the entry time is still right.
+        let mut timings = har::Timings::unknown();
+        if let Some(end_ts) = builder.loading_finished_timestamp {
+            timings.wait = ((end_ts - builder.timestamp) * 1000.0).max(0.0);
+        }
+        return timings;
+    };
+    let phase = |name: &str| t[name].as_f64().filter(|v| *v >= 0.0);
+    let breakdown = timing_for_builder(builder);
+    let send = match (phase("sendStart"), phase("sendEnd")) {
+        (Some(start), Some(end)) => end - start,
+        _ => 0.0,
+    };
+    har::Timings {
+        blocked: ["dnsStart", "connectStart", "sendStart"]
+            .into_iter()
+            .find_map(phase)
+            .unwrap_or(-1.0),
+        dns: phase("dnsStart").map_or(-1.0, |_| breakdown.dns_ms),
+        connect: phase("connectStart").map_or(-1.0, |_| breakdown.connect_ms),
+        send,
+        wait: breakdown.ttfb_ms,
+        receive: breakdown.download_ms,
+        ssl: phase("sslStart").map_or(-1.0, |_| breakdown.tls_ms),
+    }
+}
+
+fn har_request(
+    method: &str,
+    url: &str,
+    http_version: &str,
+    headers: Vec<har::NameValue>,
+    post_data: Option<String>,
+) -> har::Request {
+    let body_size = post_data
+        .as_ref()
+        .map_or(0, |body| i64::try_from(body.len()).unwrap_or(i64::MAX));
+    let post_data = post_data.map(|text| har::PostData {
+        mime_type: har::header_value(&headers, "content-type")
+            .unwrap_or("")
+            .to_string(),
+        text,
+    });
+    har::Request {
+        method: method.to_string(),
+        url: url.to_string(),
+        http_version: http_version.to_string(),
+        cookies: Vec::new(),
+        query_string: har::query_string(url),
+        headers,
+        post_data,
+        headers_size: -1,
+        body_size,
+    }
+}
+
+/// Response content, with the body when one was fetched.
+fn har_content(
+    builder: &NetworkRequestBuilder,
+    body: Option<(String, bool)>,
+    encoded_size: i64,
+) -> har::Content {
+    use base64::Engine;
+
+    let mime_type = builder.mime_type.clone().unwrap_or_default();
+    match body {
+        Some((text, true)) => har::Content {
+            size: base64::engine::general_purpose::STANDARD
+                .decode(&text)
+                .map_or(-1, |bytes| i64::try_from(bytes.len()).unwrap_or(i64::MAX)),
+            mime_type,
+            text: Some(text),
+            encoding: Some("base64".to_string()),
+        },
+        Some((text, false)) => har::Content {
+            size: i64::try_from(text.len()).unwrap_or(i64::MAX),
+            mime_type,
+            text: Some(text),
+            encoding: None,
+        },
+        None => har::Content {
+            size: encoded_size.max(0),
+            mime_type,
+            text: None,
+            encoding: None,
+        },
+    }
+}
+
+/// Convert one correlated request into HAR entries.
+///
+/// Chrome reuses the request ID across redirects, so a builder with a
+/// redirect chain expands into one entry per hop followed by the final
+/// request. Only the URL and status of intermediate hops are known.
+fn builder_to_har_entries(
+    builder: &NetworkRequestBuilder,
+    pageref: Option<&str>,
+    bodies: HarBodies,
+) -> Vec<har::Entry> {
+    let started_date_time = timestamp_to_iso(builder.wall_time);
+    let mut entries = Vec::with_capacity(builder.redirect_chain.len() + 1);
+
+    for (index, hop) in builder.redirect_chain.iter().enumerate() {
+        let next_url = builder
+            .redirect_chain
+            .get(index + 1)
+            .map_or(builder.url.as_str(), |next| next.url.as_str());
+        entries.push(har::Entry {
+            pageref: pageref.map(String::from),
+            started_date_time: started_date_time.clone(),
+            time: 0.0,
+            request: har_request(&builder.method, &hop.url, "", Vec::new(), None),
+            response: har::Response {
+                status: hop.status,
+                status_text: String::new(),
+                http_version: String::new(),
+                cookies: Vec::new(),
+                headers: vec![har::NameValue {
+                    name: "location".to_string(),
+                    value: next_url.to_string(),
+                }],
+                content: har::Content {
+                    size: 0,
+                    mime_type: String::new(),
+                    text: None,
+                    encoding: None,
+                },
+                redirect_url: next_url.to_string(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: serde_json::json!({}),
+            timings: har::Timings::unknown(),
+            resource_type: Some(builder.resource_type.clone()),
+            error: None,
+        });
+    }
+
+    let http_version = har_http_version(builder.protocol.as_deref());
+    let response_headers = har::headers_from_cdp(&builder.response_headers);
+    let redirect_url = har::header_value(&response_headers, "location")
+        .unwrap_or("")
+        .to_string();
+    let encoded_size = resolve_size(builder.encoded_data_length, &builder.response_headers)
+        .map_or(-1, |size| i64::try_from(size).unwrap_or(i64::MAX));
+    let content = har_content(builder, bodies.response, encoded_size);
+    let timings = har_timings(builder);
+
+    entries.push(har::Entry {
+        pageref: pageref.map(String::from),
+        started_date_time,
+        time: timings.total(),
+        request: har_request(
+            &builder.method,
+            &builder.url,
+            &http_version,
+            har::headers_from_cdp(&builder.request_headers),
+            bodies.request,
+        ),
+        response: har::Response {
+            status: builder.status.unwrap_or(0),
+            status_text: builder.status_text.clone(),
+            http_version,
+            cookies: Vec::new(),
+            headers: response_headers,
+            content,
+            redirect_url,
+            headers_size: -1,
+            body_size: encoded_size,
+        },
+        cache: serde_json::json!({}),
+        timings,
+        resource_type: Some(builder.resource_type.clone()),
+        error: builder.failed.then(|| {
+            builder
+                .error_text
+                .clone()
+                .unwrap_or_else(|| "failed".into())
+        }),
+    });
+    entries
+}
+
+/// Assemble a HAR log from requests in capture order.
+///
+/// Each main-frame document request starts a new page; requests seen before
+/// the first one are left without a `pageref`.
+fn build_har(requests: Vec<(&NetworkRequestBuilder, HarBodies)>) -> har::Har {
+    let mut har = har::Har::new();
+    let main_frame = requests
+        .iter()
+        .find(|(builder, _)| builder.resource_type == "document")
+        .and_then(|(builder, _)| builder.frame_id.clone());
+    let mut pageref: Option<String> = None;
+
+    for (builder, bodies) in requests {
+        if builder.resource_type == "document" && builder.frame_id == main_frame {
+            let id = format!("page_{}", har.log.pages.len() + 1);
+            har.log.pages.push(har::Page {
+                started_date_time: timestamp_to_iso(builder.wall_time),
+                id: id.clone(),
+                title: builder.url.clone(),
+                page_timings: har::PageTimings::default(),
+            });
+            pageref = Some(id);
+        }
+        har.log
+            .entries
+            .extend(builder_to_har_entries(builder, pageref.as_deref(), bodies));
+    }
+    har
+}
+
+async fn execute_har(global: &GlobalOpts, args: &NetworkHarArgs) -> Result<(), AppError> {
+    let (client, mut managed, _context) = setup_network_session(global).await?;
+
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+
+    let (mut builders, _nav_id) = collect_requests(
+        &client,
+        &mut managed,
+        args.include_preserved,
+        global.timeout,
+    )
+    .await?;
+    builders.sort_by_key(|b| b.assigned_id);
+    if let Some(ref types) = resolve_type_filter(args.r#type.as_deref()) {
+        builders.retain(|b| types.contains(&b.resource_type));
+    }
+    if let Some(ref url_pattern) = args.url {
+        builders.retain(|b| b.url.contains(url_pattern.as_str()));
+    }
+
+    managed
+        .ensure_domain("Network")
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to enable Network domain: {e}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        })?;
+
+    let mut requests = Vec::with_capacity(builders.len());
+    for builder in &builders {
+        let response = if args.include_bodies && builder.completed && !builder.failed {
+            fetch_response_body(&managed, builder).await
+        } else {
+            None
+        };
+        let bodies = HarBodies {
+            request: fetch_request_body(&managed, builder).await,
+            response,
+        };
+        requests.push((builder, bodies));
+    }
+
+    let har = build_har(requests);
+    har::write_har(&args.file, &har)?;
+
+    let result = HarFileResult {
+        file: args.file.display().to_string(),
+        entries: har.log.entries.len(),
+        pages: har.log.pages.len(),
+    };
+    if global.output.plain {
+        println!("Wrote {} entries to {}", result.entries, result.file);
+        return Ok(());
+    }
+    print_output(&result, &global.output)
+}
+
+// =============================================================================
+// Replay
+// =============================================================================
+
+/// Response headers that describe the recorded transfer rather than the
+/// replayed body, which is served decoded and re-sized by Chrome.
+const REPLAY_DROPPED_HEADERS: &[&str] =
+    &["content-encoding", "content-length", "transfer-encoding"];
+
+fn body_hash(body: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A URL without its query string and fragment.
+fn url_without_query(url: &str) -> &str {
+    url.split(['?', '#']).next().unwrap_or(url)
+}
+
+/// HAR entries indexed for the replay loop.
+struct ReplayIndex {
+    entries: Vec<har::Entry>,
+    /// Candidate entries per upper-cased method and URL, filled on first use.
+    candidates: HashMap<(String, String), Vec<usize>>,
+    body_hashes: Vec<Option<u64>>,
+    match_body: bool,
+    /// Times each entry has been served.
+    served: Vec<usize>,
+}
+
+impl ReplayIndex {
+    fn new(entries: Vec<har::Entry>, match_body: bool) -> Self {
+        let body_hashes = entries
+            .iter()
+            .map(|entry| entry.request.post_data.as_ref().map(|p| body_hash(&p.text)))
+            .collect();
+        let served = vec![0; entries.len()];
+        Self {
+            entries,
+            candidates: HashMap::new(),
+            body_hashes,
+            match_body,
+            served,
+        }
+    }
+
+    /// Entries recorded for the same method and resource as a request: the
+    /// `network list` method and URL predicates, narrowed to entries whose
+    /// URL differs from the request's at most in the query string or
+    /// fragment. Exact URL matches come first, the rest in archive order.
+    fn candidates_for(entries: &[har::Entry], method: &str, url: &str) -> Vec<usize> {
+        let base = url_without_query(url);
+        let mut matching: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                let request = &entry.request;
+                method_matches(&request.method, method)
+                    && url_matches(&request.url, base)
+                    && url_without_query(&request.url) == base
+            })
+            .map(|(id, _)| id)
+            .collect();
+        matching.sort_by_key(|&id| entries[id].request.url != url);
+        matching
+    }
+
+    /// Pick the entry that answers a request, from the entries with the same
+    /// method and resource (see [`Self::candidates_for`]); with
+    /// `--match-body` the body hash must match too. A request seen
+    /// repeatedly walks through its matching entries in that order, then
+    /// keeps getting the last one.
+    fn find(&mut self, method: &str, url: &str, body: Option<&str>) -> Option<&har::Entry> {
+        let hash = body.map(body_hash);
+        let entries = &self.entries;
+        let candidates: Vec<usize> = self
+            .candidates
+            .entry((method.to_uppercase(), url.to_string()))
+            .or_insert_with(|| Self::candidates_for(entries, method, url))
+            .iter()
+            .copied()
+            .filter(|&id| !self.match_body || self.body_hashes[id] == hash)
+            .collect();
+        let id = candidates
+            .iter()
+            .copied()
+            .find(|&id| self.served[id] == 0)
+            .or_else(|| candidates.last().copied())?;
+        self.served[id] += 1;
+        Some(&self.entries[id])
+    }
+}
+
+/// Posted body of a paused request as sent inline: `postData`, or for large
+/// and multipart bodies the concatenated `postDataEntries`. `None` when the
+/// request has no body or Chrome left it out entirely.
+fn inline_post_data(request: &serde_json::Value) -> Option<String> {
+    use base64::Engine;
+
+    if let Some(body) = request["postData"].as_str() {
+        return Some(body.to_string());
+    }
+    let entries = request["postDataEntries"].as_array()?;
+    let mut bytes = Vec::new();
+    for entry in entries {
+        let chunk = entry["bytes"].as_str().unwrap_or_default();
+        bytes.extend(
+            base64::engine::general_purpose::STANDARD
+                .decode(chunk)
+                .ok()?,
+        );
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Posted body of a paused request for `--match-body`. When Chrome flags a
+/// body (`hasPostData`) without sending it, fetch it by the request's
+/// network ID.
+async fn paused_request_body(
+    managed: &mut ManagedSession,
+    params: &serde_json::Value,
+) -> Option<String> {
+    let request = &params["request"];
+    if let Some(body) = inline_post_data(request) {
+        return Some(body);
+    }
+    if request["hasPostData"] != true {
+        return None;
+    }
+    let network_id = params["networkId"].as_str()?;
+    managed.ensure_domain("Network").await.ok()?;
+    managed
+        .send_command(
+            "Network.getRequestPostData",
+            Some(serde_json::json!({ "requestId": network_id })),
+        )
+        .await
+        .ok()
+        .and_then(|result| result["postData"].as_str().map(String::from))
+}
+
+/// CDP command that answers a paused request from an archived entry.
+fn replay_command(
+    request_id: &serde_json::Value,
+    entry: &har::Entry,
+) -> (&'static str, serde_json::Value) {
+    use base64::Engine;
+
+    // Status 0 means the request failed when it was recorded.
+    if entry.response.status == 0 {
+        return (
+            "Fetch.failRequest",
+            serde_json::json!({ "requestId": request_id, "errorReason": "Failed" }),
+        );
+    }
+    let headers: Vec<&har::NameValue> = entry
+        .response
+        .headers
+        .iter()
+        .filter(|h| {
+            !REPLAY_DROPPED_HEADERS
+                .iter()
+                .any(|dropped| h.name.eq_ignore_ascii_case(dropped))
+        })
+        .collect();
+    let content = &entry.response.content;
+    let body = match (&content.text, content.encoding.as_deref()) {
+        (Some(text), Some("base64")) => text.clone(),
+        (Some(text), _) => base64::engine::general_purpose::STANDARD.encode(text),
+        (None, _) => String::new(),
+    };
+    let mut params = serde_json::json!({
+        "requestId": request_id,
+        "responseCode": entry.response.status,
+        "responseHeaders": headers,
+        "body": body,
+    });
+    if !entry.response.status_text.is_empty() {
+        params["responsePhrase"] = serde_json::json!(entry.response.status_text);
+    }
+    ("Fetch.fulfillRequest", params)
+}
+
+/// CDP command for a paused request the archive has no entry for.
+fn unmatched_command(
+    request_id: &serde_json::Value,
+    not_found: ReplayNotFound,
+) -> (&'static str, serde_json::Value) {
+    match not_found {
+        ReplayNotFound::Abort => (
+            "Fetch.failRequest",
+            serde_json::json!({ "requestId": request_id, "errorReason": "InternetDisconnected" }),
+        ),
+        ReplayNotFound::Passthrough => (
+            "Fetch.continueRequest",
+            serde_json::json!({ "requestId": request_id }),
+        ),
+    }
+}
+
+async fn execute_replay(global: &GlobalOpts, args: &NetworkReplayArgs) -> Result<(), AppError> {
+    let archive = har::read_har(&args.har)?;
+    let entries = archive.log.entries.len();
+    let mut index = ReplayIndex::new(archive.log.entries, args.match_body);
+
+    let (_client, mut managed, _context) = setup_network_session(global).await?;
+
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+
+    let mut paused_rx = managed
+        .subscribe("Fetch.requestPaused")
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to subscribe to Fetch.requestPaused: {e}"),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        })?;
+    managed
+        .send_command(
+            "Fetch.enable",
+            Some(agentchrome::route::interception_params()),
+        )
+        .await?;
+
+    let deadline = args
+        .timeout
+        .map(|ms| tokio::time::Instant::now() + Duration::from_millis(ms));
+    let mut served = 0;
+    let mut unmatched = Vec::new();
+
+    loop {
+        tokio::select! {
+            event = paused_rx.recv() => {
+                let Some(ev) = event else {
+                    return Err(AppError {
+                        message: "CDP connection closed".to_string(),
+                        code: ExitCode::ConnectionError,
+                        custom_json: None,
+                    });
+                };
+                let request = &ev.params["request"];
+                let method = request["method"].as_str().unwrap_or("GET");
+                let url = request["url"].as_str().unwrap_or_default();
+                let request_id = &ev.params["requestId"];
+                let body = if args.match_body {
+                    paused_request_body(&mut managed, &ev.params).await
+                } else {
+                    None
+                };
+                let (command, params) =
+                    if let Some(entry) = index.find(method, url, body.as_deref()) {
+                        served += 1;
+                        replay_command(request_id, entry)
+                    } else {
+                        unmatched.push(UnmatchedRequest {
+                            method: method.to_string(),
+                            url: url.to_string(),
+                        });
+                        unmatched_command(request_id, args.not_found)
+                    };
+                // The page may cancel a paused request; that only affects
+                // this request, so keep replaying.
+                let _ = managed.send_command(command, Some(params)).await;
+            }
+            () = async {
+                if let Some(d) = deadline {
+                    tokio::time::sleep_until(d).await;
+                } else {
+                    std::future::pending::<()>().await;
+                }
+            } => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    let _ = managed.send_command("Fetch.disable", None).await;
+
+    let summary = ReplaySummary {
+        har: args.har.display().to_string(),
+        entries,
+        served,
+        not_found: match args.not_found {
+            ReplayNotFound::Abort => "abort",
+            ReplayNotFound::Passthrough => "passthrough",
+        },
+        unmatched,
+    };
+    if global.output.plain {
+        println!(
+            "Served {} request(s) from {}; {} unmatched",
+            summary.served,
+            summary.har,
+            summary.unmatched.len()
+        );
+        for request in &summary.unmatched {
+            println!("  {} {}", request.method, request.url);
+        }
+        return Ok(());
+    }
+    print_output(&summary, &global.output)
+}
+
 // =============================================================================
 // Follow: streaming mode
 // =============================================================================
 
 #[allow(clippy::too_many_lines)]
 async fn execute_follow(global: &GlobalOpts, args: &NetworkFollowArgs) -> Result<(), AppError> {
-    let (_client, mut managed, _context) = setup_network_session(global).await?;
+    let (client, mut managed, _context) = setup_network_session(global).await?;
 
     if global.auto_dismiss_dialogs {
         let _dismiss = managed.spawn_auto_dismiss().await?;
     }
 
+    // A running daemon already enforces routes in its own session.
+    let _routes = if agentchrome::daemon::is_daemon_client(&client) {
+        None
+    } else {
+        managed.spawn_route_handler().await?
+    };
+
     // Enable required domains
     managed.ensure_domain("Network").await?;
 
@@ -2097,6 +2848,7 @@ mod tests {
             status_text: "OK".to_string(),
             response_headers: serde_json::Value::Null,
             mime_type: None,
+            protocol: None,
             encoded_data_length: None,
             timing: None,
             redirect_chain: Vec::new(),
@@ -2274,6 +3026,7 @@ mod tests {
                 status_text: "OK".to_string(),
                 response_headers: serde_json::Value::Null,
                 mime_type: None,
+                protocol: None,
                 encoded_data_length: Some(1),
                 timing: None,
                 redirect_chain: Vec::new(),
@@ -2541,6 +3294,7 @@ mod tests {
             status_text: "OK".to_string(),
             response_headers: serde_json::json!({"content-length": "377301"}),
             mime_type: None,
+            protocol: None,
             encoded_data_length: Some(0),
             timing: None,
             redirect_chain: Vec::new(),
@@ -2601,4 +3355,344 @@ mod tests {
             Some("example.com".to_string())
         );
     }
+
+    // =========================================================================
+    // HAR export
+    // =========================================================================
+
+    fn har_builders(raw_events: &[RawNetworkEvent]) -> Vec<NetworkRequestBuilder> {
+        correlate_raw_events(raw_events, true, 0, None)
+    }
+
+    #[test]
+    fn har_entry_from_correlated_request() {
+        let builders = har_builders(&[
+            raw_event(
+                NetworkEventType::RequestWillBeSent,
+                0,
+                request_event("req-1", "https://example.com/?q=a%20b", "Document"),
+            ),
+            raw_event(
+                NetworkEventType::ResponseReceived,
+                0,
+                response_event("req-1"),
+            ),
+            raw_event(
+                NetworkEventType::LoadingFinished,
+                0,
+                finished_event("req-1"),
+            ),
+        ]);
+        let bodies = HarBodies {
+            request: None,
+            response: Some(("<html></html>".to_string(), false)),
+        };
+
+        let har = build_har(vec![(&builders[0], bodies)]);
+
+        assert_eq!(har.log.version, "1.2");
+        assert_eq!(har.log.pages.len(), 1);
+        assert_eq!(har.log.pages[0].title, "https://example.com/?q=a%20b");
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.pageref.as_deref(), Some("page_1"));
+        assert_eq!(entry.started_date_time, "2024-02-14T12:00:00.123Z");
+        assert_eq!(entry.request.query_string[0].value, "a b");
+        assert_eq!(entry.request.headers[0].name, "accept");
+        assert_eq!(entry.response.status, 200);
+        assert_eq!(entry.response.body_size, 456);
+        assert_eq!(entry.response.content.size, 13);
+        assert_eq!(entry.response.content.mime_type, "text/html");
+        assert_eq!(
+            entry.response.content.text.as_deref(),
+            Some("<html></html>")
+        );
+        assert!((entry.timings.dns - 1.0).abs() < 1e-9);
+        assert!((entry.timings.connect - 1.0).abs() < 1e-9);
+        assert!((entry.timings.ssl + 1.0).abs() < 1e-9);
+        assert!((entry.timings.wait - 17.0).abs() < 1e-9);
+        assert!((entry.timings.receive - 480.0).abs() < 1e-6);
+        assert!((entry.time - 499.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn har_redirect_chain_expands_into_entries() {
+        let mut redirected = request_event("req-1", "https://example.com/new", "Document");
+        redirected["redirectResponse"] = serde_json::json!({"status": 301});
+        let builders = har_builders(&[
+            raw_event(
+                NetworkEventType::RequestWillBeSent,
+                0,
+                request_event("req-1", "https://example.com/old", "Document"),
+            ),
+            raw_event(NetworkEventType::RequestWillBeSent, 0, redirected),
+            raw_event(
+                NetworkEventType::ResponseReceived,
+                0,
+                response_event("req-1"),
+            ),
+        ]);
+
+        let har = build_har(vec![(&builders[0], HarBodies::default())]);
+
+        assert_eq!(har.log.entries.len(), 2);
+        let hop = &har.log.entries[0];
+        assert_eq!(hop.request.url, "https://example.com/old");
+        assert_eq!(hop.response.status, 301);
+        assert_eq!(hop.response.redirect_url, "https://example.com/new");
+        let last = &har.log.entries[1];
+        assert_eq!(last.request.url, "https://example.com/new");
+        assert_eq!(last.response.status, 200);
+        assert_eq!(last.response.redirect_url, "");
+        assert!(last.response.content.text.is_none());
+    }
+
+    #[test]
+    fn har_failed_request_reports_error() {
+        let builders = har_builders(&[
+            raw_event(
+                NetworkEventType::RequestWillBeSent,
+                0,
+                request_event("req-1", "https://example.com/api", "XHR"),
+            ),
+            raw_event(
+                NetworkEventType::LoadingFailed,
+                0,
+                serde_json::json!({"requestId": "req-1", "errorText": "net::ERR_FAILED"}),
+            ),
+        ]);
+
+        let har = build_har(vec![(&builders[0], HarBodies::default())]);
+
+        assert!(har.log.pages.is_empty());
+        let entry = &har.log.entries[0];
+        assert!(entry.pageref.is_none());
+        assert_eq!(entry.response.status, 0);
+        assert_eq!(entry.error.as_deref(), Some("net::ERR_FAILED"));
+        assert_eq!(entry.resource_type.as_deref(), Some("xhr"));
+        assert!((entry.timings.dns + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn har_binary_body_is_base64_with_decoded_size() {
+        let builders = har_builders(&[raw_event(
+            NetworkEventType::RequestWillBeSent,
+            0,
+            request_event("req-1", "https://example.com/a.png", "Image"),
+        )]);
+        let bodies = HarBodies {
+            request: None,
+            response: Some(("iVBORw==".to_string(), true)),
+        };
+
+        let har = build_har(vec![(&builders[0], bodies)]);
+
+        let content = &har.log.entries[0].response.content;
+        assert_eq!(content.encoding.as_deref(), Some("base64"));
+        assert_eq!(content.size, 4);
+    }
+
+    #[test]
+    fn har_http_version_names() {
+        assert_eq!(har_http_version(Some("http/1.1")), "HTTP/1.1");
+        assert_eq!(har_http_version(Some("h2")), "h2");
+        assert_eq!(har_http_version(None), "");
+    }
+
+    // =========================================================================
+    // Replay
+    // =========================================================================
+
+    fn replay_entry(method: &str, url: &str, status: u16, body: Option<&str>) -> har::Entry {
+        let builders = har_builders(&[raw_event(
+            NetworkEventType::RequestWillBeSent,
+            0,
+            request_event("req-1", url, "Fetch"),
+        )]);
+        let bodies = HarBodies {
+            request: body.map(String::from),
+            response: None,
+        };
+        let mut entry = builder_to_har_entries(&builders[0], None, bodies).remove(0);
+        entry.request.method = method.to_string();
+        entry.response.status = status;
+        entry
+    }
+
+    #[test]
+    fn replay_index_matches_method_and_exact_url() {
+        let mut index = ReplayIndex::new(
+            vec![
+                replay_entry("GET", "https://example.com/api/users?page=2", 200, None),
+                replay_entry("POST", "https://example.com/api/users", 201, None),
+                replay_entry("GET", "https://example.com/api/users", 200, None),
+            ],
+            false,
+        );
+
+        let entry = index
+            .find("get", "https://example.com/api/users", None)
+            .unwrap();
+        assert_eq!(entry.request.url, "https://example.com/api/users");
+        assert_eq!(entry.request.method, "GET");
+        assert_eq!(
+            index
+                .find("POST", "https://example.com/api/users", None)
+                .unwrap()
+                .response
+                .status,
+            201
+        );
+        assert!(
+            index
+                .find("DELETE", "https://example.com/api/users", None)
+                .is_none()
+        );
+        assert!(index.find("GET", "https://example.com/api", None).is_none());
+    }
+
+    #[test]
+    fn replay_index_falls_back_across_query_strings() {
+        let mut index = ReplayIndex::new(
+            vec![
+                replay_entry("GET", "https://example.com/app.js?v=1", 200, None),
+                replay_entry("GET", "https://example.com/app.js", 203, None),
+                replay_entry("GET", "https://example.com/app.json", 404, None),
+            ],
+            false,
+        );
+
+        // An exact match wins over entries that differ only in the query
+        let entry = index
+            .find("GET", "https://example.com/app.js", None)
+            .unwrap();
+        assert_eq!(entry.response.status, 203);
+
+        // A cache-busting query falls back to the same resource
+        let entry = index
+            .find("GET", "https://example.com/app.js?v=2", None)
+            .unwrap();
+        assert_eq!(entry.response.status, 200);
+        assert!(
+            index
+                .find("GET", "https://example.com/app.j", None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn replay_index_steps_through_repeated_requests() {
+        let mut index = ReplayIndex::new(
+            vec![
+                replay_entry("GET", "https://example.com/poll", 202, None),
+                replay_entry("GET", "https://example.com/poll", 200, None),
+            ],
+            false,
+        );
+        let statuses: Vec<u16> = (0..3)
+            .map(|_| {
+                index
+                    .find("GET", "https://example.com/poll", None)
+                    .unwrap()
+                    .response
+                    .status
+            })
+            .collect();
+        assert_eq!(statuses, [202, 200, 200]);
+    }
+
+    #[test]
+    fn replay_index_match_body_compares_hashes() {
+        let entries = vec![
+            replay_entry("POST", "https://example.com/q", 200, Some(r#"{"a":1}"#)),
+            replay_entry("POST", "https://example.com/q", 404, Some(r#"{"a":2}"#)),
+        ];
+        let mut index = ReplayIndex::new(entries.clone(), true);
+        let entry = index
+            .find("POST", "https://example.com/q", Some(r#"{"a":2}"#))
+            .unwrap();
+        assert_eq!(entry.response.status, 404);
+        assert!(
+            index
+                .find("POST", "https://example.com/q", Some("{}"))
+                .is_none()
+        );
+
+        let mut loose = ReplayIndex::new(entries, false);
+        let entry = loose
+            .find("POST", "https://example.com/q", Some("{}"))
+            .unwrap();
+        assert_eq!(entry.response.status, 200);
+    }
+
+    #[test]
+    fn inline_post_data_reads_post_data_entries() {
+        let request = serde_json::json!({ "postData": "a=1", "hasPostData": true });
+        assert_eq!(inline_post_data(&request).as_deref(), Some("a=1"));
+
+        // Large and multipart bodies arrive only as base64 chunks.
+        let request = serde_json::json!({
+            "hasPostData": true,
+            "postDataEntries": [{ "bytes": "YT0x" }, { "bytes": "JmI9Mg==" }],
+        });
+        assert_eq!(inline_post_data(&request).as_deref(), Some("a=1&b=2"));
+
+        let request = serde_json::json!({ "hasPostData": true });
+        assert!(inline_post_data(&request).is_none());
+        assert!(inline_post_data(&serde_json::json!({})).is_none());
+    }
+
+    #[test]
+    fn replay_command_fulfills_with_base64_body_and_clean_headers() {
+        let mut entry = replay_entry("GET", "https://example.com/", 200, None);
+        entry.response.status_text = "OK".into();
+        entry.response.headers = vec![
+            har::NameValue {
+                name: "Content-Type".into(),
+                value: "text/plain".into(),
+            },
+            har::NameValue {
+                name: "Content-Encoding".into(),
+                value: "gzip".into(),
+            },
+            har::NameValue {
+                name: "content-length".into(),
+                value: "99".into(),
+            },
+        ];
+        entry.response.content.text = Some("hi".into());
+
+        let (method, params) = replay_command(&serde_json::json!("I1"), &entry);
+
+        assert_eq!(method, "Fetch.fulfillRequest");
+        assert_eq!(params["requestId"], "I1");
+        assert_eq!(params["responseCode"], 200);
+        assert_eq!(params["responsePhrase"], "OK");
+        assert_eq!(params["body"], "aGk=");
+        assert_eq!(
+            params["responseHeaders"],
+            serde_json::json!([{"name": "Content-Type", "value": "text/plain"}])
+        );
+
+        entry.response.content.encoding = Some("base64".into());
+        entry.response.content.text = Some("iVBORw==".into());
+        let (_, params) = replay_command(&serde_json::json!("I1"), &entry);
+        assert_eq!(params["body"], "iVBORw==");
+    }
+
+    #[test]
+    fn replay_command_fails_recorded_failures_and_unmatched_requests() {
+        let failed = replay_entry("GET", "https://example.com/", 0, None);
+        let (method, params) = replay_command(&serde_json::json!("I1"), &failed);
+        assert_eq!(method, "Fetch.failRequest");
+        assert_eq!(params["errorReason"], "Failed");
+
+        let id = serde_json::json!("I2");
+        assert_eq!(
+            unmatched_command(&id, ReplayNotFound::Abort).0,
+            "Fetch.failRequest"
+        );
+        let (method, params) = unmatched_command(&id, ReplayNotFound::Passthrough);
+        assert_eq!(method, "Fetch.continueRequest");
+        assert_eq!(params, serde_json::json!({"requestId": "I2"}));
+    }
 }
@@ -8,6 +8,7 @@ use agentchrome::error::{AppError, ExitCode};
 
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::emulate::apply_emulate_state;
+use crate::inject::apply_inject_scripts;
 use crate::snapshot;
 
 // =============================================================================
@@ -156,7 +157,8 @@ pub async fn connect_from_global_with_timeout(
 // Session setup
 // =============================================================================
 
-/// Connect to Chrome, attach to a target, and apply emulation state.
+/// Connect to Chrome, attach to a target, and apply emulation state and
+/// `js inject` preload scripts.
 pub async fn setup_session(global: &GlobalOpts) -> Result<(CdpClient, ManagedSession), AppError> {
     let conn = connect_from_global(global).await?;
     let target = resolve_target(
@@ -170,6 +172,7 @@ pub async fn setup_session(global: &GlobalOpts) -> Result<(CdpClient, ManagedSes
     let session = conn.client.create_session(&target.id).await?;
     let mut managed = ManagedSession::new(session);
     apply_emulate_state(&mut managed).await?;
+    apply_inject_scripts(&managed, &target.id).await?;
 
     Ok((conn.client, managed))
 }
@@ -339,21 +342,36 @@ async fn verify_frame_still_attached(
 
 /// Emit plain text through the large-response gate.
 ///
-/// If the text exceeds the threshold, it is written to a temp file and
-/// the file path is printed on stdout instead.
+/// If the text exceeds the threshold, it is written to a `.txt` temp file
+/// and the file path is printed on stdout instead.
 pub fn emit_plain(text: &str, output: &OutputFormat) -> Result<(), AppError> {
-    let threshold = output.large_response_threshold.unwrap_or(DEFAULT_THRESHOLD);
+    emit_plain_as(text, output, "txt")
+}
 
-    if text.len() <= threshold {
-        print!("{text}");
-        return Ok(());
+/// Like [`emit_plain`], but an offloaded temp file gets `extension` (e.g.
+/// `csv`) so it opens with the right tool.
+pub fn emit_plain_as(text: &str, output: &OutputFormat, extension: &str) -> Result<(), AppError> {
+    match offload_plain(text, output, extension)? {
+        Some(path) => println!("{path}"),
+        None => print!("{text}"),
     }
-
-    let path = write_temp_file(text, "txt")?;
-    println!("{path}");
     Ok(())
 }
 
+/// Write `text` to a temp file if it exceeds the threshold, returning the
+/// file path; `None` when it is small enough to print.
+fn offload_plain(
+    text: &str,
+    output: &OutputFormat,
+    extension: &str,
+) -> Result<Option<String>, AppError> {
+    let threshold = output.large_response_threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if text.len() <= threshold {
+        return Ok(None);
+    }
+    write_temp_file(text, extension).map(Some)
+}
+
 /// Emit a serializable value through the large-response gate.
 ///
 /// If the serialized JSON exceeds the threshold, the full output is written
@@ -671,6 +689,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn offload_plain_uses_requested_extension() {
+        let output = OutputFormat {
+            json: false,
+            pretty: false,
+            plain: true,
+            large_response_threshold: Some(5),
+        };
+        assert!(offload_plain("a,b", &output, "csv").unwrap().is_none());
+
+        let path = offload_plain("a,b\n1,2\n", &output, "csv")
+            .unwrap()
+            .unwrap();
+        assert!(
+            std::path::Path::new(&path)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a,b\n1,2\n");
+        let _ = std::fs::remove_file(&path);
+    }
+
     // -------------------------------------------------------------------------
     // emit_with_snapshot tests
     // -------------------------------------------------------------------------
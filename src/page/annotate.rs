@@ -0,0 +1,352 @@
+use serde::Serialize;
+
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::AppError;
+
+use crate::cli::AnnotateMode;
+
+use super::find::{BoundingBox, resolve_bounding_box};
+
+/// DOM id of the overlay injected for the duration of the capture.
+const OVERLAY_ID: &str = "__agentchrome_annotations";
+
+/// Spacing of the coordinate grid, in CSS pixels.
+const GRID_STEP: u32 = 100;
+
+/// Box colours for UID marks, cycled so neighbouring boxes stay distinguishable.
+const MARK_COLORS: &[&str] = &[
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#008080", "#9a6324", "#f032e6",
+];
+
+const GRID_COLOR: &str = "rgba(255,0,0,0.55)";
+
+/// Height of a mark label; labels of boxes closer than this to the top edge
+/// are drawn inside the box.
+const LABEL_HEIGHT: f64 = 16.0;
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+/// Legend returned alongside an annotated screenshot.
+#[derive(Debug, Serialize)]
+pub(super) struct Annotations {
+    mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid_step: Option<u32>,
+    legend: Vec<LegendEntry>,
+}
+
+/// One drawn label and the region it marks, in viewport CSS pixels.
+#[derive(Debug, Serialize)]
+struct LegendEntry {
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "boundingBox")]
+    bounding_box: BoundingBox,
+}
+
+/// A box to draw, as passed to the overlay script.
+#[derive(Serialize)]
+struct Mark<'a> {
+    label: &'a str,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    color: &'a str,
+    border: &'a str,
+    inside: bool,
+}
+
+// =============================================================================
+// Overlay injection
+// =============================================================================
+
+/// Draw the requested overlay into the page and return its legend.
+///
+/// Only marks intersecting `region` (the capture clip, or the viewport when
+/// `None`) are drawn and listed. Call [`remove_annotations`] after capturing.
+///
+/// # Errors
+///
+/// Returns `AppError` if `uids` mode has no snapshot state, or the overlay
+/// cannot be injected.
+pub(super) async fn inject_annotations(
+    session: &ManagedSession,
+    mode: AnnotateMode,
+    region: Option<BoundingBox>,
+) -> Result<Annotations, AppError> {
+    let (vw, vh) = super::get_viewport_dimensions(session).await?;
+    let region = region.unwrap_or(BoundingBox {
+        x: 0.0,
+        y: 0.0,
+        width: f64::from(vw),
+        height: f64::from(vh),
+    });
+
+    let (legend, grid_step) = match mode {
+        AnnotateMode::Uids => (uid_legend(session, &region).await?, None),
+        AnnotateMode::Grid => (grid_legend(vw, vh, &region), Some(GRID_STEP)),
+    };
+
+    let marks: Vec<Mark<'_>> = legend
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let b = &entry.bounding_box;
+            let (color, border) = match mode {
+                AnnotateMode::Uids => (MARK_COLORS[i % MARK_COLORS.len()], "2px solid"),
+                AnnotateMode::Grid => (GRID_COLOR, "1px dashed"),
+            };
+            Mark {
+                label: &entry.label,
+                x: b.x,
+                y: b.y,
+                width: b.width,
+                height: b.height,
+                color,
+                border,
+                inside: matches!(mode, AnnotateMode::Grid) || b.y < LABEL_HEIGHT,
+            }
+        })
+        .collect();
+
+    let marks_json = serde_json::to_string(&marks).unwrap_or_else(|_| "[]".to_string());
+    let expression = format!(
+        r"(function(marks, id) {{
+            var old = document.getElementById(id);
+            if (old) old.remove();
+            var root = document.createElement('div');
+            root.id = id;
+            root.style.cssText = 'position:fixed;left:0;top:0;width:0;height:0;' +
+                'z-index:2147483647;pointer-events:none;';
+            marks.forEach(function(m) {{
+                var box = document.createElement('div');
+                box.style.cssText = 'position:fixed;box-sizing:border-box;left:' + m.x +
+                    'px;top:' + m.y + 'px;width:' + m.width + 'px;height:' + m.height +
+                    'px;border:' + m.border + ' ' + m.color + ';';
+                var label = document.createElement('span');
+                label.textContent = m.label;
+                label.style.cssText = 'position:absolute;left:-1px;top:' +
+                    (m.inside ? '-1px' : '-{LABEL_HEIGHT}px') +
+                    ';font:bold 11px/14px monospace;color:#fff;background:' + m.color +
+                    ';padding:1px 3px;white-space:nowrap;';
+                box.appendChild(label);
+                root.appendChild(box);
+            }});
+            (document.body || document.documentElement).appendChild(root);
+            return marks.length;
+        }})({marks_json}, '{OVERLAY_ID}')"
+    );
+    session
+        .send_command(
+            "Runtime.evaluate",
+            Some(serde_json::json!({ "expression": expression, "returnByValue": true })),
+        )
+        .await
+        .map_err(|e| AppError::screenshot_failed(&format!("Failed to draw annotations: {e}")))?;
+
+    Ok(Annotations {
+        mode: match mode {
+            AnnotateMode::Uids => "uids",
+            AnnotateMode::Grid => "grid",
+        },
+        grid_step,
+        legend,
+    })
+}
+
+/// Remove the overlay injected by [`inject_annotations`]. Best effort.
+pub(super) async fn remove_annotations(session: &ManagedSession) {
+    let expression = format!(
+        "(function() {{ var e = document.getElementById('{OVERLAY_ID}'); if (e) e.remove(); }})()"
+    );
+    let _ = session
+        .send_command(
+            "Runtime.evaluate",
+            Some(serde_json::json!({ "expression": expression })),
+        )
+        .await;
+}
+
+// =============================================================================
+// Legends
+// =============================================================================
+
+/// The snapshot's main-frame UIDs with their backend node IDs, ordered by
+/// UID number. Iframe UIDs are left out: their boxes resolve in the frame's
+/// own coordinates, not the screenshot's.
+fn main_frame_uids(state: &crate::snapshot::SnapshotState) -> Vec<(&String, i64)> {
+    let mut uids: Vec<(&String, i64)> = state
+        .uid_map
+        .iter()
+        .filter(|(uid, _)| crate::snapshot::uid_in_frame(state, uid, None))
+        .map(|(uid, &backend_id)| (uid, backend_id))
+        .collect();
+    uids.sort_by_key(|(uid, _)| crate::snapshot::parse_uid_number(uid).unwrap_or(u32::MAX));
+    uids
+}
+
+/// Legend entries for every main-frame UID in the latest snapshot whose
+/// element is visible inside `region`, ordered by UID number.
+async fn uid_legend(
+    session: &ManagedSession,
+    region: &BoundingBox,
+) -> Result<Vec<LegendEntry>, AppError> {
+    let state = crate::snapshot::read_snapshot_state()
+        .ok()
+        .flatten()
+        .ok_or_else(AppError::no_snapshot_state)?;
+
+    let mut legend = Vec::new();
+    for (uid, backend_id) in main_frame_uids(&state) {
+        let Some(bounding_box) = resolve_bounding_box(session, backend_id).await else {
+            continue;
+        };
+        if bounding_box.width <= 0.0
+            || bounding_box.height <= 0.0
+            || !intersects(&bounding_box, region)
+        {
+            continue;
+        }
+        let node = state
+            .nodes
+            .iter()
+            .find(|n| n.uid.as_deref() == Some(uid.as_str()));
+        legend.push(LegendEntry {
+            label: uid.clone(),
+            uid: Some(uid.clone()),
+            role: node.map(|n| n.role.clone()),
+            name: node.map(|n| n.name.clone()),
+            bounding_box,
+        });
+    }
+    Ok(legend)
+}
+
+/// Grid cells labelled spreadsheet-style (`A1` top-left, columns by letter,
+/// rows by number) covering the viewport, limited to those inside `region`.
+fn grid_legend(
+    viewport_width: u32,
+    viewport_height: u32,
+    region: &BoundingBox,
+) -> Vec<LegendEntry> {
+    let mut legend = Vec::new();
+    for (row, y) in (0..viewport_height).step_by(GRID_STEP as usize).enumerate() {
+        for (col, x) in (0..viewport_width).step_by(GRID_STEP as usize).enumerate() {
+            let cell = BoundingBox {
+                x: f64::from(x),
+                y: f64::from(y),
+                width: f64::from(GRID_STEP.min(viewport_width - x)),
+                height: f64::from(GRID_STEP.min(viewport_height - y)),
+            };
+            if !intersects(&cell, region) {
+                continue;
+            }
+            legend.push(LegendEntry {
+                label: format!("{}{}", column_label(col), row + 1),
+                uid: None,
+                role: None,
+                name: None,
+                bounding_box: cell,
+            });
+        }
+    }
+    legend
+}
+
+/// Spreadsheet column name for a zero-based index: A..Z, AA..AZ, ...
+fn column_label(mut index: usize) -> String {
+    let mut label = Vec::new();
+    loop {
+        label.push(b'A' + u8::try_from(index % 26).unwrap_or(0));
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    label.reverse();
+    String::from_utf8(label).unwrap_or_default()
+}
+
+fn intersects(a: &BoundingBox, b: &BoundingBox) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(x: f64, y: f64, width: f64, height: f64) -> BoundingBox {
+        BoundingBox {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn main_frame_uids_skip_iframe_uids() {
+        let mut state: crate::snapshot::SnapshotState = serde_json::from_value(serde_json::json!({
+            "url": "https://example.com/",
+            "timestamp": "2026-02-17T12:00:00Z",
+            "uid_map": { "s3": 30, "s1": 10, "s2": 20 },
+            "aggregate": true,
+            "frame_uid_ranges": [[0, [1, 2]], [1, [3, 3]]],
+            "frame_ids": [[1, "FRAME-1"]],
+        }))
+        .unwrap();
+        let uids: Vec<&str> = main_frame_uids(&state)
+            .into_iter()
+            .map(|(uid, _)| uid.as_str())
+            .collect();
+        assert_eq!(uids, ["s1", "s2"]);
+
+        // A snapshot taken inside an iframe has no main-frame UIDs.
+        state.aggregate = false;
+        state.frame_id = Some("FRAME-1".into());
+        assert!(main_frame_uids(&state).is_empty());
+    }
+
+    #[test]
+    fn column_labels_follow_spreadsheet_order() {
+        assert_eq!(column_label(0), "A");
+        assert_eq!(column_label(25), "Z");
+        assert_eq!(column_label(26), "AA");
+        assert_eq!(column_label(27), "AB");
+        assert_eq!(column_label(701), "ZZ");
+        assert_eq!(column_label(702), "AAA");
+    }
+
+    #[test]
+    fn grid_covers_viewport_with_partial_edge_cells() {
+        let legend = grid_legend(250, 120, &region(0.0, 0.0, 250.0, 120.0));
+        let labels: Vec<&str> = legend.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, ["A1", "B1", "C1", "A2", "B2", "C2"]);
+        let last = &legend[5].bounding_box;
+        assert!((last.x - 200.0).abs() < f64::EPSILON);
+        assert!((last.width - 50.0).abs() < f64::EPSILON);
+        assert!((last.height - 20.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn grid_is_limited_to_the_clip_region() {
+        let legend = grid_legend(400, 400, &region(150.0, 150.0, 100.0, 40.0));
+        let labels: Vec<&str> = legend.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, ["B2", "C2"]);
+    }
+
+    #[test]
+    fn intersects_excludes_touching_edges() {
+        let a = region(0.0, 0.0, 100.0, 100.0);
+        assert!(intersects(&a, &region(50.0, 50.0, 100.0, 100.0)));
+        assert!(!intersects(&a, &region(100.0, 0.0, 10.0, 10.0)));
+    }
+}
@@ -0,0 +1,374 @@
+//! `page archive` subcommand — MHTML capture and bug-report bundles.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use agentchrome::cdp::CdpClient;
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::AppError;
+
+use crate::cli::{GlobalOpts, PageArchiveArgs};
+
+use super::{get_page_info, print_output, setup_session};
+
+const MHTML_FILE: &str = "page.mhtml";
+const SNAPSHOT_TEXT_FILE: &str = "snapshot.txt";
+const SNAPSHOT_JSON_FILE: &str = "snapshot.json";
+const SCREENSHOT_FILE: &str = "screenshot.png";
+const CONSOLE_FILE: &str = "console.json";
+const NETWORK_FILE: &str = "network.json";
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Every file a bundle writes; a reused bundle directory is cleared of these.
+const BUNDLE_FILES: [&str; 7] = [
+    MHTML_FILE,
+    SNAPSHOT_TEXT_FILE,
+    SNAPSHOT_JSON_FILE,
+    SCREENSHOT_FILE,
+    CONSOLE_FILE,
+    NETWORK_FILE,
+    MANIFEST_FILE,
+];
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+struct ArchiveResult {
+    file: String,
+    size_bytes: u64,
+    url: String,
+    title: String,
+}
+
+/// One file of a bundle, or the reason it could not be captured.
+#[derive(Debug, Serialize)]
+struct Artifact {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Contents of `manifest.json`.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    url: String,
+    title: String,
+    captured_at: String,
+    agentchrome_version: &'static str,
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Serialize)]
+struct BundleResult {
+    bundle: String,
+    manifest: String,
+    #[serde(flatten)]
+    contents: Manifest,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Capture the page as MHTML via `Page.captureSnapshot`.
+async fn capture_mhtml(managed: &ManagedSession) -> Result<String, AppError> {
+    let result = managed
+        .send_command(
+            "Page.captureSnapshot",
+            Some(serde_json::json!({ "format": "mhtml" })),
+        )
+        .await
+        .map_err(|e| AppError::archive_failed(&e.to_string()))?;
+    result["data"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::archive_failed("response missing 'data'"))
+}
+
+/// Capture a PNG of the viewport.
+async fn capture_screenshot(managed: &ManagedSession) -> Result<Vec<u8>, String> {
+    use base64::Engine;
+
+    let result = managed
+        .send_command(
+            "Page.captureScreenshot",
+            Some(serde_json::json!({ "format": "png" })),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let data = result["data"].as_str().ok_or("response missing 'data'")?;
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("invalid image data: {e}"))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<u64, AppError> {
+    fs::write(path, bytes).map_err(|e| {
+        AppError::archive_failed(&format!("Failed to write {}: {e}", path.display()))
+    })?;
+    Ok(bytes.len() as u64)
+}
+
+/// Write one bundle artifact, turning a capture or write failure into an
+/// artifact entry carrying the error instead of aborting the bundle.
+fn write_artifact(
+    dir: &Path,
+    kind: &'static str,
+    file: &'static str,
+    captured: Result<(Vec<u8>, Option<usize>), String>,
+) -> Artifact {
+    let outcome = captured.and_then(|(bytes, count)| {
+        write_file(&dir.join(file), &bytes)
+            .map(|size| (size, count))
+            .map_err(|e| e.message)
+    });
+    match outcome {
+        Ok((size, count)) => Artifact {
+            kind,
+            file: Some(file),
+            size_bytes: Some(size),
+            count,
+            error: None,
+        },
+        Err(error) => {
+            eprintln!("warning: could not capture {kind}: {error}");
+            Artifact {
+                kind,
+                file: None,
+                size_bytes: None,
+                count: None,
+                error: Some(error),
+            }
+        }
+    }
+}
+
+fn to_json_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|e| format!("serialization error: {e}"))
+}
+
+// =============================================================================
+// Bundle
+// =============================================================================
+
+/// Capture every bundle artifact after the MHTML. The network summary needs
+/// the daemon's event history; without it, collecting would reload the page.
+async fn capture_bundle_artifacts(
+    client: &CdpClient,
+    managed: &mut ManagedSession,
+    dir: &Path,
+    timeout_ms: Option<u64>,
+) -> Vec<Artifact> {
+    let mut artifacts = Vec::new();
+
+    match super::snapshot::capture_tree(managed).await {
+        Ok(root) => {
+            let text = crate::snapshot::format_text(&root, false);
+            let json = serde_json::to_value(&root).map_err(|e| format!("serialization error: {e}"));
+            let count = json
+                .as_ref()
+                .ok()
+                .and_then(|v| usize::try_from(crate::snapshot::count_nodes(v)).ok());
+            artifacts.push(write_artifact(
+                dir,
+                "snapshot",
+                SNAPSHOT_TEXT_FILE,
+                Ok((text.into_bytes(), count)),
+            ));
+            artifacts.push(write_artifact(
+                dir,
+                "snapshot_json",
+                SNAPSHOT_JSON_FILE,
+                json.and_then(|v| to_json_bytes(&v)).map(|b| (b, count)),
+            ));
+        }
+        Err(e) => {
+            artifacts.push(write_artifact(
+                dir,
+                "snapshot",
+                SNAPSHOT_TEXT_FILE,
+                Err(e.message),
+            ));
+        }
+    }
+
+    let screenshot = capture_screenshot(managed).await.map(|b| (b, None));
+    artifacts.push(write_artifact(
+        dir,
+        "screenshot",
+        SCREENSHOT_FILE,
+        screenshot,
+    ));
+
+    let console = crate::console::read_messages(client, managed, timeout_ms)
+        .await
+        .map_err(|e| e.message)
+        .and_then(|m| to_json_bytes(&m).map(|b| (b, Some(m.len()))));
+    artifacts.push(write_artifact(dir, "console", CONSOLE_FILE, console));
+
+    let network = if agentchrome::daemon::is_daemon_client(client) {
+        crate::network::summarize_requests(client, managed, timeout_ms)
+            .await
+            .map_err(|e| e.message)
+            .and_then(|r| to_json_bytes(&r).map(|b| (b, Some(r.len()))))
+    } else {
+        Err("requires the daemon (would reload the page)".to_string())
+    };
+    artifacts.push(write_artifact(dir, "network", NETWORK_FILE, network));
+
+    artifacts
+}
+
+/// Delete the files a bundle writes to `dir`; other files are left alone.
+fn clear_bundle(dir: &Path) -> std::io::Result<()> {
+    for file in BUNDLE_FILES {
+        match fs::remove_file(dir.join(file)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+async fn execute_bundle(
+    global: &GlobalOpts,
+    client: &CdpClient,
+    managed: &mut ManagedSession,
+    dir: &Path,
+) -> Result<(), AppError> {
+    fs::create_dir_all(dir).map_err(|e| {
+        AppError::archive_failed(&format!(
+            "Failed to create bundle directory {}: {e}",
+            dir.display()
+        ))
+    })?;
+    // A failed capture must not leave the previous run's artifact behind.
+    clear_bundle(dir).map_err(|e| {
+        AppError::archive_failed(&format!(
+            "Failed to clear previous bundle in {}: {e}",
+            dir.display()
+        ))
+    })?;
+
+    let captured_at = agentchrome::session::now_iso8601();
+    let (url, title) = get_page_info(managed).await?;
+    let mhtml = capture_mhtml(managed).await?;
+    let mhtml_size = write_file(&dir.join(MHTML_FILE), mhtml.as_bytes())?;
+
+    let mut artifacts = vec![Artifact {
+        kind: "mhtml",
+        file: Some(MHTML_FILE),
+        size_bytes: Some(mhtml_size),
+        count: None,
+        error: None,
+    }];
+    artifacts.extend(capture_bundle_artifacts(client, managed, dir, global.timeout).await);
+
+    let manifest = Manifest {
+        url,
+        title,
+        captured_at,
+        agentchrome_version: env!("CARGO_PKG_VERSION"),
+        artifacts,
+    };
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let bytes = to_json_bytes(&manifest).map_err(|e| AppError::archive_failed(&e))?;
+    write_file(&manifest_path, &bytes)?;
+
+    let output = BundleResult {
+        bundle: dir.display().to_string(),
+        manifest: manifest_path.display().to_string(),
+        contents: manifest,
+    };
+    print_output(&output, &global.output)
+}
+
+// =============================================================================
+// Executor
+// =============================================================================
+
+pub async fn execute_archive(global: &GlobalOpts, args: &PageArchiveArgs) -> Result<(), AppError> {
+    let (client, mut managed) = setup_session(global).await?;
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+    managed.ensure_domain("Page").await?;
+    managed.ensure_domain("Runtime").await?;
+
+    if let Some(ref dir) = args.bundle {
+        return execute_bundle(global, &client, &mut managed, dir).await;
+    }
+
+    let Some(ref file) = args.file else {
+        return Err(AppError::archive_failed(
+            "either --file or --bundle is required",
+        ));
+    };
+    let (url, title) = get_page_info(&managed).await?;
+    let mhtml = capture_mhtml(&managed).await?;
+    let size_bytes = write_file(file, mhtml.as_bytes())?;
+
+    let output = ArchiveResult {
+        file: file.display().to_string(),
+        size_bytes,
+        url,
+        title,
+    };
+    print_output(&output, &global.output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_bundle_removes_only_bundle_files() {
+        let dir = std::env::temp_dir().join("agentchrome-test-clear-bundle");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(SCREENSHOT_FILE), b"old").unwrap();
+        fs::write(dir.join(MANIFEST_FILE), b"{}").unwrap();
+        fs::write(dir.join("notes.txt"), b"keep").unwrap();
+
+        clear_bundle(&dir).unwrap();
+        assert!(!dir.join(SCREENSHOT_FILE).exists());
+        assert!(!dir.join(MANIFEST_FILE).exists());
+        assert!(dir.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_artifact_records_error_without_file() {
+        let dir = std::env::temp_dir();
+        let artifact = write_artifact(&dir, "network", NETWORK_FILE, Err("offline".into()));
+        let json = serde_json::to_value(&artifact).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "network", "error": "offline" })
+        );
+    }
+
+    #[test]
+    fn written_artifact_reports_size_and_count() {
+        let dir = std::env::temp_dir().join(format!("agentchrome-archive-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let artifact = write_artifact(&dir, "console", CONSOLE_FILE, Ok((b"[]".to_vec(), Some(0))));
+        let json = serde_json::to_value(&artifact).unwrap();
+        assert_eq!(json["file"], CONSOLE_FILE);
+        assert_eq!(json["size_bytes"], 2);
+        assert_eq!(json["count"], 0);
+        assert_eq!(fs::read(dir.join(CONSOLE_FILE)).unwrap(), b"[]");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,1232 @@
+//! `page extract` subcommand — schema-driven structured data extraction.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use url::Url;
+
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::AppError;
+use agentchrome::frame::{AutoFrameTarget, FrameArg};
+
+use crate::cli::{GlobalOpts, PageExtractArgs};
+
+use super::{get_page_info, setup_session};
+
+/// Walks the plan from the document (`this`) and returns the raw values as a
+/// JSON string. UID and role matches arrive as extra arguments, referenced by
+/// index from `plan.groups`. A missing attribute reads as `false`, and a
+/// selector that throws is reported as `{ "__error": message }`.
+const EXTRACT_FUNCTION: &str = r"function(plan) {
+  const refs = Array.prototype.slice.call(arguments, 1);
+  const groups = plan.groups.map((g) => g.map((i) => refs[i]).sort((a, b) =>
+    a.compareDocumentPosition(b) & Node.DOCUMENT_POSITION_FOLLOWING ? -1 : 1));
+  const select = (scope, sel, all) => {
+    if (!sel) return [scope];
+    const found = sel.css !== undefined
+      ? (all ? Array.from(scope.querySelectorAll(sel.css))
+             : [scope.querySelector(sel.css)].filter(Boolean))
+      : groups[sel.group].filter((el) => el !== scope && scope.contains(el));
+    return all ? found : found.slice(0, 1);
+  };
+  const read = (el, field) => {
+    if (field.attr) {
+      const v = el.getAttribute(field.attr);
+      return v === null ? false : v;
+    }
+    for (const name of field.fallback) {
+      const v = el.getAttribute(name);
+      if (v !== null) return v;
+    }
+    return el.innerText !== undefined ? el.innerText : el.textContent;
+  };
+  const extract = (scope, fields) => {
+    const out = {};
+    for (const field of fields) {
+      let els;
+      try {
+        els = select(scope, field.sel, field.list);
+      } catch (e) {
+        out[field.name] = { __error: String((e && e.message) || e) };
+        continue;
+      }
+      const values = els.map((el) => field.fields ? extract(el, field.fields) : read(el, field));
+      out[field.name] = field.list ? values : (values.length ? values[0] : null);
+    }
+    return out;
+  };
+  return JSON.stringify({ data: extract(this, plan.fields), base_url: this.baseURI });
+}";
+
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[-+]?(?:\d[\d,]*(?:\.\d+)?|\.\d+)(?:[eE][-+]?\d+)?").expect("valid regex")
+});
+
+static ISO_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(\d{4})-(\d{2})-(\d{2})(?:[T ](\d{2}):(\d{2})(?::(\d{2})(\.\d+)?)?\s*(Z|[+-]\d{2}:?\d{2})?)?$",
+    )
+    .expect("valid regex")
+});
+
+static SLASH_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4})/(\d{1,2})/(\d{1,2})$").expect("valid regex"));
+
+static MONTH_FIRST_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[A-Za-z]+,\s*)?([A-Za-z]+)\.?\s+(\d{1,2})(?:st|nd|rd|th)?,?\s+(\d{4})$")
+        .expect("valid regex")
+});
+
+static DAY_FIRST_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[A-Za-z]+,\s*)?(\d{1,2})(?:st|nd|rd|th)?\s+([A-Za-z]+)\.?,?\s+(\d{4})$")
+        .expect("valid regex")
+});
+
+static ROLE_SELECTOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^([A-Za-z]+)(?:\[name=(?:"([^"]*)"|'([^']*)'|([^\]"']*))\])?$"#)
+        .expect("valid regex")
+});
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// =============================================================================
+// Schema
+// =============================================================================
+
+/// Output type of a field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FieldType {
+    #[default]
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Date,
+    Url,
+}
+
+/// A field definition as written in the schema file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldDef {
+    selector: Option<String>,
+    attribute: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<FieldType>,
+    #[serde(default)]
+    list: bool,
+    #[serde(default)]
+    required: bool,
+    frame: Option<String>,
+    fields: Option<FieldMap>,
+}
+
+/// Field definitions in the order the schema file lists them.
+#[derive(Debug, Default)]
+struct FieldMap(Vec<(String, FieldDef)>);
+
+impl<'de> Deserialize<'de> for FieldMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldMapVisitor;
+
+        impl<'de> Visitor<'de> for FieldMapVisitor {
+            type Value = FieldMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object mapping field names to definitions")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<FieldMap, A::Error> {
+                let mut fields = Vec::new();
+                while let Some((name, FieldSpec(def))) = map.next_entry::<String, FieldSpec>()? {
+                    fields.push((name, def));
+                }
+                Ok(FieldMap(fields))
+            }
+        }
+
+        deserializer.deserialize_map(FieldMapVisitor)
+    }
+}
+
+/// A field written either as a bare selector string or as a full definition.
+struct FieldSpec(FieldDef);
+
+impl<'de> Deserialize<'de> for FieldSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldSpecVisitor;
+
+        impl<'de> Visitor<'de> for FieldSpecVisitor {
+            type Value = FieldSpec;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a selector string or a field definition object")
+            }
+
+            fn visit_str<E: de::Error>(self, selector: &str) -> Result<FieldSpec, E> {
+                Ok(FieldSpec(FieldDef {
+                    selector: Some(selector.to_string()),
+                    ..FieldDef::default()
+                }))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<FieldSpec, A::Error> {
+                FieldDef::deserialize(de::value::MapAccessDeserializer::new(map)).map(FieldSpec)
+            }
+        }
+
+        deserializer.deserialize_any(FieldSpecVisitor)
+    }
+}
+
+/// How a field finds its elements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Selector {
+    Css(String),
+    Uid(String),
+    Role { role: String, name: Option<String> },
+}
+
+impl Selector {
+    fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if crate::snapshot::is_uid(value) {
+            return Ok(Self::Uid(value.to_string()));
+        }
+        if let Some(rest) = value.strip_prefix("role:") {
+            let caps = ROLE_SELECTOR.captures(rest.trim()).ok_or_else(|| {
+                format!("invalid role selector '{value}' (expected role:ROLE or role:ROLE[name=\"NAME\"])")
+            })?;
+            let name = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .or_else(|| caps.get(4))
+                .map(|m| m.as_str().trim().to_string());
+            return Ok(Self::Role {
+                role: caps[1].to_ascii_lowercase(),
+                name,
+            });
+        }
+        let css = value.strip_prefix("css:").unwrap_or(value).trim();
+        if css.is_empty() {
+            return Err("empty selector".to_string());
+        }
+        Ok(Self::Css(css.to_string()))
+    }
+
+    /// Target for `--frame auto` discovery; role selectors cannot be searched.
+    fn auto_target(&self) -> Option<AutoFrameTarget<'_>> {
+        match self {
+            Self::Css(css) => Some(AutoFrameTarget::CssSelector(css)),
+            Self::Uid(uid) => Some(AutoFrameTarget::Uid(uid)),
+            Self::Role { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Css(css) => f.write_str(css),
+            Self::Uid(uid) => f.write_str(uid),
+            Self::Role { role, name: None } => write!(f, "role:{role}"),
+            Self::Role {
+                role,
+                name: Some(name),
+            } => write!(f, "role:{role}[name=\"{name}\"]"),
+        }
+    }
+}
+
+/// A validated schema field.
+#[derive(Debug)]
+struct Field {
+    name: String,
+    selector: Option<Selector>,
+    attribute: Option<String>,
+    kind: FieldType,
+    list: bool,
+    required: bool,
+    frame: Option<String>,
+    fields: Option<Vec<Field>>,
+}
+
+fn parse_schema(text: &str) -> Result<Vec<Field>, String> {
+    let map: FieldMap = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    compile_fields(map, "", true)
+}
+
+fn compile_fields(map: FieldMap, prefix: &str, top_level: bool) -> Result<Vec<Field>, String> {
+    if map.0.is_empty() {
+        return Err(if top_level {
+            "schema defines no fields".to_string()
+        } else {
+            format!("{prefix}: 'fields' is empty")
+        });
+    }
+    let mut fields: Vec<Field> = Vec::with_capacity(map.0.len());
+    for (name, def) in map.0 {
+        let path = if top_level {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        if name.is_empty() {
+            return Err("field names must not be empty".to_string());
+        }
+        if fields.iter().any(|f| f.name == name) {
+            return Err(format!("{path}: duplicate field"));
+        }
+        fields.push(compile_field(name, def, &path, top_level)?);
+    }
+    Ok(fields)
+}
+
+fn compile_field(
+    name: String,
+    def: FieldDef,
+    path: &str,
+    top_level: bool,
+) -> Result<Field, String> {
+    let selector = def
+        .selector
+        .as_deref()
+        .map(Selector::parse)
+        .transpose()
+        .map_err(|e| format!("{path}: {e}"))?;
+    let fields = def
+        .fields
+        .map(|map| compile_fields(map, path, false))
+        .transpose()?;
+
+    if fields.is_some() && (def.attribute.is_some() || def.kind.is_some()) {
+        return Err(format!(
+            "{path}: 'attribute' and 'type' cannot be combined with nested 'fields'"
+        ));
+    }
+    if selector.is_none() && (fields.is_none() || def.list) {
+        return Err(format!("{path}: 'selector' is required"));
+    }
+    if def
+        .attribute
+        .as_deref()
+        .is_some_and(|a| a.trim().is_empty())
+    {
+        return Err(format!("{path}: 'attribute' must not be empty"));
+    }
+    if let Some(ref frame) = def.frame {
+        if !top_level {
+            return Err(format!(
+                "{path}: 'frame' is only allowed on top-level fields"
+            ));
+        }
+        let arg = agentchrome::frame::parse_frame_arg(frame)
+            .map_err(|e| format!("{path}: {}", e.message))?;
+        if matches!(arg, FrameArg::Auto)
+            && selector.as_ref().is_none_or(|s| s.auto_target().is_none())
+        {
+            return Err(format!("{path}: frame 'auto' needs a CSS or UID selector"));
+        }
+    }
+
+    Ok(Field {
+        name,
+        selector,
+        attribute: def.attribute.map(|a| a.trim().to_string()),
+        kind: def.kind.unwrap_or_default(),
+        list: def.list,
+        required: def.required,
+        frame: def.frame,
+        fields,
+    })
+}
+
+fn load_schema(path: &Path) -> Result<Vec<Field>, AppError> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        AppError::extraction_failed(&format!("cannot read schema {}: {e}", path.display()))
+    })?;
+    parse_schema(&text).map_err(|e| {
+        AppError::extraction_failed(&format!("invalid schema {}: {e}", path.display()))
+    })
+}
+
+// =============================================================================
+// Extraction plan
+// =============================================================================
+
+#[derive(Serialize)]
+struct Plan<'a> {
+    fields: Vec<PlanField<'a>>,
+    groups: Vec<Vec<usize>>,
+}
+
+#[derive(Serialize)]
+struct PlanField<'a> {
+    name: &'a str,
+    sel: Option<PlanSelector<'a>>,
+    attr: Option<&'a str>,
+    fallback: &'static [&'static str],
+    list: bool,
+    fields: Option<Vec<PlanField<'a>>>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum PlanSelector<'a> {
+    Css { css: &'a str },
+    Ref { group: usize },
+}
+
+/// Attributes read before the text when a field names none.
+fn fallback_attributes(field: &Field) -> &'static [&'static str] {
+    if field.attribute.is_some() || field.fields.is_some() {
+        return &[];
+    }
+    match field.kind {
+        FieldType::Date => &["datetime"],
+        FieldType::Url => &["href", "src"],
+        _ => &[],
+    }
+}
+
+/// Build the plan for `fields`, collecting UID and role selectors into `refs`
+/// so they can be resolved over CDP before the walk.
+fn plan_fields<'a>(fields: &[&'a Field], refs: &mut Vec<&'a Selector>) -> Vec<PlanField<'a>> {
+    fields
+        .iter()
+        .map(|field| {
+            let sel = field.selector.as_ref().map(|selector| match selector {
+                Selector::Css(css) => PlanSelector::Css { css },
+                other => {
+                    let group = refs.iter().position(|r| *r == other).unwrap_or_else(|| {
+                        refs.push(other);
+                        refs.len() - 1
+                    });
+                    PlanSelector::Ref { group }
+                }
+            });
+            let nested: Option<Vec<&Field>> = field.fields.as_ref().map(|f| f.iter().collect());
+            PlanField {
+                name: &field.name,
+                sel,
+                attr: field.attribute.as_deref(),
+                fallback: fallback_attributes(field),
+                list: field.list,
+                fields: nested.map(|nested| plan_fields(&nested, refs)),
+            }
+        })
+        .collect()
+}
+
+/// Group top-level fields by the frame they are read from, keeping schema
+/// order. `auto` fields each search for their own frame.
+fn frame_groups<'a>(
+    fields: &'a [Field],
+    page_frame: Option<&'a str>,
+) -> Vec<(Option<&'a str>, Vec<usize>)> {
+    let mut groups: Vec<(Option<&str>, Vec<usize>)> = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let spec = field.frame.as_deref().or(page_frame);
+        let shared = groups
+            .iter_mut()
+            .find(|(s, _)| *s == spec && spec != Some("auto"));
+        match shared {
+            Some((_, members)) => members.push(i),
+            None => groups.push((spec, vec![i])),
+        }
+    }
+    groups
+}
+
+// =============================================================================
+// CDP
+// =============================================================================
+
+/// Raw values read from one frame, shared by the fields it covered.
+#[derive(Debug, Default)]
+struct FrameOutcome {
+    data: Value,
+    base_url: Option<Url>,
+    ref_errors: HashMap<Selector, String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawExtraction {
+    data: Value,
+    base_url: Option<String>,
+}
+
+async fn resolve_object(
+    session: &ManagedSession,
+    backend_node_id: i64,
+    context_id: Option<i64>,
+) -> Result<String, AppError> {
+    let mut params = serde_json::json!({ "backendNodeId": backend_node_id });
+    if let Some(id) = context_id {
+        params["executionContextId"] = Value::from(id);
+    }
+    let resolved = session
+        .send_command("DOM.resolveNode", Some(params))
+        .await?;
+    resolved["object"]["objectId"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::extraction_failed("DOM.resolveNode returned no object"))
+}
+
+/// Resolve a UID or role selector to remote object ids, in any order.
+async fn resolve_ref(
+    session: &ManagedSession,
+    document_id: &str,
+    selector: &Selector,
+    context_id: Option<i64>,
+) -> Result<Vec<String>, AppError> {
+    let backend_ids = match selector {
+        Selector::Css(_) => Vec::new(),
+        Selector::Uid(uid) => vec![crate::snapshot::resolve_uid(session, uid).await?],
+        Selector::Role { role, name } => {
+            let mut params = serde_json::json!({ "objectId": document_id, "role": role });
+            if let Some(name) = name {
+                params["accessibleName"] = Value::from(name.as_str());
+            }
+            let result = session
+                .send_command("Accessibility.queryAXTree", Some(params))
+                .await?;
+            result["nodes"]
+                .as_array()
+                .map(|nodes| {
+                    nodes
+                        .iter()
+                        .filter(|n| n["ignored"].as_bool() != Some(true))
+                        .filter_map(|n| n["backendDOMNodeId"].as_i64())
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+    };
+    let mut objects = Vec::with_capacity(backend_ids.len());
+    for backend_id in backend_ids {
+        objects.push(resolve_object(session, backend_id, context_id).await?);
+    }
+    Ok(objects)
+}
+
+/// Read the raw values of `fields` from the document of `session`.
+async fn extract_in_frame(
+    session: &mut ManagedSession,
+    context_id: Option<i64>,
+    fields: &[&Field],
+) -> Result<FrameOutcome, AppError> {
+    session.ensure_domain("Runtime").await?;
+    let mut refs = Vec::new();
+    let plan_fields = plan_fields(fields, &mut refs);
+    if !refs.is_empty() {
+        session.ensure_domain("DOM").await?;
+        session.ensure_domain("Accessibility").await?;
+    }
+
+    let mut params = serde_json::json!({ "expression": "document" });
+    if let Some(id) = context_id {
+        params["contextId"] = Value::from(id);
+    }
+    let document = session
+        .send_command("Runtime.evaluate", Some(params))
+        .await?;
+    let document_id = document["result"]["objectId"]
+        .as_str()
+        .ok_or_else(|| AppError::extraction_failed("could not access the document"))?
+        .to_string();
+
+    let mut arguments = Vec::new();
+    let mut groups = Vec::with_capacity(refs.len());
+    let mut ref_errors = HashMap::new();
+    for selector in refs {
+        let mut group = Vec::new();
+        match resolve_ref(session, &document_id, selector, context_id).await {
+            Ok(objects) => {
+                for object_id in objects {
+                    group.push(arguments.len());
+                    arguments.push(serde_json::json!({ "objectId": object_id }));
+                }
+            }
+            Err(e) => {
+                ref_errors.insert(selector.clone(), e.message);
+            }
+        }
+        groups.push(group);
+    }
+    let plan = Plan {
+        fields: plan_fields,
+        groups,
+    };
+    let mut call_arguments = vec![serde_json::json!({ "value": plan })];
+    call_arguments.extend(arguments);
+
+    let result = session
+        .send_command(
+            "Runtime.callFunctionOn",
+            Some(serde_json::json!({
+                "functionDeclaration": EXTRACT_FUNCTION,
+                "objectId": document_id,
+                "arguments": call_arguments,
+                "returnByValue": true,
+            })),
+        )
+        .await?;
+    if let Some(exception) = result.get("exceptionDetails") {
+        let description = exception["exception"]["description"]
+            .as_str()
+            .or_else(|| exception["text"].as_str())
+            .unwrap_or("unknown error");
+        return Err(AppError::extraction_failed(description));
+    }
+    let raw: RawExtraction = result["result"]["value"]
+        .as_str()
+        .and_then(|v| serde_json::from_str(v).ok())
+        .ok_or_else(|| AppError::extraction_failed("page returned an invalid extraction result"))?;
+
+    Ok(FrameOutcome {
+        data: raw.data,
+        base_url: raw.base_url.as_deref().and_then(|u| Url::parse(u).ok()),
+        ref_errors,
+        error: None,
+    })
+}
+
+// =============================================================================
+// Coercion
+// =============================================================================
+
+/// An extracted value, serialized with object fields in schema order.
+#[derive(Debug, Clone, PartialEq)]
+enum Extracted {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Number(f64),
+    Text(String),
+    List(Vec<Extracted>),
+    Object(Vec<(String, Extracted)>),
+}
+
+impl Serialize for Extracted {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_none(),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Int(i) => serializer.serialize_i64(*i),
+            Self::Number(n) => serializer.serialize_f64(*n),
+            Self::Text(s) => serializer.serialize_str(s),
+            Self::List(items) => serializer.collect_seq(items),
+            Self::Object(fields) => serializer.collect_map(fields.iter().map(|(k, v)| (k, v))),
+        }
+    }
+}
+
+/// A field that was missing or could not be converted.
+#[derive(Debug, Serialize)]
+struct FieldError {
+    field: String,
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    required: bool,
+}
+
+/// First number in `value`, with `,` read as a thousands separator.
+fn parse_number(value: &str) -> Option<f64> {
+    let number = NUMBER.find(value)?.as_str().replace(',', "");
+    number.parse().ok()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" | "checked" | "selected" | "enabled" => Some(true),
+        "false" | "no" | "off" | "0" | "unchecked" | "disabled" => Some(false),
+        _ => None,
+    }
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.to_ascii_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(&name))
+        .and_then(|i| u32::try_from(i + 1).ok())
+}
+
+fn ymd(year: &str, month: u32, day: &str) -> Option<String> {
+    let year: u32 = year.parse().ok()?;
+    let day: u32 = day.parse().ok()?;
+    let leap = (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    (1..=days)
+        .contains(&day)
+        .then(|| format!("{year:04}-{month:02}-{day:02}"))
+}
+
+/// Normalize a date to ISO 8601: `YYYY-MM-DD`, or `YYYY-MM-DDTHH:MM:SS`
+/// with any fraction and offset when the value has a time.
+fn parse_date(value: &str) -> Option<String> {
+    if let Some(caps) = ISO_DATE.captures(value) {
+        let date = ymd(&caps[1], caps[2].parse().ok()?, &caps[3])?;
+        let (Some(hour), Some(minute)) = (caps.get(4), caps.get(5)) else {
+            return Some(date);
+        };
+        let second = caps.get(6).map_or("00", |m| m.as_str());
+        let valid = hour.as_str() < "24" && minute.as_str() < "60" && second <= "60";
+        if !valid {
+            return None;
+        }
+        let fraction = caps.get(7).map_or("", |m| m.as_str());
+        let offset = match caps.get(8).map(|m| m.as_str()) {
+            None => String::new(),
+            Some(z) if z.eq_ignore_ascii_case("z") => "Z".to_string(),
+            Some(offset) if offset.len() == 5 => format!("{}:{}", &offset[..3], &offset[3..]),
+            Some(offset) => offset.to_string(),
+        };
+        return Some(format!(
+            "{date}T{}:{}:{second}{fraction}{offset}",
+            hour.as_str(),
+            minute.as_str()
+        ));
+    }
+    if let Some(caps) = SLASH_DATE.captures(value) {
+        return ymd(&caps[1], caps[2].parse().ok()?, &caps[3]);
+    }
+    if let Some(caps) = MONTH_FIRST_DATE.captures(value) {
+        return ymd(&caps[3], month_number(&caps[1])?, &caps[2]);
+    }
+    let caps = DAY_FIRST_DATE.captures(value)?;
+    ymd(&caps[3], month_number(&caps[2])?, &caps[1])
+}
+
+fn resolve_url(value: &str, base: Option<&Url>) -> Result<String, String> {
+    match base {
+        Some(base) => base.join(value),
+        None => Url::parse(value),
+    }
+    .map(String::from)
+    .map_err(|e| format!("invalid URL: {e}"))
+}
+
+/// Convert one raw value to the field's type. A raw `false` means the element
+/// exists but lacks the attribute.
+fn coerce_value(field: &Field, raw: &Value, base_url: Option<&Url>) -> Result<Extracted, String> {
+    let Some(raw) = raw.as_str() else {
+        if field.kind == FieldType::Boolean {
+            return Ok(Extracted::Bool(false));
+        }
+        if field.required {
+            let attribute = field.attribute.as_deref().unwrap_or_default();
+            return Err(format!("element has no '{attribute}' attribute"));
+        }
+        return Ok(Extracted::Null);
+    };
+    let value = if field.attribute.is_some() {
+        raw.trim().to_string()
+    } else {
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    if value.is_empty() {
+        if field.kind == FieldType::Boolean && field.attribute.is_some() {
+            return Ok(Extracted::Bool(true));
+        }
+        if field.required {
+            return Err("empty value".to_string());
+        }
+        return Ok(if field.kind == FieldType::String {
+            Extracted::Text(value)
+        } else {
+            Extracted::Null
+        });
+    }
+
+    match field.kind {
+        FieldType::String => Ok(Extracted::Text(value)),
+        FieldType::Number => parse_number(&value)
+            .map(Extracted::Number)
+            .ok_or_else(|| "expected a number".to_string()),
+        FieldType::Integer => match parse_number(&value) {
+            #[allow(clippy::cast_possible_truncation)]
+            Some(n) if n.fract() == 0.0 && n.abs() < 9.0e15 => Ok(Extracted::Int(n as i64)),
+            _ => Err("expected an integer".to_string()),
+        },
+        FieldType::Boolean => parse_bool(&value)
+            .map(Extracted::Bool)
+            .ok_or_else(|| "expected a boolean".to_string()),
+        FieldType::Date => parse_date(&value)
+            .map(Extracted::Text)
+            .ok_or_else(|| "unrecognized date".to_string()),
+        FieldType::Url => resolve_url(&value, base_url).map(Extracted::Text),
+    }
+}
+
+/// Turns raw values into typed output, recording an error per failed field
+/// path (`reviews[2].rating`).
+struct Coercer<'a> {
+    outcome: &'a FrameOutcome,
+    errors: Vec<FieldError>,
+}
+
+impl Coercer<'_> {
+    fn fail(&mut self, field: &Field, path: &str, error: String, value: Option<&str>) {
+        self.errors.push(FieldError {
+            field: path.to_string(),
+            error,
+            value: value.map(str::to_string),
+            required: field.required,
+        });
+    }
+
+    fn missing(&mut self, field: &Field, path: &str) {
+        let error = match field.selector {
+            Some(ref selector) => match self.outcome.ref_errors.get(selector) {
+                Some(reason) => format!("{selector}: {reason}"),
+                None => format!("no element matches '{selector}'"),
+            },
+            None => "no value".to_string(),
+        };
+        self.fail(field, path, error, None);
+    }
+
+    fn field(&mut self, field: &Field, raw: &Value, path: &str) -> Extracted {
+        if let Some(message) = raw.get("__error").and_then(Value::as_str) {
+            self.fail(field, path, message.to_string(), None);
+            return if field.list {
+                Extracted::List(Vec::new())
+            } else {
+                Extracted::Null
+            };
+        }
+        if !field.list {
+            return self.one(field, raw, path);
+        }
+        let items = raw.as_array().map(Vec::as_slice).unwrap_or_default();
+        if items.is_empty() && field.required {
+            self.missing(field, path);
+        }
+        Extracted::List(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| self.one(field, item, &format!("{path}[{i}]")))
+                .collect(),
+        )
+    }
+
+    fn one(&mut self, field: &Field, raw: &Value, path: &str) -> Extracted {
+        if raw.is_null() {
+            if field.required {
+                self.missing(field, path);
+            }
+            return Extracted::Null;
+        }
+        if let Some(ref fields) = field.fields {
+            return Extracted::Object(
+                fields
+                    .iter()
+                    .map(|f| {
+                        let path = format!("{path}.{}", f.name);
+                        (f.name.clone(), self.field(f, &raw[&f.name], &path))
+                    })
+                    .collect(),
+            );
+        }
+        match coerce_value(field, raw, self.outcome.base_url.as_ref()) {
+            Ok(value) => value,
+            Err(error) => {
+                self.fail(field, path, error, raw.as_str());
+                Extracted::Null
+            }
+        }
+    }
+}
+
+/// Typed output for every top-level field, reading each from the outcome of
+/// the frame group it belongs to (`assignment[i]` indexes `outcomes`).
+fn assemble(
+    fields: &[Field],
+    outcomes: &[FrameOutcome],
+    assignment: &[usize],
+) -> (Extracted, Vec<FieldError>) {
+    let mut errors = Vec::new();
+    let mut data = Vec::with_capacity(fields.len());
+    for (field, &group) in fields.iter().zip(assignment) {
+        let outcome = &outcomes[group];
+        let mut coercer = Coercer {
+            outcome,
+            errors: Vec::new(),
+        };
+        let value = if let Some(ref error) = outcome.error {
+            coercer.fail(field, &field.name, error.clone(), None);
+            if field.list {
+                Extracted::List(Vec::new())
+            } else {
+                Extracted::Null
+            }
+        } else {
+            coercer.field(field, &outcome.data[&field.name], &field.name)
+        };
+        errors.append(&mut coercer.errors);
+        data.push((field.name.clone(), value));
+    }
+    (Extracted::Object(data), errors)
+}
+
+// =============================================================================
+// Command executor
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+struct ExtractResult {
+    url: String,
+    title: String,
+    data: Extracted,
+    errors: Vec<FieldError>,
+}
+
+pub async fn execute_extract(
+    global: &GlobalOpts,
+    args: &PageExtractArgs,
+    frame: Option<&str>,
+) -> Result<(), AppError> {
+    let fields = load_schema(&args.schema)?;
+    if let Some(frame) = frame {
+        agentchrome::frame::parse_frame_arg(frame)?;
+    }
+
+    let (client, mut managed) = setup_session(global).await?;
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+    managed.ensure_domain("Runtime").await?;
+
+    let mut outcomes = Vec::new();
+    let mut assignment = vec![0; fields.len()];
+    for (spec, members) in frame_groups(&fields, frame) {
+        // A frame named on the command line must resolve; one named by a
+        // field only fails the fields that use it.
+        let from_cli = members.iter().all(|&i| fields[i].frame.is_none());
+        let target = (spec == Some("auto"))
+            .then(|| fields[members[0]].selector.as_ref())
+            .flatten()
+            .and_then(Selector::auto_target);
+        let members_fields: Vec<&Field> = members.iter().map(|&i| &fields[i]).collect();
+
+        let outcome = match crate::output::resolve_optional_frame_with_target(
+            &client,
+            &mut managed,
+            spec,
+            target,
+        )
+        .await
+        {
+            Ok(resolved) => {
+                let mut ctx = resolved.map(|r| r.context);
+                let context_id = ctx
+                    .as_ref()
+                    .and_then(agentchrome::frame::execution_context_id);
+                let session = match ctx.as_mut() {
+                    Some(ctx) => agentchrome::frame::frame_session_mut(ctx, &mut managed),
+                    None => &mut managed,
+                };
+                extract_in_frame(session, context_id, &members_fields).await
+            }
+            Err(e) => Err(e),
+        };
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) if from_cli => return Err(e),
+            Err(e) => FrameOutcome {
+                error: Some(format!("frame {}: {}", spec.unwrap_or("0"), e.message)),
+                ..FrameOutcome::default()
+            },
+        };
+        for &i in &members {
+            assignment[i] = outcomes.len();
+        }
+        outcomes.push(outcome);
+    }
+
+    let (data, errors) = assemble(&fields, &outcomes, &assignment);
+    let (url, title) = get_page_info(&managed).await?;
+    let result = ExtractResult {
+        url,
+        title,
+        data,
+        errors,
+    };
+
+    let failed = result.errors.iter().filter(|e| e.required).count();
+    if failed > 0 {
+        let report = serde_json::to_value(&result).unwrap_or_default();
+        return Err(AppError::extraction_invalid(failed, &report));
+    }
+
+    crate::output::emit(&result, &global.output, "page extract", |r| {
+        serde_json::json!({
+            "field_count": fields.len(),
+            "error_count": r.errors.len(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(data: Value, base_url: &str) -> FrameOutcome {
+        FrameOutcome {
+            data,
+            base_url: Url::parse(base_url).ok(),
+            ..FrameOutcome::default()
+        }
+    }
+
+    #[test]
+    fn schema_keeps_field_order_and_accepts_shorthand() {
+        let fields = parse_schema(
+            r#"{
+                "title": "h1",
+                "author": { "selector": "css:.by", "required": true },
+                "save": "s12",
+                "next": { "selector": "role:link[name=\"Next page\"]", "type": "url" },
+                "items": { "selector": "li", "list": true, "fields": { "sku": { "selector": "[data-sku]", "attribute": "data-sku" } } }
+            }"#,
+        )
+        .unwrap();
+        let names: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["title", "author", "save", "next", "items"]);
+        assert_eq!(fields[1].selector, Some(Selector::Css(".by".into())));
+        assert!(fields[1].required);
+        assert_eq!(fields[2].selector, Some(Selector::Uid("s12".into())));
+        assert_eq!(
+            fields[3].selector,
+            Some(Selector::Role {
+                role: "link".into(),
+                name: Some("Next page".into())
+            })
+        );
+        assert_eq!(fields[3].kind, FieldType::Url);
+        let nested = fields[4].fields.as_ref().unwrap();
+        assert_eq!(nested[0].attribute.as_deref(), Some("data-sku"));
+    }
+
+    #[test]
+    fn invalid_schemas_are_rejected_with_the_field_path() {
+        let error = |schema: &str| parse_schema(schema).unwrap_err();
+        assert_eq!(error("{}"), "schema defines no fields");
+        assert!(error(r#"{ "a": { "type": "number" } }"#).contains("a: 'selector' is required"));
+        assert!(error(r#"{ "a": { "selector": "p", "kind": "x" } }"#).contains("unknown field"));
+        assert!(
+            error(r#"{ "a": { "selector": "p", "type": "money" } }"#).contains("unknown variant")
+        );
+        assert!(error(r#"{ "a": "role:link[name=" }"#).contains("invalid role selector"));
+        assert!(
+            error(r#"{ "a": { "selector": "ul", "fields": { "b": { "selector": "li", "frame": "1" } } } }"#)
+                .contains("a.b: 'frame' is only allowed on top-level fields")
+        );
+        assert!(
+            error(r#"{ "a": { "selector": "role:button", "frame": "auto" } }"#)
+                .contains("needs a CSS or UID selector")
+        );
+        assert!(
+            error(r#"{ "a": { "selector": "ul", "type": "number", "fields": { "b": "li" } } }"#)
+                .contains("cannot be combined")
+        );
+    }
+
+    #[test]
+    fn numbers_booleans_and_dates_are_normalized() {
+        assert_eq!(
+            parse_number("Price: $1,299.50").map(|n| n.to_string()),
+            Some("1299.5".into())
+        );
+        assert_eq!(
+            parse_number("-3 items").map(|n| n.to_string()),
+            Some("-3".into())
+        );
+        assert_eq!(parse_number("n/a"), None);
+        assert_eq!(parse_bool("Yes"), Some(true));
+        assert_eq!(parse_bool("maybe"), None);
+        assert_eq!(parse_date("2024-03-05").as_deref(), Some("2024-03-05"));
+        assert_eq!(
+            parse_date("2024-03-05 14:30+0530").as_deref(),
+            Some("2024-03-05T14:30:00+05:30")
+        );
+        assert_eq!(
+            parse_date("2024-03-05T14:30:15.250z").as_deref(),
+            Some("2024-03-05T14:30:15.250Z")
+        );
+        assert_eq!(
+            parse_date("Tue, March 5th, 2024").as_deref(),
+            Some("2024-03-05")
+        );
+        assert_eq!(parse_date("5 Sept. 2024").as_deref(), Some("2024-09-05"));
+        assert_eq!(parse_date("2024/2/29").as_deref(), Some("2024-02-29"));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn raw_values_are_typed_in_schema_order_with_field_paths() {
+        let fields = parse_schema(
+            r#"{
+                "title": "h1",
+                "price": { "selector": ".price", "type": "number" },
+                "link": { "selector": "a", "type": "url" },
+                "sold_out": { "selector": "button", "attribute": "disabled", "type": "boolean" },
+                "reviews": { "selector": ".review", "list": true, "fields": {
+                    "rating": { "selector": ".stars", "type": "integer", "required": true }
+                } }
+            }"#,
+        )
+        .unwrap();
+        let raw = serde_json::json!({
+            "title": "  Blue \n Kettle ",
+            "price": "$24.00",
+            "link": "../kettles/blue",
+            "sold_out": false,
+            "reviews": [{ "rating": "4" }, { "rating": "great" }, { "rating": null }],
+        });
+        let outcomes = [outcome(raw, "https://shop.example/p/42/")];
+        let (data, errors) = assemble(&fields, &outcomes, &[0; 5]);
+
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"title":"Blue Kettle","price":24.0,"link":"https://shop.example/p/kettles/blue","sold_out":false,"reviews":[{"rating":4},{"rating":null},{"rating":null}]}"#
+        );
+        let report: Vec<(&str, &str, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.field.as_str(), e.error.as_str(), e.value.as_deref()))
+            .collect();
+        assert_eq!(
+            report,
+            [
+                ("reviews[1].rating", "expected an integer", Some("great")),
+                ("reviews[2].rating", "no element matches '.stars'", None),
+            ]
+        );
+        assert!(errors.iter().all(|e| e.required));
+    }
+
+    #[test]
+    fn optional_misses_are_silent_and_failed_refs_and_frames_explain_themselves() {
+        let fields = parse_schema(
+            r##"{
+                "subtitle": "h2",
+                "save": { "selector": "s9", "required": true },
+                "ad": { "selector": "#ad", "frame": "2" }
+            }"##,
+        )
+        .unwrap();
+        let mut main = outcome(
+            serde_json::json!({ "subtitle": null, "save": null }),
+            "https://a.example/",
+        );
+        main.ref_errors
+            .insert(Selector::Uid("s9".into()), "stale UID".into());
+        let failed = FrameOutcome {
+            error: Some("frame 2: Frame 2 not found".into()),
+            ..FrameOutcome::default()
+        };
+        let (data, errors) = assemble(&fields, &[main, failed], &[0, 0, 1]);
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            serde_json::json!({ "subtitle": null, "save": null, "ad": null })
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error, "s9: stale UID");
+        assert!(errors[0].required);
+        assert_eq!(errors[1].field, "ad");
+        assert!(!errors[1].required);
+    }
+
+    #[test]
+    fn fields_are_grouped_by_frame_with_auto_fields_kept_apart() {
+        let fields = parse_schema(
+            r##"{
+                "a": "h1",
+                "b": { "selector": "#x", "frame": "1" },
+                "c": "p",
+                "d": { "selector": "#y", "frame": "auto" },
+                "e": { "selector": "#z", "frame": "auto" },
+                "f": { "selector": "#w", "frame": "1" }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(
+            frame_groups(&fields, None),
+            [
+                (None, vec![0, 2]),
+                (Some("1"), vec![1, 5]),
+                (Some("auto"), vec![3]),
+                (Some("auto"), vec![4]),
+            ]
+        );
+        assert_eq!(
+            frame_groups(&fields[..1], Some("1")),
+            [(Some("1"), vec![0])]
+        );
+    }
+
+    #[test]
+    fn plan_shares_one_group_per_ref_selector() {
+        let fields = parse_schema(
+            r#"{
+                "a": "role:button",
+                "b": { "selector": "ul", "fields": { "c": "role:button", "d": "s3", "e": { "selector": "time", "type": "date" } } }
+            }"#,
+        )
+        .unwrap();
+        let top: Vec<&Field> = fields.iter().collect();
+        let mut refs = Vec::new();
+        let plan = serde_json::to_value(plan_fields(&top, &mut refs)).unwrap();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(plan[0]["sel"], serde_json::json!({ "group": 0 }));
+        assert_eq!(plan[1]["sel"], serde_json::json!({ "css": "ul" }));
+        assert_eq!(
+            plan[1]["fields"][0]["sel"],
+            serde_json::json!({ "group": 0 })
+        );
+        assert_eq!(
+            plan[1]["fields"][1]["sel"],
+            serde_json::json!({ "group": 1 })
+        );
+        assert_eq!(
+            plan[1]["fields"][2]["fallback"],
+            serde_json::json!(["datetime"])
+        );
+    }
+}
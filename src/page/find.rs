@@ -25,11 +25,11 @@ struct FindMatch {
 
 /// Pixel-based bounding box of an element.
 #[derive(Debug, Clone, Serialize)]
-struct BoundingBox {
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
+pub(super) struct BoundingBox {
+    pub(super) x: f64,
+    pub(super) y: f64,
+    pub(super) width: f64,
+    pub(super) height: f64,
 }
 
 // =============================================================================
@@ -65,7 +65,7 @@ fn summary_of_find(matches: &[FindMatch]) -> serde_json::Value {
 /// Resolve bounding box for a DOM node by its `backendDOMNodeId`.
 ///
 /// Returns `None` if the element is invisible or has been removed from the DOM.
-async fn resolve_bounding_box(
+pub(super) async fn resolve_bounding_box(
     managed: &ManagedSession,
     backend_dom_node_id: i64,
 ) -> Option<BoundingBox> {
@@ -232,7 +232,11 @@ async fn capture_snapshot(
     let nodes = ax_result["nodes"]
         .as_array()
         .ok_or_else(|| AppError::snapshot_failed("response missing 'nodes' array"))?;
-    let build = crate::snapshot::build_tree(nodes, false);
+    let previous = crate::snapshot::read_snapshot_state().ok().flatten();
+    let document = crate::snapshot::document_id(managed).await;
+    let uids =
+        crate::snapshot::UidAssigner::carried_from(previous.as_ref(), None, document.as_deref());
+    let build = crate::snapshot::build_tree_with_uids(nodes, false, uids);
 
     let (url, _title) = get_page_info(managed).await?;
     let state = crate::snapshot::SnapshotState {
@@ -244,6 +248,10 @@ async fn capture_snapshot(
         aggregate: false,
         frame_uid_ranges: Vec::new(),
         frame_ids: Vec::new(),
+        nodes: build.nodes.clone(),
+        stale_uids: std::collections::HashMap::new(),
+        last_uid: 0,
+        document,
     };
     if let Err(e) = crate::snapshot::write_snapshot_state(&state) {
         eprintln!("warning: could not save snapshot state: {e}");
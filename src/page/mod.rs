@@ -1,10 +1,16 @@
 pub(crate) mod analyze;
+mod annotate;
+mod archive;
 mod coords;
 mod element;
+mod extract;
 mod find;
 mod hittest;
+mod pdf;
+pub(crate) mod record;
 mod screenshot;
 mod snapshot;
+mod tables;
 mod text;
 pub(crate) mod wait;
 
@@ -111,6 +117,15 @@ pub async fn execute_page(global: &GlobalOpts, args: &PageArgs) -> Result<(), Ap
         PageCommand::Coords(coords_args) => {
             coords::execute_coords(global, coords_args, frame).await
         }
+        PageCommand::Pdf(pdf_args) => pdf::execute_pdf(global, pdf_args).await,
+        PageCommand::Archive(archive_args) => archive::execute_archive(global, archive_args).await,
+        PageCommand::Record(record_args) => record::execute_record(global, record_args).await,
+        PageCommand::Tables(tables_args) => {
+            tables::execute_tables(global, tables_args, frame).await
+        }
+        PageCommand::Extract(extract_args) => {
+            extract::execute_extract(global, extract_args, frame).await
+        }
     }
 }
 
@@ -0,0 +1,342 @@
+//! `page pdf` subcommand — print the page to PDF via `Page.printToPDF`.
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::AppError;
+
+use crate::cli::{GlobalOpts, PagePdfArgs, PaperSize};
+
+use super::{print_output, setup_session};
+
+/// Bytes requested per `IO.read` call while streaming the document.
+const READ_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Template used for the half of the header/footer pair that was not given,
+/// so Chrome does not fall back to its default date/title/url banner.
+const EMPTY_TEMPLATE: &str = "<span></span>";
+
+const MM_PER_INCH: f64 = 25.4;
+const CM_PER_INCH: f64 = 2.54;
+const PX_PER_INCH: f64 = 96.0;
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+struct PdfResult {
+    file: String,
+    size_bytes: u64,
+    paper_width: f64,
+    paper_height: f64,
+    landscape: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_ranges: Option<String>,
+}
+
+// =============================================================================
+// Option parsing
+// =============================================================================
+
+/// Paper dimensions in inches (width, height) for a named size, portrait.
+fn paper_dimensions(paper: PaperSize) -> (f64, f64) {
+    match paper {
+        PaperSize::Letter => (8.5, 11.0),
+        PaperSize::Legal => (8.5, 14.0),
+        PaperSize::Tabloid => (11.0, 17.0),
+        PaperSize::Ledger => (17.0, 11.0),
+        PaperSize::A0 => (841.0 / MM_PER_INCH, 1189.0 / MM_PER_INCH),
+        PaperSize::A1 => (594.0 / MM_PER_INCH, 841.0 / MM_PER_INCH),
+        PaperSize::A2 => (420.0 / MM_PER_INCH, 594.0 / MM_PER_INCH),
+        PaperSize::A3 => (297.0 / MM_PER_INCH, 420.0 / MM_PER_INCH),
+        PaperSize::A4 => (210.0 / MM_PER_INCH, 297.0 / MM_PER_INCH),
+        PaperSize::A5 => (148.0 / MM_PER_INCH, 210.0 / MM_PER_INCH),
+        PaperSize::A6 => (105.0 / MM_PER_INCH, 148.0 / MM_PER_INCH),
+    }
+}
+
+/// Parse a single length such as `1in`, `2.5cm`, `10mm`, `48px`, or `0.5`
+/// (inches) into inches.
+fn parse_length(input: &str) -> Result<f64, String> {
+    let trimmed = input.trim();
+    let (number, per_inch) = if let Some(n) = trimmed.strip_suffix("in") {
+        (n, 1.0)
+    } else if let Some(n) = trimmed.strip_suffix("cm") {
+        (n, CM_PER_INCH)
+    } else if let Some(n) = trimmed.strip_suffix("mm") {
+        (n, MM_PER_INCH)
+    } else if let Some(n) = trimmed.strip_suffix("px") {
+        (n, PX_PER_INCH)
+    } else {
+        (trimmed, 1.0)
+    };
+    let value: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid length '{trimmed}'"))?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(format!("length must be a non-negative number: '{trimmed}'"));
+    }
+    Ok(value / per_inch)
+}
+
+/// Parse a CSS-style margin shorthand (1-4 comma-separated lengths) into
+/// `[top, right, bottom, left]` inches.
+fn parse_margins(input: &str) -> Result<[f64; 4], String> {
+    let values = input
+        .split(',')
+        .map(parse_length)
+        .collect::<Result<Vec<_>, _>>()?;
+    match values.as_slice() {
+        [all] => Ok([*all; 4]),
+        [vertical, horizontal] => Ok([*vertical, *horizontal, *vertical, *horizontal]),
+        [top, horizontal, bottom] => Ok([*top, *horizontal, *bottom, *horizontal]),
+        [top, right, bottom, left] => Ok([*top, *right, *bottom, *left]),
+        _ => Err(format!(
+            "expected 1 to 4 comma-separated values, got {}",
+            values.len()
+        )),
+    }
+}
+
+/// Build the `Page.printToPDF` parameters from the command arguments.
+fn build_print_params(args: &PagePdfArgs) -> Result<serde_json::Value, AppError> {
+    let (default_width, default_height) = paper_dimensions(args.paper);
+    let paper_width = args.paper_width.unwrap_or(default_width);
+    let paper_height = args.paper_height.unwrap_or(default_height);
+    if !(paper_width > 0.0 && paper_height > 0.0) {
+        return Err(AppError::pdf_failed(
+            "--paper-width and --paper-height must be positive",
+        ));
+    }
+    if let Some(scale) = args.scale
+        && !(0.1..=2.0).contains(&scale)
+    {
+        return Err(AppError::pdf_failed(&format!(
+            "--scale must be between 0.1 and 2.0, got {scale}"
+        )));
+    }
+
+    let mut params = serde_json::json!({
+        "paperWidth": paper_width,
+        "paperHeight": paper_height,
+        "landscape": args.landscape,
+        "printBackground": args.print_background,
+        "preferCSSPageSize": args.prefer_css_page_size,
+        "transferMode": "ReturnAsStream",
+    });
+
+    if let Some(ref margin) = args.margin {
+        let [top, right, bottom, left] = parse_margins(margin)
+            .map_err(|e| AppError::pdf_failed(&format!("invalid --margin: {e}")))?;
+        params["marginTop"] = top.into();
+        params["marginRight"] = right.into();
+        params["marginBottom"] = bottom.into();
+        params["marginLeft"] = left.into();
+    }
+    if let Some(ref ranges) = args.page_ranges {
+        params["pageRanges"] = ranges.as_str().into();
+    }
+    if args.header_template.is_some() || args.footer_template.is_some() {
+        params["displayHeaderFooter"] = true.into();
+        params["headerTemplate"] = args
+            .header_template
+            .as_deref()
+            .unwrap_or(EMPTY_TEMPLATE)
+            .into();
+        params["footerTemplate"] = args
+            .footer_template
+            .as_deref()
+            .unwrap_or(EMPTY_TEMPLATE)
+            .into();
+    }
+    if let Some(scale) = args.scale {
+        params["scale"] = scale.into();
+    }
+    Ok(params)
+}
+
+// =============================================================================
+// Streaming
+// =============================================================================
+
+/// Read the `IO` stream `handle` to completion, writing it to `path`.
+/// Returns the number of bytes written.
+async fn stream_pdf_to_file(
+    managed: &ManagedSession,
+    handle: &str,
+    path: &Path,
+) -> Result<u64, AppError> {
+    use base64::Engine;
+
+    let write_failed = |e: &dyn std::fmt::Display| {
+        AppError::pdf_failed(&format!("Failed to write {}: {e}", path.display()))
+    };
+    let file = fs::File::create(path).map_err(|e| write_failed(&e))?;
+    let mut writer = BufWriter::new(file);
+    let mut written: u64 = 0;
+
+    loop {
+        let chunk = managed
+            .send_command(
+                "IO.read",
+                Some(serde_json::json!({ "handle": handle, "size": READ_CHUNK_SIZE })),
+            )
+            .await
+            .map_err(|e| AppError::pdf_failed(&format!("Failed to read PDF stream: {e}")))?;
+
+        let data = chunk["data"].as_str().unwrap_or_default();
+        let bytes = if chunk["base64Encoded"].as_bool().unwrap_or(false) {
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| AppError::pdf_failed(&format!("Failed to decode PDF data: {e}")))?
+        } else {
+            data.as_bytes().to_vec()
+        };
+        writer.write_all(&bytes).map_err(|e| write_failed(&e))?;
+        written += bytes.len() as u64;
+
+        if chunk["eof"].as_bool().unwrap_or(true) {
+            break;
+        }
+    }
+
+    writer.flush().map_err(|e| write_failed(&e))?;
+    Ok(written)
+}
+
+// =============================================================================
+// Executor
+// =============================================================================
+
+pub async fn execute_pdf(global: &GlobalOpts, args: &PagePdfArgs) -> Result<(), AppError> {
+    let params = build_print_params(args)?;
+
+    let (_client, mut managed) = setup_session(global).await?;
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+    managed.ensure_domain("Page").await?;
+
+    let result = managed
+        .send_command("Page.printToPDF", Some(params))
+        .await
+        .map_err(|e| AppError::pdf_failed(&e.to_string()))?;
+    let handle = result["stream"]
+        .as_str()
+        .ok_or_else(|| AppError::pdf_failed("Chrome did not return a PDF stream"))?
+        .to_string();
+
+    let streamed = stream_pdf_to_file(&managed, &handle, &args.file).await;
+    let _ = managed
+        .send_command("IO.close", Some(serde_json::json!({ "handle": handle })))
+        .await;
+    let size_bytes = streamed?;
+
+    let (default_width, default_height) = paper_dimensions(args.paper);
+    let output = PdfResult {
+        file: args.file.display().to_string(),
+        size_bytes,
+        paper_width: args.paper_width.unwrap_or(default_width),
+        paper_height: args.paper_height.unwrap_or(default_height),
+        landscape: args.landscape,
+        page_ranges: args.page_ranges.clone(),
+    };
+    print_output(&output, &global.output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> PagePdfArgs {
+        PagePdfArgs {
+            file: "out.pdf".into(),
+            paper: PaperSize::Letter,
+            paper_width: None,
+            paper_height: None,
+            margin: None,
+            landscape: false,
+            page_ranges: None,
+            header_template: None,
+            footer_template: None,
+            print_background: false,
+            prefer_css_page_size: false,
+            scale: None,
+        }
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn lengths_convert_to_inches() {
+        assert!(approx(parse_length("1in").unwrap(), 1.0));
+        assert!(approx(parse_length("0.5").unwrap(), 0.5));
+        assert!(approx(parse_length("2.54cm").unwrap(), 1.0));
+        assert!(approx(parse_length("25.4mm").unwrap(), 1.0));
+        assert!(approx(parse_length(" 48px ").unwrap(), 0.5));
+        assert!(parse_length("1pt").is_err());
+        assert!(parse_length("-1in").is_err());
+    }
+
+    #[test]
+    fn margins_follow_css_shorthand() {
+        let expand = |input: &str| parse_margins(input).unwrap().map(|v| v.to_string());
+        assert_eq!(expand("1"), ["1", "1", "1", "1"]);
+        assert_eq!(expand("1,2"), ["1", "2", "1", "2"]);
+        assert_eq!(expand("1,2,3"), ["1", "2", "3", "2"]);
+        assert_eq!(expand("1in,2,3,96px"), ["1", "2", "3", "1"]);
+        assert!(parse_margins("1,2,3,4,5").is_err());
+        assert!(parse_margins("").is_err());
+    }
+
+    #[test]
+    fn a4_dimensions_are_in_inches() {
+        let (width, height) = paper_dimensions(PaperSize::A4);
+        assert!(approx(width, 210.0 / 25.4));
+        assert!(approx(height, 297.0 / 25.4));
+    }
+
+    #[test]
+    fn params_request_a_stream_with_defaults() {
+        let params = build_print_params(&args()).unwrap();
+        assert_eq!(params["transferMode"], "ReturnAsStream");
+        assert_eq!(params["paperWidth"], 8.5);
+        assert_eq!(params["paperHeight"], 11.0);
+        assert!(params.get("displayHeaderFooter").is_none());
+        assert!(params.get("marginTop").is_none());
+    }
+
+    #[test]
+    fn either_template_enables_header_and_footer() {
+        let mut a = args();
+        a.footer_template = Some("<span class='pageNumber'></span>".into());
+        let params = build_print_params(&a).unwrap();
+        assert_eq!(params["displayHeaderFooter"], true);
+        assert_eq!(params["headerTemplate"], EMPTY_TEMPLATE);
+        assert_eq!(params["footerTemplate"], "<span class='pageNumber'></span>");
+    }
+
+    #[test]
+    fn custom_size_overrides_paper_and_scale_is_validated() {
+        let mut a = args();
+        a.paper = PaperSize::A4;
+        a.paper_width = Some(4.0);
+        let params = build_print_params(&a).unwrap();
+        assert_eq!(params["paperWidth"], 4.0);
+        assert!(approx(
+            params["paperHeight"].as_f64().unwrap(),
+            297.0 / 25.4
+        ));
+
+        a.scale = Some(3.0);
+        assert!(build_print_params(&a).is_err());
+    }
+}
@@ -0,0 +1,840 @@
+//! `page record` subcommands — screencast recording via `Page.startScreencast`.
+//!
+//! `start` spawns this binary again as `page record start --foreground`, which
+//! attaches to the tab, acknowledges every `Page.screencastFrame`, and saves
+//! the frames it keeps to the recording directory alongside `frames.jsonl`.
+//! While the recording state file exists, `interact` and `form` commands,
+//! including `script run` steps, append a line to `markers.jsonl` (see
+//! [`CommandMarker`]). `stop` signals the
+//! recorder, then assembles `recording.png` (APNG) and `timeline.json`.
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::session::{self, SessionError};
+
+use crate::cli::{GlobalOpts, PageRecordArgs, PageRecordCommand, PageRecordStartArgs};
+use crate::image_diff::RgbaImage;
+
+use super::{print_output, setup_session};
+
+const FRAMES_FILE: &str = "frames.jsonl";
+const MARKERS_FILE: &str = "markers.jsonl";
+const ANIMATION_FILE: &str = "recording.png";
+const TIMELINE_FILE: &str = "timeline.json";
+const RECORDER_LOG_FILE: &str = "recorder.log";
+
+/// How long the last frame of the animation stays on screen.
+const LAST_FRAME_DELAY_MS: u16 = 1000;
+
+/// Shortest frame delay written to the animation; viewers clamp smaller ones.
+const MIN_FRAME_DELAY_MS: u16 = 10;
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// =============================================================================
+// Recording state
+// =============================================================================
+
+/// The session's active recording: `recording.json` in the session directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordingState {
+    pid: u32,
+    dir: PathBuf,
+    started_at: String,
+    /// Start time in seconds since the Unix epoch, the clock frame and marker
+    /// timestamps use.
+    started_timestamp: f64,
+}
+
+fn recording_state_path() -> Result<PathBuf, SessionError> {
+    Ok(session::session_file_path()?.with_file_name("recording.json"))
+}
+
+fn read_recording_state() -> Result<Option<RecordingState>, SessionError> {
+    let path = recording_state_path()?;
+    match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| SessionError::InvalidFormat(format!("{} at {}", e, path.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(SessionError::Io(e)),
+    }
+}
+
+fn write_recording_state(state: &RecordingState) -> Result<(), SessionError> {
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+    session::write_state_file(&recording_state_path()?, json.as_bytes())
+}
+
+fn delete_recording_state() -> Result<(), SessionError> {
+    match fs::remove_file(recording_state_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(SessionError::Io(e)),
+        _ => Ok(()),
+    }
+}
+
+fn recorder_alive(pid: u32) -> bool {
+    !matches!(
+        agentchrome::chrome::is_process_alive(pid),
+        agentchrome::chrome::ProbeResult::Dead
+    )
+}
+
+fn unix_now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64())
+}
+
+fn record_error(message: String) -> AppError {
+    AppError {
+        message,
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    }
+}
+
+// =============================================================================
+// Timeline types
+// =============================================================================
+
+/// One saved frame, as appended to `frames.jsonl` by the recorder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FrameEntry {
+    index: u32,
+    file: String,
+    timestamp: f64,
+}
+
+/// One `interact`/`form` command, as appended to `markers.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MarkerEntry {
+    timestamp: f64,
+    duration_ms: u64,
+    command: String,
+    args: Vec<String>,
+    ok: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct TimelineFrame {
+    #[serde(flatten)]
+    frame: FrameEntry,
+    offset_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct TimelineMarker {
+    #[serde(flatten)]
+    marker: MarkerEntry,
+    offset_ms: u64,
+    /// Last frame captured at or before the command started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame_index: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct Timeline {
+    started_at: String,
+    stopped_at: String,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animation: Option<&'static str>,
+    frames: Vec<TimelineFrame>,
+    markers: Vec<TimelineMarker>,
+}
+
+#[derive(Debug, Serialize)]
+struct StartResult {
+    recording: bool,
+    dir: String,
+    pid: u32,
+    started_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct StopResult {
+    dir: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animation: Option<String>,
+    timeline: String,
+    frame_count: usize,
+    marker_count: usize,
+    duration_ms: u64,
+}
+
+fn offset_ms(start: f64, timestamp: f64) -> u64 {
+    // Offsets are non-negative and far below u64::MAX milliseconds.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let ms = ((timestamp - start) * 1000.0).round().max(0.0) as u64;
+    ms
+}
+
+/// Read a JSON Lines file, skipping lines that do not parse (e.g. a line cut
+/// short when the recorder was killed). A missing file is empty.
+fn read_jsonl<T: DeserializeOwned>(path: &Path) -> Vec<T> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn append_jsonl<T: Serialize>(path: &Path, entry: &T) -> std::io::Result<()> {
+    let mut line = serde_json::to_vec(entry).map_err(std::io::Error::other)?;
+    line.push(b'\n');
+    let mut options = fs::OpenOptions::new();
+    options.create(true).append(true);
+    // Markers carry command arguments; keep them owner-only.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(&line)
+}
+
+// =============================================================================
+// Command markers
+// =============================================================================
+
+/// A marker for an `interact`/`form` command run while a recording is active.
+pub(crate) struct CommandMarker {
+    path: PathBuf,
+    timestamp: f64,
+    started: std::time::Instant,
+    command: String,
+    args: Vec<String>,
+}
+
+impl CommandMarker {
+    /// Begin a marker if a recording is running; `None` otherwise.
+    pub(crate) fn start(command: String, args: Vec<String>) -> Option<Self> {
+        let state = read_recording_state().ok().flatten()?;
+        if !recorder_alive(state.pid) {
+            return None;
+        }
+        Some(Self {
+            path: state.dir.join(MARKERS_FILE),
+            timestamp: unix_now(),
+            started: std::time::Instant::now(),
+            command,
+            args,
+        })
+    }
+
+    /// Append the marker with the command's outcome. Best effort.
+    pub(crate) fn finish(self, ok: bool) {
+        let entry = MarkerEntry {
+            timestamp: self.timestamp,
+            duration_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            command: self.command,
+            args: self.args,
+            ok,
+        };
+        if let Err(e) = append_jsonl(&self.path, &entry) {
+            eprintln!(
+                "warning: could not record marker in {}: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+// =============================================================================
+// Recorder
+// =============================================================================
+
+/// Whether a frame at `timestamp` should be kept under `max_fps`, given the
+/// timestamp of the last kept frame.
+fn keep_frame(last_kept: Option<f64>, timestamp: f64, max_fps: Option<u32>) -> bool {
+    match (last_kept, max_fps) {
+        (Some(last), Some(fps)) => timestamp - last >= 1.0 / f64::from(fps),
+        _ => true,
+    }
+}
+
+/// Resolves when the recorder is asked to stop (Ctrl+C or SIGTERM).
+async fn stop_requested() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut term) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = term.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Create the recording directory and remove what an earlier recording left
+/// in it, so frames and markers of the two runs never mix.
+fn prepare_dir(dir: &Path) -> Result<PathBuf, AppError> {
+    let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+    fs::create_dir_all(&dir).map_err(|e| {
+        record_error(format!(
+            "Failed to create recording directory {}: {e}",
+            dir.display()
+        ))
+    })?;
+    clear_recording(&dir).map_err(|e| {
+        record_error(format!(
+            "Failed to clear previous recording in {}: {e}",
+            dir.display()
+        ))
+    })?;
+    Ok(dir)
+}
+
+/// Delete the files a recording writes to `dir`; other files are left alone.
+fn clear_recording(dir: &Path) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_frame = name.starts_with("frame-") && name.ends_with(".jpg");
+        if is_frame || [FRAMES_FILE, MARKERS_FILE, ANIMATION_FILE, TIMELINE_FILE].contains(&&*name)
+        {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Run the recorder in this process until interrupted.
+async fn run_recorder(global: &GlobalOpts, args: &PageRecordStartArgs) -> Result<(), AppError> {
+    use base64::Engine;
+
+    let dir = prepare_dir(&args.dir)?;
+
+    let (_client, mut managed) = setup_session(global).await?;
+    managed.ensure_domain("Page").await?;
+    let mut frames_rx = managed
+        .subscribe("Page.screencastFrame")
+        .await
+        .map_err(|e| record_error(format!("Failed to subscribe to screencast frames: {e}")))?;
+    managed
+        .send_command(
+            "Page.startScreencast",
+            Some(serde_json::json!({ "format": "jpeg", "quality": args.quality })),
+        )
+        .await
+        .map_err(|e| record_error(format!("Failed to start screencast: {e}")))?;
+
+    // The state file doubles as the readiness signal for `page record start`.
+    write_recording_state(&RecordingState {
+        pid: std::process::id(),
+        dir: dir.clone(),
+        started_at: session::now_iso8601(),
+        started_timestamp: unix_now(),
+    })?;
+
+    let frames_path = dir.join(FRAMES_FILE);
+    let mut index: u32 = 0;
+    let mut last_kept: Option<f64> = None;
+    let stop = stop_requested();
+    tokio::pin!(stop);
+
+    loop {
+        let event = tokio::select! {
+            event = frames_rx.recv() => event,
+            () = &mut stop => break,
+        };
+        let Some(event) = event else { break };
+
+        // Chrome sends the next frame only after the previous one is acked.
+        let _ = managed
+            .send_command(
+                "Page.screencastFrameAck",
+                Some(serde_json::json!({ "sessionId": event.params["sessionId"] })),
+            )
+            .await;
+
+        let timestamp = event.params["metadata"]["timestamp"]
+            .as_f64()
+            .unwrap_or_else(unix_now);
+        if !keep_frame(last_kept, timestamp, args.max_fps) {
+            continue;
+        }
+        let Ok(bytes) = base64::engine::general_purpose::STANDARD
+            .decode(event.params["data"].as_str().unwrap_or_default())
+        else {
+            continue;
+        };
+
+        let file = format!("frame-{index:05}.jpg");
+        let saved = fs::write(dir.join(&file), &bytes).and_then(|()| {
+            append_jsonl(
+                &frames_path,
+                &FrameEntry {
+                    index,
+                    file,
+                    timestamp,
+                },
+            )
+        });
+        if let Err(e) = saved {
+            eprintln!("warning: could not save frame {index}: {e}");
+            continue;
+        }
+        last_kept = Some(timestamp);
+        index += 1;
+    }
+
+    let _ = managed.send_command("Page.stopScreencast", None).await;
+    Ok(())
+}
+
+/// Re-run this binary as `page record start --foreground` in a new session,
+/// pinned to the current tab, and wait until it has written the state file.
+async fn spawn_recorder(
+    global: &GlobalOpts,
+    args: &PageRecordStartArgs,
+) -> Result<RecordingState, AppError> {
+    use std::process::Stdio;
+
+    let dir = prepare_dir(&args.dir)?;
+
+    let conn = crate::output::connect_from_global(global).await?;
+    let target = agentchrome::connection::resolve_target(
+        &conn.resolved.host,
+        conn.resolved.port,
+        global.tab.as_deref(),
+        global.page_id.as_deref(),
+    )
+    .await?;
+
+    let spawn_failed =
+        |e: &dyn std::fmt::Display| record_error(format!("Failed to start recorder: {e}"));
+    let exe = std::env::current_exe().map_err(|e| spawn_failed(&e))?;
+    let log_path = dir.join(RECORDER_LOG_FILE);
+    let log = fs::File::create(&log_path).map_err(|e| spawn_failed(&e))?;
+
+    // Forward only the connection flags the user gave: otherwise the recorder
+    // resolves the browser from the session file exactly as this process did,
+    // including sessions reachable only through the daemon or a pipe.
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(["--host", &global.host]);
+    if let Some(port) = global.port {
+        cmd.args(["--port", &port.to_string()]);
+    }
+    if let Some(ws_url) = &global.ws_url {
+        cmd.args(["--ws-url", ws_url]);
+    }
+    cmd.args(["--page-id", &target.id])
+        .args(["--session", session::session_name()])
+        .args(["page", "record", "start", "--foreground", "--dir"])
+        .arg(&dir)
+        .args(["--quality", &args.quality.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::from(log));
+    if let Some(fps) = args.max_fps {
+        cmd.args(["--max-fps", &fps.to_string()]);
+    }
+    if let Some(config) = &global.config {
+        cmd.arg("--config").arg(config);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: `pre_exec` runs in the child after fork and before exec.
+        // The closure only calls `setsid`, an async-signal-safe libc function,
+        // and returns an OS error if it fails.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    let mut child = cmd.spawn().map_err(|e| spawn_failed(&e))?;
+
+    let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        if let Ok(Some(state)) = read_recording_state()
+            && state.pid == child.id()
+        {
+            return Ok(state);
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            let detail = fs::read_to_string(&log_path).unwrap_or_default();
+            return Err(spawn_failed(&format!(
+                "recorder exited with {status}: {}",
+                detail.trim()
+            )));
+        }
+        if tokio::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            return Err(AppError {
+                message: format!(
+                    "Recorder did not start within {}s (log: {})",
+                    STARTUP_TIMEOUT.as_secs(),
+                    log_path.display()
+                ),
+                code: ExitCode::TimeoutError,
+                custom_json: None,
+            });
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+// =============================================================================
+// Assembly
+// =============================================================================
+
+/// Decode a JPEG frame into RGBA.
+fn decode_jpeg(bytes: &[u8]) -> Result<RgbaImage, String> {
+    let mut decoder = jpeg_decoder::Decoder::new(bytes);
+    let pixels = decoder.decode().map_err(|e| e.to_string())?;
+    let info = decoder.info().ok_or("missing JPEG header")?;
+    let data = match info.pixel_format {
+        jpeg_decoder::PixelFormat::L8 => pixels.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        jpeg_decoder::PixelFormat::L16 => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let k = u16::from(p[3]);
+                let channel = |c: u8| u8::try_from(u16::from(c) * k / 255).unwrap_or(255);
+                [channel(p[0]), channel(p[1]), channel(p[2]), 255]
+            })
+            .collect(),
+    };
+    Ok(RgbaImage {
+        width: u32::from(info.width),
+        height: u32::from(info.height),
+        data,
+    })
+}
+
+/// Copy `image` onto a `width` x `height` transparent canvas, cropping or
+/// padding at the right and bottom, so frames from a resized viewport still
+/// fit the animation.
+fn fit_to_canvas(image: &RgbaImage, width: u32, height: u32) -> Vec<u8> {
+    if image.width == width && image.height == height {
+        return image.data.clone();
+    }
+    let mut canvas = vec![0u8; width as usize * height as usize * 4];
+    let copy_width = image.width.min(width) as usize * 4;
+    for row in 0..image.height.min(height) as usize {
+        let src = row * image.width as usize * 4;
+        let dst = row * width as usize * 4;
+        canvas[dst..dst + copy_width].copy_from_slice(&image.data[src..src + copy_width]);
+    }
+    canvas
+}
+
+/// Display time of frame `i` in milliseconds: the gap to the next frame.
+fn frame_delay_ms(frames: &[FrameEntry], i: usize) -> u16 {
+    let Some(next) = frames.get(i + 1) else {
+        return LAST_FRAME_DELAY_MS;
+    };
+    let gap = offset_ms(frames[i].timestamp, next.timestamp);
+    u16::try_from(gap)
+        .unwrap_or(u16::MAX)
+        .max(MIN_FRAME_DELAY_MS)
+}
+
+/// Encode the saved frames as an animated PNG at `path`, sized to the first frame.
+fn write_animation(dir: &Path, frames: &[FrameEntry], path: &Path) -> Result<(), String> {
+    let load = |frame: &FrameEntry| {
+        fs::read(dir.join(&frame.file))
+            .map_err(|e| format!("{}: {e}", frame.file))
+            .and_then(|bytes| decode_jpeg(&bytes).map_err(|e| format!("{}: {e}", frame.file)))
+    };
+    let first = load(&frames[0])?;
+    let (width, height) = (first.width, first.height);
+
+    let file = fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(u32::try_from(frames.len()).unwrap_or(u32::MAX), 0)
+        .map_err(|e| e.to_string())?;
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+
+    let mut current = Some(first);
+    for i in 0..frames.len() {
+        let image = match current.take() {
+            Some(image) => image,
+            None => load(&frames[i])?,
+        };
+        writer
+            .set_frame_delay(frame_delay_ms(frames, i), 1000)
+            .map_err(|e| e.to_string())?;
+        writer
+            .write_image_data(&fit_to_canvas(&image, width, height))
+            .map_err(|e| e.to_string())?;
+    }
+    writer.finish().map_err(|e| e.to_string())
+}
+
+/// Build the timeline from the recorder's frames and the command markers.
+fn build_timeline(
+    state: &RecordingState,
+    frames: Vec<FrameEntry>,
+    markers: Vec<MarkerEntry>,
+    stopped_timestamp: f64,
+) -> Timeline {
+    let start = state.started_timestamp;
+    let markers = markers
+        .into_iter()
+        .map(|marker| TimelineMarker {
+            offset_ms: offset_ms(start, marker.timestamp),
+            frame_index: frames
+                .iter()
+                .take_while(|f| f.timestamp <= marker.timestamp)
+                .last()
+                .map(|f| f.index),
+            marker,
+        })
+        .collect();
+    let frames = frames
+        .into_iter()
+        .map(|frame| TimelineFrame {
+            offset_ms: offset_ms(start, frame.timestamp),
+            frame,
+        })
+        .collect();
+    Timeline {
+        started_at: state.started_at.clone(),
+        stopped_at: session::now_iso8601(),
+        duration_ms: offset_ms(start, stopped_timestamp),
+        animation: None,
+        frames,
+        markers,
+    }
+}
+
+// =============================================================================
+// Executors
+// =============================================================================
+
+pub async fn execute_record(global: &GlobalOpts, args: &PageRecordArgs) -> Result<(), AppError> {
+    match &args.command {
+        PageRecordCommand::Start(start_args) => execute_start(global, start_args).await,
+        PageRecordCommand::Stop => execute_stop(global).await,
+    }
+}
+
+async fn execute_start(global: &GlobalOpts, args: &PageRecordStartArgs) -> Result<(), AppError> {
+    if let Some(state) = read_recording_state()?
+        && recorder_alive(state.pid)
+    {
+        return Err(record_error(format!(
+            "A recording is already running into {} (pid {}). Run 'agentchrome page record \
+             stop' first.",
+            state.dir.display(),
+            state.pid
+        )));
+    }
+
+    if args.foreground {
+        return run_recorder(global, args).await;
+    }
+
+    let state = spawn_recorder(global, args).await?;
+    let output = StartResult {
+        recording: true,
+        dir: state.dir.display().to_string(),
+        pid: state.pid,
+        started_at: state.started_at,
+    };
+    print_output(&output, &global.output)
+}
+
+async fn execute_stop(global: &GlobalOpts) -> Result<(), AppError> {
+    let state = read_recording_state()?.ok_or_else(|| {
+        record_error(
+            "No active recording. Run 'agentchrome page record start --dir <DIR>' first."
+                .to_string(),
+        )
+    })?;
+
+    if recorder_alive(state.pid) {
+        crate::daemon_cli::terminate(state.pid);
+        if !crate::daemon_cli::wait_for_exit(state.pid).await {
+            eprintln!(
+                "warning: recorder (pid {}) did not exit; assembling the frames saved so far",
+                state.pid
+            );
+        }
+    }
+    let stopped_timestamp = unix_now();
+
+    let dir = &state.dir;
+    let frames: Vec<FrameEntry> = read_jsonl(&dir.join(FRAMES_FILE));
+    let markers: Vec<MarkerEntry> = read_jsonl(&dir.join(MARKERS_FILE));
+
+    let animation_path = dir.join(ANIMATION_FILE);
+    let animation = if frames.is_empty() {
+        None
+    } else {
+        match write_animation(dir, &frames, &animation_path) {
+            Ok(()) => Some(ANIMATION_FILE),
+            Err(e) => {
+                eprintln!("warning: could not assemble {ANIMATION_FILE}: {e}");
+                None
+            }
+        }
+    };
+
+    let mut timeline = build_timeline(&state, frames, markers, stopped_timestamp);
+    timeline.animation = animation;
+    let timeline_path = dir.join(TIMELINE_FILE);
+    let json = serde_json::to_vec_pretty(&timeline)
+        .map_err(|e| record_error(format!("serialization error: {e}")))?;
+    session::write_private_file(&timeline_path, &json).map_err(|e| {
+        record_error(format!(
+            "Failed to write timeline {}: {e}",
+            timeline_path.display()
+        ))
+    })?;
+    delete_recording_state()?;
+
+    let output = StopResult {
+        dir: dir.display().to_string(),
+        animation: animation.map(|_| animation_path.display().to_string()),
+        timeline: timeline_path.display().to_string(),
+        frame_count: timeline.frames.len(),
+        marker_count: timeline.markers.len(),
+        duration_ms: timeline.duration_ms,
+    };
+    print_output(&output, &global.output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(index: u32, timestamp: f64) -> FrameEntry {
+        FrameEntry {
+            index,
+            file: format!("frame-{index:05}.jpg"),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn reusing_a_directory_clears_the_previous_recording() {
+        let dir = std::env::temp_dir().join(format!("agentchrome-rerecord-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            FRAMES_FILE,
+            MARKERS_FILE,
+            ANIMATION_FILE,
+            TIMELINE_FILE,
+            "frame-00000.jpg",
+            "frame-00001.jpg",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(file), b"old").unwrap();
+        }
+
+        let prepared = prepare_dir(&dir).unwrap();
+        let mut left: Vec<String> = fs::read_dir(&prepared)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["notes.txt"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn max_fps_drops_frames_closer_than_the_interval() {
+        assert!(keep_frame(None, 10.0, Some(2)));
+        assert!(!keep_frame(Some(10.0), 10.4, Some(2)));
+        assert!(keep_frame(Some(10.0), 10.5, Some(2)));
+        assert!(keep_frame(Some(10.0), 10.01, None));
+    }
+
+    #[test]
+    fn frame_delays_follow_timestamps() {
+        let frames = [frame(0, 100.0), frame(1, 100.25), frame(2, 100.251)];
+        assert_eq!(frame_delay_ms(&frames, 0), 250);
+        assert_eq!(frame_delay_ms(&frames, 1), MIN_FRAME_DELAY_MS);
+        assert_eq!(frame_delay_ms(&frames, 2), LAST_FRAME_DELAY_MS);
+    }
+
+    #[test]
+    fn smaller_frames_are_padded_onto_the_canvas() {
+        let image = RgbaImage {
+            width: 1,
+            height: 1,
+            data: vec![1, 2, 3, 255],
+        };
+        let canvas = fit_to_canvas(&image, 2, 2);
+        assert_eq!(canvas.len(), 16);
+        assert_eq!(&canvas[..4], &[1, 2, 3, 255]);
+        assert!(canvas[4..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn markers_point_at_the_last_frame_before_them() {
+        let state = RecordingState {
+            pid: 1,
+            dir: PathBuf::from("frames"),
+            started_at: "2026-01-01T00:00:00Z".into(),
+            started_timestamp: 100.0,
+        };
+        let marker = |timestamp: f64| MarkerEntry {
+            timestamp,
+            duration_ms: 5,
+            command: "interact click".into(),
+            args: vec!["interact".into(), "click".into(), "s1".into()],
+            ok: true,
+        };
+        let timeline = build_timeline(
+            &state,
+            vec![frame(0, 100.1), frame(1, 101.0)],
+            vec![marker(100.05), marker(100.5)],
+            102.0,
+        );
+        assert_eq!(timeline.duration_ms, 2000);
+        assert_eq!(timeline.frames[1].offset_ms, 1000);
+        assert_eq!(timeline.markers[0].frame_index, None);
+        assert_eq!(timeline.markers[1].frame_index, Some(0));
+        assert_eq!(timeline.markers[1].offset_ms, 500);
+    }
+
+    #[test]
+    fn jsonl_skips_truncated_lines() {
+        let path =
+            std::env::temp_dir().join(format!("agentchrome-frames-{}.jsonl", std::process::id()));
+        fs::write(
+            &path,
+            "{\"index\":0,\"file\":\"frame-00000.jpg\",\"timestamp\":1.0}\n{\"index\":1,\"fi",
+        )
+        .unwrap();
+        let frames: Vec<FrameEntry> = read_jsonl(&path);
+        assert_eq!(frames.len(), 1);
+        let _ = fs::remove_file(&path);
+    }
+}
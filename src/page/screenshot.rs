@@ -5,6 +5,8 @@ use agentchrome::error::{AppError, ExitCode};
 
 use crate::cli::{GlobalOpts, PageScreenshotArgs, ScreenshotFormat};
 
+use super::annotate::{Annotations, inject_annotations, remove_annotations};
+use super::find::BoundingBox;
 use super::{get_viewport_dimensions, print_output, setup_session};
 
 // =============================================================================
@@ -18,6 +20,8 @@ struct ScreenshotResult {
     data: String,
     width: u32,
     height: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotations: Option<Annotations>,
 }
 
 /// Output when screenshot is saved to a file (--file).
@@ -27,6 +31,28 @@ struct ScreenshotFileResult {
     file: String,
     width: u32,
     height: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotations: Option<Annotations>,
+}
+
+/// Output when the capture is compared against a baseline (--compare).
+#[derive(Serialize)]
+struct ScreenshotCompareResult {
+    baseline: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff_file: Option<String>,
+    width: u32,
+    height: u32,
+    baseline_width: u32,
+    baseline_height: u32,
+    mismatched_pixels: u64,
+    anti_aliased_pixels: u64,
+    total_pixels: u64,
+    mismatch_ratio: f64,
+    threshold: f64,
+    passed: bool,
 }
 
 // =============================================================================
@@ -42,6 +68,17 @@ struct ClipRegion {
     height: f64,
 }
 
+impl ClipRegion {
+    fn to_bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
 /// Parse a "X,Y,WIDTH,HEIGHT" string into a [`ClipRegion`].
 fn parse_clip(input: &str) -> Result<ClipRegion, AppError> {
     let parts: Vec<&str> = input.split(',').collect();
@@ -133,10 +170,7 @@ async fn resolve_uid_clip(managed: &mut ManagedSession, uid: &str) -> Result<Cli
             custom_json: None,
         })?;
 
-    let backend_node_id = state
-        .uid_map
-        .get(uid)
-        .ok_or_else(|| AppError::uid_not_found(uid))?;
+    let backend_node_id = crate::snapshot::lookup_uid(&state, uid)?;
 
     // Pass backendNodeId directly to DOM.getBoxModel instead of resolving via
     // describeNode first — the intermediate nodeId was not anchored in the document
@@ -395,6 +429,105 @@ fn validate_scroll_container(args: &PageScreenshotArgs) -> Result<(), AppError>
     Ok(())
 }
 
+/// Validate `--compare` flag combinations.
+fn validate_compare(args: &PageScreenshotArgs) -> Result<(), AppError> {
+    if args.compare.is_none() {
+        return Ok(());
+    }
+    if !matches!(args.format, ScreenshotFormat::Png) {
+        return Err(AppError::screenshot_failed(
+            "--compare requires --format png",
+        ));
+    }
+    if let Some(threshold) = args.threshold
+        && !(0.0..=1.0).contains(&threshold)
+    {
+        return Err(AppError::screenshot_failed(
+            "--threshold must be between 0.0 and 1.0",
+        ));
+    }
+    Ok(())
+}
+
+/// Compare a captured PNG (base64) against the `--compare` baseline, writing
+/// `--file` and `--diff-out` when given. A mismatch ratio above `--threshold`
+/// is returned as a `screenshot_mismatch` error.
+fn compare_with_baseline(
+    args: &PageScreenshotArgs,
+    baseline: &std::path::Path,
+    data: &str,
+) -> Result<ScreenshotCompareResult, AppError> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| AppError::screenshot_failed(&format!("Failed to decode image data: {e}")))?;
+    if let Some(ref file_path) = args.file {
+        std::fs::write(file_path, &bytes).map_err(|e| {
+            AppError::screenshot_failed(&format!(
+                "Failed to write screenshot to file: {}: {e}",
+                file_path.display()
+            ))
+        })?;
+    }
+
+    let baseline_bytes = std::fs::read(baseline).map_err(|e| {
+        AppError::screenshot_failed(&format!(
+            "Failed to read baseline {}: {e}",
+            baseline.display()
+        ))
+    })?;
+    let expected = crate::image_diff::decode_png(&baseline_bytes).map_err(|e| {
+        AppError::screenshot_failed(&format!(
+            "Baseline {} is not a readable PNG: {e}",
+            baseline.display()
+        ))
+    })?;
+    let actual = crate::image_diff::decode_png(&bytes)
+        .map_err(|e| AppError::screenshot_failed(&format!("Failed to decode captured PNG: {e}")))?;
+    let comparison = crate::image_diff::compare(&expected, &actual);
+
+    if let Some(ref diff_path) = args.diff_out {
+        let encoded = crate::image_diff::encode_png(&comparison.diff).map_err(|e| {
+            AppError::screenshot_failed(&format!("Failed to encode diff image: {e}"))
+        })?;
+        std::fs::write(diff_path, encoded).map_err(|e| {
+            AppError::screenshot_failed(&format!(
+                "Failed to write diff image: {}: {e}",
+                diff_path.display()
+            ))
+        })?;
+    }
+
+    let threshold = args.threshold.unwrap_or(0.0);
+    let mismatch_ratio = comparison.mismatch_ratio();
+    let result = ScreenshotCompareResult {
+        baseline: baseline.display().to_string(),
+        file: args.file.as_ref().map(|p| p.display().to_string()),
+        diff_file: args.diff_out.as_ref().map(|p| p.display().to_string()),
+        width: actual.width,
+        height: actual.height,
+        baseline_width: expected.width,
+        baseline_height: expected.height,
+        mismatched_pixels: comparison.mismatched_pixels,
+        anti_aliased_pixels: comparison.anti_aliased_pixels,
+        total_pixels: comparison.total_pixels,
+        mismatch_ratio,
+        threshold,
+        passed: mismatch_ratio <= threshold,
+    };
+    if !result.passed {
+        let report = serde_json::to_value(&result).unwrap_or_default();
+        return Err(AppError::screenshot_mismatch(
+            &result.baseline,
+            mismatch_ratio,
+            threshold,
+            &report,
+        ));
+    }
+    Ok(result)
+}
+
 // =============================================================================
 // Command executor
 // =============================================================================
@@ -407,6 +540,7 @@ pub async fn execute_screenshot(
 ) -> Result<(), AppError> {
     // Validate --scroll-container flag combinations (checked first for specific error messages)
     validate_scroll_container(args)?;
+    validate_compare(args)?;
 
     // Validate mutual exclusion: --full-page vs --selector/--uid
     if args.full_page && (args.selector.is_some() || args.uid.is_some()) {
@@ -437,7 +571,7 @@ pub async fn execute_screenshot(
         };
         eff_mut.ensure_domain("Page").await?;
         eff_mut.ensure_domain("Runtime").await?;
-        if args.selector.is_some() || args.scroll_container.is_some() {
+        if args.selector.is_some() || args.scroll_container.is_some() || args.annotate.is_some() {
             eff_mut.ensure_domain("DOM").await?;
         }
     }
@@ -528,11 +662,21 @@ pub async fn execute_screenshot(
     } else {
         &managed
     };
+    let annotations = if let Some(mode) = args.annotate {
+        let region = clip.as_ref().map(ClipRegion::to_bounding_box);
+        Some(inject_annotations(effective, mode, region).await?)
+    } else {
+        None
+    };
     let result = effective
         .send_command("Page.captureScreenshot", Some(params))
         .await
         .map_err(|e| AppError::screenshot_failed(&e.to_string()));
 
+    if annotations.is_some() {
+        remove_annotations(effective).await;
+    }
+
     // Restore container styles if we overrode them (before checking the capture result)
     if let Some(ref token) = saved_styles_token {
         let _ = restore_container_styles(effective, token).await;
@@ -559,6 +703,11 @@ pub async fn execute_screenshot(
         );
     }
 
+    if let Some(ref baseline) = args.compare {
+        let output = compare_with_baseline(args, baseline, data)?;
+        return print_output(&output, &global.output);
+    }
+
     if let Some(ref file_path) = args.file {
         use base64::Engine;
         let bytes = base64::engine::general_purpose::STANDARD
@@ -578,6 +727,7 @@ pub async fn execute_screenshot(
             file: file_path.display().to_string(),
             width,
             height,
+            annotations,
         };
         print_output(&output, &global.output)
     } else {
@@ -586,6 +736,7 @@ pub async fn execute_screenshot(
             data: data.to_string(),
             width,
             height,
+            annotations,
         };
         print_output(&output, &global.output)
     }
@@ -610,6 +761,7 @@ pub async fn compute_screenshot(
     args: &PageScreenshotArgs,
 ) -> Result<serde_json::Value, AppError> {
     validate_scroll_container(args)?;
+    validate_compare(args)?;
     if args.full_page && (args.selector.is_some() || args.uid.is_some()) {
         return Err(AppError::screenshot_failed(
             "Cannot combine --full-page with --selector or --uid",
@@ -618,7 +770,7 @@ pub async fn compute_screenshot(
 
     managed.ensure_domain("Page").await?;
     managed.ensure_domain("Runtime").await?;
-    if args.selector.is_some() || args.scroll_container.is_some() {
+    if args.selector.is_some() || args.scroll_container.is_some() || args.annotate.is_some() {
         managed.ensure_domain("DOM").await?;
     }
 
@@ -684,11 +836,21 @@ pub async fn compute_screenshot(
         params["captureBeyondViewport"] = serde_json::json!(true);
     }
 
+    let annotations = if let Some(mode) = args.annotate {
+        let region = clip.as_ref().map(ClipRegion::to_bounding_box);
+        Some(inject_annotations(managed, mode, region).await?)
+    } else {
+        None
+    };
     let result = managed
         .send_command("Page.captureScreenshot", Some(params))
         .await
         .map_err(|e| AppError::screenshot_failed(&e.to_string()));
 
+    if annotations.is_some() {
+        remove_annotations(managed).await;
+    }
+
     if let Some(ref token) = saved_styles_token {
         let _ = restore_container_styles(managed, token).await;
     }
@@ -709,7 +871,9 @@ pub async fn compute_screenshot(
         );
     }
 
-    let value = if let Some(ref file_path) = args.file {
+    let value = if let Some(ref baseline) = args.compare {
+        serde_json::to_value(compare_with_baseline(args, baseline, data)?)
+    } else if let Some(ref file_path) = args.file {
         use base64::Engine;
         let bytes = base64::engine::general_purpose::STANDARD
             .decode(data)
@@ -727,6 +891,7 @@ pub async fn compute_screenshot(
             file: file_path.display().to_string(),
             width,
             height,
+            annotations,
         })
     } else {
         serde_json::to_value(ScreenshotResult {
@@ -734,6 +899,7 @@ pub async fn compute_screenshot(
             data: data.to_string(),
             width,
             height,
+            annotations,
         })
     }
     .map_err(|e| AppError {
@@ -764,6 +930,7 @@ mod tests {
             data: "iVBORw0KGgo=".to_string(),
             width: 1280,
             height: 720,
+            annotations: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["format"], "png");
@@ -780,6 +947,7 @@ mod tests {
             file: "/tmp/screenshot.jpg".to_string(),
             width: 800,
             height: 600,
+            annotations: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["format"], "jpeg");
@@ -796,6 +964,7 @@ mod tests {
             data: "UklGR...".to_string(),
             width: 640,
             height: 480,
+            annotations: None,
         };
         let json: serde_json::Value = serde_json::to_value(&result).unwrap();
         assert_eq!(json["format"], "webp");
@@ -912,6 +1081,10 @@ mod tests {
             quality: None,
             file: None,
             clip: None,
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         let err = validate_scroll_container(&args).unwrap_err();
         assert!(
@@ -931,6 +1104,10 @@ mod tests {
             quality: None,
             file: None,
             clip: None,
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         let err = validate_scroll_container(&args).unwrap_err();
         assert!(err.message.contains("Cannot combine --scroll-container"));
@@ -947,6 +1124,10 @@ mod tests {
             quality: None,
             file: None,
             clip: None,
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         let err = validate_scroll_container(&args).unwrap_err();
         assert!(err.message.contains("Cannot combine --scroll-container"));
@@ -963,6 +1144,10 @@ mod tests {
             quality: None,
             file: None,
             clip: Some("0,0,100,100".to_string()),
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         let err = validate_scroll_container(&args).unwrap_err();
         assert!(err.message.contains("Cannot combine --scroll-container"));
@@ -979,10 +1164,42 @@ mod tests {
             quality: None,
             file: None,
             clip: None,
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         assert!(validate_scroll_container(&args).is_ok());
     }
 
+    #[test]
+    fn validate_compare_requires_png_and_ratio_threshold() {
+        let mut args = PageScreenshotArgs {
+            full_page: false,
+            selector: None,
+            uid: None,
+            scroll_container: None,
+            format: ScreenshotFormat::Jpeg,
+            quality: None,
+            file: None,
+            clip: None,
+            compare: Some("baseline.png".into()),
+            threshold: None,
+            diff_out: None,
+            annotate: None,
+        };
+        let err = validate_compare(&args).unwrap_err();
+        assert!(err.message.contains("--compare requires --format png"));
+
+        args.format = ScreenshotFormat::Png;
+        args.threshold = Some(1.5);
+        let err = validate_compare(&args).unwrap_err();
+        assert!(err.message.contains("--threshold must be between"));
+
+        args.threshold = Some(0.1);
+        assert!(validate_compare(&args).is_ok());
+    }
+
     #[test]
     fn validate_scroll_container_none_is_ok() {
         let args = PageScreenshotArgs {
@@ -994,6 +1211,10 @@ mod tests {
             quality: None,
             file: None,
             clip: None,
+            compare: None,
+            threshold: None,
+            diff_out: None,
+            annotate: None,
         };
         assert!(validate_scroll_container(&args).is_ok());
     }
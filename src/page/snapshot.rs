@@ -2,6 +2,7 @@ use agentchrome::connection::ManagedSession;
 use agentchrome::error::{AppError, ExitCode};
 
 use crate::cli::{GlobalOpts, PageSnapshotArgs};
+use crate::output::print_output;
 
 use super::{get_page_info, setup_session};
 
@@ -256,8 +257,24 @@ pub async fn execute_snapshot(
         .as_array()
         .ok_or_else(|| AppError::snapshot_failed("response missing 'nodes' array"))?;
 
-    // Build tree and assign UIDs
-    let mut build = crate::snapshot::build_tree(nodes, args.verbose);
+    // Frame the snapshot covers (None = main frame)
+    let snap_frame_id = match &frame_ctx {
+        Some(
+            agentchrome::frame::FrameContext::SameOrigin { frame_id, .. }
+            | agentchrome::frame::FrameContext::OutOfProcess { frame_id, .. },
+        ) => Some(frame_id.clone()),
+        _ => None,
+    };
+
+    // Build tree, keeping the UIDs of elements seen by the previous snapshot
+    let previous = crate::snapshot::read_snapshot_state().ok().flatten();
+    let document = crate::snapshot::document_id(&managed).await;
+    let uids = crate::snapshot::UidAssigner::carried_from(
+        previous.as_ref(),
+        snap_frame_id.as_deref(),
+        document.as_deref(),
+    );
+    let mut build = crate::snapshot::build_tree_with_uids(nodes, args.verbose, uids);
 
     // Supplemental shadow DOM pass: find interactive elements inside open shadow
     // roots that the AX tree may have missed.
@@ -270,7 +287,7 @@ pub async fn execute_snapshot(
 
         // Collect known backendDOMNodeIds from the main AX pass.
         let known_ids: std::collections::HashSet<i64> = build.uid_map.values().copied().collect();
-        let next_uid = build.uid_map.len();
+        let next_uid = build.last_uid;
 
         let supplemental = shadow_dom_supplemental_pass(effective, &known_ids, next_uid).await;
 
@@ -281,25 +298,14 @@ pub async fn execute_snapshot(
     // Get page URL for snapshot state (always from main frame)
     let (url, _title) = get_page_info(&managed).await?;
 
-    // Determine frame metadata for snapshot state
-    let (snap_frame_index, snap_frame_id) = match &frame_ctx {
-        Some(agentchrome::frame::FrameContext::SameOrigin { frame_id, .. }) => {
-            let fid_clone = frame_id.clone();
-            let frames_result = agentchrome::frame::list_frames(&mut managed).await;
-            let idx = frames_result
-                .ok()
-                .and_then(|frames| frames.iter().find(|f| f.id == fid_clone).map(|f| f.index));
-            (idx, Some(fid_clone))
-        }
-        Some(agentchrome::frame::FrameContext::OutOfProcess { frame_id, .. }) => {
-            let fid_clone = frame_id.clone();
-            let frames_result = agentchrome::frame::list_frames(&mut managed).await;
-            let idx = frames_result
-                .ok()
-                .and_then(|frames| frames.iter().find(|f| f.id == fid_clone).map(|f| f.index));
-            (idx, Some(fid_clone))
-        }
-        _ => (None, None),
+    // Determine frame index for snapshot state
+    let snap_frame_index = if let Some(ref fid_clone) = snap_frame_id {
+        let frames_result = agentchrome::frame::list_frames(&mut managed).await;
+        frames_result
+            .ok()
+            .and_then(|frames| frames.iter().find(|f| &f.id == fid_clone).map(|f| f.index))
+    } else {
+        None
     };
 
     // Persist UID mapping
@@ -312,11 +318,22 @@ pub async fn execute_snapshot(
         aggregate: false,
         frame_uid_ranges: Vec::new(),
         frame_ids: Vec::new(),
+        nodes: build.nodes,
+        stale_uids: std::collections::HashMap::new(),
+        last_uid: 0,
+        document,
     };
     if let Err(e) = crate::snapshot::write_snapshot_state(&state) {
         eprintln!("warning: could not save snapshot state: {e}");
     }
 
+    if args.diff {
+        return emit_snapshot_diff(global, args, previous.as_ref(), &state);
+    }
+    if args.assert.is_some() || args.update_golden.is_some() {
+        return run_golden(global, args, &state.url, &build.root);
+    }
+
     // Apply compact filtering if requested
     let root = if args.compact {
         crate::snapshot::compact_tree(&build.root)
@@ -393,6 +410,116 @@ pub async fn execute_snapshot(
     })
 }
 
+/// Emit `page snapshot --diff` output: the nodes that changed between the
+/// previous snapshot state and the one just persisted.
+fn emit_snapshot_diff(
+    global: &GlobalOpts,
+    args: &PageSnapshotArgs,
+    previous: Option<&crate::snapshot::SnapshotState>,
+    state: &crate::snapshot::SnapshotState,
+) -> Result<(), AppError> {
+    let (diff, json_value) = crate::snapshot::diff_against_state(previous, state);
+
+    let text_output = !global.output.json && !global.output.pretty;
+    if let Some(ref file_path) = args.file {
+        let formatted = if text_output {
+            crate::snapshot::format_diff_text(&diff)
+        } else if global.output.pretty {
+            serde_json::to_string_pretty(&json_value).unwrap_or_default()
+        } else {
+            json_value.to_string()
+        };
+        return std::fs::write(file_path, formatted).map_err(|e| {
+            AppError::file_write_failed(&file_path.display().to_string(), &e.to_string())
+        });
+    }
+    if text_output {
+        return crate::output::emit_plain(
+            &crate::snapshot::format_diff_text(&diff),
+            &global.output,
+        );
+    }
+    crate::output::emit(&json_value, &global.output, "page snapshot", |v| {
+        let count = |field: &str| v[field].as_array().map_or(0, Vec::len);
+        serde_json::json!({
+            "added": count("added"),
+            "removed": count("removed"),
+            "changed": count("changed"),
+            "unchanged": v["unchanged"],
+        })
+    })
+}
+
+/// Handle `page snapshot --update-golden` and `--assert`: record or compare the
+/// compacted tree. A failed assertion is returned as a `snapshot_mismatch` error.
+fn run_golden(
+    global: &GlobalOpts,
+    args: &PageSnapshotArgs,
+    url: &str,
+    root: &crate::snapshot::SnapshotNode,
+) -> Result<(), AppError> {
+    use crate::golden::{CompareOptions, GoldenFile, GoldenNode};
+
+    let tree = GoldenNode::from(&crate::snapshot::compact_tree(root));
+    let text_output = !global.output.json && !global.output.pretty;
+
+    if let Some(ref path) = args.update_golden {
+        let nodes = tree.count();
+        crate::golden::write_golden(
+            path,
+            &GoldenFile {
+                url: url.to_string(),
+                tree,
+            },
+        )?;
+        let golden = path.display().to_string();
+        if text_output {
+            return crate::output::emit_plain(
+                &format!("Updated golden {golden} ({nodes} nodes)\n"),
+                &global.output,
+            );
+        }
+        return print_output(
+            &serde_json::json!({ "golden": golden, "updated": true, "url": url, "nodes": nodes }),
+            &global.output,
+        );
+    }
+
+    let Some(ref path) = args.assert else {
+        return Ok(());
+    };
+    let opts = CompareOptions::new(&args.ignore_name, args.ignore_order)?;
+    let golden_file = crate::golden::read_golden(path)?;
+    let comparison = crate::golden::compare(&golden_file.tree, &tree, &opts);
+    let golden = path.display().to_string();
+    if !comparison.mismatches.is_empty() {
+        let mismatches = serde_json::to_value(&comparison.mismatches).unwrap_or_default();
+        return Err(AppError::snapshot_mismatch(
+            &golden,
+            comparison.compared_nodes,
+            &mismatches,
+        ));
+    }
+    if text_output {
+        return crate::output::emit_plain(
+            &format!(
+                "Snapshot matches golden {golden} ({} nodes compared)\n",
+                comparison.compared_nodes
+            ),
+            &global.output,
+        );
+    }
+    print_output(
+        &serde_json::json!({
+            "golden": golden,
+            "passed": true,
+            "compared_nodes": comparison.compared_nodes,
+            "mismatches": [],
+        }),
+        &global.output,
+    )
+}
+
 // =============================================================================
 // Script runner compute function
 // =============================================================================
@@ -408,7 +535,23 @@ pub async fn compute_snapshot(
     managed: &mut agentchrome::connection::ManagedSession,
     _args: &PageSnapshotArgs,
 ) -> Result<serde_json::Value, AppError> {
-    // Delegate to a minimal implementation using the provided session.
+    let root = capture_tree(managed).await?;
+    serde_json::to_value(&root).map_err(|e| AppError {
+        message: format!("serialization error: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+/// Capture the main frame's accessibility tree with default options and
+/// persist its UID mapping, as a plain `page snapshot` would.
+///
+/// # Errors
+///
+/// Returns `AppError` on snapshot failure.
+pub(super) async fn capture_tree(
+    managed: &mut agentchrome::connection::ManagedSession,
+) -> Result<crate::snapshot::SnapshotNode, AppError> {
     managed.ensure_domain("Accessibility").await?;
     managed.ensure_domain("Runtime").await?;
 
@@ -421,29 +564,41 @@ pub async fn compute_snapshot(
         .as_array()
         .ok_or_else(|| AppError::snapshot_failed("response missing 'nodes' array"))?;
 
-    let build = crate::snapshot::build_tree(nodes, false);
+    let previous = crate::snapshot::read_snapshot_state().ok().flatten();
+    let document = crate::snapshot::document_id(managed).await;
+    let uids =
+        crate::snapshot::UidAssigner::carried_from(previous.as_ref(), None, document.as_deref());
+    let build = crate::snapshot::build_tree_with_uids(nodes, false, uids);
 
     // Persist UID mapping
     let (url, _) = super::get_page_info(managed).await?;
     let state = crate::snapshot::SnapshotState {
         url,
         timestamp: agentchrome::session::now_iso8601(),
-        uid_map: build.uid_map.clone(),
+        uid_map: build.uid_map,
         frame_index: None,
         frame_id: None,
         aggregate: false,
         frame_uid_ranges: Vec::new(),
         frame_ids: Vec::new(),
+        nodes: build.nodes,
+        stale_uids: std::collections::HashMap::new(),
+        last_uid: 0,
+        document,
     };
     if let Err(e) = crate::snapshot::write_snapshot_state(&state) {
         eprintln!("warning: could not save snapshot state: {e}");
     }
 
-    serde_json::to_value(&build.root).map_err(|e| AppError {
-        message: format!("serialization error: {e}"),
-        code: ExitCode::GeneralError,
-        custom_json: None,
-    })
+    Ok(build.root)
+}
+
+/// Highest UID number among `uids`, or `floor` if none is higher.
+fn highest_uid(uids: &std::collections::HashMap<String, i64>, floor: usize) -> usize {
+    uids.keys()
+        .filter_map(|uid| crate::snapshot::parse_uid_number(uid))
+        .filter_map(|n| usize::try_from(n).ok())
+        .fold(floor, usize::max)
 }
 
 /// Aggregate snapshot: build main-frame tree and splice every enumerable
@@ -473,27 +628,35 @@ async fn execute_aggregate_snapshot(
     let main_nodes = main_ax["nodes"]
         .as_array()
         .ok_or_else(|| AppError::snapshot_failed("response missing 'nodes' array"))?;
-    let mut main_build = crate::snapshot::build_tree(main_nodes, args.verbose);
+
+    // Every frame keeps the UIDs the previous snapshot gave its elements;
+    // new elements in any frame are numbered above one shared high-water mark.
+    let previous = crate::snapshot::read_snapshot_state().ok().flatten();
+    let document = crate::snapshot::document_id(&managed).await;
+    let uids =
+        crate::snapshot::UidAssigner::carried_from(previous.as_ref(), None, document.as_deref());
+    let mut main_build = crate::snapshot::build_tree_with_uids(main_nodes, args.verbose, uids);
+    let mut last_uid = main_build.last_uid;
 
     // Optional shadow-DOM pass on the main frame.
     if args.pierce_shadow {
         let known: std::collections::HashSet<i64> = main_build.uid_map.values().copied().collect();
-        let next_uid = main_build.uid_map.len();
-        let supplemental = shadow_dom_supplemental_pass(&managed, &known, next_uid).await;
+        let supplemental = shadow_dom_supplemental_pass(&managed, &known, last_uid).await;
+        last_uid = highest_uid(&supplemental, last_uid);
         main_build.uid_map.extend(supplemental);
     }
 
     let mut merged_root = main_build.root;
     let mut merged_uid_map = main_build.uid_map;
+    let mut merged_nodes = main_build.nodes;
     let mut frame_uid_ranges: Vec<(u32, (u32, u32))> = Vec::new();
     let mut frame_ids: Vec<(u32, String)> = Vec::new();
-    // Record main-frame range.
-    if !merged_uid_map.is_empty() {
-        frame_uid_ranges.push((
-            0,
-            (1, u32::try_from(merged_uid_map.len()).unwrap_or(u32::MAX)),
-        ));
-    }
+    // Record main-frame ranges.
+    frame_uid_ranges.extend(
+        crate::snapshot::uid_ranges(&merged_uid_map)
+            .into_iter()
+            .map(|range| (0, range)),
+    );
     if let Some(main_frame) = frames.first() {
         frame_ids.push((0, main_frame.id.clone()));
     }
@@ -565,30 +728,41 @@ async fn execute_aggregate_snapshot(
             continue;
         };
 
-        let uid_offset = merged_uid_map.len();
+        let uids = crate::snapshot::UidAssigner::carried_from(
+            previous.as_ref(),
+            Some(&frame_info.id),
+            document.as_deref(),
+        )
+        .continuing_from(last_uid);
         let mut frame_build =
-            crate::snapshot::build_tree_with_uid_offset(frame_nodes, args.verbose, uid_offset);
+            crate::snapshot::build_tree_with_uids(frame_nodes, args.verbose, uids);
+        last_uid = frame_build.last_uid;
 
         // Optional shadow-DOM pass within this frame.
         if args.pierce_shadow {
             let known: std::collections::HashSet<i64> =
                 frame_build.uid_map.values().copied().collect();
-            let next_uid = uid_offset + frame_build.uid_map.len();
             let shadow_session = frame_session_for_shadow.as_ref().unwrap_or(&managed);
-            let supplemental = shadow_dom_supplemental_pass(shadow_session, &known, next_uid).await;
+            let supplemental = shadow_dom_supplemental_pass(shadow_session, &known, last_uid).await;
+            last_uid = highest_uid(&supplemental, last_uid);
             frame_build.uid_map.extend(supplemental);
         }
 
-        let uid_start = u32::try_from(uid_offset + 1).unwrap_or(u32::MAX);
-        let uid_end =
-            u32::try_from(merged_uid_map.len() + frame_build.uid_map.len()).unwrap_or(u32::MAX);
-        if !frame_build.uid_map.is_empty() {
-            frame_uid_ranges.push((frame_info.index, (uid_start, uid_end)));
-        }
+        frame_uid_ranges.extend(
+            crate::snapshot::uid_ranges(&frame_build.uid_map)
+                .into_iter()
+                .map(|range| (frame_info.index, range)),
+        );
         frame_ids.push((frame_info.index, frame_info.id.clone()));
 
         merged_uid_map.extend(frame_build.uid_map);
 
+        // Backend node ids are only unique per frame target; scope the diff keys.
+        merged_nodes.extend(frame_build.nodes.into_iter().map(|mut node| {
+            node.key = format!("f{}:{}", frame_info.index, node.key);
+            node
+        }));
+
         // Annotate the spliced subtree's root with the frame index.
         let mut frame_root = frame_build.root;
         frame_root.frame = Some(frame_info.index);
@@ -607,6 +781,10 @@ async fn execute_aggregate_snapshot(
         aggregate: true,
         frame_uid_ranges,
         frame_ids,
+        nodes: merged_nodes,
+        stale_uids: std::collections::HashMap::new(),
+        last_uid: 0,
+        document,
     };
     if let Err(e) = crate::snapshot::write_snapshot_state(&state) {
         let warning = serde_json::json!({
@@ -616,6 +794,13 @@ async fn execute_aggregate_snapshot(
         eprintln!("{warning}");
     }
 
+    if args.diff {
+        return emit_snapshot_diff(global, args, previous.as_ref(), &state);
+    }
+    if args.assert.is_some() || args.update_golden.is_some() {
+        return run_golden(global, args, &state.url, &merged_root);
+    }
+
     let root = if args.compact {
         crate::snapshot::compact_tree(&merged_root)
     } else {
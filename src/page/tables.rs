@@ -0,0 +1,556 @@
+//! `page tables` subcommand — structured extraction of HTML data tables.
+
+use kuchiki::traits::TendrilSink;
+use kuchiki::{NodeRef, parse_html};
+use serde::Serialize;
+use serde::ser::SerializeMap;
+
+use agentchrome::error::{AppError, ExitCode};
+
+use crate::cli::{GlobalOpts, PageTablesArgs, TableFormat};
+use crate::markdown::{is_layout_table, normalized_text};
+
+use super::setup_session;
+
+/// Largest `colspan` honoured, as in the HTML table model.
+const MAX_COLSPAN: usize = 1000;
+
+/// Largest `rowspan` honoured, as in the HTML table model.
+const MAX_ROWSPAN: usize = 65534;
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+struct TablesResult {
+    url: String,
+    title: String,
+    tables: Vec<Table>,
+}
+
+/// One extracted data table.
+#[derive(Debug, Serialize)]
+struct Table {
+    /// Position among all `<table>` elements of the extracted HTML.
+    index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    caption: Option<String>,
+    headers: Vec<String>,
+    rows: Vec<Row>,
+    row_count: usize,
+}
+
+/// A row keyed by header text, serialized as an object in column order.
+#[derive(Debug)]
+struct Row(Vec<(String, String)>);
+
+impl Serialize for Row {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TablesPayload {
+    html: String,
+    is_table: bool,
+    url: String,
+    title: String,
+}
+
+/// A grid slot; spanning cells fill every slot they cover.
+#[derive(Debug, Clone, Default)]
+struct Cell {
+    text: String,
+    header: bool,
+}
+
+// =============================================================================
+// Table model
+// =============================================================================
+
+/// Header and body rows of `table`, excluding rows of nested tables.
+fn table_rows(table: &NodeRef) -> (Vec<NodeRef>, Vec<NodeRef>) {
+    let mut head = Vec::new();
+    let mut body = Vec::new();
+    for child in table.children() {
+        match tag_name(&child).as_deref() {
+            Some("tr") => body.push(child),
+            Some("thead") => head.extend(child.children().filter(is_row)),
+            Some("tbody" | "tfoot") => body.extend(child.children().filter(is_row)),
+            _ => {}
+        }
+    }
+    (head, body)
+}
+
+fn is_row(node: &NodeRef) -> bool {
+    tag_name(node).as_deref() == Some("tr")
+}
+
+fn tag_name(node: &NodeRef) -> Option<String> {
+    node.as_element()
+        .map(|element| element.name.local.to_string().to_ascii_lowercase())
+}
+
+fn span_attr(cell: &NodeRef, name: &str) -> Option<usize> {
+    let element = cell.as_element()?;
+    let attributes = element.attributes.borrow();
+    attributes.get(name)?.trim().parse().ok()
+}
+
+/// Lay `rows` out on a rectangular grid, copying `colspan`/`rowspan` cells
+/// into every slot they cover. A `rowspan` of 0 reaches the last row.
+fn build_grid(rows: &[NodeRef]) -> Vec<Vec<Cell>> {
+    let mut grid: Vec<Vec<Option<Cell>>> = vec![Vec::new(); rows.len()];
+    for (r, row) in rows.iter().enumerate() {
+        let mut col = 0;
+        for node in row.children() {
+            let header = match tag_name(&node).as_deref() {
+                Some("th") => true,
+                Some("td") => false,
+                _ => continue,
+            };
+            while grid[r].get(col).is_some_and(Option::is_some) {
+                col += 1;
+            }
+            let colspan = span_attr(&node, "colspan")
+                .unwrap_or(1)
+                .clamp(1, MAX_COLSPAN);
+            let remaining = rows.len() - r;
+            let rowspan = match span_attr(&node, "rowspan").unwrap_or(1) {
+                0 => remaining,
+                n => n.min(MAX_ROWSPAN).min(remaining),
+            };
+            let cell = Cell {
+                text: normalized_text(&node.text_contents()),
+                header,
+            };
+            for line in &mut grid[r..r + rowspan] {
+                if line.len() < col + colspan {
+                    line.resize(col + colspan, None);
+                }
+                for slot in &mut line[col..col + colspan] {
+                    if slot.is_none() {
+                        *slot = Some(cell.clone());
+                    }
+                }
+            }
+            col += colspan;
+        }
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    grid.into_iter()
+        .map(|line| {
+            let mut line: Vec<Cell> = line.into_iter().map(Option::unwrap_or_default).collect();
+            line.resize(width, Cell::default());
+            line
+        })
+        .collect()
+}
+
+/// Column header for each column, joining stacked header rows with " / ".
+fn combine_headers(header_rows: &[Vec<Cell>], width: usize) -> Vec<String> {
+    (0..width)
+        .map(|col| {
+            let mut parts: Vec<&str> = Vec::new();
+            for row in header_rows {
+                let text = row[col].text.as_str();
+                if !text.is_empty() && parts.last() != Some(&text) {
+                    parts.push(text);
+                }
+            }
+            parts.join(" / ")
+        })
+        .collect()
+}
+
+/// Make header names usable as object keys: blank ones become `column_N`
+/// and repeats get a `_2`, `_3`, ... suffix.
+fn unique_keys(headers: Vec<String>) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::with_capacity(headers.len());
+    for (i, header) in headers.into_iter().enumerate() {
+        let base = if header.is_empty() {
+            format!("column_{}", i + 1)
+        } else {
+            header
+        };
+        let mut key = base.clone();
+        let mut n = 2;
+        while keys.contains(&key) {
+            key = format!("{base}_{n}");
+            n += 1;
+        }
+        keys.push(key);
+    }
+    keys
+}
+
+fn keyed_rows<'a>(keys: &[String], rows: impl Iterator<Item = Vec<&'a str>>) -> Vec<Row> {
+    rows.filter(|values| values.iter().any(|v| !v.is_empty()))
+        .map(|values| {
+            Row(keys
+                .iter()
+                .cloned()
+                .zip(values.into_iter().map(str::to_string))
+                .collect())
+        })
+        .collect()
+}
+
+/// Extract one table, or `None` if it has no cells.
+fn extract_table(table: &NodeRef, index: usize) -> Option<Table> {
+    let (head, body) = table_rows(table);
+    let mut rows = head;
+    let mut header_count = rows.len();
+    rows.extend(body);
+    let grid = build_grid(&rows);
+    let width = grid.first().map_or(0, Vec::len);
+    if width == 0 {
+        return None;
+    }
+
+    // Without a <thead>, leading all-<th> rows are header rows, keeping at
+    // least one row of data.
+    if header_count == 0 {
+        header_count = grid
+            .iter()
+            .take(grid.len() - 1)
+            .take_while(|row| row.iter().all(|cell| cell.header))
+            .count();
+    }
+    let (header_rows, data_rows) = grid.split_at(header_count);
+    let header_column = width > 1
+        && !data_rows.is_empty()
+        && data_rows.iter().all(|row| row[0].header)
+        && !data_rows
+            .iter()
+            .all(|row| row.iter().all(|cell| cell.header));
+
+    let (headers, rows) = if header_rows.is_empty() && header_column {
+        // Key/value table: each value column becomes one row.
+        let keys = unique_keys(data_rows.iter().map(|row| row[0].text.clone()).collect());
+        let columns = (1..width).map(|col| {
+            data_rows
+                .iter()
+                .map(|row| row[col].text.as_str())
+                .collect::<Vec<_>>()
+        });
+        let rows = keyed_rows(&keys, columns);
+        (keys, rows)
+    } else {
+        let keys = unique_keys(combine_headers(header_rows, width));
+        let rows = keyed_rows(
+            &keys,
+            data_rows
+                .iter()
+                .map(|row| row.iter().map(|cell| cell.text.as_str()).collect()),
+        );
+        (keys, rows)
+    };
+
+    let caption = table
+        .children()
+        .find(|child| tag_name(child).as_deref() == Some("caption"))
+        .map(|caption| normalized_text(&caption.text_contents()))
+        .filter(|caption| !caption.is_empty());
+
+    Some(Table {
+        index,
+        caption,
+        headers,
+        row_count: rows.len(),
+        rows,
+    })
+}
+
+fn is_presentational(table: &NodeRef) -> bool {
+    table.as_element().is_some_and(|element| {
+        matches!(
+            element.attributes.borrow().get("role"),
+            Some("presentation" | "none")
+        )
+    })
+}
+
+/// Extract the data tables of `html`. With `keep_first`, the first table is
+/// kept even if it looks like a layout table.
+fn extract_tables(html: &str, keep_first: bool) -> Vec<Table> {
+    let document = parse_html().one(html);
+    let Ok(tables) = document.select("table") else {
+        return Vec::new();
+    };
+    tables
+        .enumerate()
+        .filter_map(|(index, table)| {
+            let table = table.as_node();
+            let layout = is_presentational(table) || is_layout_table(table);
+            if layout && !(keep_first && index == 0) {
+                return None;
+            }
+            extract_table(table, index)
+        })
+        .collect()
+}
+
+// =============================================================================
+// CSV
+// =============================================================================
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let mut line = fields.map(csv_field).collect::<Vec<_>>().join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Render tables as CSV (RFC 4180), separated by a blank line.
+fn to_csv(tables: &[Table]) -> String {
+    let mut out = String::new();
+    for (i, table) in tables.iter().enumerate() {
+        if i > 0 {
+            out.push_str("\r\n");
+        }
+        out.push_str(&csv_line(table.headers.iter().map(String::as_str)));
+        for row in &table.rows {
+            out.push_str(&csv_line(row.0.iter().map(|(_, value)| value.as_str())));
+        }
+    }
+    out
+}
+
+// =============================================================================
+// Command executor
+// =============================================================================
+
+fn tables_expression(selector: Option<&str>) -> String {
+    let target = match selector {
+        Some(selector) => format!(
+            "document.querySelector({})",
+            serde_json::Value::from(selector)
+        ),
+        None => "document.documentElement".to_string(),
+    };
+    format!(
+        r#"(() => {{
+  const el = {target};
+  if (!el) return null;
+  return JSON.stringify({{
+    html: el.outerHTML,
+    is_table: el.tagName === "TABLE",
+    url: location.href,
+    title: document.title
+  }});
+}})()"#
+    )
+}
+
+pub async fn execute_tables(
+    global: &GlobalOpts,
+    args: &PageTablesArgs,
+    frame: Option<&str>,
+) -> Result<(), AppError> {
+    let (client, mut managed) = setup_session(global).await?;
+    if global.auto_dismiss_dialogs {
+        let _dismiss = managed.spawn_auto_dismiss().await?;
+    }
+
+    let mut frame_ctx = if let Some(frame_str) = frame {
+        let arg = agentchrome::frame::parse_frame_arg(frame_str)?;
+        Some(agentchrome::frame::resolve_frame(&client, &mut managed, &arg).await?)
+    } else {
+        None
+    };
+    let effective = if let Some(ref mut ctx) = frame_ctx {
+        agentchrome::frame::frame_session_mut(ctx, &mut managed)
+    } else {
+        &mut managed
+    };
+    effective.ensure_domain("Runtime").await?;
+
+    let mut params = serde_json::json!({
+        "expression": tables_expression(args.selector.as_deref()),
+        "returnByValue": true,
+    });
+    if let Some(ctx_id) = frame_ctx
+        .as_ref()
+        .and_then(agentchrome::frame::execution_context_id)
+    {
+        params["contextId"] = serde_json::Value::from(ctx_id);
+    }
+    let effective = if let Some(ref ctx) = frame_ctx {
+        agentchrome::frame::frame_session(ctx, &managed)
+    } else {
+        &managed
+    };
+    let result = effective
+        .send_command("Runtime.evaluate", Some(params))
+        .await?;
+
+    if let Some(exception) = result.get("exceptionDetails") {
+        let description = exception["exception"]["description"]
+            .as_str()
+            .or_else(|| exception["text"].as_str())
+            .unwrap_or("unknown error");
+        return Err(AppError::table_extraction_failed(description));
+    }
+    let Some(value) = result["result"]["value"].as_str() else {
+        return Err(match args.selector {
+            Some(ref selector) => AppError::element_not_found(selector),
+            None => AppError::table_extraction_failed("page has no document element"),
+        });
+    };
+    let payload: TablesPayload = serde_json::from_str(value).map_err(|e| AppError {
+        message: format!("Table extraction returned invalid payload: {e}"),
+        code: ExitCode::ProtocolError,
+        custom_json: None,
+    })?;
+
+    let tables = extract_tables(&payload.html, payload.is_table);
+
+    if args.format == TableFormat::Csv {
+        return crate::output::emit_plain_as(&to_csv(&tables), &global.output, "csv");
+    }
+
+    let output = TablesResult {
+        url: payload.url,
+        title: payload.title,
+        tables,
+    };
+    crate::output::emit(&output, &global.output, "page tables", |r| {
+        serde_json::json!({
+            "table_count": r.tables.len(),
+            "row_count": r.tables.iter().map(|t| t.row_count).sum::<usize>(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn only_table(html: &str) -> serde_json::Value {
+        let tables = extract_tables(html, false);
+        assert_eq!(tables.len(), 1, "expected one table in {html}");
+        serde_json::to_value(&tables[0]).unwrap()
+    }
+
+    #[test]
+    fn thead_headers_key_each_row_in_column_order() {
+        let table = only_table(
+            "<table><caption>Prices</caption>\
+             <thead><tr><th>Name</th><th>Price</th></tr></thead>\
+             <tbody><tr><td>Apple</td><td>1.20</td></tr><tr><td>Pear</td><td>0.90</td></tr></tbody>\
+             </table>",
+        );
+        assert_eq!(table["caption"], "Prices");
+        assert_eq!(table["headers"], serde_json::json!(["Name", "Price"]));
+        assert_eq!(table["row_count"], 2);
+        assert_eq!(
+            table["rows"][1],
+            serde_json::json!({ "Name": "Pear", "Price": "0.90" })
+        );
+        let row = serde_json::to_string(
+            &extract_tables(
+                "<table><tr><th>Z</th><th>A</th></tr><tr><td>1</td><td>2</td></tr></table>",
+                false,
+            )[0]
+            .rows[0],
+        )
+        .unwrap();
+        assert_eq!(row, r#"{"Z":"1","A":"2"}"#);
+    }
+
+    #[test]
+    fn colspan_and_rowspan_fill_every_covered_column() {
+        let table = only_table(
+            "<table>\
+             <tr><th rowspan=2>Region</th><th colspan=2>Q1</th></tr>\
+             <tr><th>Sales</th><th>Units</th></tr>\
+             <tr><td rowspan=2>North</td><td>10</td><td>1</td></tr>\
+             <tr><td colspan=2>n/a</td></tr>\
+             </table>",
+        );
+        assert_eq!(
+            table["headers"],
+            serde_json::json!(["Region", "Q1 / Sales", "Q1 / Units"])
+        );
+        assert_eq!(
+            table["rows"][1],
+            serde_json::json!({ "Region": "North", "Q1 / Sales": "n/a", "Q1 / Units": "n/a" })
+        );
+    }
+
+    #[test]
+    fn header_column_without_header_row_is_turned_into_rows() {
+        let table = only_table(
+            "<table>\
+             <tr><th>Model</th><td>X1</td><td>X2</td></tr>\
+             <tr><th>Weight</th><td>1 kg</td><td>2 kg</td></tr>\
+             </table>",
+        );
+        assert_eq!(table["headers"], serde_json::json!(["Model", "Weight"]));
+        assert_eq!(
+            table["rows"],
+            serde_json::json!([
+                { "Model": "X1", "Weight": "1 kg" },
+                { "Model": "X2", "Weight": "2 kg" },
+            ])
+        );
+    }
+
+    #[test]
+    fn blank_and_repeated_headers_get_unique_keys() {
+        assert_eq!(
+            unique_keys(vec![String::new(), "A".into(), "A".into(), "A".into()]),
+            ["column_1", "A", "A_2", "A_3"]
+        );
+        let table =
+            only_table("<table><tr><td>1</td><td>2</td></tr><tr><td>3</td><td></td></tr></table>");
+        assert_eq!(
+            table["headers"],
+            serde_json::json!(["column_1", "column_2"])
+        );
+        assert_eq!(
+            table["rows"][1],
+            serde_json::json!({ "column_1": "3", "column_2": "" })
+        );
+    }
+
+    #[test]
+    fn layout_tables_are_skipped_unless_selected() {
+        let html = "<table><tr><td>nav</td><td>content</td></tr></table>\
+                    <table role=presentation><tr><td>a</td><td>b</td></tr><tr><td>c</td><td>d</td></tr></table>\
+                    <table><tr><th>K</th></tr><tr><td>v</td></tr></table>";
+        let tables = extract_tables(html, false);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].index, 2);
+        assert_eq!(extract_tables(html, true)[0].index, 0);
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_separates_tables() {
+        let tables = extract_tables(
+            "<table><tr><th>Name</th><th>Note</th></tr><tr><td>a, b</td><td>say \"hi\"</td></tr></table>\
+             <table><tr><th>X</th></tr><tr><td>1</td></tr></table>",
+            false,
+        );
+        assert_eq!(
+            to_csv(&tables),
+            "Name,Note\r\n\"a, b\",\"say \"\"hi\"\"\"\r\n\r\nX\r\n1\r\n"
+        );
+    }
+}
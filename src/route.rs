@@ -0,0 +1,453 @@
+//! Request interception rules managed by `agentchrome network route`.
+//!
+//! Rules are persisted in `~/.agentchrome/routes.json` and enforced through the
+//! CDP `Fetch` domain by whichever process currently holds a session open:
+//! `network follow`, `script run`, or the background daemon. Every request is
+//! paused at the request stage and matched locally against the rules' URL
+//! globs, so the glob syntax is the same one `page wait --url` uses rather
+//! than Chrome's own `urlPattern` wildcards.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::cdp::{CdpError, CdpSession};
+use crate::error::AppError;
+use crate::session::{self, SessionError};
+
+/// A single `name: value` HTTP header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+}
+
+impl Header {
+    /// Parse a `name:value` argument. Whitespace around both parts is trimmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if there is no `:` or the name is empty.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (name, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid header '{s}': expected NAME:VALUE"))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format!("invalid header '{s}': name is empty"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+/// What to do with a request that matches a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RouteAction {
+    /// Answer the request without contacting the server.
+    Fulfill {
+        status: u16,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        headers: Vec<Header>,
+        /// Absolute path of the file served as the response body.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body_file: Option<String>,
+    },
+    /// Fail the request with a network error (a CDP `Network.ErrorReason`).
+    Abort { error_reason: String },
+    /// Let the request through with its headers changed.
+    Modify {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        set_headers: Vec<Header>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        remove_headers: Vec<String>,
+    },
+}
+
+/// A persisted interception rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteRule {
+    pub id: u32,
+    pub url_pattern: String,
+    #[serde(flatten)]
+    pub action: RouteAction,
+}
+
+/// Contents of `routes.json`. Rules are evaluated in order; the first match wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteState {
+    pub next_id: u32,
+    pub rules: Vec<RouteRule>,
+}
+
+impl RouteState {
+    /// Append a rule, assigning it the next free ID.
+    pub fn add(&mut self, url_pattern: String, action: RouteAction) -> &RouteRule {
+        self.next_id = self.next_id.max(1);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.rules.push(RouteRule {
+            id,
+            url_pattern,
+            action,
+        });
+        &self.rules[self.rules.len() - 1]
+    }
+
+    /// Remove the rule with the given ID, returning it if it existed.
+    pub fn remove(&mut self, id: u32) -> Option<RouteRule> {
+        let index = self.rules.iter().position(|r| r.id == id)?;
+        Some(self.rules.remove(index))
+    }
+}
+
+/// Compile a URL glob. `*` matches across `/`, as in `page wait --url`.
+///
+/// # Errors
+///
+/// Returns `globset::Error` if the pattern is not a valid glob.
+pub fn compile_pattern(pattern: &str) -> Result<GlobMatcher, globset::Error> {
+    Ok(GlobBuilder::new(pattern)
+        .literal_separator(false)
+        .build()?
+        .compile_matcher())
+}
+
+/// Rules with their URL globs compiled, ready for matching.
+#[derive(Debug, Default)]
+pub struct RouteTable {
+    rules: Vec<(GlobMatcher, RouteRule)>,
+}
+
+impl RouteTable {
+    /// Compile the given rules. Rules with an invalid pattern are skipped;
+    /// `network route add` rejects those before they are persisted.
+    #[must_use]
+    pub fn new(rules: &[RouteRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|r| Some((compile_pattern(&r.url_pattern).ok()?, r.clone())))
+            .collect();
+        Self { rules }
+    }
+
+    /// Load and compile the persisted rules.
+    ///
+    /// # Errors
+    ///
+    /// See [`read_routes`].
+    pub fn load() -> Result<Self, SessionError> {
+        Ok(Self::new(&read_routes()?.rules))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule whose pattern matches `url`.
+    #[must_use]
+    pub fn find(&self, url: &str) -> Option<&RouteRule> {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(url))
+            .map(|(_, rule)| rule)
+    }
+}
+
+// =============================================================================
+// Persistence
+// =============================================================================
+
+/// Returns the path to the route rules file: `routes.json` next to the session file.
+///
+/// # Errors
+///
+/// Returns `SessionError::NoHomeDir` if the home directory cannot be determined.
+pub fn routes_file_path() -> Result<PathBuf, SessionError> {
+    Ok(session::session_file_path()?.with_file_name("routes.json"))
+}
+
+/// Write route rules to a specific path (atomic, owner-only permissions).
+///
+/// # Errors
+///
+/// Returns `SessionError` on serialization or I/O failure.
+pub fn write_routes_to(path: &Path, state: &RouteState) -> Result<(), SessionError> {
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+    session::write_state_file(path, json.as_bytes())
+}
+
+/// Write route rules to the default location.
+///
+/// # Errors
+///
+/// See [`write_routes_to`].
+pub fn write_routes(state: &RouteState) -> Result<(), SessionError> {
+    write_routes_to(&routes_file_path()?, state)
+}
+
+/// Read route rules from a specific path. A missing file means no rules.
+///
+/// # Errors
+///
+/// Returns `SessionError::InvalidFormat` on malformed JSON, or
+/// `SessionError::Io` on other I/O errors.
+pub fn read_routes_from(path: &Path) -> Result<RouteState, SessionError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| SessionError::InvalidFormat(format!("{} at {}", e, path.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RouteState::default()),
+        Err(e) => Err(SessionError::Io(e)),
+    }
+}
+
+/// Read route rules from the default location.
+///
+/// # Errors
+///
+/// See [`read_routes_from`].
+pub fn read_routes() -> Result<RouteState, SessionError> {
+    read_routes_from(&routes_file_path()?)
+}
+
+// =============================================================================
+// Enforcement
+// =============================================================================
+
+/// `Fetch.enable` parameters that pause every request at the request stage.
+#[must_use]
+pub fn interception_params() -> Value {
+    json!({ "patterns": [{ "urlPattern": "*", "requestStage": "Request" }] })
+}
+
+/// Start pausing every request in `session` at the request stage.
+///
+/// # Errors
+///
+/// Returns `CdpError` if `Fetch.enable` fails.
+pub async fn enable_interception(session: &CdpSession) -> Result<(), CdpError> {
+    session
+        .send_command("Fetch.enable", Some(interception_params()))
+        .await?;
+    Ok(())
+}
+
+/// Resolve one `Fetch.requestPaused` event against `table`.
+///
+/// Unmatched requests are continued unchanged. A fulfill rule whose body file
+/// can no longer be read also lets the request through rather than stalling it.
+///
+/// # Errors
+///
+/// Returns `CdpError` if the resolving `Fetch.*` command fails.
+pub async fn handle_paused(
+    session: &CdpSession,
+    table: &RouteTable,
+    params: &Value,
+) -> Result<(), CdpError> {
+    let request_id = params["requestId"].clone();
+    let url = params["request"]["url"].as_str().unwrap_or_default();
+    let continue_request = json!({ "requestId": request_id });
+
+    let (method, body) = match table.find(url).map(|r| &r.action) {
+        None => ("Fetch.continueRequest", continue_request),
+        Some(RouteAction::Fulfill {
+            status,
+            headers,
+            body_file,
+        }) => match read_body(body_file.as_deref()) {
+            Some(body) => (
+                "Fetch.fulfillRequest",
+                json!({
+                    "requestId": request_id,
+                    "responseCode": status,
+                    "responseHeaders": headers,
+                    "body": body,
+                }),
+            ),
+            None => ("Fetch.continueRequest", continue_request),
+        },
+        Some(RouteAction::Abort { error_reason }) => (
+            "Fetch.failRequest",
+            json!({ "requestId": request_id, "errorReason": error_reason }),
+        ),
+        Some(RouteAction::Modify {
+            set_headers,
+            remove_headers,
+        }) => (
+            "Fetch.continueRequest",
+            json!({
+                "requestId": request_id,
+                "headers": modified_headers(&params["request"]["headers"], set_headers, remove_headers),
+            }),
+        ),
+    };
+    session.send_command(method, Some(body)).await?;
+    Ok(())
+}
+
+/// Base64-encoded body for a fulfill rule; `None` if the file cannot be read.
+fn read_body(path: Option<&str>) -> Option<String> {
+    let bytes = match path {
+        Some(path) => std::fs::read(path).ok()?,
+        None => Vec::new(),
+    };
+    Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// Apply `set` and `remove` to the paused request's header object.
+/// Header names compare case-insensitively.
+fn modified_headers(original: &Value, set: &[Header], remove: &[String]) -> Vec<Header> {
+    let replaced = |name: &str| {
+        remove.iter().any(|r| r.eq_ignore_ascii_case(name))
+            || set.iter().any(|h| h.name.eq_ignore_ascii_case(name))
+    };
+    let mut headers: Vec<Header> = original
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| !replaced(name))
+        .map(|(name, value)| Header {
+            name: name.clone(),
+            value: value.as_str().unwrap_or_default().to_string(),
+        })
+        .collect();
+    headers.extend(set.iter().cloned());
+    headers
+}
+
+/// Enforce the persisted rules in `session` until the session goes away.
+///
+/// Returns `Ok(None)` without touching the session when no rules exist.
+/// Otherwise enables interception and returns the handle of the task that
+/// answers `Fetch.requestPaused`; `abort()` it to stop early.
+///
+/// # Errors
+///
+/// Returns `AppError` if the rules file cannot be read, or if the
+/// subscription or `Fetch.enable` fails.
+pub async fn spawn_route_handler(
+    session: &CdpSession,
+) -> Result<Option<tokio::task::JoinHandle<()>>, AppError> {
+    let table = RouteTable::load()?;
+    if table.is_empty() {
+        return Ok(None);
+    }
+    let mut paused_rx = session.subscribe("Fetch.requestPaused").await?;
+    enable_interception(session).await?;
+
+    let session = session.clone();
+    Ok(Some(tokio::spawn(async move {
+        while let Some(event) = paused_rx.recv().await {
+            // Best-effort: the request may already be gone (tab navigated/closed).
+            let _ = handle_paused(&session, &table, &event.params).await;
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fulfill(status: u16) -> RouteAction {
+        RouteAction::Fulfill {
+            status,
+            headers: vec![],
+            body_file: None,
+        }
+    }
+
+    #[test]
+    fn header_parse_splits_on_first_colon() {
+        let h = Header::parse("X-Url: https://a.test:8080/").unwrap();
+        assert_eq!(h.name, "X-Url");
+        assert_eq!(h.value, "https://a.test:8080/");
+        assert!(Header::parse("no-colon").is_err());
+        assert!(Header::parse(" :value").is_err());
+        assert_eq!(Header::parse("X-Empty:").unwrap().value, "");
+    }
+
+    #[test]
+    fn state_assigns_increasing_ids_and_removes() {
+        let mut state = RouteState::default();
+        assert_eq!(state.add("*/a".into(), fulfill(200)).id, 1);
+        assert_eq!(state.add("*/b".into(), fulfill(500)).id, 2);
+        assert_eq!(state.remove(1).unwrap().url_pattern, "*/a");
+        assert!(state.remove(1).is_none());
+        // IDs are never reused, even after removal.
+        assert_eq!(state.add("*/c".into(), fulfill(200)).id, 3);
+    }
+
+    #[test]
+    fn rule_serializes_flat_with_action_tag() {
+        let rule = RouteRule {
+            id: 4,
+            url_pattern: "*/api/*".into(),
+            action: RouteAction::Abort {
+                error_reason: "Failed".into(),
+            },
+        };
+        let json = serde_json::to_value(&rule).unwrap();
+        assert_eq!(
+            json,
+            json!({"id": 4, "url_pattern": "*/api/*", "action": "abort", "error_reason": "Failed"})
+        );
+        let back: RouteRule = serde_json::from_value(json).unwrap();
+        assert_eq!(back, rule);
+    }
+
+    #[test]
+    fn table_first_match_wins_and_skips_invalid_patterns() {
+        let mut state = RouteState::default();
+        state.add("[".into(), fulfill(418));
+        state.add("*/api/users*".into(), fulfill(500));
+        state.add("*/api/*".into(), fulfill(200));
+        let table = RouteTable::new(&state.rules);
+        let hit = table.find("https://example.com/api/users?page=2").unwrap();
+        assert_eq!(hit.id, 2);
+        assert_eq!(table.find("https://example.com/api/items").unwrap().id, 3);
+        assert!(table.find("https://example.com/index.html").is_none());
+    }
+
+    #[test]
+    fn modified_headers_replaces_case_insensitively() {
+        let original = json!({"Accept": "*/*", "Authorization": "Bearer old", "Cookie": "a=1"});
+        let set = vec![Header::parse("authorization: Bearer new").unwrap()];
+        let remove = vec!["cookie".to_string()];
+        let headers = modified_headers(&original, &set, &remove);
+        assert_eq!(
+            headers,
+            vec![
+                Header::parse("Accept: */*").unwrap(),
+                Header::parse("authorization: Bearer new").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn routes_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("agentchrome-routes-{}", std::process::id()));
+        let path = dir.join("routes.json");
+        assert_eq!(read_routes_from(&path).unwrap(), RouteState::default());
+
+        let mut state = RouteState::default();
+        state.add(
+            "*/flaky".into(),
+            RouteAction::Modify {
+                set_headers: vec![Header::parse("X-Test: 1").unwrap()],
+                remove_headers: vec![],
+            },
+        );
+        write_routes_to(&path, &state).unwrap();
+        assert_eq!(read_routes_from(&path).unwrap(), state);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
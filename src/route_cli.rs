@@ -0,0 +1,335 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use agentchrome::cdp::{CdpClient, CdpConfig};
+use agentchrome::daemon;
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::route::{self, Header, RouteAction, RouteRule};
+
+use crate::cli::{
+    GlobalOpts, NetworkRouteAddArgs, NetworkRouteArgs, NetworkRouteCommand, NetworkRouteRemoveArgs,
+    RouteErrorReason,
+};
+use crate::output::print_output;
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct RemoveResult {
+    removed: u32,
+}
+
+#[derive(Serialize)]
+struct ClearResult {
+    cleared: usize,
+}
+
+// =============================================================================
+// Output formatting
+// =============================================================================
+
+fn describe_action(action: &RouteAction) -> String {
+    match action {
+        RouteAction::Fulfill {
+            status, body_file, ..
+        } => match body_file {
+            Some(file) => format!("respond {status} with {file}"),
+            None => format!("respond {status}"),
+        },
+        RouteAction::Abort { error_reason } => format!("abort ({error_reason})"),
+        RouteAction::Modify {
+            set_headers,
+            remove_headers,
+        } => {
+            let mut parts: Vec<String> = set_headers
+                .iter()
+                .map(|h| format!("set {}", h.name))
+                .collect();
+            parts.extend(remove_headers.iter().map(|h| format!("remove {h}")));
+            format!("modify headers ({})", parts.join(", "))
+        }
+    }
+}
+
+fn print_rules_plain(rules: &[RouteRule]) {
+    if rules.is_empty() {
+        println!("No routes");
+        return;
+    }
+    for rule in rules {
+        println!(
+            "{:>3}  {}  {}",
+            rule.id,
+            rule.url_pattern,
+            describe_action(&rule.action)
+        );
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn cdp_error_reason(reason: RouteErrorReason) -> &'static str {
+    match reason {
+        RouteErrorReason::Failed => "Failed",
+        RouteErrorReason::Aborted => "Aborted",
+        RouteErrorReason::TimedOut => "TimedOut",
+        RouteErrorReason::AccessDenied => "AccessDenied",
+        RouteErrorReason::ConnectionClosed => "ConnectionClosed",
+        RouteErrorReason::ConnectionReset => "ConnectionReset",
+        RouteErrorReason::ConnectionRefused => "ConnectionRefused",
+        RouteErrorReason::ConnectionAborted => "ConnectionAborted",
+        RouteErrorReason::ConnectionFailed => "ConnectionFailed",
+        RouteErrorReason::NameNotResolved => "NameNotResolved",
+        RouteErrorReason::InternetDisconnected => "InternetDisconnected",
+        RouteErrorReason::AddressUnreachable => "AddressUnreachable",
+        RouteErrorReason::BlockedByClient => "BlockedByClient",
+        RouteErrorReason::BlockedByResponse => "BlockedByResponse",
+    }
+}
+
+fn parse_headers(raw: &[String]) -> Result<Vec<Header>, AppError> {
+    raw.iter()
+        .map(|h| {
+            Header::parse(h).map_err(|message| AppError {
+                message,
+                code: ExitCode::GeneralError,
+                custom_json: None,
+            })
+        })
+        .collect()
+}
+
+/// Work out the rule's action from the mutually exclusive flag sets.
+fn action_from_args(args: &NetworkRouteAddArgs) -> Result<RouteAction, AppError> {
+    if args.abort {
+        return Ok(RouteAction::Abort {
+            error_reason: cdp_error_reason(args.error_reason).to_string(),
+        });
+    }
+    if !args.set_headers.is_empty() || !args.remove_headers.is_empty() {
+        return Ok(RouteAction::Modify {
+            set_headers: parse_headers(&args.set_headers)?,
+            remove_headers: args.remove_headers.clone(),
+        });
+    }
+    if args.respond_file.is_none() && args.status.is_none() && args.headers.is_empty() {
+        return Err(AppError {
+            message: "No route action given: use --respond-file/--status/--header, --abort, \
+                      or --set-header/--remove-header"
+                .into(),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        });
+    }
+    let body_file = match &args.respond_file {
+        Some(path) => {
+            let display = path.display().to_string();
+            let absolute =
+                std::fs::canonicalize(path).map_err(|_| AppError::file_not_found(&display))?;
+            if !absolute.is_file() {
+                return Err(AppError::file_not_readable(&display));
+            }
+            Some(absolute.display().to_string())
+        }
+        None => None,
+    };
+    Ok(RouteAction::Fulfill {
+        status: args.status.unwrap_or(200),
+        headers: parse_headers(&args.headers)?,
+        body_file,
+    })
+}
+
+/// Tell a running daemon to pick up the new rules. Best-effort: without a
+/// daemon the rules simply take effect in the next long-lived session.
+async fn notify_daemon() {
+    let Some(record) = daemon::running_daemon() else {
+        return;
+    };
+    let config = CdpConfig {
+        connect_timeout: Duration::from_secs(2),
+        command_timeout: Duration::from_secs(5),
+        ..CdpConfig::default()
+    };
+    if let Ok(client) = CdpClient::connect(&record.url(), config).await {
+        let _ = client
+            .send_command(daemon::METHOD_RELOAD_ROUTES, None)
+            .await;
+    }
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `network route` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the arguments are invalid or the rules file cannot
+/// be read or written.
+pub async fn execute_route(global: &GlobalOpts, args: &NetworkRouteArgs) -> Result<(), AppError> {
+    match &args.command {
+        NetworkRouteCommand::Add(add_args) => execute_add(global, add_args).await,
+        NetworkRouteCommand::List => execute_list(global),
+        NetworkRouteCommand::Remove(remove_args) => execute_remove(global, remove_args).await,
+        NetworkRouteCommand::Clear => execute_clear(global).await,
+    }
+}
+
+async fn execute_add(global: &GlobalOpts, args: &NetworkRouteAddArgs) -> Result<(), AppError> {
+    route::compile_pattern(&args.url_pattern).map_err(|e| AppError {
+        message: format!("Invalid URL pattern '{}': {e}", args.url_pattern),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    let action = action_from_args(args)?;
+
+    let mut state = route::read_routes()?;
+    let rule = state.add(args.url_pattern.clone(), action).clone();
+    route::write_routes(&state)?;
+    notify_daemon().await;
+
+    if global.output.plain {
+        print_rules_plain(std::slice::from_ref(&rule));
+        return Ok(());
+    }
+    print_output(&rule, &global.output)
+}
+
+fn execute_list(global: &GlobalOpts) -> Result<(), AppError> {
+    let state = route::read_routes()?;
+    if global.output.plain {
+        print_rules_plain(&state.rules);
+        return Ok(());
+    }
+    print_output(&state.rules, &global.output)
+}
+
+async fn execute_remove(
+    global: &GlobalOpts,
+    args: &NetworkRouteRemoveArgs,
+) -> Result<(), AppError> {
+    let mut state = route::read_routes()?;
+    if state.remove(args.id).is_none() {
+        return Err(AppError {
+            message: format!(
+                "No route with ID {}. Run 'agentchrome network route list'.",
+                args.id
+            ),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        });
+    }
+    route::write_routes(&state)?;
+    notify_daemon().await;
+
+    if global.output.plain {
+        println!("Removed route {}", args.id);
+        return Ok(());
+    }
+    print_output(&RemoveResult { removed: args.id }, &global.output)
+}
+
+async fn execute_clear(global: &GlobalOpts) -> Result<(), AppError> {
+    let mut state = route::read_routes()?;
+    let cleared = state.rules.len();
+    state.rules.clear();
+    route::write_routes(&state)?;
+    notify_daemon().await;
+
+    if global.output.plain {
+        println!("Cleared {cleared} route(s)");
+        return Ok(());
+    }
+    print_output(&ClearResult { cleared }, &global.output)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_args() -> NetworkRouteAddArgs {
+        NetworkRouteAddArgs {
+            url_pattern: "*/api/*".into(),
+            respond_file: None,
+            status: None,
+            headers: vec![],
+            abort: false,
+            error_reason: RouteErrorReason::Failed,
+            set_headers: vec![],
+            remove_headers: vec![],
+        }
+    }
+
+    #[test]
+    fn action_requires_a_mode() {
+        let err = action_from_args(&add_args()).unwrap_err();
+        assert!(err.message.contains("No route action"));
+    }
+
+    #[test]
+    fn action_status_only_fulfills_without_body() {
+        let args = NetworkRouteAddArgs {
+            status: Some(503),
+            headers: vec!["retry-after: 5".into()],
+            ..add_args()
+        };
+        assert_eq!(
+            action_from_args(&args).unwrap(),
+            RouteAction::Fulfill {
+                status: 503,
+                headers: vec![Header::parse("retry-after:5").unwrap()],
+                body_file: None,
+            }
+        );
+    }
+
+    #[test]
+    fn action_abort_maps_error_reason() {
+        let args = NetworkRouteAddArgs {
+            abort: true,
+            error_reason: RouteErrorReason::NameNotResolved,
+            ..add_args()
+        };
+        assert_eq!(
+            action_from_args(&args).unwrap(),
+            RouteAction::Abort {
+                error_reason: "NameNotResolved".into()
+            }
+        );
+    }
+
+    #[test]
+    fn action_missing_respond_file_is_an_error() {
+        let args = NetworkRouteAddArgs {
+            respond_file: Some("/nonexistent/agentchrome/body.json".into()),
+            ..add_args()
+        };
+        assert!(matches!(
+            action_from_args(&args).unwrap_err().code,
+            ExitCode::GeneralError
+        ));
+    }
+
+    #[test]
+    fn describe_modify_lists_header_changes() {
+        let action = RouteAction::Modify {
+            set_headers: vec![Header::parse("authorization:x").unwrap()],
+            remove_headers: vec!["cookie".into()],
+        };
+        assert_eq!(
+            describe_action(&action),
+            "modify headers (set authorization, remove cookie)"
+        );
+    }
+}
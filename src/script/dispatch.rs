@@ -17,7 +17,7 @@ use crate::script::context::VarContext;
 /// Subcommands that the script dispatcher supports.
 pub const KNOWN_SUBCOMMANDS: &[&str] = &[
     "navigate", "page", "js", "form", "interact", "tabs", "console", "dialog", "dom", "network",
-    "media", "emulate", "perf", "cookie",
+    "media", "emulate", "perf", "cookie", "storage",
 ];
 
 #[must_use]
@@ -64,14 +64,22 @@ pub async fn invoke(
         });
     }
 
-    let full_argv = std::iter::once("agentchrome").chain(argv.iter().map(String::as_str));
-    let cli = Cli::try_parse_from(full_argv).map_err(|e| AppError {
+    let full_argv: Vec<String> = std::iter::once("agentchrome".to_string())
+        .chain(argv.iter().cloned())
+        .collect();
+    let cli = Cli::try_parse_from(&full_argv).map_err(|e| AppError {
         message: format!("script step parse error for '{subcommand}': {e}"),
         code: ExitCode::GeneralError,
         custom_json: None,
     })?;
 
-    dispatch_command(&cli.command, client, session, global).await
+    // Steps are marked on an active `page record` timeline like top-level commands.
+    let marker = crate::command_marker(&cli.command, &full_argv);
+    let result = dispatch_command(&cli.command, client, session, global).await;
+    if let Some(marker) = marker {
+        marker.finish(result.is_ok());
+    }
+    result
 }
 
 async fn dispatch_command(
@@ -95,6 +103,7 @@ async fn dispatch_command(
         Command::Emulate(args) => crate::emulate::run_from_session(session, global, args).await,
         Command::Perf(args) => crate::perf::run_from_session(session, global, args).await,
         Command::Cookie(args) => crate::cookie::run_from_session(session, global, args).await,
+        Command::Storage(args) => crate::storage::run_from_session(session, global, args).await,
         _ => Err(AppError {
             message: "this command is not supported inside scripts".into(),
             code: ExitCode::GeneralError,
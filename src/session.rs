@@ -1,5 +1,6 @@
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +20,69 @@ pub struct SessionData {
     /// Cumulative successful auto-reconnects for this session file.
     #[serde(default)]
     pub reconnect_count: u32,
+    /// Browser contexts created with `agentchrome context create` and not yet
+    /// disposed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub contexts: Vec<BrowserContextRecord>,
+}
+
+/// A browser context recorded in the session, with the tabs opened in it via
+/// `agentchrome tabs create --context`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrowserContextRecord {
+    pub id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tabs: Vec<String>,
+}
+
+impl SessionData {
+    /// Record a browser context, if not already recorded.
+    pub fn add_context(&mut self, id: &str) {
+        if !self.contexts.iter().any(|c| c.id == id) {
+            self.contexts.push(BrowserContextRecord {
+                id: id.to_string(),
+                tabs: Vec::new(),
+            });
+        }
+    }
+
+    /// Record a tab opened in browser context `context`.
+    pub fn add_context_tab(&mut self, context: &str, tab: &str) {
+        self.add_context(context);
+        if let Some(record) = self.contexts.iter_mut().find(|c| c.id == context)
+            && !record.tabs.iter().any(|t| t == tab)
+        {
+            record.tabs.push(tab.to_string());
+        }
+    }
+
+    /// Forget a browser context and the tabs recorded in it.
+    pub fn remove_context(&mut self, id: &str) {
+        self.contexts.retain(|c| c.id != id);
+    }
+
+    /// Forget tabs that have been closed.
+    pub fn remove_context_tabs(&mut self, closed: &[String]) {
+        for record in &mut self.contexts {
+            record.tabs.retain(|t| !closed.contains(t));
+        }
+    }
+
+    /// Forget recorded tabs that Chrome no longer reports in their context.
+    /// `live` maps each open tab to its browser context.
+    pub fn retain_live_context_tabs(&mut self, live: &std::collections::HashMap<String, String>) {
+        for record in &mut self.contexts {
+            record.tabs.retain(|t| live.get(t) == Some(&record.id));
+        }
+    }
+
+    /// Whether `tab` was opened in one of the recorded browser contexts.
+    #[must_use]
+    pub fn has_context_tab(&self, tab: &str) -> bool {
+        self.contexts
+            .iter()
+            .any(|c| c.tabs.iter().any(|t| t == tab))
+    }
 }
 
 /// Errors that can occur during session file operations.
@@ -73,16 +137,104 @@ impl From<SessionError> for crate::error::AppError {
     }
 }
 
-/// Returns the path to the session file: `~/.agentchrome/session.json`.
+/// Name of the session used when neither `--session` nor
+/// `AGENTCHROME_SESSION` is given. Its state lives directly in
+/// `~/.agentchrome/`, where single-session installs have always kept it.
+pub const DEFAULT_SESSION: &str = "default";
+
+static SESSION_NAME: OnceLock<String> = OnceLock::new();
+
+/// Select the named session whose state files this process reads and writes.
+///
+/// Must run before any state file path is resolved; only the first call takes
+/// effect.
+pub fn select_session(name: &str) {
+    let _ = SESSION_NAME.set(name.to_string());
+}
+
+/// The session selected for this process ([`DEFAULT_SESSION`] unless
+/// [`select_session`] ran).
+#[must_use]
+pub fn session_name() -> &'static str {
+    SESSION_NAME.get().map_or(DEFAULT_SESSION, String::as_str)
+}
+
+/// Returns `~/.agentchrome`, the root of all agentchrome state.
+fn state_root() -> Result<PathBuf, SessionError> {
+    Ok(home_dir()?.join(".agentchrome"))
+}
+
+/// State directory of session `name` under `root`: `root` itself for the
+/// default session, `root/sessions/<name>` otherwise.
+fn session_dir_in(root: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_SESSION {
+        root.to_path_buf()
+    } else {
+        root.join("sessions").join(name)
+    }
+}
+
+/// Returns the state directory of the selected session:
+/// `~/.agentchrome/` for the default session, `~/.agentchrome/sessions/<name>/`
+/// otherwise.
+///
+/// # Errors
+///
+/// Returns `SessionError::NoHomeDir` if the home directory cannot be determined.
+pub fn session_dir() -> Result<PathBuf, SessionError> {
+    Ok(session_dir_in(&state_root()?, session_name()))
+}
+
+/// Returns the path to the session file: `session.json` in [`session_dir`].
 ///
-/// Uses `$HOME` on Unix and `%USERPROFILE%` on Windows.
+/// Uses `$HOME` on Unix and `%USERPROFILE%` on Windows. Other per-session
+/// state files (daemon, routes, snapshots) are siblings of this file.
 ///
 /// # Errors
 ///
 /// Returns `SessionError::NoHomeDir` if the home directory cannot be determined.
 pub fn session_file_path() -> Result<PathBuf, SessionError> {
-    let home = home_dir()?;
-    Ok(home.join(".agentchrome").join("session.json"))
+    Ok(session_dir()?.join("session.json"))
+}
+
+/// Names of the sessions that have a state directory, default first, then
+/// named sessions in alphabetical order.
+///
+/// # Errors
+///
+/// Returns `SessionError::NoHomeDir` if the home directory cannot be determined,
+/// or `SessionError::Io` if the sessions directory cannot be read.
+pub fn list_sessions() -> Result<Vec<String>, SessionError> {
+    list_sessions_in(&state_root()?)
+}
+
+fn list_sessions_in(root: &Path) -> Result<Vec<String>, SessionError> {
+    let mut names = Vec::new();
+    if root.join("session.json").exists() {
+        names.push(DEFAULT_SESSION.to_string());
+    }
+    let mut named_sessions: Vec<String> = match std::fs::read_dir(root.join("sessions")) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(SessionError::Io(e)),
+    };
+    named_sessions.sort();
+    names.extend(named_sessions);
+    Ok(names)
+}
+
+/// Read the session file of session `name` without selecting it.
+///
+/// # Errors
+///
+/// Returns `SessionError::InvalidFormat` if the file contains invalid JSON,
+/// or `SessionError::Io` for other I/O errors.
+pub fn read_named_session(name: &str) -> Result<Option<SessionData>, SessionError> {
+    read_session_from(&session_dir_in(&state_root()?, name).join("session.json"))
 }
 
 fn home_dir() -> Result<PathBuf, SessionError> {
@@ -141,15 +293,53 @@ pub fn write_session(data: &SessionData) -> Result<(), SessionError> {
 ///
 /// Returns `SessionError::Io` on I/O failure.
 pub fn write_session_to(path: &std::path::Path, data: &SessionData) -> Result<(), SessionError> {
+    let json = serde_json::to_string_pretty(data)
+        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+
+    write_state_file(path, json.as_bytes())
+}
+
+/// Write an owner-only state file next to the session file, creating the
+/// parent directory (`0o700`) and replacing the file atomically (`0o600`).
+///
+/// # Errors
+///
+/// Returns `SessionError::Io` on I/O failure.
+pub fn write_state_file(path: &std::path::Path, bytes: &[u8]) -> Result<(), SessionError> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
         set_owner_only_perms(parent, 0o700)?;
     }
 
-    let json = serde_json::to_string_pretty(data)
-        .map_err(|e| SessionError::InvalidFormat(e.to_string()))?;
+    write_session_atomic(path, bytes)
+}
 
-    write_session_atomic(path, json.as_bytes())
+/// Write a user-chosen file that holds credentials (cookies, web storage),
+/// readable by the owner only (`0o600` on Unix). Unlike
+/// [`write_state_file`], the parent directory is left as it is.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be created or written.
+pub fn write_private_file(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files; tighten an existing one before
+    // writing into it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(bytes)
 }
 
 #[cfg(unix)]
@@ -324,6 +514,7 @@ pub fn rewrite_preserving_to(
         timestamp: now.clone(),
         last_reconnect_at: Some(now),
         reconnect_count: existing.reconnect_count.saturating_add(1),
+        contexts: existing.contexts.clone(),
     };
     write_session_to(path, &updated)?;
     Ok(updated)
@@ -383,6 +574,36 @@ mod tests {
         assert!(path.ends_with(".agentchrome/session.json"));
     }
 
+    #[test]
+    fn default_session_keeps_legacy_directory() {
+        let root = Path::new("/home/me/.agentchrome");
+        assert_eq!(session_dir_in(root, DEFAULT_SESSION), root);
+        assert_eq!(
+            session_dir_in(root, "agent-a"),
+            root.join("sessions").join("agent-a")
+        );
+    }
+
+    #[test]
+    fn list_sessions_puts_default_first_then_sorted_names() {
+        let root = std::env::temp_dir().join("agentchrome-test-list-sessions");
+        let _ = std::fs::remove_dir_all(&root);
+        assert!(list_sessions_in(&root).unwrap().is_empty());
+
+        std::fs::create_dir_all(root.join("sessions").join("zeta")).unwrap();
+        std::fs::create_dir_all(root.join("sessions").join("alpha")).unwrap();
+        std::fs::write(root.join("sessions").join("stray.json"), "{}").unwrap();
+        assert_eq!(list_sessions_in(&root).unwrap(), ["alpha", "zeta"]);
+
+        std::fs::write(root.join("session.json"), "{}").unwrap();
+        assert_eq!(
+            list_sessions_in(&root).unwrap(),
+            ["default", "alpha", "zeta"]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn format_unix_epoch() {
         assert_eq!(format_unix_secs(0), "1970-01-01T00:00:00Z");
@@ -421,6 +642,7 @@ mod tests {
             timestamp: "2026-02-11T12:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
 
         write_session_to(&path, &data).unwrap();
@@ -449,6 +671,7 @@ mod tests {
             timestamp: "2026-02-11T12:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
 
         write_session_to(&path, &data).unwrap();
@@ -534,6 +757,7 @@ mod tests {
             timestamp: "2026-02-15T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
         write_session_to(&path, &launch).unwrap();
 
@@ -563,6 +787,7 @@ mod tests {
             timestamp: "2026-02-15T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
         write_session_to(&path, &launch).unwrap();
 
@@ -604,6 +829,7 @@ mod tests {
             timestamp: "2026-02-15T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
         write_session_to(&path, &existing).unwrap();
 
@@ -628,6 +854,7 @@ mod tests {
             timestamp: "2026-02-17T12:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
 
         write_session_to(&path, &data).unwrap();
@@ -652,6 +879,7 @@ mod tests {
             timestamp: "2026-02-17T12:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
 
         write_session_to(&path, &data).unwrap();
@@ -664,6 +892,98 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn write_private_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("agentchrome-test-private-file");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("auth.json");
+
+        write_private_file(&path, b"{}").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // An existing world-readable file is tightened and overwritten
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_file(&path, b"[]").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn contexts_round_trip_and_skipped_when_empty() {
+        let dir = std::env::temp_dir().join("agentchrome-test-session-contexts");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("session.json");
+
+        let mut data = SessionData {
+            ws_url: "ws://127.0.0.1:9222/devtools/browser/ctx".into(),
+            port: 9222,
+            pid: None,
+            active_tab_id: None,
+            timestamp: "2026-02-17T12:00:00Z".into(),
+            last_reconnect_at: None,
+            reconnect_count: 0,
+            contexts: Vec::new(),
+        };
+
+        write_session_to(&path, &data).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("contexts"));
+
+        data.add_context("CTX-1");
+        data.add_context_tab("CTX-2", "TAB-1");
+        data.add_context_tab("CTX-2", "TAB-1");
+        write_session_to(&path, &data).unwrap();
+        let read = read_session_from(&path).unwrap().unwrap();
+        assert_eq!(read.contexts, data.contexts);
+        assert_eq!(read.contexts.len(), 2);
+        assert_eq!(read.contexts[1].tabs, ["TAB-1"]);
+        assert!(read.has_context_tab("TAB-1"));
+
+        data.remove_context_tabs(&["TAB-1".to_string()]);
+        assert!(!data.has_context_tab("TAB-1"));
+        data.remove_context("CTX-1");
+        assert_eq!(data.contexts.len(), 1);
+        assert_eq!(data.contexts[0].id, "CTX-2");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn retain_live_context_tabs_drops_closed_tabs() {
+        let mut data = SessionData {
+            ws_url: "ws://127.0.0.1:9222/devtools/browser/ctx".into(),
+            port: 9222,
+            pid: None,
+            active_tab_id: None,
+            timestamp: "2026-02-17T12:00:00Z".into(),
+            last_reconnect_at: None,
+            reconnect_count: 0,
+            contexts: Vec::new(),
+        };
+        data.add_context_tab("CTX-1", "TAB-1");
+        data.add_context_tab("CTX-1", "TAB-2");
+        data.add_context_tab("CTX-2", "TAB-3");
+
+        let live = std::collections::HashMap::from([
+            ("TAB-1".to_string(), "CTX-1".to_string()),
+            ("TAB-3".to_string(), "CTX-1".to_string()),
+        ]);
+        data.retain_live_context_tabs(&live);
+        assert!(data.has_context_tab("TAB-1"));
+        assert!(!data.has_context_tab("TAB-2"));
+        assert!(!data.has_context_tab("TAB-3"));
+        // Contexts themselves are kept; only their tab lists shrink.
+        assert_eq!(data.contexts.len(), 2);
+    }
+
     #[test]
     fn old_session_without_active_tab_id_deserializes() {
         let dir = std::env::temp_dir().join("agentchrome-test-session-compat");
@@ -729,6 +1049,10 @@ mod tests {
             timestamp: "2026-04-18T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 2,
+            contexts: vec![BrowserContextRecord {
+                id: "CTX-1".into(),
+                tabs: vec!["TAB-B".into()],
+            }],
         };
         write_session_to(&path, &original).unwrap();
 
@@ -745,6 +1069,7 @@ mod tests {
         assert_eq!(updated.active_tab_id.as_deref(), Some("TAB-A"));
         assert_eq!(updated.reconnect_count, 3);
         assert!(updated.last_reconnect_at.is_some());
+        assert_eq!(updated.contexts, original.contexts);
 
         // The persisted file matches the returned record
         let on_disk = read_session_from(&path).unwrap().unwrap();
@@ -847,6 +1172,7 @@ mod tests {
             timestamp: "2026-04-21T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
 
         write_session_to(&path, &data).unwrap();
@@ -882,6 +1208,7 @@ mod tests {
             timestamp: "2026-04-21T00:00:00Z".into(),
             last_reconnect_at: None,
             reconnect_count: 0,
+            contexts: Vec::new(),
         };
         write_session_to(&path, &data).unwrap();
         let read = read_session_from(&path).unwrap().unwrap();
@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use agentchrome::connection::ManagedSession;
 use agentchrome::error::AppError;
 
 // =============================================================================
@@ -70,6 +71,22 @@ const COMPACT_KEPT_ROLES: &[&str] = &[
 /// Roles unconditionally removed in compact mode (noise nodes).
 const COMPACT_EXCLUDED_ROLES: &[&str] = &["InlineTextBox", "LineBreak"];
 
+/// AX properties that describe a node's state and are compared by snapshot diffs.
+const DIFF_STATE_PROPERTIES: &[&str] = &[
+    "busy",
+    "checked",
+    "disabled",
+    "expanded",
+    "focused",
+    "invalid",
+    "modal",
+    "pressed",
+    "readonly",
+    "required",
+    "selected",
+    "valuetext",
+];
+
 // =============================================================================
 // Internal CDP node representation
 // =============================================================================
@@ -80,6 +97,7 @@ struct AxNode {
     ignored: bool,
     role: String,
     name: String,
+    value: Option<String>,
     properties: Vec<(String, serde_json::Value)>,
     child_ids: Vec<String>,
     backend_dom_node_id: Option<i64>,
@@ -117,6 +135,11 @@ fn parse_ax_nodes(nodes: &[serde_json::Value]) -> Vec<AxNode> {
                 ignored: n["ignored"].as_bool().unwrap_or(false),
                 role: n["role"]["value"].as_str().unwrap_or_default().to_string(),
                 name: n["name"]["value"].as_str().unwrap_or_default().to_string(),
+                value: match &n["value"]["value"] {
+                    serde_json::Value::Null => None,
+                    serde_json::Value::String(v) => Some(v.clone()),
+                    other => Some(other.to_string()),
+                },
                 properties,
                 child_ids,
                 backend_dom_node_id: n["backendDOMNodeId"].as_i64(),
@@ -156,25 +179,100 @@ pub struct BuildResult {
     pub uid_map: HashMap<String, i64>,
     pub truncated: bool,
     pub total_nodes: usize,
+    /// Flat node records persisted in `SnapshotState` for `--diff`.
+    pub nodes: Vec<DiffNode>,
+    /// Highest UID number handed out so far (for supplemental passes).
+    pub last_uid: usize,
+}
+
+/// Hands out UIDs during a tree build.
+///
+/// Elements whose backend DOM node was seen by an earlier snapshot keep their
+/// UID; new elements are numbered above every UID issued before, so a retired
+/// UID never silently points at a different element.
+#[derive(Debug, Default)]
+pub struct UidAssigner {
+    known: HashMap<i64, String>,
+    last: usize,
+}
+
+impl UidAssigner {
+    /// Number UIDs sequentially from `s{offset+1}`, ignoring earlier snapshots.
+    #[must_use]
+    pub fn sequential(offset: usize) -> Self {
+        Self {
+            known: HashMap::new(),
+            last: offset,
+        }
+    }
+
+    /// Carry forward the UIDs `previous` recorded for `frame_id` (`None` =
+    /// main frame). For aggregate snapshots only the UIDs that were issued in
+    /// that frame are carried, since backend node IDs are per frame target.
+    /// Nothing is carried once the page has loaded another `document` (see
+    /// [`document_id`]); numbering still continues above every earlier UID.
+    #[must_use]
+    pub fn carried_from(
+        previous: Option<&SnapshotState>,
+        frame_id: Option<&str>,
+        document: Option<&str>,
+    ) -> Self {
+        let Some(previous) = previous else {
+            return Self::sequential(0);
+        };
+        if previous.document.as_deref() != document {
+            return Self::sequential(uid_high_water(previous));
+        }
+        let mut known = HashMap::new();
+        for (uid, stale) in &previous.stale_uids {
+            if stale.frame_id.as_deref() == frame_id {
+                known.insert(stale.backend_node_id, uid.clone());
+            }
+        }
+        for (uid, backend_id) in &previous.uid_map {
+            if uid_in_frame(previous, uid, frame_id) {
+                known.insert(*backend_id, uid.clone());
+            }
+        }
+        Self {
+            known,
+            last: uid_high_water(previous),
+        }
+    }
+
+    /// Number new UIDs above `last` too, so the assigners of several frames
+    /// can share one high-water mark.
+    #[must_use]
+    pub fn continuing_from(mut self, last: usize) -> Self {
+        self.last = self.last.max(last);
+        self
+    }
+
+    fn assign(&mut self, backend_id: i64) -> String {
+        if let Some(uid) = self.known.get(&backend_id) {
+            return uid.clone();
+        }
+        self.last += 1;
+        let uid = format!("s{}", self.last);
+        self.known.insert(backend_id, uid.clone());
+        uid
+    }
 }
 
 /// Build a `SnapshotNode` tree from the flat CDP `Accessibility.getFullAXTree` response.
 ///
 /// Assigns sequential UIDs (`s1`, `s2`, ...) to interactive elements in depth-first order.
 /// Returns the root node and the uid-to-`backendDOMNodeId` mapping.
+#[cfg(test)]
 pub fn build_tree(nodes: &[serde_json::Value], verbose: bool) -> BuildResult {
-    build_tree_with_uid_offset(nodes, verbose, 0)
+    build_tree_with_uids(nodes, verbose, UidAssigner::sequential(0))
 }
 
-/// Build a snapshot tree, starting UID assignment at `s{uid_offset+1}`.
-///
-/// Used by aggregate snapshots (`page snapshot --include-iframes`) so that UIDs
-/// from different frames do not collide. Returns a `BuildResult` whose
-/// `uid_map` contains only the UIDs assigned in this call.
-pub fn build_tree_with_uid_offset(
+/// Build a snapshot tree, taking UIDs from `uids` (see [`UidAssigner`]).
+pub fn build_tree_with_uids(
     nodes: &[serde_json::Value],
     verbose: bool,
-    uid_offset: usize,
+    mut uids: UidAssigner,
 ) -> BuildResult {
     let mut ax_nodes = parse_ax_nodes(nodes);
     let total_nodes = ax_nodes.len();
@@ -220,7 +318,6 @@ pub fn build_tree_with_uid_offset(
         lookup.insert(&node.node_id, node);
     }
 
-    let mut uid_counter: usize = uid_offset;
     let mut uid_map: HashMap<String, i64> = HashMap::new();
     let mut node_count: usize = 0;
     let truncated = total_nodes > MAX_NODES;
@@ -229,7 +326,7 @@ pub fn build_tree_with_uid_offset(
         &root_id,
         &lookup,
         verbose,
-        &mut uid_counter,
+        &mut uids,
         &mut uid_map,
         &mut node_count,
         truncated,
@@ -248,11 +345,15 @@ pub fn build_tree_with_uid_offset(
         }
     };
 
+    let nodes = diff_nodes(&ax_nodes, &uid_map);
+
     BuildResult {
         root,
         uid_map,
         truncated,
         total_nodes,
+        nodes,
+        last_uid: uids.last,
     }
 }
 
@@ -260,7 +361,7 @@ fn build_subtree(
     node_id: &str,
     lookup: &HashMap<&str, &AxNode>,
     verbose: bool,
-    uid_counter: &mut usize,
+    uids: &mut UidAssigner,
     uid_map: &mut HashMap<String, i64>,
     node_count: &mut usize,
     truncated: bool,
@@ -279,15 +380,7 @@ fn build_subtree(
             .child_ids
             .iter()
             .flat_map(|cid| {
-                build_subtree(
-                    cid,
-                    lookup,
-                    verbose,
-                    uid_counter,
-                    uid_map,
-                    node_count,
-                    truncated,
-                )
+                build_subtree(cid, lookup, verbose, uids, uid_map, node_count, truncated)
             })
             .collect();
     }
@@ -297,8 +390,7 @@ fn build_subtree(
     // Assign UID if interactive and has a backend node ID
     let uid = if INTERACTIVE_ROLES.contains(&ax.role.as_str()) {
         if let Some(backend_id) = ax.backend_dom_node_id {
-            *uid_counter += 1;
-            let uid = format!("s{uid_counter}");
+            let uid = uids.assign(backend_id);
             uid_map.insert(uid.clone(), backend_id);
             Some(uid)
         } else {
@@ -320,17 +412,7 @@ fn build_subtree(
     let children: Vec<SnapshotNode> = ax
         .child_ids
         .iter()
-        .flat_map(|cid| {
-            build_subtree(
-                cid,
-                lookup,
-                verbose,
-                uid_counter,
-                uid_map,
-                node_count,
-                truncated,
-            )
-        })
+        .flat_map(|cid| build_subtree(cid, lookup, verbose, uids, uid_map, node_count, truncated))
         .collect();
 
     vec![SnapshotNode {
@@ -344,6 +426,220 @@ fn build_subtree(
     }]
 }
 
+// =============================================================================
+// Snapshot diffing
+// =============================================================================
+
+/// Comparable record of one accessibility node, persisted with the snapshot
+/// state so the next snapshot can be diffed against it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffNode {
+    /// Stable identity: `b<backendDOMNodeId>`, or `ax<nodeId>` for nodes without a DOM node.
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    pub role: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub state: BTreeMap<String, serde_json::Value>,
+}
+
+/// A node present in both snapshots whose role, name, value, or state changed.
+#[derive(Debug, Serialize)]
+pub struct ChangedNode {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    pub role: String,
+    pub name: String,
+    /// Field name → `{"from": old, "to": new}`; state fields use their AX property name.
+    pub changes: BTreeMap<String, serde_json::Value>,
+}
+
+/// Difference between two snapshots, keyed by node identity.
+#[derive(Debug, Serialize)]
+pub struct SnapshotDiff {
+    pub added: Vec<DiffNode>,
+    pub removed: Vec<DiffNode>,
+    pub changed: Vec<ChangedNode>,
+    pub unchanged: usize,
+}
+
+/// Flatten non-ignored AX nodes (minus inline text boxes) into diff records.
+fn diff_nodes(ax_nodes: &[AxNode], uid_map: &HashMap<String, i64>) -> Vec<DiffNode> {
+    let uid_by_backend: HashMap<i64, &str> = uid_map
+        .iter()
+        .map(|(uid, backend)| (*backend, uid.as_str()))
+        .collect();
+    ax_nodes
+        .iter()
+        .filter(|n| !n.ignored && !COMPACT_EXCLUDED_ROLES.contains(&n.role.as_str()))
+        .take(MAX_NODES)
+        .map(|n| DiffNode {
+            key: n
+                .backend_dom_node_id
+                .map_or_else(|| format!("ax{}", n.node_id), |id| format!("b{id}")),
+            uid: n
+                .backend_dom_node_id
+                .and_then(|id| uid_by_backend.get(&id))
+                .map(|uid| (*uid).to_string()),
+            role: n.role.clone(),
+            name: n.name.clone(),
+            value: n.value.clone(),
+            state: n
+                .properties
+                .iter()
+                .filter(|(k, _)| DIFF_STATE_PROPERTIES.contains(&k.as_str()))
+                .cloned()
+                .collect(),
+        })
+        .collect()
+}
+
+/// Compare two flat snapshots. Added and changed nodes follow `after` order;
+/// removed nodes follow `before` order.
+pub fn diff_snapshots(before: &[DiffNode], after: &[DiffNode]) -> SnapshotDiff {
+    let previous: HashMap<&str, &DiffNode> = before.iter().map(|n| (n.key.as_str(), n)).collect();
+    let current: HashSet<&str> = after.iter().map(|n| n.key.as_str()).collect();
+
+    let mut diff = SnapshotDiff {
+        added: Vec::new(),
+        removed: before
+            .iter()
+            .filter(|n| !current.contains(n.key.as_str()))
+            .cloned()
+            .collect(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+    for node in after {
+        let Some(old) = previous.get(node.key.as_str()) else {
+            diff.added.push(node.clone());
+            continue;
+        };
+        let changes = node_changes(old, node);
+        if changes.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            diff.changed.push(ChangedNode {
+                key: node.key.clone(),
+                uid: node.uid.clone(),
+                role: node.role.clone(),
+                name: node.name.clone(),
+                changes,
+            });
+        }
+    }
+    diff
+}
+
+fn node_changes(old: &DiffNode, new: &DiffNode) -> BTreeMap<String, serde_json::Value> {
+    let mut changes = BTreeMap::new();
+    let mut record = |field: &str, from: serde_json::Value, to: serde_json::Value| {
+        if from != to {
+            changes.insert(
+                field.to_string(),
+                serde_json::json!({ "from": from, "to": to }),
+            );
+        }
+    };
+    record("role", old.role.clone().into(), new.role.clone().into());
+    record("name", old.name.clone().into(), new.name.clone().into());
+    record("value", old.value.clone().into(), new.value.clone().into());
+    let fields: std::collections::BTreeSet<&String> =
+        old.state.keys().chain(new.state.keys()).collect();
+    for field in fields {
+        record(
+            field,
+            old.state.get(field).cloned().unwrap_or_default(),
+            new.state.get(field).cloned().unwrap_or_default(),
+        );
+    }
+    changes
+}
+
+/// Format a snapshot diff as text: `+` added, `-` removed, `~` changed.
+pub fn format_diff_text(diff: &SnapshotDiff) -> String {
+    use std::fmt::Write;
+
+    let label = |role: &str, name: &str, uid: Option<&String>| {
+        let uid_str = uid.map_or(String::new(), |uid| format!(" [{uid}]"));
+        format!("{role} \"{name}\"{uid_str}")
+    };
+    let mut output = String::new();
+    for node in &diff.added {
+        let _ = writeln!(
+            output,
+            "+ {}",
+            label(&node.role, &node.name, node.uid.as_ref())
+        );
+    }
+    for node in &diff.removed {
+        let _ = writeln!(
+            output,
+            "- {}",
+            label(&node.role, &node.name, node.uid.as_ref())
+        );
+    }
+    for node in &diff.changed {
+        let changes: Vec<String> = node
+            .changes
+            .iter()
+            .map(|(field, change)| format!("{field}: {} -> {}", change["from"], change["to"]))
+            .collect();
+        let _ = writeln!(
+            output,
+            "~ {} {}",
+            label(&node.role, &node.name, node.uid.as_ref()),
+            changes.join(", ")
+        );
+    }
+    if output.is_empty() {
+        output.push_str("No changes\n");
+    }
+    output
+}
+
+/// Diff `state` against the previously persisted snapshot state and render
+/// the JSON payload for `page snapshot --diff` and `--include-snapshot-diff`.
+///
+/// Without a usable baseline every node is reported as added and `baseline`
+/// is `null`. A baseline taken on a different document is not compared
+/// against (its backend node IDs may name unrelated elements): every node is
+/// reported as added and `baseline.document_changed` is `true`.
+pub fn diff_against_state(
+    previous: Option<&SnapshotState>,
+    state: &SnapshotState,
+) -> (SnapshotDiff, serde_json::Value) {
+    let baseline = previous.filter(|p| !p.nodes.is_empty());
+    let document_changed = baseline.is_some_and(|p| p.document != state.document);
+    let baseline_nodes = match baseline {
+        Some(p) if !document_changed => p.nodes.as_slice(),
+        _ => &[],
+    };
+    let diff = diff_snapshots(baseline_nodes, &state.nodes);
+    let json = serde_json::json!({
+        "url": state.url,
+        "baseline": baseline.map(|p| {
+            let mut baseline = serde_json::json!({
+                "url": p.url,
+                "timestamp": p.timestamp,
+            });
+            if document_changed {
+                baseline["document_changed"] = serde_json::Value::Bool(true);
+            }
+            baseline
+        }),
+        "added": diff.added,
+        "removed": diff.removed,
+        "changed": diff.changed,
+        "unchanged": diff.unchanged,
+    });
+    (diff, json)
+}
+
 // =============================================================================
 // Tree search
 // =============================================================================
@@ -636,8 +932,9 @@ pub struct SnapshotState {
     /// Whether this snapshot aggregates content from multiple frames (`--include-iframes`).
     #[serde(default, skip_serializing_if = "is_false")]
     pub aggregate: bool,
-    /// Frame index → (inclusive) UID numeric range assigned to that frame's elements.
-    /// Only populated when `aggregate == true`. Each entry is `(frame_index, (min_uid, max_uid))`.
+    /// Frame index → (inclusive) UID numeric ranges assigned to that frame's elements.
+    /// Only populated when `aggregate == true`. Each entry is `(frame_index, (min_uid, max_uid))`;
+    /// a frame has several entries when its carried-forward UIDs are not contiguous.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub frame_uid_ranges: Vec<(u32, (u32, u32))>,
     /// Frame index → CDP frame ID mapping, parallel to `frame_uid_ranges`.
@@ -645,16 +942,245 @@ pub struct SnapshotState {
     /// has been detached or navigated away before executing.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub frame_ids: Vec<(u32, String)>,
+    /// Flat node records used as the baseline for the next `--diff`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<DiffNode>,
+    /// UIDs whose elements disappeared in a later snapshot, with their last
+    /// known role and name. Filled in by `write_snapshot_state`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub stale_uids: HashMap<String, StaleUid>,
+    /// Highest UID number ever issued in this session, so retired UIDs are
+    /// never reassigned. Filled in by `write_snapshot_state`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub last_uid: u32,
+    /// Loader ID of the main-frame document the UIDs were issued in (see
+    /// [`document_id`]). Backend node IDs are only meaningful within it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document: Option<String>,
 }
 
+/// Last known identity of an element whose UID has been retired.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaleUid {
+    pub backend_node_id: i64,
+    pub role: String,
+    pub name: String,
+    /// CDP frame ID the element lived in (None = main frame).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_id: Option<String>,
+}
+
+/// Maximum number of retired UIDs remembered for stale-uid errors.
+const MAX_STALE_UIDS: usize = MAX_NODES;
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 fn is_false(b: &bool) -> bool {
     !*b
 }
 
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+/// Highest UID number recorded anywhere in `state`.
+fn uid_high_water(state: &SnapshotState) -> usize {
+    let highest = state
+        .uid_map
+        .keys()
+        .chain(state.stale_uids.keys())
+        .filter_map(|uid| parse_uid_number(uid))
+        .fold(state.last_uid, u32::max);
+    usize::try_from(highest).unwrap_or(usize::MAX)
+}
+
+/// Whether the live UID `uid` of `state` was issued in `frame_id` (None =
+/// main frame). Aggregate UIDs outside every recorded range match no frame.
+pub(crate) fn uid_in_frame(state: &SnapshotState, uid: &str, frame_id: Option<&str>) -> bool {
+    if !state.aggregate {
+        return state.frame_id.as_deref() == frame_id;
+    }
+    match aggregate_frame_for_uid(state, uid) {
+        None => false,
+        Some(0) => frame_id.is_none(),
+        Some(index) => frame_id.is_some() && aggregate_frame_id(state, index) == frame_id,
+    }
+}
+
+/// CDP frame ID of the frame the live UID `uid` of `state` was issued in
+/// (None = main frame).
+fn uid_frame_id(state: &SnapshotState, uid: &str) -> Option<String> {
+    if !state.aggregate {
+        return state.frame_id.clone();
+    }
+    match aggregate_frame_for_uid(state, uid)? {
+        0 => None,
+        index => aggregate_frame_id(state, index).map(str::to_string),
+    }
+}
+
+/// Collapse the UIDs in `uid_map` into sorted, inclusive runs of consecutive
+/// numbers, as recorded in [`SnapshotState::frame_uid_ranges`].
+#[must_use]
+pub fn uid_ranges(uid_map: &HashMap<String, i64>) -> Vec<(u32, u32)> {
+    let mut numbers: Vec<u32> = uid_map.keys().filter_map(|u| parse_uid_number(u)).collect();
+    numbers.sort_unstable();
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for n in numbers {
+        match ranges.last_mut() {
+            Some((_, hi)) if *hi + 1 == n => *hi = n,
+            _ => ranges.push((n, n)),
+        }
+    }
+    ranges
+}
+
+/// Merge UID bookkeeping from `previous` into a freshly built `state`.
+///
+/// UIDs that were live in `previous` but are gone from `state` are retired
+/// with their last known role and name; UIDs that came back are un-retired.
+/// When `state` covers a different document, only the UID high-water mark
+/// survives: the old backend node IDs may now name unrelated elements.
+#[must_use]
+pub fn retire_uids(previous: Option<&SnapshotState>, state: &SnapshotState) -> SnapshotState {
+    let mut next = state.clone();
+    let Some(previous) = previous else {
+        next.last_uid = u32::try_from(uid_high_water(state)).unwrap_or(u32::MAX);
+        return next;
+    };
+    if previous.document != state.document {
+        next.stale_uids = HashMap::new();
+        next.last_uid =
+            u32::try_from(uid_high_water(previous).max(uid_high_water(state))).unwrap_or(u32::MAX);
+        return next;
+    }
+
+    let mut retired = previous.stale_uids.clone();
+    for (uid, backend_id) in &previous.uid_map {
+        if state.uid_map.contains_key(uid) {
+            continue;
+        }
+        let last_seen = previous
+            .nodes
+            .iter()
+            .find(|n| n.uid.as_deref() == Some(uid));
+        retired.insert(
+            uid.clone(),
+            StaleUid {
+                backend_node_id: *backend_id,
+                role: last_seen.map(|n| n.role.clone()).unwrap_or_default(),
+                name: last_seen.map(|n| n.name.clone()).unwrap_or_default(),
+                frame_id: uid_frame_id(previous, uid),
+            },
+        );
+    }
+    retired.retain(|uid, _| !state.uid_map.contains_key(uid));
+    if retired.len() > MAX_STALE_UIDS {
+        // Forget the oldest (lowest-numbered) retirements first.
+        let mut numbers: Vec<u32> = retired.keys().filter_map(|u| parse_uid_number(u)).collect();
+        numbers.sort_unstable();
+        let cutoff = numbers[retired.len() - MAX_STALE_UIDS];
+        retired.retain(|uid, _| parse_uid_number(uid).is_some_and(|n| n >= cutoff));
+    }
+
+    next.stale_uids = retired;
+    next.last_uid =
+        u32::try_from(uid_high_water(previous).max(uid_high_water(state))).unwrap_or(u32::MAX);
+    next
+}
+
+/// Look up the backend DOM node recorded for `uid`.
+///
+/// # Errors
+///
+/// Returns a stale-uid error for UIDs retired by a later snapshot, and
+/// `uid_not_found` for UIDs that were never issued.
+pub fn lookup_uid(state: &SnapshotState, uid: &str) -> Result<i64, AppError> {
+    if let Some(backend_node_id) = state.uid_map.get(uid) {
+        return Ok(*backend_node_id);
+    }
+    if state.stale_uids.contains_key(uid) {
+        return Err(stale_uid_error(state, uid));
+    }
+    Err(AppError::uid_not_found(uid))
+}
+
+/// Stale-uid error carrying the element's last known role and name, when recorded.
+#[must_use]
+pub fn stale_uid_error(state: &SnapshotState, uid: &str) -> AppError {
+    let last_known = state
+        .stale_uids
+        .get(uid)
+        .map(|s| (s.role.as_str(), s.name.as_str()))
+        .or_else(|| {
+            state
+                .nodes
+                .iter()
+                .find(|n| n.uid.as_deref() == Some(uid))
+                .map(|n| (n.role.as_str(), n.name.as_str()))
+        });
+    match last_known {
+        Some((role, name)) => AppError::stale_uid_last_known(uid, role, name),
+        None => AppError::stale_uid(uid),
+    }
+}
+
+/// Identify the document currently loaded in the main frame: its loader ID,
+/// which changes on every navigation. Returns `None` if it cannot be read.
+pub async fn document_id(session: &ManagedSession) -> Option<String> {
+    let tree = session.send_command("Page.getFrameTree", None).await.ok()?;
+    tree["frameTree"]["frame"]["loaderId"]
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Resolve `uid` to its backend DOM node and confirm that node is still
+/// attached to the document.
+///
+/// # Errors
+///
+/// Returns `no_snapshot_state` without a prior snapshot, `uid_not_found` for
+/// unknown UIDs, and a stale-uid error when the element has disappeared.
+pub async fn resolve_uid(session: &ManagedSession, uid: &str) -> Result<i64, AppError> {
+    let state = read_snapshot_state()?.ok_or_else(AppError::no_snapshot_state)?;
+    let backend_node_id = lookup_uid(&state, uid)?;
+    if !node_is_connected(session, backend_node_id).await {
+        return Err(stale_uid_error(&state, uid));
+    }
+    Ok(backend_node_id)
+}
+
+/// Whether a backend node still resolves and is connected to its document.
+async fn node_is_connected(session: &ManagedSession, backend_node_id: i64) -> bool {
+    let Ok(resolved) = session
+        .send_command(
+            "DOM.resolveNode",
+            Some(serde_json::json!({ "backendNodeId": backend_node_id })),
+        )
+        .await
+    else {
+        return false;
+    };
+    let Some(object_id) = resolved["object"]["objectId"].as_str() else {
+        return false;
+    };
+    // Only a definite `false` counts as detached.
+    session
+        .send_command(
+            "Runtime.callFunctionOn",
+            Some(serde_json::json!({
+                "objectId": object_id,
+                "functionDeclaration": "function() { return this.isConnected; }",
+                "returnByValue": true,
+            })),
+        )
+        .await
+        .is_ok_and(|r| r["result"]["value"].as_bool() != Some(false))
+}
+
 /// Parse the numeric portion of a UID (`s42` → `42`). Returns `None` if the
 /// input is not a valid UID.
-fn parse_uid_number(uid: &str) -> Option<u32> {
+pub fn parse_uid_number(uid: &str) -> Option<u32> {
     if !is_uid(uid) {
         return None;
     }
@@ -662,7 +1188,7 @@ fn parse_uid_number(uid: &str) -> Option<u32> {
 }
 
 /// Given an aggregate snapshot state and a UID, return the index of the frame
-/// whose UID range contains this UID. Returns `None` if the state is not
+/// with a UID range containing this UID. Returns `None` if the state is not
 /// aggregate or the UID does not fall in any recorded range.
 pub fn aggregate_frame_for_uid(state: &SnapshotState, uid: &str) -> Option<u32> {
     if !state.aggregate {
@@ -730,23 +1256,22 @@ impl From<SnapshotStateError> for AppError {
     }
 }
 
-/// Returns the path to `~/.agentchrome/snapshot.json`.
+/// Returns the path to `snapshot.json` in the selected session's state
+/// directory.
 fn snapshot_state_path() -> Result<PathBuf, SnapshotStateError> {
-    #[cfg(unix)]
-    let key = "HOME";
-    #[cfg(windows)]
-    let key = "USERPROFILE";
-
-    let home = std::env::var(key)
-        .map(PathBuf::from)
-        .map_err(|_| SnapshotStateError::NoHomeDir)?;
-    Ok(home.join(".agentchrome").join("snapshot.json"))
+    let session_path =
+        agentchrome::session::session_file_path().map_err(|_| SnapshotStateError::NoHomeDir)?;
+    Ok(session_path.with_file_name("snapshot.json"))
 }
 
-/// Write snapshot state to `~/.agentchrome/snapshot.json` using atomic write.
+/// Write snapshot state to the session's `snapshot.json` using atomic write.
+///
+/// UIDs that were live in the previous state but missing from `state` are
+/// retired (see [`retire_uids`]) so later lookups report them as stale.
 pub fn write_snapshot_state(state: &SnapshotState) -> Result<(), SnapshotStateError> {
     let path = snapshot_state_path()?;
-    write_snapshot_state_to(&path, state)
+    let previous = read_snapshot_state_from(&path).ok().flatten();
+    write_snapshot_state_to(&path, &retire_uids(previous.as_ref(), state))
 }
 
 /// Write snapshot state to a specific path (testable variant).
@@ -779,7 +1304,7 @@ pub fn write_snapshot_state_to(
     Ok(())
 }
 
-/// Read snapshot state from `~/.agentchrome/snapshot.json`.
+/// Read snapshot state from the session's `snapshot.json`.
 ///
 /// Returns `Ok(None)` if the file does not exist.
 ///
@@ -1274,6 +1799,10 @@ mod tests {
             aggregate: false,
             frame_uid_ranges: Vec::new(),
             frame_ids: Vec::new(),
+            nodes: Vec::new(),
+            stale_uids: HashMap::new(),
+            last_uid: 0,
+            document: None,
         };
 
         write_snapshot_state_to(&path, &state).unwrap();
@@ -1301,6 +1830,10 @@ mod tests {
             aggregate: true,
             frame_uid_ranges: vec![(0, (1, 1)), (1, (2, 2))],
             frame_ids: vec![(0, "MAIN".to_string()), (1, "F1".to_string())],
+            nodes: Vec::new(),
+            stale_uids: HashMap::new(),
+            last_uid: 0,
+            document: None,
         };
 
         write_snapshot_state_to(&path, &state).unwrap();
@@ -1996,6 +2529,315 @@ mod tests {
         assert_eq!(result.children[0].children[0].children[0].role, "checkbox");
     }
 
+    fn diff_node(key: &str, role: &str, name: &str) -> DiffNode {
+        DiffNode {
+            key: key.to_string(),
+            uid: None,
+            role: role.to_string(),
+            name: name.to_string(),
+            value: None,
+            state: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn build_tree_records_diff_nodes() {
+        let mut nodes = sample_cdp_nodes();
+        nodes[1]["value"] = json!({"type": "string", "value": "hi"});
+        nodes[1]["properties"] = json!([
+            {"name": "focused", "value": {"type": "boolean", "value": true}},
+            {"name": "level", "value": {"type": "integer", "value": 2}}
+        ]);
+        let result = build_tree(&nodes, false);
+        let first = &result.nodes[1];
+        assert!(first.key.starts_with('b'));
+        assert_eq!(first.value.as_deref(), Some("hi"));
+        assert_eq!(first.state.get("focused"), Some(&json!(true)));
+        assert!(!first.state.contains_key("level"));
+        let with_uid = result.nodes.iter().filter(|n| n.uid.is_some()).count();
+        assert_eq!(with_uid, result.uid_map.len());
+    }
+
+    #[test]
+    fn diff_snapshots_reports_added_removed_and_changed() {
+        let mut checkbox = diff_node("b2", "checkbox", "Agree");
+        checkbox.state.insert("checked".into(), json!("false"));
+        let before = vec![
+            diff_node("b1", "button", "Save"),
+            checkbox.clone(),
+            diff_node("b3", "dialog", "Confirm"),
+        ];
+        checkbox.state.insert("checked".into(), json!("true"));
+        let after = vec![
+            diff_node("b1", "button", "Save"),
+            checkbox,
+            diff_node("b4", "status", "Saved"),
+        ];
+
+        let diff = diff_snapshots(&before, &after);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, "b4");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].key, "b3");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].changes["checked"],
+            json!({"from": "false", "to": "true"})
+        );
+    }
+
+    #[test]
+    fn diff_snapshots_detects_name_and_value_changes() {
+        let before = vec![diff_node("b1", "textbox", "Email")];
+        let mut renamed = diff_node("b1", "textbox", "E-mail");
+        renamed.value = Some("a@b.c".into());
+        let diff = diff_snapshots(&before, &[renamed]);
+        let changes = &diff.changed[0].changes;
+        assert_eq!(changes["name"], json!({"from": "Email", "to": "E-mail"}));
+        assert_eq!(changes["value"], json!({"from": null, "to": "a@b.c"}));
+    }
+
+    fn diff_state(document: &str, nodes: Vec<DiffNode>) -> SnapshotState {
+        SnapshotState {
+            url: "https://example.com/".into(),
+            timestamp: "2026-10-17T00:00:00Z".into(),
+            uid_map: HashMap::new(),
+            frame_index: None,
+            frame_id: None,
+            aggregate: false,
+            frame_uid_ranges: Vec::new(),
+            frame_ids: Vec::new(),
+            nodes,
+            stale_uids: HashMap::new(),
+            last_uid: 0,
+            document: Some(document.into()),
+        }
+    }
+
+    #[test]
+    fn diff_against_state_without_baseline_reports_all_added() {
+        let state = diff_state("L1", vec![diff_node("b1", "button", "Save")]);
+        let (diff, json) = diff_against_state(None, &state);
+        assert_eq!(diff.added.len(), 1);
+        assert!(json["baseline"].is_null());
+        assert_eq!(json["url"], "https://example.com/");
+    }
+
+    #[test]
+    fn diff_against_state_ignores_baseline_from_another_document() {
+        let previous = diff_state("L1", vec![diff_node("b1", "button", "Save")]);
+        let state = diff_state("L2", vec![diff_node("b1", "link", "Home")]);
+        let (diff, json) = diff_against_state(Some(&previous), &state);
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.removed.is_empty() && diff.changed.is_empty());
+        assert_eq!(json["baseline"]["document_changed"], true);
+
+        let same = diff_state("L1", vec![diff_node("b1", "link", "Home")]);
+        let (diff, json) = diff_against_state(Some(&previous), &same);
+        assert_eq!(diff.changed.len(), 1);
+        assert!(json["baseline"].get("document_changed").is_none());
+    }
+
+    #[test]
+    fn format_diff_text_marks_each_kind() {
+        let mut added = diff_node("b4", "status", "Saved");
+        added.uid = Some("s9".into());
+        let diff = SnapshotDiff {
+            added: vec![added],
+            removed: vec![diff_node("b3", "dialog", "Confirm")],
+            changed: Vec::new(),
+            unchanged: 0,
+        };
+        let text = format_diff_text(&diff);
+        assert!(text.contains("+ status \"Saved\" [s9]"));
+        assert!(text.contains("- dialog \"Confirm\""));
+        let empty = SnapshotDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+            unchanged: 3,
+        };
+        assert_eq!(format_diff_text(&empty), "No changes\n");
+    }
+
+    fn button_nodes(buttons: &[(i64, &str)]) -> Vec<serde_json::Value> {
+        let child_ids: Vec<String> = (0..buttons.len()).map(|i| format!("b{i}")).collect();
+        let mut nodes = vec![json!({
+            "nodeId": "root",
+            "ignored": false,
+            "role": {"value": "RootWebArea"},
+            "name": {"value": "Page"},
+            "childIds": child_ids,
+            "backendDOMNodeId": 1
+        })];
+        for (i, (backend_id, name)) in buttons.iter().enumerate() {
+            nodes.push(json!({
+                "nodeId": format!("b{i}"),
+                "ignored": false,
+                "role": {"value": "button"},
+                "name": {"value": name},
+                "childIds": [],
+                "backendDOMNodeId": backend_id
+            }));
+        }
+        nodes
+    }
+
+    fn state_from(build: BuildResult, frame_id: Option<&str>) -> SnapshotState {
+        SnapshotState {
+            url: String::new(),
+            timestamp: String::new(),
+            uid_map: build.uid_map,
+            frame_index: None,
+            frame_id: frame_id.map(str::to_string),
+            aggregate: false,
+            frame_uid_ranges: Vec::new(),
+            frame_ids: Vec::new(),
+            nodes: build.nodes,
+            stale_uids: HashMap::new(),
+            last_uid: 0,
+            document: None,
+        }
+    }
+
+    #[test]
+    fn carried_uids_survive_inserted_elements() {
+        let first = build_tree(&button_nodes(&[(10, "Save"), (20, "Cancel")]), false);
+        assert_eq!(first.uid_map.get("s2"), Some(&20));
+        let previous = retire_uids(None, &state_from(first, None));
+
+        // A new button appears before the others.
+        let nodes = button_nodes(&[(30, "Help"), (10, "Save"), (20, "Cancel")]);
+        let uids = UidAssigner::carried_from(Some(&previous), None, None);
+        let second = build_tree_with_uids(&nodes, false, uids);
+        assert_eq!(second.uid_map.get("s1"), Some(&10));
+        assert_eq!(second.uid_map.get("s2"), Some(&20));
+        assert_eq!(second.uid_map.get("s3"), Some(&30));
+        assert_eq!(second.last_uid, 3);
+    }
+
+    #[test]
+    fn carried_uids_are_not_shared_across_frames() {
+        let first = build_tree(&button_nodes(&[(10, "Save")]), false);
+        let previous = retire_uids(None, &state_from(first, None));
+        let uids = UidAssigner::carried_from(Some(&previous), Some("FRAME"), None);
+        let second = build_tree_with_uids(&button_nodes(&[(10, "Other")]), false, uids);
+        // Same backend id in another frame is a different element: new number.
+        assert_eq!(second.uid_map.get("s2"), Some(&10));
+        assert!(!second.uid_map.contains_key("s1"));
+    }
+
+    #[test]
+    fn carried_uids_follow_their_frame_in_aggregate_snapshots() {
+        let mut previous = agg_state(vec![(0, (1, 1)), (1, (2, 2))]);
+        // The same backend id in two frames is two different elements.
+        previous.uid_map = HashMap::from([("s1".into(), 10), ("s2".into(), 10)]);
+        previous.last_uid = 2;
+
+        let main = build_tree_with_uids(
+            &button_nodes(&[(10, "Save")]),
+            false,
+            UidAssigner::carried_from(Some(&previous), None, None),
+        );
+        assert_eq!(main.uid_map.get("s1"), Some(&10));
+
+        let uids = UidAssigner::carried_from(Some(&previous), Some("F1"), None).continuing_from(5);
+        let frame = build_tree_with_uids(&button_nodes(&[(10, "Pay"), (20, "New")]), false, uids);
+        assert_eq!(frame.uid_map.get("s2"), Some(&10));
+        assert_eq!(frame.uid_map.get("s6"), Some(&20));
+        assert_eq!(uid_ranges(&frame.uid_map), vec![(2, 2), (6, 6)]);
+
+        // An aggregate UID that disappears is retired with its frame.
+        let mut current = agg_state(vec![(0, (1, 1))]);
+        current.uid_map = HashMap::from([("s1".into(), 10)]);
+        let current = retire_uids(Some(&previous), &current);
+        assert_eq!(current.stale_uids["s2"].frame_id.as_deref(), Some("F1"));
+        let uids = UidAssigner::carried_from(Some(&current), Some("F1"), None);
+        let back = build_tree_with_uids(&button_nodes(&[(10, "Pay")]), false, uids);
+        assert_eq!(back.uid_map.get("s2"), Some(&10));
+    }
+
+    #[test]
+    fn new_document_starts_a_fresh_uid_map() {
+        let mut first = state_from(
+            build_tree(&button_nodes(&[(10, "Save"), (20, "Cancel")]), false),
+            None,
+        );
+        first.document = Some("L1".into());
+        let previous = retire_uids(None, &first);
+
+        // A cross-process navigation can hand the old backend ids to new elements.
+        let uids = UidAssigner::carried_from(Some(&previous), None, Some("L2"));
+        let second = build_tree_with_uids(&button_nodes(&[(20, "Buy")]), false, uids);
+        assert_eq!(second.uid_map.get("s3"), Some(&20));
+        assert!(!second.uid_map.contains_key("s2"));
+
+        let mut next = state_from(second, None);
+        next.document = Some("L2".into());
+        let current = retire_uids(Some(&previous), &next);
+        assert!(current.stale_uids.is_empty());
+        assert_eq!(current.last_uid, 3);
+        let err = lookup_uid(&current, "s2").expect_err("old UID must not resolve");
+        assert!(err.message.contains("not found"));
+
+        // The same document keeps carrying UIDs forward.
+        let uids = UidAssigner::carried_from(Some(&current), None, Some("L2"));
+        let third = build_tree_with_uids(&button_nodes(&[(20, "Buy")]), false, uids);
+        assert_eq!(third.uid_map.get("s3"), Some(&20));
+    }
+
+    #[test]
+    fn retire_uids_records_last_known_identity() {
+        let first = build_tree(&button_nodes(&[(10, "Save"), (20, "Cancel")]), false);
+        let previous = retire_uids(None, &state_from(first, None));
+        assert_eq!(previous.last_uid, 2);
+
+        let uids = UidAssigner::carried_from(Some(&previous), None, None);
+        let second = build_tree_with_uids(&button_nodes(&[(10, "Save")]), false, uids);
+        let current = retire_uids(Some(&previous), &state_from(second, None));
+
+        assert_eq!(current.last_uid, 2);
+        assert_eq!(
+            current.stale_uids.get("s2"),
+            Some(&StaleUid {
+                backend_node_id: 20,
+                role: "button".into(),
+                name: "Cancel".into(),
+                frame_id: None,
+            })
+        );
+        let err = lookup_uid(&current, "s2").expect_err("retired UID must be stale");
+        assert!(err.message.contains("no longer exists"));
+        assert!(err.message.contains("button \"Cancel\""));
+        assert_eq!(lookup_uid(&current, "s1").unwrap(), 10);
+        let err = lookup_uid(&current, "s9").expect_err("unknown UID");
+        assert!(err.message.contains("not found"));
+
+        // New elements never reuse a retired number; a returning element gets its UID back.
+        let uids = UidAssigner::carried_from(Some(&current), None, None);
+        let third = build_tree_with_uids(
+            &button_nodes(&[(10, "Save"), (40, "New"), (20, "Cancel")]),
+            false,
+            uids,
+        );
+        assert_eq!(third.uid_map.get("s3"), Some(&40));
+        assert_eq!(third.uid_map.get("s2"), Some(&20));
+        let revived = retire_uids(Some(&current), &state_from(third, None));
+        assert!(revived.stale_uids.is_empty());
+    }
+
+    #[test]
+    fn stale_uid_error_uses_live_node_identity() {
+        let build = build_tree(&button_nodes(&[(10, "Save")]), false);
+        let state = state_from(build, None);
+        let err = stale_uid_error(&state, "s1");
+        let json: serde_json::Value =
+            serde_json::from_str(err.custom_json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["kind"], "stale_uid");
+        assert_eq!(json["last_known"]["name"], "Save");
+    }
+
     fn agg_state(ranges: Vec<(u32, (u32, u32))>) -> SnapshotState {
         SnapshotState {
             url: String::new(),
@@ -2010,6 +2852,10 @@ mod tests {
                 (1, "F1".to_string()),
                 (2, "F2".to_string()),
             ],
+            nodes: Vec::new(),
+            stale_uids: HashMap::new(),
+            last_uid: 0,
+            document: None,
         }
     }
 
@@ -2075,7 +2921,7 @@ mod tests {
                 "backendDOMNodeId": 99
             }),
         ];
-        let result = build_tree_with_uid_offset(&nodes, false, 10);
+        let result = build_tree_with_uids(&nodes, false, UidAssigner::sequential(10));
         assert!(result.uid_map.contains_key("s11"));
         assert_eq!(result.uid_map.get("s11"), Some(&99));
     }
@@ -0,0 +1,544 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::session;
+
+use crate::cli::{GlobalOpts, StateArgs, StateCommand, StateLoadArgs, StateSaveArgs, WaitUntil};
+use crate::cookie::{
+    CookieInfo, ImportCookie, ImportFailure, parse_cookies, print_failures_plain, set_cookies,
+};
+use crate::navigate::{DEFAULT_NAVIGATE_TIMEOUT_MS, navigate_and_wait};
+use crate::output::{print_output, setup_session};
+use crate::storage::{frame_tree_origins, get_items, set_item, storage_id};
+
+/// Document served for origins loaded while seeding storage.
+const SEED_DOCUMENT: &str = "<!doctype html><title></title>";
+
+// =============================================================================
+// State file format (Playwright storageState)
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StorageState {
+    #[serde(default)]
+    cookies: Vec<StateCookie>,
+    #[serde(default)]
+    origins: Vec<OriginState>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateCookie {
+    name: String,
+    value: String,
+    domain: String,
+    #[serde(default = "default_path")]
+    path: String,
+    /// Unix time in seconds; -1 for session cookies.
+    #[serde(default = "session_expiry")]
+    expires: f64,
+    #[serde(default)]
+    http_only: bool,
+    #[serde(default)]
+    secure: bool,
+    #[serde(default = "default_same_site")]
+    same_site: String,
+    /// CHIPS partition key of partitioned cookies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    partition_key: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OriginState {
+    origin: String,
+    #[serde(default)]
+    local_storage: Vec<NameValue>,
+    /// Not part of Playwright's format; Playwright ignores it when loading.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    session_storage: Vec<NameValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NameValue {
+    name: String,
+    value: String,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn session_expiry() -> f64 {
+    -1.0
+}
+
+fn default_same_site() -> String {
+    "Lax".to_string()
+}
+
+impl From<CookieInfo> for StateCookie {
+    fn from(cookie: CookieInfo) -> Self {
+        Self {
+            name: cookie.name,
+            value: cookie.value,
+            domain: cookie.domain,
+            path: cookie.path,
+            expires: cookie.expires,
+            http_only: cookie.http_only,
+            secure: cookie.secure,
+            // Chrome omits sameSite when unset; browsers treat that as Lax.
+            same_site: if cookie.same_site.is_empty() {
+                default_same_site()
+            } else {
+                cookie.same_site
+            },
+            partition_key: cookie.partition_key,
+        }
+    }
+}
+
+impl From<StateCookie> for ImportCookie {
+    fn from(cookie: StateCookie) -> Self {
+        Self {
+            name: cookie.name,
+            value: cookie.value,
+            domain: cookie.domain,
+            url: None,
+            path: cookie.path,
+            expires: cookie.expires,
+            http_only: cookie.http_only,
+            secure: cookie.secure,
+            same_site: cookie.same_site,
+            partition_key: cookie.partition_key,
+            host_only: false,
+        }
+    }
+}
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct StateResult {
+    file: String,
+    cookies: usize,
+    origins: Vec<String>,
+    /// Cookies the browser did not accept on load.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<ImportFailure>,
+}
+
+fn print_result_plain(verb: &str, result: &StateResult) {
+    println!(
+        "{verb} {} cookie(s) and storage for {} origin(s) ({})",
+        result.cookies,
+        result.origins.len(),
+        result.file
+    );
+    print_failures_plain(&result.failed);
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `state` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the subcommand fails.
+pub async fn execute_state(global: &GlobalOpts, args: &StateArgs) -> Result<(), AppError> {
+    match &args.command {
+        StateCommand::Save(save_args) => execute_save(global, save_args).await,
+        StateCommand::Load(load_args) => execute_load(global, load_args).await,
+    }
+}
+
+// =============================================================================
+// Save
+// =============================================================================
+
+async fn execute_save(global: &GlobalOpts, args: &StateSaveArgs) -> Result<(), AppError> {
+    let (_client, mut managed) = setup_session(global).await?;
+    managed.ensure_domain("Network").await?;
+    managed.ensure_domain("DOMStorage").await?;
+
+    let response = send(&managed, "Network.getAllCookies", None).await?;
+    let cookies: Vec<StateCookie> = parse_cookies(&response)
+        .into_iter()
+        .map(StateCookie::from)
+        .collect();
+
+    let explicit = !args.origins.is_empty();
+    let candidates = if explicit {
+        args.origins
+            .iter()
+            .map(|o| o.trim_end_matches('/').to_string())
+            .collect()
+    } else {
+        let tree = send(&managed, "Page.getFrameTree", None).await?;
+        let mut origins = Vec::new();
+        frame_tree_origins(&tree["frameTree"], &mut origins);
+        origins
+    };
+
+    let mut origins = Vec::new();
+    for origin in candidates {
+        match read_origin(&managed, &origin).await {
+            Ok(state) if state.local_storage.is_empty() && state.session_storage.is_empty() => {}
+            Ok(state) => origins.push(state),
+            Err(e) if explicit => return Err(e),
+            // Frames of other processes cannot be read from the page session.
+            Err(e) => eprintln!("warning: skipping storage of {origin}: {}", e.message),
+        }
+    }
+
+    let state = StorageState { cookies, origins };
+    write_state(&args.file, &state)?;
+
+    let result = StateResult {
+        file: args.file.display().to_string(),
+        cookies: state.cookies.len(),
+        origins: state.origins.into_iter().map(|o| o.origin).collect(),
+        failed: Vec::new(),
+    };
+    if global.output.plain {
+        print_result_plain("Saved", &result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+async fn read_origin(session: &ManagedSession, origin: &str) -> Result<OriginState, AppError> {
+    let to_pairs = |items: Vec<crate::storage::StorageItem>| {
+        items
+            .into_iter()
+            .map(|item| NameValue {
+                name: item.key,
+                value: item.value,
+            })
+            .collect()
+    };
+    Ok(OriginState {
+        origin: origin.to_string(),
+        local_storage: to_pairs(get_items(session, &storage_id(origin, true)).await?),
+        session_storage: to_pairs(get_items(session, &storage_id(origin, false)).await?),
+    })
+}
+
+fn write_state(path: &Path, state: &StorageState) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| AppError {
+        message: format!("Failed to serialize state: {e}"),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    // The file holds every auth cookie and the web storage contents.
+    session::write_private_file(path, json.as_bytes()).map_err(|e| AppError {
+        message: format!("Failed to write state file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+fn read_state(path: &Path) -> Result<StorageState, AppError> {
+    let json = std::fs::read_to_string(path).map_err(|e| AppError {
+        message: format!("Failed to read state file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    serde_json::from_str(&json).map_err(|e| AppError {
+        message: format!("Invalid state file {}: {e}", path.display()),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })
+}
+
+// =============================================================================
+// Load
+// =============================================================================
+
+async fn execute_load(global: &GlobalOpts, args: &StateLoadArgs) -> Result<(), AppError> {
+    let state = read_state(&args.file)?;
+    let (_client, mut managed) = setup_session(global).await?;
+    managed.ensure_domain("Network").await?;
+
+    let cookies = state.cookies.into_iter().map(ImportCookie::from).collect();
+    let (cookies, failed) = set_cookies(&managed, cookies).await;
+
+    let seeded: Vec<&OriginState> = state
+        .origins
+        .iter()
+        .filter(|o| !o.local_storage.is_empty() || !o.session_storage.is_empty())
+        .collect();
+    if !seeded.is_empty() {
+        let timeout_ms = global.timeout.unwrap_or(DEFAULT_NAVIGATE_TIMEOUT_MS);
+        seed_storage(&mut managed, &seeded, timeout_ms).await?;
+    }
+
+    let result = StateResult {
+        file: args.file.display().to_string(),
+        cookies,
+        origins: seeded.iter().map(|o| o.origin.clone()).collect(),
+        failed,
+    };
+    if global.output.plain {
+        print_result_plain("Loaded", &result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+/// Write each origin's storage by loading the origin in the current tab with
+/// an intercepted empty document, then return the tab to its previous URL.
+async fn seed_storage(
+    managed: &mut ManagedSession,
+    origins: &[&OriginState],
+    timeout_ms: u64,
+) -> Result<(), AppError> {
+    managed.ensure_domain("Page").await?;
+    managed.ensure_domain("DOMStorage").await?;
+
+    let history = send(managed, "Page.getNavigationHistory", None).await?;
+    let original_url = history["currentIndex"]
+        .as_u64()
+        .and_then(|i| history["entries"].get(usize::try_from(i).ok()?))
+        .and_then(|entry| entry["url"].as_str())
+        .filter(|url| !url.is_empty())
+        .unwrap_or("about:blank")
+        .to_string();
+
+    let patterns: Vec<serde_json::Value> = origins
+        .iter()
+        .map(|o| {
+            serde_json::json!({
+                "urlPattern": format!("{}/*", o.origin.trim_end_matches('/')),
+                "resourceType": "Document",
+                "requestStage": "Request",
+            })
+        })
+        .collect();
+    let mut paused_rx = managed.subscribe("Fetch.requestPaused").await?;
+    send(
+        managed,
+        "Fetch.enable",
+        Some(serde_json::json!({ "patterns": patterns })),
+    )
+    .await?;
+
+    let mut outcome = Ok(());
+    for origin in origins {
+        outcome = seed_origin(managed, &mut paused_rx, origin, timeout_ms).await;
+        if outcome.is_err() {
+            break;
+        }
+    }
+    let _ = managed.send_command("Fetch.disable", None).await;
+    outcome?;
+
+    navigate_and_wait(managed, &original_url, WaitUntil::Load, timeout_ms).await?;
+    Ok(())
+}
+
+async fn seed_origin(
+    managed: &ManagedSession,
+    paused_rx: &mut tokio::sync::mpsc::Receiver<agentchrome::cdp::CdpEvent>,
+    origin: &OriginState,
+    timeout_ms: u64,
+) -> Result<(), AppError> {
+    use base64::Engine;
+
+    let url = format!("{}/", origin.origin.trim_end_matches('/'));
+    let mut load_rx = managed.subscribe("Page.loadEventFired").await?;
+
+    // Page.navigate only returns once the document request is answered, so
+    // fulfil the paused request concurrently.
+    let fulfil = async {
+        let paused = paused_rx.recv().await.ok_or_else(|| AppError {
+            message: "CDP connection closed".to_string(),
+            code: ExitCode::ConnectionError,
+            custom_json: None,
+        })?;
+        let body = base64::engine::general_purpose::STANDARD.encode(SEED_DOCUMENT);
+        send(
+            managed,
+            "Fetch.fulfillRequest",
+            Some(serde_json::json!({
+                "requestId": paused.params["requestId"],
+                "responseCode": 200,
+                "responseHeaders": [{ "name": "Content-Type", "value": "text/html" }],
+                "body": body,
+            })),
+        )
+        .await
+    };
+    let navigate = send(
+        managed,
+        "Page.navigate",
+        Some(serde_json::json!({ "url": url })),
+    );
+    let seeded = async {
+        let (navigated, fulfilled) = tokio::join!(navigate, fulfil);
+        navigated?;
+        fulfilled?;
+        load_rx.recv().await;
+        Ok::<(), AppError>(())
+    };
+    tokio::time::timeout(Duration::from_millis(timeout_ms), seeded)
+        .await
+        .map_err(|_| AppError {
+            message: format!("Timed out loading {url} to restore its storage"),
+            code: ExitCode::TimeoutError,
+            custom_json: None,
+        })??;
+
+    for (items, is_local) in [
+        (&origin.local_storage, true),
+        (&origin.session_storage, false),
+    ] {
+        let id = storage_id(&origin.origin, is_local);
+        for item in items {
+            set_item(managed, &id, &item.name, &item.value).await?;
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+async fn send(
+    session: &ManagedSession,
+    method: &str,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, AppError> {
+    session
+        .send_command(method, params)
+        .await
+        .map_err(|e| AppError {
+            message: format!("{method} failed: {e}"),
+            code: ExitCode::ProtocolError,
+            custom_json: None,
+        })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_playwright_storage_state() {
+        let json = r#"{
+            "cookies": [{
+                "name": "sid", "value": "abc", "domain": ".example.com", "path": "/",
+                "expires": -1, "httpOnly": true, "secure": true, "sameSite": "Strict"
+            }],
+            "origins": [{
+                "origin": "https://app.example.com",
+                "localStorage": [{"name": "token", "value": "t"}]
+            }]
+        }"#;
+        let state: StorageState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.cookies[0].same_site, "Strict");
+        assert!(state.cookies[0].http_only);
+        assert_eq!(state.origins[0].local_storage[0].name, "token");
+        assert!(state.origins[0].session_storage.is_empty());
+    }
+
+    #[test]
+    fn cookie_defaults_fill_missing_fields() {
+        let cookie: StateCookie =
+            serde_json::from_str(r#"{"name": "a", "value": "1", "domain": "example.com"}"#)
+                .unwrap();
+        assert_eq!(cookie.path, "/");
+        assert!((cookie.expires + 1.0).abs() < f64::EPSILON);
+        assert_eq!(cookie.same_site, "Lax");
+    }
+
+    #[test]
+    fn session_storage_is_omitted_when_empty() {
+        let origin = OriginState {
+            origin: "https://example.com".into(),
+            local_storage: vec![NameValue {
+                name: "k".into(),
+                value: "v".into(),
+            }],
+            session_storage: Vec::new(),
+        };
+        let json = serde_json::to_value(&origin).unwrap();
+        assert_eq!(json["localStorage"][0]["name"], "k");
+        assert!(json.get("sessionStorage").is_none());
+    }
+
+    #[test]
+    fn state_cookies_use_the_cookie_import_param() {
+        let cookie = StateCookie {
+            name: "sid".into(),
+            value: "abc".into(),
+            domain: ".example.com".into(),
+            path: "/".into(),
+            expires: -1.0,
+            http_only: true,
+            secure: false,
+            same_site: "Lax".into(),
+            partition_key: None,
+        };
+        let param = crate::cookie::cookie_param(&ImportCookie::from(cookie)).unwrap();
+        assert!(param.get("expires").is_none());
+        assert_eq!(param["sameSite"], "Lax");
+        assert_eq!(param["httpOnly"], true);
+
+        // SameSite=None and partitioned cookies must be Secure
+        let insecure_none = StateCookie {
+            name: "a".into(),
+            value: "1".into(),
+            domain: "example.com".into(),
+            path: "/".into(),
+            expires: 1_900_000_000.0,
+            http_only: false,
+            secure: false,
+            same_site: "None".into(),
+            partition_key: None,
+        };
+        assert!(crate::cookie::cookie_param(&ImportCookie::from(insecure_none)).is_err());
+
+        let partitioned: StateCookie = serde_json::from_value(serde_json::json!({
+            "name": "p", "value": "1", "domain": "example.com", "secure": true,
+            "expires": 1_900_000_000.0,
+            "partitionKey": {"topLevelSite": "https://example.com", "hasCrossSiteAncestor": false}
+        }))
+        .unwrap();
+        let param = crate::cookie::cookie_param(&ImportCookie::from(partitioned)).unwrap();
+        assert_eq!(param["expires"], 1_900_000_000.0);
+        assert_eq!(param["partitionKey"]["topLevelSite"], "https://example.com");
+    }
+
+    #[test]
+    fn cookie_info_maps_unset_same_site_to_lax() {
+        let info = CookieInfo {
+            name: "a".into(),
+            value: "1".into(),
+            domain: "example.com".into(),
+            path: "/".into(),
+            expires: -1.0,
+            http_only: false,
+            secure: false,
+            same_site: String::new(),
+            size: 2,
+            partition_key: None,
+        };
+        assert_eq!(StateCookie::from(info).same_site, "Lax");
+    }
+}
@@ -0,0 +1,560 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::{AppError, ExitCode};
+
+use crate::cli::{
+    GlobalOpts, StorageArgs, StorageIdbArgs, StorageIdbClearArgs, StorageIdbCommand,
+    StorageIdbDumpArgs,
+};
+use crate::output::{self, print_output};
+
+use super::{StorageSession, send};
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Serialize)]
+struct DatabasesResult {
+    origin: String,
+    databases: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DatabaseInfo {
+    name: String,
+    version: f64,
+    #[serde(rename = "objectStores")]
+    object_stores: Vec<ObjectStoreInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct ObjectStoreInfo {
+    name: String,
+    #[serde(rename = "keyPath")]
+    key_path: serde_json::Value,
+    #[serde(rename = "autoIncrement")]
+    auto_increment: bool,
+    indexes: Vec<IndexInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct IndexInfo {
+    name: String,
+    #[serde(rename = "keyPath")]
+    key_path: serde_json::Value,
+    unique: bool,
+    #[serde(rename = "multiEntry")]
+    multi_entry: bool,
+}
+
+#[derive(Serialize)]
+struct DumpResult {
+    database: String,
+    store: String,
+    entries: Vec<Record>,
+    #[serde(rename = "hasMore")]
+    has_more: bool,
+}
+
+#[derive(Serialize)]
+struct Record {
+    key: serde_json::Value,
+    #[serde(rename = "primaryKey")]
+    primary_key: serde_json::Value,
+    value: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct ClearResult {
+    database: String,
+    cleared: Vec<String>,
+}
+
+// =============================================================================
+// Output formatting
+// =============================================================================
+
+fn print_databases_plain(result: &DatabasesResult) {
+    if result.databases.is_empty() {
+        println!("No databases");
+        return;
+    }
+    for name in &result.databases {
+        println!("{name}");
+    }
+}
+
+fn print_stores_plain(info: &DatabaseInfo) {
+    println!("{} (version {})", info.name, info.version);
+    for store in &info.object_stores {
+        println!(
+            "  {} (keyPath: {}, {} index(es))",
+            store.name,
+            store.key_path,
+            store.indexes.len()
+        );
+    }
+}
+
+fn dump_plain(result: &DumpResult) -> String {
+    let mut out = String::new();
+    for record in &result.entries {
+        let _ = writeln!(out, "{}\t{}", record.key, record.value);
+    }
+    if result.has_more {
+        out.push_str("(more records available; raise --limit)\n");
+    }
+    out
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute `storage idb`.
+pub async fn execute_idb(
+    global: &GlobalOpts,
+    storage_args: &StorageArgs,
+    args: &StorageIdbArgs,
+) -> Result<(), AppError> {
+    let target_session = StorageSession::open(global, storage_args, "IndexedDB").await?;
+    let session = target_session.session();
+
+    match &args.command {
+        StorageIdbCommand::Databases(a) => {
+            let origin = target_session.origin(a.origin.as_deref()).await?;
+            let result = DatabasesResult {
+                databases: request_database_names(session, &origin).await?,
+                origin,
+            };
+            if global.output.plain {
+                print_databases_plain(&result);
+                Ok(())
+            } else {
+                print_output(&result, &global.output)
+            }
+        }
+        StorageIdbCommand::Stores(a) => {
+            let origin = target_session.origin(a.origin.as_deref()).await?;
+            let info = request_database(session, &origin, &a.database).await?;
+            if global.output.plain {
+                print_stores_plain(&info);
+                Ok(())
+            } else {
+                print_output(&info, &global.output)
+            }
+        }
+        StorageIdbCommand::Dump(a) => {
+            let origin = target_session.origin(a.origin.as_deref()).await?;
+            execute_dump(global, session, &origin, a).await
+        }
+        StorageIdbCommand::Clear(a) => {
+            let origin = target_session.origin(a.origin.as_deref()).await?;
+            execute_clear(global, session, &origin, a).await
+        }
+    }
+}
+
+// =============================================================================
+// Databases / Stores: schema
+// =============================================================================
+
+async fn request_database_names(
+    session: &ManagedSession,
+    origin: &str,
+) -> Result<Vec<String>, AppError> {
+    let response = send(
+        session,
+        "IndexedDB.requestDatabaseNames",
+        serde_json::json!({ "securityOrigin": origin }),
+    )
+    .await?;
+    Ok(response["databaseNames"]
+        .as_array()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|n| n.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+async fn request_database(
+    session: &ManagedSession,
+    origin: &str,
+    database: &str,
+) -> Result<DatabaseInfo, AppError> {
+    let response = send(
+        session,
+        "IndexedDB.requestDatabase",
+        serde_json::json!({ "securityOrigin": origin, "databaseName": database }),
+    )
+    .await?;
+    Ok(parse_database(&response["databaseWithObjectStores"]))
+}
+
+fn parse_database(db: &serde_json::Value) -> DatabaseInfo {
+    let object_stores = db["objectStores"]
+        .as_array()
+        .map(|stores| stores.iter().map(parse_object_store).collect())
+        .unwrap_or_default();
+    DatabaseInfo {
+        name: db["name"].as_str().unwrap_or_default().to_string(),
+        version: db["version"].as_f64().unwrap_or(0.0),
+        object_stores,
+    }
+}
+
+fn parse_object_store(store: &serde_json::Value) -> ObjectStoreInfo {
+    let indexes = store["indexes"]
+        .as_array()
+        .map(|indexes| {
+            indexes
+                .iter()
+                .map(|index| IndexInfo {
+                    name: index["name"].as_str().unwrap_or_default().to_string(),
+                    key_path: key_path_value(&index["keyPath"]),
+                    unique: index["unique"].as_bool().unwrap_or(false),
+                    multi_entry: index["multiEntry"].as_bool().unwrap_or(false),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    ObjectStoreInfo {
+        name: store["name"].as_str().unwrap_or_default().to_string(),
+        key_path: key_path_value(&store["keyPath"]),
+        auto_increment: store["autoIncrement"].as_bool().unwrap_or(false),
+        indexes,
+    }
+}
+
+/// Flatten an `IndexedDB.KeyPath` into `null`, a string, or an array of strings.
+fn key_path_value(key_path: &serde_json::Value) -> serde_json::Value {
+    match key_path["type"].as_str() {
+        Some("string") => key_path["string"].clone(),
+        Some("array") => key_path["array"].clone(),
+        _ => serde_json::Value::Null,
+    }
+}
+
+// =============================================================================
+// Dump: records
+// =============================================================================
+
+async fn execute_dump(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    origin: &str,
+    args: &StorageIdbDumpArgs,
+) -> Result<(), AppError> {
+    let mut params = serde_json::json!({
+        "securityOrigin": origin,
+        "databaseName": args.database,
+        "objectStoreName": args.store,
+        "indexName": "",
+        "skipCount": 0,
+        "pageSize": args.limit,
+    });
+    if let Some(ref range) = args.key_range {
+        params["keyRange"] = parse_key_range(range)?;
+    }
+    let response = send(session, "IndexedDB.requestData", params).await?;
+
+    let mut entries = Vec::new();
+    for entry in response["objectStoreDataEntries"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let [key, primary_key, value] = remote_values(
+            session,
+            [&entry["key"], &entry["primaryKey"], &entry["value"]],
+        )
+        .await;
+        entries.push(Record {
+            key,
+            primary_key,
+            value,
+        });
+    }
+    // Best-effort: the objects only pin memory in the page until released.
+    let _ = session
+        .send_command(
+            "Runtime.releaseObjectGroup",
+            Some(serde_json::json!({ "objectGroup": IDB_OBJECT_GROUP })),
+        )
+        .await;
+    let result = DumpResult {
+        database: args.database.clone(),
+        store: args.store.clone(),
+        entries,
+        has_more: response["hasMore"].as_bool().unwrap_or(false),
+    };
+
+    if global.output.plain {
+        return output::emit_plain(&dump_plain(&result), &global.output);
+    }
+    output::emit(&result, &global.output, "storage idb dump", |r| {
+        serde_json::json!({
+            "database": r.database,
+            "store": r.store,
+            "entry_count": r.entries.len(),
+            "hasMore": r.has_more,
+        })
+    })
+}
+
+/// Parse `LOWER..UPPER` into an `IndexedDB.KeyRange` with inclusive bounds.
+fn parse_key_range(range: &str) -> Result<serde_json::Value, AppError> {
+    let invalid = || AppError {
+        message: format!(
+            "Invalid --key-range '{range}': expected LOWER..UPPER with at least one bound"
+        ),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    };
+    let (lower, upper) = range.split_once("..").ok_or_else(invalid)?;
+    if lower.is_empty() && upper.is_empty() {
+        return Err(invalid());
+    }
+    let mut key_range = serde_json::json!({ "lowerOpen": false, "upperOpen": false });
+    if !lower.is_empty() {
+        key_range["lower"] = idb_key(lower);
+    }
+    if !upper.is_empty() {
+        key_range["upper"] = idb_key(upper);
+    }
+    Ok(key_range)
+}
+
+/// Build an `IndexedDB.Key`; bounds that parse as numbers are numeric keys.
+fn idb_key(bound: &str) -> serde_json::Value {
+    match bound.parse::<f64>() {
+        Ok(number) if number.is_finite() => {
+            serde_json::json!({ "type": "number", "number": number })
+        }
+        _ => serde_json::json!({ "type": "string", "string": bound }),
+    }
+}
+
+/// Object group Chrome wraps `IndexedDB.requestData` results in. The command
+/// takes no `objectGroup` parameter, so this is the group to release.
+const IDB_OBJECT_GROUP: &str = "indexeddb";
+
+/// Convert the `Runtime.RemoteObject`s of one `requestData` entry into plain
+/// JSON. Objects are fetched by value with a single `callFunctionOn`; an
+/// object that cannot be fetched falls back to its description.
+async fn remote_values<const N: usize>(
+    session: &ManagedSession,
+    objects: [&serde_json::Value; N],
+) -> [serde_json::Value; N] {
+    let mut values = objects.map(local_value);
+    let fetch: Vec<usize> = (0..N).filter(|&i| values[i].is_none()).collect();
+    if let Some(&first) = fetch.first() {
+        let arguments: Vec<_> = fetch
+            .iter()
+            .map(|&i| serde_json::json!({ "objectId": objects[i]["objectId"] }))
+            .collect();
+        if let Ok(response) = session
+            .send_command(
+                "Runtime.callFunctionOn",
+                Some(serde_json::json!({
+                    "objectId": objects[first]["objectId"],
+                    "functionDeclaration": "function(...values) { return values; }",
+                    "arguments": arguments,
+                    "returnByValue": true,
+                    "objectGroup": IDB_OBJECT_GROUP,
+                })),
+            )
+            .await
+            && let Some(fetched) = response["result"]["value"].as_array()
+        {
+            for (&i, value) in fetch.iter().zip(fetched) {
+                values[i] = Some(value.clone());
+            }
+        }
+    }
+    std::array::from_fn(|i| values[i].take().unwrap_or_else(|| description(objects[i])))
+}
+
+/// The plain JSON for a remote object that needs no round trip, or `None` if
+/// it has to be fetched by object ID. Dates are kept as their description.
+fn local_value(object: &serde_json::Value) -> Option<serde_json::Value> {
+    if let Some(value) = object.get("value") {
+        return Some(value.clone());
+    }
+    if let Some(unserializable) = object["unserializableValue"].as_str() {
+        return Some(serde_json::Value::String(unserializable.to_string()));
+    }
+    if object["subtype"].as_str() == Some("date") || !object["objectId"].is_string() {
+        return Some(description(object));
+    }
+    None
+}
+
+fn description(object: &serde_json::Value) -> serde_json::Value {
+    object["description"]
+        .as_str()
+        .map_or(serde_json::Value::Null, |d| {
+            serde_json::Value::String(d.to_string())
+        })
+}
+
+// =============================================================================
+// Clear: empty object stores
+// =============================================================================
+
+async fn execute_clear(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    origin: &str,
+    args: &StorageIdbClearArgs,
+) -> Result<(), AppError> {
+    let stores = match args.store {
+        Some(ref store) => vec![store.clone()],
+        None => request_database(session, origin, &args.database)
+            .await?
+            .object_stores
+            .into_iter()
+            .map(|store| store.name)
+            .collect(),
+    };
+    for store in &stores {
+        send(
+            session,
+            "IndexedDB.clearObjectStore",
+            serde_json::json!({
+                "securityOrigin": origin,
+                "databaseName": args.database,
+                "objectStoreName": store,
+            }),
+        )
+        .await?;
+    }
+
+    let result = ClearResult {
+        database: args.database.clone(),
+        cleared: stores,
+    };
+    if global.output.plain {
+        println!(
+            "Cleared {} object store(s) in {}",
+            result.cleared.len(),
+            result.database
+        );
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_value_resolves_primitives_and_dates_without_a_fetch() {
+        let number = serde_json::json!({"type": "number", "value": 7});
+        assert_eq!(local_value(&number), Some(serde_json::json!(7)));
+        let infinity = serde_json::json!({"type": "number", "unserializableValue": "Infinity"});
+        assert_eq!(local_value(&infinity), Some(serde_json::json!("Infinity")));
+        let date = serde_json::json!({
+            "type": "object",
+            "subtype": "date",
+            "objectId": "1",
+            "description": "Sat Oct 17 2026"
+        });
+        assert_eq!(
+            local_value(&date),
+            Some(serde_json::json!("Sat Oct 17 2026"))
+        );
+        let object =
+            serde_json::json!({"type": "object", "objectId": "2", "description": "Object"});
+        assert_eq!(local_value(&object), None);
+    }
+
+    #[test]
+    fn key_range_with_both_bounds() {
+        let range = parse_key_range("10..20").unwrap();
+        assert_eq!(range["lower"]["type"], "number");
+        assert_eq!(range["lower"]["number"], 10.0);
+        assert_eq!(range["upper"]["number"], 20.0);
+        assert_eq!(range["lowerOpen"], false);
+        assert_eq!(range["upperOpen"], false);
+    }
+
+    #[test]
+    fn key_range_with_one_string_bound() {
+        let range = parse_key_range("m..").unwrap();
+        assert_eq!(
+            range["lower"],
+            serde_json::json!({"type": "string", "string": "m"})
+        );
+        assert!(range.get("upper").is_none());
+    }
+
+    #[test]
+    fn key_range_rejects_missing_bounds() {
+        assert!(parse_key_range("..").is_err());
+        assert!(parse_key_range("10").is_err());
+    }
+
+    #[test]
+    fn parse_database_flattens_key_paths() {
+        let db = serde_json::json!({
+            "name": "app-db",
+            "version": 3.0,
+            "objectStores": [{
+                "name": "todos",
+                "keyPath": {"type": "string", "string": "id"},
+                "autoIncrement": true,
+                "indexes": [{
+                    "name": "by_tag",
+                    "keyPath": {"type": "array", "array": ["tag", "done"]},
+                    "unique": false,
+                    "multiEntry": true
+                }]
+            }, {
+                "name": "blobs",
+                "keyPath": {"type": "null"},
+                "autoIncrement": false,
+                "indexes": []
+            }]
+        });
+        let info = parse_database(&db);
+        assert_eq!(info.name, "app-db");
+        assert_eq!(info.object_stores[0].key_path, "id");
+        assert!(info.object_stores[0].auto_increment);
+        assert_eq!(
+            info.object_stores[0].indexes[0].key_path,
+            serde_json::json!(["tag", "done"])
+        );
+        assert!(info.object_stores[1].key_path.is_null());
+    }
+
+    #[test]
+    fn dump_plain_lists_records() {
+        let result = DumpResult {
+            database: "db".into(),
+            store: "s".into(),
+            entries: vec![Record {
+                key: serde_json::json!(1),
+                primary_key: serde_json::json!(1),
+                value: serde_json::json!({"title": "x"}),
+            }],
+            has_more: true,
+        };
+        let text = dump_plain(&result);
+        assert!(text.starts_with("1\t{\"title\":\"x\"}\n"));
+        assert!(text.contains("raise --limit"));
+    }
+}
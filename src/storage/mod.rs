@@ -0,0 +1,509 @@
+mod idb;
+
+use serde::Serialize;
+
+use agentchrome::cdp::CdpClient;
+use agentchrome::connection::ManagedSession;
+use agentchrome::error::{AppError, ExitCode};
+use agentchrome::frame::FrameContext;
+
+use crate::cli::{GlobalOpts, StorageArea, StorageArgs, StorageCommand, StorageSetArgs};
+use crate::output::{print_output, setup_session};
+
+// =============================================================================
+// Output types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub(crate) struct StorageItem {
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+#[derive(Serialize)]
+struct SetResult {
+    success: bool,
+    storage: &'static str,
+    origin: String,
+    key: String,
+}
+
+#[derive(Serialize)]
+struct RemoveResult {
+    removed: bool,
+    storage: &'static str,
+    origin: String,
+    key: String,
+}
+
+#[derive(Serialize)]
+struct ClearResult {
+    cleared: u64,
+    storage: &'static str,
+    origin: String,
+}
+
+// =============================================================================
+// Output formatting
+// =============================================================================
+
+fn print_list_plain(items: &[StorageItem]) {
+    if items.is_empty() {
+        println!("No items");
+        return;
+    }
+    for item in items {
+        println!("{}: {}", item.key, item.value);
+    }
+}
+
+fn print_set_plain(result: &SetResult) {
+    println!(
+        "Set {} item: {} (origin: {})",
+        result.storage, result.key, result.origin
+    );
+}
+
+fn print_remove_plain(result: &RemoveResult) {
+    let verb = if result.removed { "Removed" } else { "No" };
+    println!(
+        "{verb} {} item: {} (origin: {})",
+        result.storage, result.key, result.origin
+    );
+}
+
+fn print_clear_plain(result: &ClearResult) {
+    println!("Cleared {} {} item(s)", result.cleared, result.storage);
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// Execute the `storage` subcommand group.
+///
+/// # Errors
+///
+/// Returns `AppError` if the subcommand fails.
+pub async fn execute_storage(global: &GlobalOpts, args: &StorageArgs) -> Result<(), AppError> {
+    let (is_local, area_args) = match &args.area {
+        StorageArea::Local(area_args) => (true, area_args),
+        StorageArea::Session(area_args) => (false, area_args),
+        StorageArea::Idb(idb_args) => return idb::execute_idb(global, args, idb_args).await,
+    };
+    let storage = if is_local { "local" } else { "session" };
+
+    let target_session = StorageSession::open(global, args, "DOMStorage").await?;
+    let effective = target_session.session();
+
+    let origin_arg = match &area_args.command {
+        StorageCommand::List(a) | StorageCommand::Clear(a) => a.origin.as_deref(),
+        StorageCommand::Get(a) | StorageCommand::Remove(a) => a.origin.as_deref(),
+        StorageCommand::Set(a) => a.origin.as_deref(),
+    };
+    let origin = target_session.origin(origin_arg).await?;
+    let target = StorageTarget {
+        storage,
+        storage_id: storage_id(&origin, is_local),
+        origin,
+    };
+
+    match &area_args.command {
+        StorageCommand::List(_) => execute_list(global, effective, &target).await,
+        StorageCommand::Get(a) => execute_get(global, effective, &target, &a.key).await,
+        StorageCommand::Set(a) => execute_set(global, effective, target, a).await,
+        StorageCommand::Remove(a) => execute_remove(global, effective, target, &a.key).await,
+        StorageCommand::Clear(_) => execute_clear(global, effective, target).await,
+    }
+}
+
+/// The page (or `--frame`) session a storage command runs against.
+struct StorageSession {
+    _client: CdpClient,
+    managed: ManagedSession,
+    frame_ctx: Option<FrameContext>,
+}
+
+impl StorageSession {
+    /// Connect, resolve `--frame`, and enable `domain` on the frame's session.
+    async fn open(global: &GlobalOpts, args: &StorageArgs, domain: &str) -> Result<Self, AppError> {
+        let (client, mut managed) = setup_session(global).await?;
+        let mut frame_ctx = crate::output::resolve_optional_frame(
+            &client,
+            &mut managed,
+            args.frame.as_deref(),
+            None,
+        )
+        .await?;
+        {
+            let eff_mut = if let Some(ref mut ctx) = frame_ctx {
+                agentchrome::frame::frame_session_mut(ctx, &mut managed)
+            } else {
+                &mut managed
+            };
+            eff_mut.ensure_domain(domain).await?;
+        }
+        Ok(Self {
+            _client: client,
+            managed,
+            frame_ctx,
+        })
+    }
+
+    /// The session CDP storage commands go to (the OOPIF session for
+    /// cross-origin frames).
+    fn session(&self) -> &ManagedSession {
+        match self.frame_ctx {
+            Some(ref ctx) => agentchrome::frame::frame_session(ctx, &self.managed),
+            None => &self.managed,
+        }
+    }
+
+    /// The `--origin` value, or the security origin of the targeted frame.
+    async fn origin(&self, explicit: Option<&str>) -> Result<String, AppError> {
+        if let Some(origin) = explicit {
+            return Ok(origin.to_string());
+        }
+        let frame_id = self
+            .frame_ctx
+            .as_ref()
+            .and_then(agentchrome::frame::frame_id);
+        frame_origin(self.session(), frame_id).await
+    }
+}
+
+/// The storage area and origin a command operates on.
+struct StorageTarget {
+    storage: &'static str,
+    origin: String,
+    storage_id: serde_json::Value,
+}
+
+// =============================================================================
+// List / Get: read items
+// =============================================================================
+
+async fn execute_list(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    target: &StorageTarget,
+) -> Result<(), AppError> {
+    let items = get_items(session, &target.storage_id).await?;
+    if global.output.plain {
+        print_list_plain(&items);
+        Ok(())
+    } else {
+        print_output(&items, &global.output)
+    }
+}
+
+async fn execute_get(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    target: &StorageTarget,
+    key: &str,
+) -> Result<(), AppError> {
+    let items = get_items(session, &target.storage_id).await?;
+    let item = find_item(items, key).ok_or_else(|| AppError {
+        message: format!(
+            "Key '{key}' not found in {}Storage for {}",
+            target.storage, target.origin
+        ),
+        code: ExitCode::GeneralError,
+        custom_json: None,
+    })?;
+    if global.output.plain {
+        println!("{}", item.value);
+        Ok(())
+    } else {
+        print_output(&item, &global.output)
+    }
+}
+
+// =============================================================================
+// Set / Remove / Clear: write items
+// =============================================================================
+
+async fn execute_set(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    target: StorageTarget,
+    args: &StorageSetArgs,
+) -> Result<(), AppError> {
+    set_item(session, &target.storage_id, &args.key, &args.value).await?;
+    let result = SetResult {
+        success: true,
+        storage: target.storage,
+        origin: target.origin,
+        key: args.key.clone(),
+    };
+    if global.output.plain {
+        print_set_plain(&result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+async fn execute_remove(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    target: StorageTarget,
+    key: &str,
+) -> Result<(), AppError> {
+    // Removing an absent key is a no-op; report it as such.
+    let items = get_items(session, &target.storage_id).await?;
+    let result = remove_result(&target, key, items);
+    if result.removed {
+        let params = serde_json::json!({ "storageId": target.storage_id, "key": key });
+        send(session, "DOMStorage.removeDOMStorageItem", params).await?;
+    }
+    if global.output.plain {
+        print_remove_plain(&result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+/// Result of removing `key`, given the items stored before the removal.
+fn remove_result(target: &StorageTarget, key: &str, items: Vec<StorageItem>) -> RemoveResult {
+    RemoveResult {
+        removed: find_item(items, key).is_some(),
+        storage: target.storage,
+        origin: target.origin.clone(),
+        key: key.to_string(),
+    }
+}
+
+async fn execute_clear(
+    global: &GlobalOpts,
+    session: &ManagedSession,
+    target: StorageTarget,
+) -> Result<(), AppError> {
+    let count = get_items(session, &target.storage_id).await?.len() as u64;
+    let params = serde_json::json!({ "storageId": target.storage_id });
+    send(session, "DOMStorage.clear", params).await?;
+    let result = ClearResult {
+        cleared: count,
+        storage: target.storage,
+        origin: target.origin,
+    };
+    if global.output.plain {
+        print_clear_plain(&result);
+        Ok(())
+    } else {
+        print_output(&result, &global.output)
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+async fn send(
+    session: &ManagedSession,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, AppError> {
+    session
+        .send_command(method, Some(params))
+        .await
+        .map_err(|e| AppError {
+            message: format!("{method} failed: {e}"),
+            code: ExitCode::ProtocolError,
+            custom_json: None,
+        })
+}
+
+/// `DOMStorage.StorageId` of the localStorage or sessionStorage of `origin`.
+pub(crate) fn storage_id(origin: &str, is_local: bool) -> serde_json::Value {
+    serde_json::json!({ "securityOrigin": origin, "isLocalStorage": is_local })
+}
+
+pub(crate) async fn get_items(
+    session: &ManagedSession,
+    storage_id: &serde_json::Value,
+) -> Result<Vec<StorageItem>, AppError> {
+    let response = send(
+        session,
+        "DOMStorage.getDOMStorageItems",
+        serde_json::json!({ "storageId": storage_id }),
+    )
+    .await?;
+    Ok(parse_items(&response))
+}
+
+pub(crate) async fn set_item(
+    session: &ManagedSession,
+    storage_id: &serde_json::Value,
+    key: &str,
+    value: &str,
+) -> Result<(), AppError> {
+    let params = serde_json::json!({ "storageId": storage_id, "key": key, "value": value });
+    send(session, "DOMStorage.setDOMStorageItem", params).await?;
+    Ok(())
+}
+
+/// Distinct security origins of the frames in a `Page.getFrameTree` tree,
+/// main frame first. Opaque origins are skipped.
+pub(crate) fn frame_tree_origins(node: &serde_json::Value, origins: &mut Vec<String>) {
+    if let Some(origin) = node["frame"]["securityOrigin"].as_str()
+        && !origin.is_empty()
+        && origin != "null"
+        && origin != "://"
+        && !origins.iter().any(|o| o == origin)
+    {
+        origins.push(origin.to_string());
+    }
+    for child in node["childFrames"].as_array().into_iter().flatten() {
+        frame_tree_origins(child, origins);
+    }
+}
+
+/// Look up the security origin of `frame_id` (or the root frame of the
+/// session when `None`) in the session's frame tree.
+async fn frame_origin(
+    session: &ManagedSession,
+    frame_id: Option<&str>,
+) -> Result<String, AppError> {
+    let tree = send(session, "Page.getFrameTree", serde_json::json!({})).await?;
+    let origin = find_frame_origin(&tree["frameTree"], frame_id).unwrap_or_default();
+    if origin.is_empty() || origin == "null" || origin == "://" {
+        return Err(AppError {
+            message: format!(
+                "The target frame has no storage origin ('{origin}'); pass --origin <origin>"
+            ),
+            code: ExitCode::GeneralError,
+            custom_json: None,
+        });
+    }
+    Ok(origin)
+}
+
+fn find_frame_origin(node: &serde_json::Value, frame_id: Option<&str>) -> Option<String> {
+    let frame = &node["frame"];
+    if frame_id.is_none_or(|id| frame["id"].as_str() == Some(id)) {
+        return frame["securityOrigin"].as_str().map(String::from);
+    }
+    node["childFrames"]
+        .as_array()?
+        .iter()
+        .find_map(|child| find_frame_origin(child, frame_id))
+}
+
+/// Parse `DOMStorage.getDOMStorageItems` entries (`[[key, value], ...]`).
+fn parse_items(response: &serde_json::Value) -> Vec<StorageItem> {
+    let Some(entries) = response["entries"].as_array() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            Some(StorageItem {
+                key: entry.get(0)?.as_str()?.to_string(),
+                value: entry.get(1)?.as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+fn find_item(items: Vec<StorageItem>, key: &str) -> Option<StorageItem> {
+    items.into_iter().find(|item| item.key == key)
+}
+
+// =============================================================================
+// Script runner adapter
+// =============================================================================
+
+/// Run a `storage` command against an existing session and return a JSON value.
+///
+/// # Errors
+///
+/// Propagates `AppError` from the underlying storage logic.
+pub async fn run_from_session(
+    _managed: &mut ManagedSession,
+    global: &GlobalOpts,
+    args: &StorageArgs,
+) -> Result<serde_json::Value, AppError> {
+    execute_storage(global, args).await?;
+    Ok(serde_json::json!({"executed": true}))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_items_reads_entry_pairs() {
+        let response = serde_json::json!({
+            "entries": [["theme", "dark"], ["cart", "{\"items\":[]}"], ["broken"]]
+        });
+        let items = parse_items(&response);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].key, "theme");
+        assert_eq!(items[1].value, "{\"items\":[]}");
+        assert!(parse_items(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn find_item_by_key() {
+        let items = parse_items(&serde_json::json!({"entries": [["a", "1"], ["b", "2"]]}));
+        assert_eq!(find_item(items, "b").unwrap().value, "2");
+        let items = parse_items(&serde_json::json!({"entries": [["a", "1"]]}));
+        assert!(find_item(items, "missing").is_none());
+    }
+
+    #[test]
+    fn remove_result_reports_absent_keys() {
+        let target = StorageTarget {
+            storage: "local",
+            origin: "https://example.com".into(),
+            storage_id: storage_id("https://example.com", true),
+        };
+        let items = || parse_items(&serde_json::json!({"entries": [["theme", "dark"]]}));
+
+        let present = remove_result(&target, "theme", items());
+        assert!(present.removed);
+
+        let absent = remove_result(&target, "missing", items());
+        assert!(!absent.removed);
+        let json = serde_json::to_value(&absent).unwrap();
+        assert_eq!(json["removed"], false);
+        assert_eq!(json["key"], "missing");
+    }
+
+    #[test]
+    fn storage_item_serialization() {
+        let json = serde_json::to_value(StorageItem {
+            key: "theme".into(),
+            value: "dark".into(),
+        })
+        .unwrap();
+        assert_eq!(json, serde_json::json!({"key": "theme", "value": "dark"}));
+    }
+
+    #[test]
+    fn frame_origin_lookup() {
+        let tree = serde_json::json!({
+            "frame": {"id": "F1", "securityOrigin": "https://example.com"},
+            "childFrames": [{
+                "frame": {"id": "F2", "securityOrigin": "https://ads.example.net"},
+                "childFrames": [{"frame": {"id": "F3", "securityOrigin": "https://deep.test"}}]
+            }]
+        });
+        assert_eq!(
+            find_frame_origin(&tree, None).as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            find_frame_origin(&tree, Some("F3")).as_deref(),
+            Some("https://deep.test")
+        );
+        assert!(find_frame_origin(&tree, Some("F9")).is_none());
+    }
+}
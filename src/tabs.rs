@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::time::Duration;
 
@@ -21,7 +22,13 @@ pub async fn execute_tabs(global: &GlobalOpts, args: &TabsArgs) -> Result<(), Ap
     match &args.command {
         TabsCommand::List(list_args) => execute_list(global, list_args.all).await,
         TabsCommand::Create(create_args) => {
-            execute_create(global, create_args.url.as_deref(), create_args.background).await
+            execute_create(
+                global,
+                create_args.url.as_deref(),
+                create_args.background,
+                create_args.context.as_deref(),
+            )
+            .await
         }
         TabsCommand::Close(close_args) => {
             let mut merged: Vec<String> = close_args.targets.clone();
@@ -53,6 +60,8 @@ struct TabInfo {
     url: String,
     title: String,
     active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -80,21 +89,32 @@ struct ActivateResult {
 // =============================================================================
 
 fn format_plain_table(tabs: &[TabInfo]) -> String {
+    // The CONTEXT column only appears once a tab lives outside the default
+    // browser context.
+    let show_context = tabs.iter().any(|t| t.context.is_some());
     let mut out = String::new();
-    let _ = writeln!(
+    let _ = write!(
         out,
         "  {:<3} {:<14} {:<20} {:<26} ACTIVE",
         "#", "ID", "TITLE", "URL"
     );
+    out.push_str(if show_context { "  CONTEXT\n" } else { "\n" });
     for (i, tab) in tabs.iter().enumerate() {
         let active_marker = if tab.active { "*" } else { "" };
         let title: String = tab.title.chars().take(20).collect();
         let url: String = tab.url.chars().take(26).collect();
-        let _ = writeln!(
-            out,
-            "  {i:<3} {:<14} {:<20} {:<26} {}",
-            tab.id, title, url, active_marker
-        );
+        let _ = write!(out, "  {i:<3} {:<14} {:<20} {:<26} ", tab.id, title, url);
+        if show_context {
+            // Pad the marker to the width of "ACTIVE  " so CONTEXT lines up.
+            let _ = write!(
+                out,
+                "{active_marker:<8}{}",
+                tab.context.as_deref().unwrap_or_default()
+            );
+        } else {
+            out.push_str(active_marker);
+        }
+        out.push('\n');
     }
     out
 }
@@ -140,6 +160,18 @@ async fn execute_list(global: &GlobalOpts, include_all: bool) -> Result<(), AppE
     // (which does not reliably reflect activation state in headless mode).
     let visible_id = query_visible_target_id(&conn.client, &filtered).await;
 
+    // Context lookup is informational; older browsers without
+    // Target.getBrowserContexts still get a tab list. When it succeeds,
+    // recorded context tabs that have since closed are forgotten so a stale
+    // `--page-id` reports target_not_found instead of failing to attach.
+    let contexts = match crate::context::tab_contexts(&conn.client).await {
+        Ok(contexts) => {
+            crate::context::update_session(|s| s.retain_live_context_tabs(&contexts));
+            contexts
+        }
+        Err(_) => HashMap::new(),
+    };
+
     let tabs: Vec<TabInfo> = filtered
         .iter()
         .enumerate()
@@ -151,6 +183,7 @@ async fn execute_list(global: &GlobalOpts, include_all: bool) -> Result<(), AppE
                 Some(vid) => t.id == *vid,
                 None => i == 0, // fallback if CDP query fails
             },
+            context: contexts.get(&t.id).cloned(),
         })
         .collect();
 
@@ -166,10 +199,15 @@ async fn execute_create(
     global: &GlobalOpts,
     url: Option<&str>,
     background: bool,
+    context: Option<&str>,
 ) -> Result<(), AppError> {
     let conn = connect_from_global(global).await?;
     let client = &conn.client;
 
+    if let Some(context) = context {
+        crate::context::ensure_context(client, context).await?;
+    }
+
     // When --background is used, record the currently active (visible) tab
     // so we can re-activate it after creation. Uses CDP visibilityState
     // rather than /json/list ordering (which is unreliable in headless mode).
@@ -194,6 +232,9 @@ async fn execute_create(
     if background {
         params["background"] = serde_json::json!(true);
     }
+    if let Some(context) = context {
+        params["browserContextId"] = serde_json::json!(context);
+    }
 
     let result = client
         .send_command("Target.createTarget", Some(params))
@@ -201,6 +242,12 @@ async fn execute_create(
 
     let target_id = result["targetId"].as_str().unwrap_or_default().to_string();
 
+    // Record the tab under its context so later `--page-id`/`--tab` lookups
+    // can route to it.
+    if let Some(context) = context {
+        crate::context::update_session(|s| s.add_context_tab(context, &target_id));
+    }
+
     // Re-activate the original tab if --background was requested.
     // Uses HTTP /json/activate to tell Chrome which tab should be visible,
     // then verifies via CDP document.visibilityState (which is authoritative,
@@ -261,6 +308,7 @@ async fn execute_close(global: &GlobalOpts, target_args: &[String]) -> Result<()
             .await?;
         closed_ids.push(target.id.clone());
     }
+    crate::context::update_session(|s| s.remove_context_tabs(&closed_ids));
 
     // Poll until Chrome's HTTP endpoint reflects the tab closures.
     // The /json/list endpoint updates asynchronously after CDP commands,
@@ -493,6 +541,7 @@ mod tests {
                     Some(vid) => t.id == *vid,
                     None => i == 0,
                 },
+                context: None,
             })
             .collect();
         assert!(!tabs[0].active);
@@ -519,6 +568,7 @@ mod tests {
                     Some(vid) => t.id == *vid,
                     None => i == 0,
                 },
+                context: None,
             })
             .collect();
         assert!(tabs[0].active);
@@ -533,12 +583,14 @@ mod tests {
                 url: "https://google.com".to_string(),
                 title: "Google".to_string(),
                 active: true,
+                context: None,
             },
             TabInfo {
                 id: "DEF456".to_string(),
                 url: "https://github.com".to_string(),
                 title: "GitHub".to_string(),
                 active: false,
+                context: None,
             },
         ];
         let output = format_plain_table(&tabs);
@@ -554,6 +606,48 @@ mod tests {
         assert!(output.contains("GitHub"));
     }
 
+    #[test]
+    fn plain_table_shows_context_column_only_when_needed() {
+        let mut tabs = vec![TabInfo {
+            id: "ABC123".to_string(),
+            url: "https://google.com".to_string(),
+            title: "Google".to_string(),
+            active: true,
+            context: None,
+        }];
+        assert!(!format_plain_table(&tabs).contains("CONTEXT"));
+
+        tabs[0].context = Some("CTX1".to_string());
+        let output = format_plain_table(&tabs);
+        assert!(output.contains("CONTEXT"));
+        assert!(output.contains("CTX1"));
+    }
+
+    #[test]
+    fn plain_table_aligns_context_column() {
+        let tabs = vec![
+            TabInfo {
+                id: "ABC123".to_string(),
+                url: "https://google.com".to_string(),
+                title: "Google".to_string(),
+                active: true,
+                context: Some("CTX1".to_string()),
+            },
+            TabInfo {
+                id: "DEF456".to_string(),
+                url: "https://github.com".to_string(),
+                title: "GitHub".to_string(),
+                active: false,
+                context: Some("CTX2".to_string()),
+            },
+        ];
+        let output = format_plain_table(&tabs);
+        let lines: Vec<&str> = output.lines().collect();
+        let column = lines[0].find("CONTEXT").unwrap();
+        assert_eq!(lines[1].find("CTX1"), Some(column));
+        assert_eq!(lines[2].find("CTX2"), Some(column));
+    }
+
     #[test]
     fn last_tab_protection_logic() {
         // Simulate: 2 page targets, closing both should fail
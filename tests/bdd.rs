@@ -5185,6 +5185,10 @@ const COOKIE_TESTABLE_SCENARIOS: &[&str] = &[
     "Cookie set requires name and value arguments",
     "Cookie delete requires name argument",
     "Cookie subcommand is required",
+    "Cookie help lists export and import",
+    "Cookie export requires a file",
+    "Cookie export rejects an unknown format",
+    "Cookie import requires a file",
 ];
 
 /// Dialog BDD scenarios that can be tested without a running Chrome instance.
@@ -5269,6 +5273,10 @@ const CONSOLE_TESTABLE_SCENARIOS: &[&str] = &[
     "Console follow help shows all flags",
     "Conflicting flags --type and --errors-only on read",
     "Conflicting flags --type and --errors-only on follow",
+    "Console read help documents exception entries",
+    "Console follow help documents exception entries",
+    "Console read and follow accept a source selector",
+    "Console read rejects an unknown source",
 ];
 
 /// Scroll BDD scenarios that can be tested without a running Chrome instance.
@@ -7604,6 +7612,156 @@ const CLEAN_MARKDOWN_TESTABLE_SCENARIOS: &[&str] = &[
     "Bound raw input and generated output (AC9)",
 ];
 
+/// Daemon scenarios testable without a running Chrome instance. Passive
+/// network/console history needs a live browser and is verified manually.
+const DAEMON_TESTABLE_SCENARIOS: &[&str] = &[
+    "Status reports not running when no daemon exists",
+    "Stop is a no-op when no daemon exists",
+    "Start fails with a connection error when Chrome is unreachable",
+    "Help documents the daemon subcommands",
+    "Start help documents foreground mode",
+];
+
+/// Raw CDP passthrough scenarios testable without a running Chrome instance.
+const CDP_PASSTHROUGH_TESTABLE_SCENARIOS: &[&str] = &[
+    "Send rejects a method without a domain",
+    "Send rejects params that are not a JSON object",
+    "Send rejects malformed params JSON",
+    "Listen requires at least one event",
+    "Listen rejects a zero count",
+    "Help documents the cdp subcommands",
+    "Send help documents browser-level mode",
+];
+
+/// Network route scenarios testable without a running Chrome instance (rule
+/// management only; enforcement needs a live browser).
+const NETWORK_ROUTE_TESTABLE_SCENARIOS: &[&str] = &[
+    "Listing routes with none defined returns an empty array",
+    "Adding a status-only route prints the stored rule",
+    "Adding an abort route records the CDP error reason",
+    "Adding a header rewrite route",
+    "A route needs an action",
+    "Abort cannot be combined with a mocked response",
+    "An invalid glob is rejected",
+    "A missing response file is rejected",
+    "Removing an unknown route fails",
+    "Clearing with no routes reports zero",
+];
+
+/// Network HAR scenarios testable without a running Chrome instance (help
+/// surface and argument validation; export and replay need a live browser).
+const NETWORK_HAR_TESTABLE_SCENARIOS: &[&str] = &[
+    "network help lists the har subcommand",
+    "har help documents its flags",
+    "har requires an output file",
+    "replay help documents its flags",
+    "replay requires a HAR file",
+    "replay rejects an unknown not-found mode",
+    "replay reports a missing HAR file",
+];
+
+const BROWSER_CONTEXT_TESTABLE_SCENARIOS: &[&str] = &[
+    "context help lists its subcommands",
+    "dispose requires a context ID",
+    "tabs create documents the context flag",
+    "tabs create requires a value for the context flag",
+];
+
+const NAMED_SESSIONS_TESTABLE_SCENARIOS: &[&str] = &[
+    "help documents the session flag and environment variable",
+    "connect help documents listing sessions",
+    "session names that could escape the state directory are rejected",
+    "list-sessions conflicts with launch",
+];
+
+const PIPE_TRANSPORT_TESTABLE_SCENARIOS: &[&str] = &[
+    "connect help documents the pipe transport",
+    "pipe requires launch",
+];
+
+const JS_INJECT_TESTABLE_SCENARIOS: &[&str] = &[
+    "js help lists the inject subcommand",
+    "inject add requires a file",
+    "inject add rejects an unknown world",
+    "inject remove requires a name or --all",
+    "inject remove rejects a name together with --all",
+];
+
+const STORAGE_TESTABLE_SCENARIOS: &[&str] = &[
+    "storage help lists both storage areas",
+    "storage area help lists the item subcommands",
+    "storage get requires a key",
+    "storage set requires a value",
+    "storage rejects an unknown area",
+    "storage idb help lists the IndexedDB subcommands",
+    "storage idb dump requires a database and store",
+    "storage idb dump rejects a non-numeric limit",
+];
+
+const STATE_TESTABLE_SCENARIOS: &[&str] = &[
+    "state help lists save and load",
+    "state save requires a file",
+    "state load requires a file",
+];
+
+/// Snapshot diff BDD scenarios that can be tested without a running Chrome instance.
+const SNAPSHOT_DIFF_TESTABLE_SCENARIOS: &[&str] = &[
+    "page snapshot help documents --diff",
+    "Interaction help documents --include-snapshot-diff",
+    "Form help documents --include-snapshot-diff",
+    "Snapshot diff cannot be combined with compact mode",
+];
+
+const STABLE_UIDS_TESTABLE_SCENARIOS: &[&str] = &["page snapshot help explains UID stability"];
+
+const GOLDEN_SNAPSHOT_TESTABLE_SCENARIOS: &[&str] = &[
+    "page snapshot help documents golden assertions",
+    "Tolerance flags require --assert",
+    "Golden assertions cannot be combined with --diff",
+];
+
+const SCREENSHOT_COMPARE_TESTABLE_SCENARIOS: &[&str] = &[
+    "page screenshot help documents --compare",
+    "Threshold requires --compare",
+    "Diff output requires --compare",
+];
+
+const SCREENSHOT_ANNOTATE_TESTABLE_SCENARIOS: &[&str] = &[
+    "page screenshot help documents --annotate",
+    "Annotate rejects unknown modes",
+    "Annotate cannot be combined with full-page capture",
+];
+
+const PAGE_PDF_TESTABLE_SCENARIOS: &[&str] = &[
+    "page pdf help documents the print options",
+    "page pdf requires an output file",
+    "page pdf rejects unknown paper sizes",
+];
+
+const PAGE_ARCHIVE_TESTABLE_SCENARIOS: &[&str] = &[
+    "page archive help documents file and bundle modes",
+    "page archive requires --file or --bundle",
+    "page archive rejects --file together with --bundle",
+];
+
+const PAGE_RECORD_TESTABLE_SCENARIOS: &[&str] = &[
+    "page record help lists start and stop",
+    "page record start documents its options",
+    "page record start requires a directory",
+    "page record start rejects an out-of-range quality",
+];
+
+const PAGE_TABLES_TESTABLE_SCENARIOS: &[&str] = &[
+    "page tables help documents selector, format, and spans",
+    "page tables rejects an unknown format",
+];
+
+const PAGE_EXTRACT_TESTABLE_SCENARIOS: &[&str] = &[
+    "page extract help documents the schema format",
+    "page extract requires a schema",
+    "page extract reports an unreadable schema file",
+];
+
 const ISSUE_286_FEATURE: &str =
     "tests/features/286-iframe-guidance-advertises-frame-command-shapes-the-parser-rejects.feature";
 const EXAMPLES_STRATEGIES_FEATURE: &str = "tests/features/examples-strategies.feature";
@@ -8362,6 +8520,202 @@ async fn main() {
         )
         .await;
 
+    // Background daemon — lifecycle commands without a daemon and help surface.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/daemon.feature",
+            |_feature, _rule, scenario| DAEMON_TESTABLE_SCENARIOS.contains(&scenario.name.as_str()),
+        )
+        .await;
+
+    // Raw CDP passthrough — argument validation and help surface.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/cdp-passthrough.feature",
+            |_feature, _rule, scenario| {
+                CDP_PASSTHROUGH_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Network route rules — add/list/remove/clear and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/network-route.feature",
+            |_feature, _rule, scenario| {
+                NETWORK_ROUTE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Network HAR export and replay — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/network-har.feature",
+            |_feature, _rule, scenario| {
+                NETWORK_HAR_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Browser contexts — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/browser-context.feature",
+            |_feature, _rule, scenario| {
+                BROWSER_CONTEXT_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Named sessions — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/named-sessions.feature",
+            |_feature, _rule, scenario| {
+                NAMED_SESSIONS_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Pipe transport — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/pipe-transport.feature",
+            |_feature, _rule, scenario| {
+                PIPE_TRANSPORT_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // JS preload scripts — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/js-inject.feature",
+            |_feature, _rule, scenario| {
+                JS_INJECT_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Web storage — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/storage.feature",
+            |_feature, _rule, scenario| {
+                STORAGE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Authentication state — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/state.feature",
+            |_feature, _rule, scenario| STATE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str()),
+        )
+        .await;
+
+    // Snapshot diffing — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/snapshot-diff.feature",
+            |_feature, _rule, scenario| {
+                SNAPSHOT_DIFF_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Stable UIDs — help surface.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/stable-uids.feature",
+            |_feature, _rule, scenario| {
+                STABLE_UIDS_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Golden snapshot assertions — help surface and flag validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/golden-snapshot.feature",
+            |_feature, _rule, scenario| {
+                GOLDEN_SNAPSHOT_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Screenshot comparison — help surface and flag validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/screenshot-compare.feature",
+            |_feature, _rule, scenario| {
+                SCREENSHOT_COMPARE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Annotated screenshots — help surface and flag validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/screenshot-annotate.feature",
+            |_feature, _rule, scenario| {
+                SCREENSHOT_ANNOTATE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // PDF export — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/page-pdf.feature",
+            |_feature, _rule, scenario| {
+                PAGE_PDF_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Page archive — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/page-archive.feature",
+            |_feature, _rule, scenario| {
+                PAGE_ARCHIVE_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Screencast recording — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/page-record.feature",
+            |_feature, _rule, scenario| {
+                PAGE_RECORD_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Table extraction — help surface and argument validation.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/page-tables.feature",
+            |_feature, _rule, scenario| {
+                PAGE_TABLES_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
+    // Schema-driven extraction — help surface and schema loading.
+    CliWorld::cucumber()
+        .filter_run_and_exit(
+            "tests/features/page-extract.feature",
+            |_feature, _rule, scenario| {
+                PAGE_EXTRACT_TESTABLE_SCENARIOS.contains(&scenario.name.as_str())
+            },
+        )
+        .await;
+
     // Batch script execution (issue #199) — schema validation, help surface, dry-run, and
     // capabilities/examples integration can be tested without Chrome. Live execution scenarios
     // (AC1-AC8, AC14, AC16) require a running Chrome instance and are verified via smoke test.
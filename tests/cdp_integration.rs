@@ -679,3 +679,72 @@ async fn invalid_json_handling() {
     let result = client.send_command("Test.second", None).await;
     assert!(result.is_ok(), "subsequent commands should still work");
 }
+
+/// Chrome's side of `--remote-debugging-pipe`: answers each NUL-terminated
+/// command with `{"id": N, "result": {"method": ...}}`, then closes its end
+/// after `n` commands.
+#[cfg(unix)]
+fn start_pipe_peer(
+    n: usize,
+) -> (
+    std::os::fd::OwnedFd,
+    std::os::fd::OwnedFd,
+    std::thread::JoinHandle<()>,
+) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let (commands_rx, commands_tx) = std::io::pipe().unwrap();
+    let (responses_rx, mut responses_tx) = std::io::pipe().unwrap();
+    let handle = std::thread::spawn(move || {
+        let mut reader = BufReader::new(commands_rx);
+        for _ in 0..n {
+            let mut buf = Vec::new();
+            if reader.read_until(0, &mut buf).unwrap_or(0) == 0 {
+                return;
+            }
+            assert_eq!(buf.pop(), Some(0), "command must be NUL-terminated");
+            let cmd: Value = serde_json::from_slice(&buf).unwrap();
+            let response = json!({"id": cmd["id"], "result": {"method": cmd["method"]}});
+            let mut framed = serde_json::to_vec(&response).unwrap();
+            framed.push(0);
+            responses_tx.write_all(&framed).unwrap();
+        }
+    });
+    (commands_tx.into(), responses_rx.into(), handle)
+}
+
+/// Commands and responses travel as NUL-delimited JSON over a pipe pair.
+#[cfg(unix)]
+#[tokio::test]
+async fn pipe_transport_round_trip() {
+    let (writer, reader, _peer) = start_pipe_peer(2);
+    let client = CdpClient::connect_pipe(writer, reader, quick_config()).unwrap();
+    assert!(client.is_connected());
+    assert_eq!(client.url(), agentchrome::cdp::PIPE_URL);
+
+    let (first, second) = tokio::join!(
+        client.send_command("Browser.getVersion", None),
+        client.send_command("Target.getTargets", None),
+    );
+    assert_eq!(first.unwrap()["method"], "Browser.getVersion");
+    assert_eq!(second.unwrap()["method"], "Target.getTargets");
+}
+
+/// Once Chrome closes the pipe, commands fail instead of reconnecting.
+#[cfg(unix)]
+#[tokio::test]
+async fn pipe_transport_closed_by_peer() {
+    let (writer, reader, peer) = start_pipe_peer(1);
+    let client = CdpClient::connect_pipe(writer, reader, quick_config()).unwrap();
+
+    assert!(client.send_command("Test.first", None).await.is_ok());
+    peer.join().unwrap();
+
+    let result = client.send_command("Test.second", None).await;
+    assert!(
+        result.is_err(),
+        "commands should fail after the pipe closes"
+    );
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!client.is_connected());
+}
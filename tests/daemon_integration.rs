@@ -0,0 +1,243 @@
+//! Integration tests for the background daemon.
+//!
+//! A mock browser stands in for Chrome; the daemon runs in-process against it
+//! and a regular `CdpClient` talks to the daemon over its Unix socket.
+
+#![cfg(unix)]
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agentchrome::cdp::{CdpClient, CdpConfig};
+use agentchrome::connection::ManagedSession;
+use agentchrome::daemon::{self, EventCategory};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Start a mock browser with one page target `T1`.
+///
+/// Every `Target.attachToTarget` gets a fresh `S<n>` session ID. The first
+/// `Runtime.enable` on any session replays one console message, mimicking
+/// Chrome's replay of messages logged before the session attached.
+///
+/// Every command received is appended to the returned log in arrival order.
+async fn start_mock_browser() -> (SocketAddr, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&log);
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let (mut sink, mut source) = ws.split();
+        let mut next_session = 1;
+        let mut replayed = false;
+        while let Some(Ok(msg)) = source.next().await {
+            let Message::Text(text) = msg else { continue };
+            let cmd: Value = serde_json::from_str(&text).unwrap();
+            received.lock().unwrap().push(cmd.clone());
+            let mut outgoing = Vec::new();
+            let result = match cmd["method"].as_str().unwrap_or_default() {
+                "Target.attachToTarget" => {
+                    let sid = format!("S{next_session}");
+                    next_session += 1;
+                    json!({ "sessionId": sid })
+                }
+                "Runtime.enable" if !replayed => {
+                    replayed = true;
+                    outgoing.push(json!({
+                        "method": "Runtime.consoleAPICalled",
+                        "params": {"type": "log", "args": [{"type": "string", "value": "early"}]},
+                        "sessionId": cmd["sessionId"],
+                    }));
+                    json!({})
+                }
+                "Page.navigate" => json!({ "frameId": "F1" }),
+                _ => json!({}),
+            };
+            let mut response = json!({ "id": cmd["id"], "result": result });
+            if let Some(sid) = cmd.get("sessionId") {
+                response["sessionId"] = sid.clone();
+            }
+            for event in outgoing {
+                sink.send(Message::Text(event.to_string().into()))
+                    .await
+                    .unwrap();
+            }
+            sink.send(Message::Text(response.to_string().into()))
+                .await
+                .unwrap();
+            if cmd["method"] == "Target.setDiscoverTargets" {
+                let created = json!({
+                    "method": "Target.targetCreated",
+                    "params": {"targetInfo": {"targetId": "T1", "type": "page", "url": "about:blank"}},
+                });
+                sink.send(Message::Text(created.to_string().into()))
+                    .await
+                    .unwrap();
+            }
+        }
+    });
+    (addr, log)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("agentchrome-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn wait_for(path: &Path) {
+    for _ in 0..100 {
+        if path.exists() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("{} was never created", path.display());
+}
+
+#[tokio::test]
+async fn daemon_relays_commands_and_serves_buffered_events() {
+    let (addr, _) = start_mock_browser().await;
+    let dir = temp_dir("daemon");
+    let socket = dir.join("daemon.sock");
+    let record = dir.join("daemon.json");
+
+    let server = {
+        let (socket, record) = (socket.clone(), record.clone());
+        tokio::spawn(async move {
+            daemon::serve(&format!("ws://{addr}"), addr.port(), &socket, &record).await
+        })
+    };
+    wait_for(&record).await;
+    let stored = daemon::read_record_from(&record).unwrap().unwrap();
+    assert_eq!(stored.port, addr.port());
+
+    let client = CdpClient::connect(&stored.url(), CdpConfig::default())
+        .await
+        .unwrap();
+    assert!(daemon::is_daemon_client(&client));
+
+    let managed = ManagedSession::new(client.create_session("T1").await.unwrap());
+    let result = managed
+        .send_command("Page.navigate", Some(json!({"url": "about:blank"})))
+        .await
+        .unwrap();
+    assert_eq!(result["frameId"], "F1");
+
+    // The daemon attached first, so it owns the replayed console message.
+    let mut console = Vec::new();
+    for _ in 0..50 {
+        console = daemon::buffered_events(&managed, EventCategory::Console)
+            .await
+            .unwrap();
+        if !console.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(console.len(), 1);
+    assert_eq!(console[0].method, "Runtime.consoleAPICalled");
+    assert_eq!(console[0].params["args"][0]["value"], "early");
+
+    let status = client
+        .send_command(daemon::METHOD_STATUS, None)
+        .await
+        .unwrap();
+    assert_eq!(status["clients"], 1);
+    assert_eq!(status["targets"][0]["target_id"], "T1");
+
+    client
+        .send_command(daemon::METHOD_SHUTDOWN, None)
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("daemon did not shut down")
+        .unwrap()
+        .unwrap();
+    assert!(!record.exists());
+    assert!(!socket.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn unknown_local_method_is_a_protocol_error() {
+    let (addr, _) = start_mock_browser().await;
+    let dir = temp_dir("daemon-unknown");
+    let socket = dir.join("daemon.sock");
+    let record = dir.join("daemon.json");
+
+    let server = {
+        let (socket, record) = (socket.clone(), record.clone());
+        tokio::spawn(async move {
+            daemon::serve(&format!("ws://{addr}"), addr.port(), &socket, &record).await
+        })
+    };
+    wait_for(&record).await;
+
+    let client = CdpClient::connect(&daemon::daemon_url(&socket), CdpConfig::default())
+        .await
+        .unwrap();
+    let err = client
+        .send_command("Agentchrome.bogus", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        agentchrome::cdp::CdpError::Protocol { code: -32601, .. }
+    ));
+
+    client
+        .send_command(daemon::METHOD_SHUTDOWN, None)
+        .await
+        .unwrap();
+    let _ = tokio::time::timeout(Duration::from_secs(5), server).await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn pipelined_commands_reach_chrome_in_order() {
+    let (addr, log) = start_mock_browser().await;
+    let dir = temp_dir("daemon-order");
+    let socket = dir.join("daemon.sock");
+    let record = dir.join("daemon.json");
+
+    let server = {
+        let (socket, record) = (socket.clone(), record.clone());
+        tokio::spawn(async move {
+            daemon::serve(&format!("ws://{addr}"), addr.port(), &socket, &record).await
+        })
+    };
+    wait_for(&record).await;
+
+    let client = CdpClient::connect(&daemon::daemon_url(&socket), CdpConfig::default())
+        .await
+        .unwrap();
+    let session = client.create_session("T1").await.unwrap();
+    let commands = (0..32).map(|n| session.send_command("Runtime.evaluate", Some(json!({"n": n}))));
+    for result in futures_util::future::join_all(commands).await {
+        result.unwrap();
+    }
+
+    let order: Vec<u64> = log
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|cmd| cmd["method"] == "Runtime.evaluate")
+        .filter_map(|cmd| cmd["params"]["n"].as_u64())
+        .collect();
+    assert_eq!(order, (0..32).collect::<Vec<u64>>());
+
+    client
+        .send_command(daemon::METHOD_SHUTDOWN, None)
+        .await
+        .unwrap();
+    let _ = tokio::time::timeout(Duration::from_secs(5), server).await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
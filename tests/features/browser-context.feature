@@ -0,0 +1,63 @@
+# File: tests/features/browser-context.feature
+#
+# Isolated browser contexts for agents sharing one Chrome instance
+
+Feature: Browser contexts
+  As an AI agent sharing a browser with other agents
+  I want to open tabs in their own browser context
+  So that cookies, storage, and cache do not leak between sessions
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: context help lists its subcommands
+    When I run "agentchrome context --help"
+    Then the exit code should be 0
+    And stdout should contain "create"
+    And stdout should contain "list"
+    And stdout should contain "dispose"
+
+  Scenario: dispose requires a context ID
+    When I run "agentchrome context dispose"
+    Then the exit code should be nonzero
+    And stderr should contain "<ID>"
+
+  Scenario: tabs create documents the context flag
+    When I run "agentchrome tabs create --help"
+    Then the exit code should be 0
+    And stdout should contain "--context"
+
+  Scenario: tabs create requires a value for the context flag
+    When I run "agentchrome tabs create https://example.com --context"
+    Then the exit code should be nonzero
+    And stderr should contain "--context"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Creating a context and opening a tab in it
+    Given Chrome is running with a session
+    When I run "agentchrome context create"
+    And I run "agentchrome tabs create https://example.com --context <CONTEXT_ID>"
+    Then the exit code should be 0
+    And "agentchrome tabs list" should show the tab with context "<CONTEXT_ID>"
+
+  Scenario: Listing contexts with their tabs
+    Given Chrome is running with a session
+    And a browser context with one tab exists
+    When I run "agentchrome context list"
+    Then the exit code should be 0
+    And stdout JSON should contain the context ID and its tab ID
+
+  Scenario: Disposing a context closes its tabs
+    Given Chrome is running with a session
+    And a browser context with one tab exists
+    When I run "agentchrome context dispose <CONTEXT_ID>"
+    Then the exit code should be 0
+    And stdout JSON should have key "closed_tabs"
+    And the context should no longer be listed by "agentchrome context list"
+
+  Scenario: Opening a tab in an unknown context fails
+    Given Chrome is running with a session
+    When I run "agentchrome tabs create --context MISSING"
+    Then the exit code should be 1
+    And stderr should contain "Browser context 'MISSING' not found"
@@ -0,0 +1,70 @@
+# File: tests/features/cdp-passthrough.feature
+#
+# Raw CDP passthrough: `cdp send` and `cdp listen`
+
+Feature: Raw CDP passthrough
+  As an AI agent automating a CDP domain agentchrome does not wrap yet
+  I want to send arbitrary CDP commands and stream arbitrary CDP events
+  So that I do not have to drop back to a Node script
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: Send rejects a method without a domain
+    When I run "agentchrome cdp send navigate"
+    Then the exit code should be 1
+    And stderr should contain "Domain.method"
+
+  Scenario: Send rejects params that are not a JSON object
+    When I run "agentchrome cdp send Page.navigate [1]"
+    Then the exit code should be 1
+    And stderr should contain "PARAMS must be a JSON object"
+
+  Scenario: Send rejects malformed params JSON
+    When I run "agentchrome cdp send Page.navigate {nope"
+    Then the exit code should be 1
+    And stderr should contain "Invalid PARAMS JSON"
+
+  Scenario: Listen requires at least one event
+    When I run "agentchrome cdp listen"
+    Then the exit code should be nonzero
+
+  Scenario: Listen rejects a zero count
+    When I run "agentchrome cdp listen Page.loadEventFired --count 0"
+    Then the exit code should be nonzero
+
+  Scenario: Help documents the cdp subcommands
+    When I run "agentchrome cdp --help"
+    Then the exit code should be 0
+    And stdout should contain "send"
+    And stdout should contain "listen"
+
+  Scenario: Send help documents browser-level mode
+    When I run "agentchrome cdp send --help"
+    Then the exit code should be 0
+    And stdout should contain "--browser"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Send returns the raw result of a tab-level method
+    Given Chrome is running with a session
+    When I run "agentchrome cdp send Page.getFrameTree"
+    Then the exit code should be 0
+    And stdout JSON should have key "frameTree"
+
+  Scenario: Send on the browser connection
+    Given Chrome is running with a session
+    When I run "agentchrome cdp send --browser Browser.getVersion"
+    Then the exit code should be 0
+    And stdout JSON should have key "product"
+
+  Scenario: Protocol errors exit with code 5
+    Given Chrome is running with a session
+    When I run "agentchrome cdp send Page.doesNotExist"
+    Then the exit code should be 5
+
+  Scenario: Listen stops after the requested count
+    Given Chrome is running with a session
+    When I run "agentchrome cdp listen Runtime.consoleAPICalled --count 1"
+    Then the exit code should be 0
+    And stdout should contain "Runtime.consoleAPICalled"
@@ -48,6 +48,32 @@ Feature: Console Message Reading with Filtering
     Then the exit code should be nonzero
     And stderr should contain "cannot be used with"
 
+  Scenario: Console read help documents exception entries
+    Given agentchrome is built
+    When I run "agentchrome console read --help"
+    Then the exit code should be 0
+    And stdout should contain "exceptionDetails"
+    And stdout should contain "error, assert, and exception"
+
+  Scenario: Console follow help documents exception entries
+    Given agentchrome is built
+    When I run "agentchrome console follow --help"
+    Then the exit code should be 0
+    And stdout should contain "Uncaught exceptions"
+
+  Scenario: Console read and follow accept a source selector
+    Given agentchrome is built
+    When I run "agentchrome console read --help"
+    Then the exit code should be 0
+    And stdout should contain "--source"
+    And stdout should contain "exception"
+
+  Scenario: Console read rejects an unknown source
+    Given agentchrome is built
+    When I run "agentchrome console read --source network"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
   # --- Console Read: List Mode (requires Chrome) ---
 
   # Scenario: List console messages from current page
@@ -82,10 +108,45 @@ Feature: Console Message Reading with Filtering
   #   Given Chrome is running with CDP enabled
   #   And a page has generated log and error messages
   #   When I run "agentchrome console read --errors-only"
-  #   Then all returned messages have type "error" or "assert"
+  #   Then all returned messages have type "error", "assert", or "exception"
+
+  # --- Console Read: Uncaught Exceptions (requires Chrome) ---
+
+  # Scenario: Uncaught errors are listed as exception entries
+  #   Given Chrome is running with CDP enabled
+  #   And a page has thrown an uncaught TypeError from an iframe
+  #   When I run "agentchrome console read --type exception"
+  #   Then each returned message has "exceptionDetails", "stackTrace", and "frameId"
+  #   And the first stack frame has "file", "line", "column", and "functionName"
+
+  # Scenario: Unhandled promise rejections are listed as exception entries
+  #   Given Chrome is running with CDP enabled
+  #   And a page has rejected a promise without a handler
+  #   When I run "agentchrome console read --errors-only"
+  #   Then a returned message has text starting with "Uncaught (in promise)"
+
+  # Scenario: fail-on-error trips on an uncaught exception
+  #   Given Chrome is running with CDP enabled
+  #   When I run "agentchrome console follow --timeout 3000 --fail-on-error"
+  #   And the page throws an uncaught error
+  #   Then the exit code should be 1
 
   # --- Console Read: Detail Mode (requires Chrome) ---
 
+  # --- Console Read: Log Domain Entries (requires Chrome) ---
+
+  # Scenario: Failed resource loads appear as log entries
+  #   Given Chrome is running with CDP enabled
+  #   And a page has failed to load a script
+  #   When I run "agentchrome console read --source log"
+  #   Then every returned message has source "log" and category "network"
+
+  # Scenario: Replayed log entries are not streamed by follow
+  #   Given Chrome is running with CDP enabled
+  #   And a page has generated Log domain entries
+  #   When I run "agentchrome console follow --source log --timeout 1000"
+  #   Then no replayed entries are printed
+
   # Scenario: Get detailed information about a specific message
   #   Given Chrome is running with CDP enabled
   #   And console messages exist
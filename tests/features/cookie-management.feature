@@ -140,3 +140,24 @@ Feature: Cookie Management
     When I run "agentchrome cookie"
     Then the exit code should be non-zero
     And stderr should contain "subcommand"
+
+  Scenario: Cookie help lists export and import
+    When I run "agentchrome cookie --help"
+    Then the exit code should be 0
+    And stdout should contain "export"
+    And stdout should contain "import"
+
+  Scenario: Cookie export requires a file
+    When I run "agentchrome cookie export"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: Cookie export rejects an unknown format
+    When I run "agentchrome cookie export --file cookies.txt --format har"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  Scenario: Cookie import requires a file
+    When I run "agentchrome cookie import"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
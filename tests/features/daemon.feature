@@ -0,0 +1,59 @@
+# File: tests/features/daemon.feature
+#
+# Background daemon that keeps the CDP connection and event buffers alive
+# between invocations.
+
+Feature: Background daemon
+  As an AI agent driving Chrome through many short CLI calls
+  I want a long-lived process that holds the CDP connection and buffers events
+  So that network and console history is passive and each command starts faster
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: Status reports not running when no daemon exists
+    When I run "agentchrome daemon status"
+    Then the exit code should be 0
+    And stdout should be valid JSON
+    And stdout JSON should have key "running"
+    And stdout should contain "false"
+    And stdout should not contain "pid"
+
+  Scenario: Stop is a no-op when no daemon exists
+    When I run "agentchrome daemon stop"
+    Then the exit code should be 0
+    And stdout JSON should have key "stopped"
+    And stdout should contain "false"
+
+  Scenario: Start fails with a connection error when Chrome is unreachable
+    When I run "agentchrome daemon start --port 1"
+    Then the exit code should be 2
+    And stderr should be valid JSON
+
+  Scenario: Help documents the daemon subcommands
+    When I run "agentchrome daemon --help"
+    Then the exit code should be 0
+    And stdout should contain "start"
+    And stdout should contain "stop"
+    And stdout should contain "status"
+
+  Scenario: Start help documents foreground mode
+    When I run "agentchrome daemon start --help"
+    Then the exit code should be 0
+    And stdout should contain "--foreground"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Network history is read without reloading the page
+    Given Chrome is running with a session
+    And the daemon has been started
+    When I run "agentchrome network list"
+    Then the exit code should be 0
+    And the page was not reloaded
+
+  Scenario: Console read returns messages logged before the command started
+    Given Chrome is running with a session
+    And the daemon has been started
+    And the page logged "hello" to the console
+    When I run "agentchrome console read"
+    Then stdout should contain "hello"
@@ -0,0 +1,54 @@
+# File: tests/features/golden-snapshot.feature
+#
+# Golden accessibility snapshot assertions (page snapshot --assert / --update-golden)
+
+Feature: Golden snapshot assertions
+  As a team pinning critical page structure in CI
+  I want to compare the compacted accessibility tree against a stored golden file
+  So that structural regressions fail the build without relying on pixels
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page snapshot help documents golden assertions
+    When I run "agentchrome page snapshot --help"
+    Then the exit code should be 0
+    And stdout should contain "--assert"
+    And stdout should contain "--update-golden"
+    And stdout should contain "--ignore-name"
+    And stdout should contain "--ignore-order"
+
+  Scenario: Tolerance flags require --assert
+    When I run "agentchrome page snapshot --ignore-order"
+    Then the exit code should be nonzero
+    And stderr should contain "--assert"
+
+  Scenario: Golden assertions cannot be combined with --diff
+    When I run "agentchrome page snapshot --assert golden.yaml --diff"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Asserting against a freshly recorded golden passes
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot --update-golden golden.yaml"
+    When I run "agentchrome page snapshot --assert golden.yaml --json"
+    Then the exit code should be 0
+    And the output should contain "\"passed\":true"
+
+  Scenario: A removed button fails the assertion with a mismatch report
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot --update-golden golden.yaml"
+    When the page removes the "Save" button
+    And I run "agentchrome page snapshot --assert golden.yaml"
+    Then the exit code should be nonzero
+    And stderr should contain "snapshot_mismatch"
+    And stderr should contain "missing"
+
+  Scenario: Ignored names do not cause mismatches
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot --update-golden golden.yaml"
+    When the page changes the status text from "3 items" to "5 items"
+    And I run "agentchrome page snapshot --assert golden.yaml --ignore-name \"items$\""
+    Then the exit code should be 0
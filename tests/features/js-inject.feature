@@ -0,0 +1,69 @@
+# File: tests/features/js-inject.feature
+#
+# Persistent preload scripts registered with Page.addScriptToEvaluateOnNewDocument
+
+Feature: JS preload scripts
+  As an AI agent testing a page behind feature flags or mocked APIs
+  I want shims that run before the page's own scripts on every navigation
+  So that I do not have to re-run them with 'js exec' after each page load
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: js help lists the inject subcommand
+    When I run "agentchrome js inject --help"
+    Then the exit code should be 0
+    And stdout should contain "add"
+    And stdout should contain "list"
+    And stdout should contain "remove"
+
+  Scenario: inject add requires a file
+    When I run "agentchrome js inject add"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: inject add rejects an unknown world
+    When I run "agentchrome js inject add --file shim.js --world utility"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  Scenario: inject remove requires a name or --all
+    When I run "agentchrome js inject remove"
+    Then the exit code should be nonzero
+    And stderr should contain "required"
+
+  Scenario: inject remove rejects a name together with --all
+    When I run "agentchrome js inject remove shim --all"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: An injected script runs after navigation
+    Given Chrome is running with a page loaded
+    And a file "shim.js" containing "window.__flag = 1;"
+    When I run "agentchrome js inject add --file shim.js"
+    And I run "agentchrome navigate https://example.com/"
+    And I run "agentchrome js exec window.__flag"
+    Then the JSON output should have "result" equal to 1
+
+  Scenario: Isolated world scripts are hidden from the page
+    Given Chrome is running with a page loaded
+    And a file "helpers.js" containing "window.__helper = 1;"
+    When I run "agentchrome js inject add --file helpers.js --world isolated"
+    And I run "agentchrome navigate https://example.com/"
+    And I run "agentchrome js exec typeof window.__helper"
+    Then the JSON output should have "result" equal to "undefined"
+
+  Scenario: Listing shows scripts for the current tab only
+    Given Chrome is running with two tabs
+    And a script "shim" was injected into the first tab
+    When I run "agentchrome --tab 1 js inject list"
+    Then stdout should be "[]"
+
+  Scenario: Removing a script stops it on the next navigation
+    Given a script "shim" was injected into the current tab
+    When I run "agentchrome js inject remove shim"
+    And I run "agentchrome navigate https://example.com/"
+    And I run "agentchrome js exec typeof window.__flag"
+    Then the JSON output should have "result" equal to "undefined"
@@ -0,0 +1,54 @@
+# File: tests/features/named-sessions.feature
+#
+# Named sessions that keep separate connection and state files per Chrome
+
+Feature: Named sessions
+  As an AI agent running next to other agents on the same machine
+  I want my connection and state files kept under my own session name
+  So that agents driving different Chrome instances do not overwrite each other
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: help documents the session flag and environment variable
+    When I run "agentchrome --help"
+    Then the exit code should be 0
+    And stdout should contain "--session"
+    And stdout should contain "AGENTCHROME_SESSION"
+
+  Scenario: connect help documents listing sessions
+    When I run "agentchrome connect --help"
+    Then the exit code should be 0
+    And stdout should contain "--list-sessions"
+    And stdout should contain "sessions/<NAME>"
+
+  Scenario: session names that could escape the state directory are rejected
+    When I run "agentchrome --session ../other connect --status"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  Scenario: list-sessions conflicts with launch
+    When I run "agentchrome connect --list-sessions --launch"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Two sessions keep separate connections
+    Given Chrome is running on port 9222 and another Chrome on port 9333
+    When I run "agentchrome connect --port 9222"
+    And I run "agentchrome --session b connect --port 9333"
+    Then "agentchrome --session b connect --status" should report port 9333
+    And "agentchrome connect --status" should report port 9222
+
+  Scenario: Listing sessions marks the current one
+    Given sessions "default" and "b" are connected
+    When I run "agentchrome --session b connect --list-sessions"
+    Then the exit code should be 0
+    And the session "b" should have "current" set to true
+    And the session "default" should have "current" set to false
+
+  Scenario: The daemon runs per session
+    Given session "b" is connected
+    When I run "agentchrome --session b daemon start"
+    Then the daemon socket should be created under "~/.agentchrome/sessions/b/"
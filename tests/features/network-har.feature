@@ -0,0 +1,74 @@
+# File: tests/features/network-har.feature
+#
+# HAR 1.2 export of captured network traffic and replay of HAR archives
+
+Feature: Network HAR export and replay
+  As an AI agent debugging and testing a web app
+  I want to export the traffic I captured as a HAR file and replay it later
+  So that I can inspect sessions in HAR viewers and run tests offline
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: network help lists the har subcommand
+    When I run "agentchrome network --help"
+    Then the exit code should be 0
+    And stdout should contain "har"
+    And stdout should contain "HAR 1.2"
+
+  Scenario: har help documents its flags
+    When I run "agentchrome network har --help"
+    Then the exit code should be 0
+    And stdout should contain "--file"
+    And stdout should contain "--include-bodies"
+    And stdout should contain "--include-preserved"
+
+  Scenario: har requires an output file
+    When I run "agentchrome network har"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: replay help documents its flags
+    When I run "agentchrome network replay --help"
+    Then the exit code should be 0
+    And stdout should contain "--har"
+    And stdout should contain "--not-found"
+    And stdout should contain "passthrough"
+    And stdout should contain "--match-body"
+
+  Scenario: replay requires a HAR file
+    When I run "agentchrome network replay"
+    Then the exit code should be nonzero
+    And stderr should contain "--har"
+
+  Scenario: replay rejects an unknown not-found mode
+    When I run "agentchrome network replay --har recording.har --not-found ignore"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  Scenario: replay reports a missing HAR file
+    When I run "agentchrome network replay --har /nonexistent/recording.har"
+    Then the exit code should be 1
+    And stderr should contain "File not found"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Exporting the current page's traffic
+    Given Chrome is running with a session
+    When I run "agentchrome network har --file session.har"
+    Then the exit code should be 0
+    And stdout JSON should have key "entries"
+    And the file "session.har" should be a HAR 1.2 log
+
+  Scenario: Exporting with response bodies
+    Given Chrome is running with a session
+    When I run "agentchrome network har --file session.har --include-bodies"
+    Then the exit code should be 0
+    And the document entry in "session.har" should have response content text
+
+  Scenario: Replaying a recording serves matching requests
+    Given Chrome is running with a session
+    When I run "agentchrome network replay --har recording.har --timeout 2000" while the page reloads
+    Then the exit code should be 0
+    And stdout JSON should have key "served"
+    And stdout JSON should have key "unmatched"
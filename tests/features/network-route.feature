@@ -0,0 +1,80 @@
+# File: tests/features/network-route.feature
+#
+# Request interception and response mocking via the Fetch domain
+
+Feature: Network route rules
+  As an AI agent testing a web UI
+  I want to stub, fail, or rewrite requests that match a URL pattern
+  So that I can force error paths without touching the real backend
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: Listing routes with none defined returns an empty array
+    When I run "agentchrome network route list"
+    Then the exit code should be 0
+    And stdout should be valid JSON
+    And stdout should contain "[]"
+
+  Scenario: Adding a status-only route prints the stored rule
+    When I run "agentchrome network route add --url-pattern */api/* --status 503"
+    Then the exit code should be 0
+    And stdout JSON should have key "id"
+    And stdout should contain "fulfill"
+    And stdout should contain "503"
+
+  Scenario: Adding an abort route records the CDP error reason
+    When I run "agentchrome network route add --url-pattern *.png --abort --error-reason name-not-resolved"
+    Then the exit code should be 0
+    And stdout should contain "NameNotResolved"
+
+  Scenario: Adding a header rewrite route
+    When I run "agentchrome network route add --url-pattern */api/* --set-header x-test:1 --remove-header cookie"
+    Then the exit code should be 0
+    And stdout should contain "modify"
+    And stdout should contain "x-test"
+
+  Scenario: A route needs an action
+    When I run "agentchrome network route add --url-pattern */api/*"
+    Then the exit code should be 1
+    And stderr should contain "No route action"
+
+  Scenario: Abort cannot be combined with a mocked response
+    When I run "agentchrome network route add --url-pattern */api/* --abort --status 500"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  Scenario: An invalid glob is rejected
+    When I run "agentchrome network route add --url-pattern [ --status 500"
+    Then the exit code should be 1
+    And stderr should contain "Invalid URL pattern"
+
+  Scenario: A missing response file is rejected
+    When I run "agentchrome network route add --url-pattern */api/* --respond-file /nonexistent/body.json"
+    Then the exit code should be 1
+    And stderr should contain "File not found"
+
+  Scenario: Removing an unknown route fails
+    When I run "agentchrome network route remove 42"
+    Then the exit code should be 1
+    And stderr should contain "No route with ID 42"
+
+  Scenario: Clearing with no routes reports zero
+    When I run "agentchrome network route clear"
+    Then the exit code should be 0
+    And stdout JSON should have key "cleared"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: network follow serves a mocked response
+    Given Chrome is running with a session
+    And a route for "*/api/users*" responds with status 500
+    When the page fetches "/api/users"
+    And I run "agentchrome network follow --timeout 2000"
+    Then stdout should contain "500"
+
+  Scenario: The daemon picks up new routes without a restart
+    Given Chrome is running with a session
+    And the daemon has been started
+    When I run "agentchrome network route add --url-pattern *.png --abort"
+    Then image requests on the page fail
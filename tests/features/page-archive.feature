@@ -0,0 +1,58 @@
+# File: tests/features/page-archive.feature
+#
+# Page archives (page archive --file / --bundle) via Page.captureSnapshot
+
+Feature: Page archive capture
+  As an engineer triaging an agent bug report
+  I want the agent to save exactly the page it saw
+  So that I can reproduce the failure from a single artifact
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page archive help documents file and bundle modes
+    When I run "agentchrome page archive --help"
+    Then the exit code should be 0
+    And stdout should contain "--file"
+    And stdout should contain "--bundle"
+    And stdout should contain "manifest.json"
+
+  Scenario: page archive requires --file or --bundle
+    When I run "agentchrome page archive"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: page archive rejects --file together with --bundle
+    When I run "agentchrome page archive --file page.mhtml --bundle out"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Save the page as a single MHTML file
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page archive --file page.mhtml"
+    Then the exit code should be 0
+    And the output should contain "\"file\":\"page.mhtml\""
+    And the file "page.mhtml" should contain "MIME-Version"
+
+  Scenario: Write a bug-report bundle
+    Given Chrome is running with a page loaded
+    And "agentchrome daemon start" has been run
+    When I run "agentchrome page archive --bundle bug"
+    Then the exit code should be 0
+    And the directory "bug" should contain "page.mhtml", "snapshot.txt", "snapshot.json", "screenshot.png", "console.json", "network.json", and "manifest.json"
+    And the output should contain "\"artifacts\""
+
+  Scenario: A failed artifact is recorded in the manifest
+    Given Chrome is running with a page that cannot be screenshotted
+    When I run "agentchrome page archive --bundle bug"
+    Then the exit code should be 0
+    And the manifest should list the "screenshot" artifact with an "error"
+
+  Scenario: The network summary is skipped without the daemon
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page archive --bundle bug"
+    Then the exit code should be 0
+    And the manifest should list the "network" artifact with an "error"
+    And the directory "bug" should not contain "network.json"
@@ -0,0 +1,57 @@
+# File: tests/features/page-extract.feature
+#
+# Schema-driven structured data extraction (page extract --schema)
+
+Feature: Schema-driven data extraction
+  As a data-scraping agent
+  I want to describe the fields I need in a schema
+  So that I get typed JSON without writing a bespoke js exec scraper
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page extract help documents the schema format
+    When I run "agentchrome page extract --help"
+    Then the exit code should be 0
+    And stdout should contain "--schema"
+    And stdout should contain "role:ROLE"
+    And stdout should contain "required"
+
+  Scenario: page extract requires a schema
+    When I run "agentchrome page extract"
+    Then the exit code should be nonzero
+    And stderr should contain "--schema"
+
+  Scenario: page extract reports an unreadable schema file
+    When I run "agentchrome page extract --schema does-not-exist.json"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot read schema"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Extract typed fields and nested lists
+    Given Chrome is running with a product page loaded
+    And a schema with a number price, a url link, and a list of reviews
+    When I run "agentchrome page extract --schema schema.json"
+    Then the exit code should be 0
+    And the output "data" should follow the schema field order
+    And relative links should be resolved against the page base URL
+
+  Scenario: Conversion failures are reported per field path
+    Given Chrome is running with a review whose rating is not a number
+    When I run "agentchrome page extract --schema schema.json"
+    Then the exit code should be 0
+    And the output "errors" should name "reviews[1].rating"
+
+  Scenario: A missing required field fails the extraction
+    Given Chrome is running with a page without a price
+    When I run "agentchrome page extract --schema schema.json"
+    Then the exit code should be nonzero
+    And stderr should contain "extraction_invalid"
+
+  Scenario: A field is read from an iframe
+    Given Chrome is running with a page whose ad text is inside frame 1
+    And a schema field with "frame": "1"
+    When I run "agentchrome page extract --schema schema.json"
+    Then the exit code should be 0
+    And the field should hold the iframe text
@@ -0,0 +1,56 @@
+# File: tests/features/page-pdf.feature
+#
+# PDF export (page pdf) backed by Page.printToPDF with streamed output
+
+Feature: Page PDF export
+  As a developer or automation engineer
+  I want to print the current page to a PDF file
+  So that I can archive reports, invoices, and receipts exactly as Chrome prints them
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page pdf help documents the print options
+    When I run "agentchrome page pdf --help"
+    Then the exit code should be 0
+    And stdout should contain "--file"
+    And stdout should contain "--paper"
+    And stdout should contain "--margin"
+    And stdout should contain "--landscape"
+    And stdout should contain "--page-ranges"
+    And stdout should contain "--header-template"
+    And stdout should contain "--footer-template"
+    And stdout should contain "--print-background"
+    And stdout should contain "--prefer-css-page-size"
+
+  Scenario: page pdf requires an output file
+    When I run "agentchrome page pdf"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: page pdf rejects unknown paper sizes
+    When I run "agentchrome page pdf --file out.pdf --paper b5"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Export the page to a PDF file
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page pdf --file out.pdf"
+    Then the exit code should be 0
+    And the output should contain "\"file\":\"out.pdf\""
+    And the file "out.pdf" should start with "%PDF"
+
+  Scenario: Print options are passed to Page.printToPDF
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page pdf --file report.pdf --paper a4 --landscape --margin 1cm --page-ranges 1-2"
+    Then the exit code should be 0
+    And the output should contain "\"landscape\":true"
+    And the output should contain "\"page_ranges\":\"1-2\""
+
+  Scenario: Invalid margins are rejected
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page pdf --file out.pdf --margin 1pt"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid --margin"
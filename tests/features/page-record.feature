@@ -0,0 +1,64 @@
+# File: tests/features/page-record.feature
+#
+# Screencast recording (page record start|stop) via Page.startScreencast
+
+Feature: Screencast recording of agent sessions
+  As an engineer reviewing what an agent did
+  I want a recording of the tab with the agent's actions marked on a timeline
+  So that I can replay a session instead of piecing it together from screenshots
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page record help lists start and stop
+    When I run "agentchrome page record --help"
+    Then the exit code should be 0
+    And stdout should contain "start"
+    And stdout should contain "stop"
+
+  Scenario: page record start documents its options
+    When I run "agentchrome page record start --help"
+    Then the exit code should be 0
+    And stdout should contain "--dir"
+    And stdout should contain "--max-fps"
+    And stdout should contain "--quality"
+
+  Scenario: page record start requires a directory
+    When I run "agentchrome page record start"
+    Then the exit code should be nonzero
+    And stderr should contain "--dir"
+
+  Scenario: page record start rejects an out-of-range quality
+    When I run "agentchrome page record start --dir frames --quality 0"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Record frames in the background
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page record start --dir frames --max-fps 5"
+    Then the exit code should be 0
+    And the output should contain "\"recording\":true"
+    And frames should be written to "frames" as the page repaints
+
+  Scenario: Only one recording runs per session
+    Given a recording is running
+    When I run "agentchrome page record start --dir other"
+    Then the exit code should be nonzero
+    And stderr should contain "already running"
+
+  Scenario: Stopping assembles the animation and the timeline with command markers
+    Given a recording is running into "frames"
+    And I have run "agentchrome interact click s1"
+    When I run "agentchrome page record stop"
+    Then the exit code should be 0
+    And the file "frames/recording.png" should be an animated PNG
+    And "frames/timeline.json" should list every frame with its timestamp
+    And "frames/timeline.json" should contain a marker for "interact click"
+
+  Scenario: Stopping without a recording fails
+    Given no recording is running
+    When I run "agentchrome page record stop"
+    Then the exit code should be nonzero
+    And stderr should contain "No active recording"
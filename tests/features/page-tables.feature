@@ -0,0 +1,50 @@
+# File: tests/features/page-tables.feature
+#
+# Structured table extraction (page tables) to JSON rows or CSV
+
+Feature: Page table extraction
+  As a data-scraping agent
+  I want the page's data tables as rows keyed by header
+  So that I do not have to rebuild them from flattened text
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page tables help documents selector, format, and spans
+    When I run "agentchrome page tables --help"
+    Then the exit code should be 0
+    And stdout should contain "--selector"
+    And stdout should contain "--format"
+    And stdout should contain "rowspan"
+
+  Scenario: page tables rejects an unknown format
+    When I run "agentchrome page tables --format xml"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Extract every data table as JSON
+    Given Chrome is running with a page containing a price table and a layout table
+    When I run "agentchrome page tables"
+    Then the exit code should be 0
+    And the output should contain "\"headers\""
+    And the output should list only the price table
+
+  Scenario: Spanned header cells are joined per column
+    Given Chrome is running with a table whose header uses colspan and rowspan
+    When I run "agentchrome page tables"
+    Then the exit code should be 0
+    And the output should contain "Cost / Each"
+
+  Scenario: Export a selected table as CSV
+    Given Chrome is running with a page containing a price table
+    When I run "agentchrome page tables --selector #prices --format csv"
+    Then the exit code should be 0
+    And the first output line should be the table headers
+
+  Scenario: A missing selector is reported
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page tables --selector #missing"
+    Then the exit code should be nonzero
+    And stderr should contain "Element not found"
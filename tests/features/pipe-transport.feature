@@ -0,0 +1,43 @@
+# File: tests/features/pipe-transport.feature
+#
+# Launching Chrome with --remote-debugging-pipe instead of a TCP debugging port
+
+Feature: Pipe transport
+  As an AI agent launching its own Chrome on a shared machine
+  I want CDP to travel over a private pipe rather than a TCP port
+  So that other local processes cannot connect to my browser
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: connect help documents the pipe transport
+    When I run "agentchrome connect --help"
+    Then the exit code should be 0
+    And stdout should contain "--pipe"
+    And stdout should contain "--remote-debugging-pipe"
+
+  Scenario: pipe requires launch
+    When I run "agentchrome connect --pipe"
+    Then the exit code should be nonzero
+    And stderr should contain "--launch"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Launching over a pipe opens no debugging port
+    When I run "agentchrome connect --launch --headless --pipe"
+    Then the exit code should be 0
+    And the JSON output should have "port" equal to 0
+    And Chrome should have been started with "--remote-debugging-pipe"
+    And Chrome should not have been started with "--remote-debugging-port"
+
+  Scenario: Later commands reach Chrome through the daemon
+    Given Chrome was launched with "agentchrome connect --launch --headless --pipe"
+    When I run "agentchrome tabs list"
+    Then the exit code should be 0
+    And stdout should contain a JSON array of tabs
+
+  Scenario: Stopping the daemon closes the browser
+    Given Chrome was launched with "agentchrome connect --launch --headless --pipe"
+    When I run "agentchrome daemon stop"
+    Then the exit code should be 0
+    And the Chrome process should exit
@@ -0,0 +1,57 @@
+# File: tests/features/screenshot-annotate.feature
+#
+# Set-of-mark annotated screenshots (page screenshot --annotate uids|grid)
+
+Feature: Annotated screenshots
+  As a vision-capable AI agent
+  I want screenshots labelled with snapshot UIDs or a coordinate grid
+  So that I can map what I see in the image to elements and coordinates I can act on
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page screenshot help documents --annotate
+    When I run "agentchrome page screenshot --help"
+    Then the exit code should be 0
+    And stdout should contain "--annotate"
+    And stdout should contain "legend"
+
+  Scenario: Annotate rejects unknown modes
+    When I run "agentchrome page screenshot --annotate boxes"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  Scenario: Annotate cannot be combined with full-page capture
+    When I run "agentchrome page screenshot --annotate grid --full-page"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: UID annotations label interactive elements from the latest snapshot
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot"
+    When I run "agentchrome page screenshot --annotate uids --file marked.png"
+    Then the exit code should be 0
+    And the output should contain "\"mode\":\"uids\""
+    And each legend entry should have a "uid", "role", "name", and "boundingBox"
+
+  Scenario: UID annotations require a snapshot
+    Given Chrome is running with a page loaded
+    And no snapshot has been taken in this session
+    When I run "agentchrome page screenshot --annotate uids"
+    Then the exit code should be nonzero
+    And stderr should contain "page snapshot"
+
+  Scenario: Grid annotations label cells spreadsheet-style
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page screenshot --annotate grid --file grid.png"
+    Then the exit code should be 0
+    And the output should contain "\"grid_step\":100"
+    And the output should contain "\"label\":\"A1\""
+
+  Scenario: The overlay is removed after capture
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page screenshot --annotate grid"
+    And I run "agentchrome js exec \"document.getElementById('__agentchrome_annotations')\""
+    Then the output should contain "null"
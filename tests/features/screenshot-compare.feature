@@ -0,0 +1,52 @@
+# File: tests/features/screenshot-compare.feature
+#
+# Visual regression: comparing screenshots against a baseline PNG (page screenshot --compare)
+
+Feature: Screenshot comparison against a baseline
+  As a team guarding visual regressions in CI
+  I want to compare a screenshot with a stored baseline image
+  So that unexpected visual changes fail the build with a measurable mismatch ratio
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page screenshot help documents --compare
+    When I run "agentchrome page screenshot --help"
+    Then the exit code should be 0
+    And stdout should contain "--compare"
+    And stdout should contain "--threshold"
+    And stdout should contain "--diff-out"
+
+  Scenario: Threshold requires --compare
+    When I run "agentchrome page screenshot --threshold 0.1"
+    Then the exit code should be nonzero
+    And stderr should contain "--compare"
+
+  Scenario: Diff output requires --compare
+    When I run "agentchrome page screenshot --diff-out diff.png"
+    Then the exit code should be nonzero
+    And stderr should contain "--compare"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: An unchanged page matches its baseline
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page screenshot --file baseline.png"
+    When I run "agentchrome page screenshot --compare baseline.png"
+    Then the exit code should be 0
+    And the output should contain "\"passed\":true"
+
+  Scenario: A changed element exceeds the threshold
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page screenshot --selector #header --file header.png"
+    When the page changes the header background colour
+    And I run "agentchrome page screenshot --selector #header --compare header.png --diff-out diff.png"
+    Then the exit code should be nonzero
+    And stderr should contain "screenshot_mismatch"
+    And the file "diff.png" should exist
+
+  Scenario: Compare requires PNG format
+    Given Chrome is running with a page loaded
+    When I run "agentchrome page screenshot --compare baseline.png --format jpeg"
+    Then the exit code should be nonzero
+    And stderr should contain "--compare requires --format png"
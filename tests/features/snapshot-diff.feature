@@ -0,0 +1,62 @@
+# File: tests/features/snapshot-diff.feature
+#
+# Diffing accessibility snapshots against the previously persisted snapshot state
+
+Feature: Snapshot diffing
+  As an AI agent driving a large web app
+  I want to see only the accessibility nodes that changed since my last snapshot
+  So that a toast or a toggled checkbox does not cost me the whole tree again
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page snapshot help documents --diff
+    When I run "agentchrome page snapshot --help"
+    Then the exit code should be 0
+    And stdout should contain "--diff"
+    And stdout should contain "since the previous snapshot"
+
+  Scenario: Interaction help documents --include-snapshot-diff
+    When I run "agentchrome interact click --help"
+    Then the exit code should be 0
+    And stdout should contain "--include-snapshot-diff"
+
+  Scenario: Form help documents --include-snapshot-diff
+    When I run "agentchrome form fill --help"
+    Then the exit code should be 0
+    And stdout should contain "--include-snapshot-diff"
+
+  Scenario: Snapshot diff cannot be combined with compact mode
+    When I run "agentchrome page snapshot --diff --compact"
+    Then the exit code should be nonzero
+    And stderr should contain "cannot be used with"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Diff reports a toggled checkbox and a new status message
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot"
+    When the page checks the "Agree" checkbox and shows a "Saved" status
+    And I run "agentchrome page snapshot --diff --json"
+    Then "changed" should contain the checkbox with "checked" from "false" to "true"
+    And "added" should contain a "status" node named "Saved"
+
+  Scenario: Diff without a baseline reports every node as added
+    Given Chrome is running with a page loaded
+    And no snapshot has been taken in this session
+    When I run "agentchrome page snapshot --diff --json"
+    Then "baseline" should be null
+
+  Scenario: Interactions return only the snapshot diff
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot"
+    When I run "agentchrome interact click s1 --include-snapshot-diff --json"
+    Then the output should contain "snapshot_diff"
+    And the output should not contain "snapshot\":"
+
+  Scenario: Form commands return only the snapshot diff
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot"
+    When I run "agentchrome form fill s3 user@example.com --include-snapshot-diff --json"
+    Then the output should contain "snapshot_diff"
+    And the output should not contain "snapshot\":"
@@ -0,0 +1,45 @@
+# File: tests/features/stable-uids.feature
+#
+# Snapshot UIDs keyed on backend DOM node identity, with stale-uid errors
+
+Feature: Stable snapshot UIDs
+  As an AI agent that clicks elements by UID
+  I want an element to keep its UID across snapshots and stale UIDs to be reported
+  So that a DOM change never turns my click into a click on a different element
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: page snapshot help explains UID stability
+    When I run "agentchrome page snapshot --help"
+    Then the exit code should be 0
+    And stdout should contain "keeps its UID"
+    And stdout should contain "stale-uid"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Existing elements keep their UIDs when an element is inserted before them
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot" and "Save" is "s1"
+    When a new button is inserted before "Save"
+    And I run "agentchrome page snapshot"
+    Then "Save" is still "s1"
+    And the new button has a UID that was not used before
+
+  Scenario: Using a retired UID reports the element's last known identity
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot" and "Save" is "s1"
+    When the "Save" button is removed
+    And I run "agentchrome page snapshot"
+    And I run "agentchrome interact click s1"
+    Then the exit code should be 1
+    And stderr should contain "\"kind\":\"stale_uid\""
+    And stderr should contain "last seen as button \"Save\""
+
+  Scenario: Using a UID whose node was detached without a new snapshot is stale
+    Given Chrome is running with a page loaded
+    And I have run "agentchrome page snapshot" and "Save" is "s1"
+    When the "Save" button is removed
+    And I run "agentchrome interact click s1"
+    Then the exit code should be 1
+    And stderr should contain "no longer exists"
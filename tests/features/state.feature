@@ -0,0 +1,51 @@
+# File: tests/features/state.feature
+#
+# Saving and restoring cookies plus web storage in Playwright storageState format
+
+Feature: Authentication state save and load
+  As an AI agent that has to log in before every run
+  I want to save the browser's cookies and storage once and restore them later
+  So that runs can skip the login flow and share fixtures with Playwright suites
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: state help lists save and load
+    When I run "agentchrome state --help"
+    Then the exit code should be 0
+    And stdout should contain "save"
+    And stdout should contain "load"
+    And stdout should contain "storageState"
+
+  Scenario: state save requires a file
+    When I run "agentchrome state save"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  Scenario: state load requires a file
+    When I run "agentchrome state load"
+    Then the exit code should be nonzero
+    And stderr should contain "--file"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: Save writes cookies and per-origin storage
+    Given Chrome is running with a page loaded
+    And the page stored "token" in localStorage
+    When I run "agentchrome state save --file auth.json"
+    Then the file "auth.json" should contain a "cookies" array
+    And its "origins" entry should list "token" under "localStorage"
+
+  Scenario: Load restores storage without contacting the site
+    Given Chrome is running with a page loaded
+    And a Playwright storageState file "auth.json" for "https://app.example.com"
+    When I run "agentchrome state load --file auth.json"
+    And I run "agentchrome navigate https://app.example.com"
+    And I run "agentchrome storage local list"
+    Then the restored localStorage entries are listed
+
+  Scenario: Loading a missing file fails
+    Given Chrome is running with a page loaded
+    When I run "agentchrome state load --file missing.json"
+    Then the exit code should be 1
+    And stderr should contain "Failed to read state file"
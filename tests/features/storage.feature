@@ -0,0 +1,97 @@
+# File: tests/features/storage.feature
+#
+# localStorage, sessionStorage, and IndexedDB access through the DOMStorage and IndexedDB domains
+
+Feature: Web storage commands
+  As an AI agent seeding and inspecting application state
+  I want to read and write localStorage and sessionStorage by origin
+  So that I do not need hand-written 'js exec' snippets that fail on cross-origin frames
+
+  Background:
+    Given agentchrome is built
+
+  Scenario: storage help lists both storage areas
+    When I run "agentchrome storage --help"
+    Then the exit code should be 0
+    And stdout should contain "local"
+    And stdout should contain "session"
+    And stdout should contain "--frame"
+
+  Scenario: storage area help lists the item subcommands
+    When I run "agentchrome storage local --help"
+    Then the exit code should be 0
+    And stdout should contain "list"
+    And stdout should contain "get"
+    And stdout should contain "set"
+    And stdout should contain "remove"
+    And stdout should contain "clear"
+
+  Scenario: storage get requires a key
+    When I run "agentchrome storage session get"
+    Then the exit code should be nonzero
+    And stderr should contain "<KEY>"
+
+  Scenario: storage set requires a value
+    When I run "agentchrome storage local set theme"
+    Then the exit code should be nonzero
+    And stderr should contain "<VALUE>"
+
+  Scenario: storage rejects an unknown area
+    When I run "agentchrome storage indexed list"
+    Then the exit code should be nonzero
+    And stderr should contain "unrecognized subcommand"
+
+  Scenario: storage idb help lists the IndexedDB subcommands
+    When I run "agentchrome storage idb --help"
+    Then the exit code should be 0
+    And stdout should contain "databases"
+    And stdout should contain "stores"
+    And stdout should contain "dump"
+    And stdout should contain "clear"
+
+  Scenario: storage idb dump requires a database and store
+    When I run "agentchrome storage idb dump app-db"
+    Then the exit code should be nonzero
+    And stderr should contain "<STORE>"
+
+  Scenario: storage idb dump rejects a non-numeric limit
+    When I run "agentchrome storage idb dump app-db todos --limit many"
+    Then the exit code should be nonzero
+    And stderr should contain "invalid value"
+
+  # --- Chrome-dependent scenarios (verified via manual smoke test) ---
+
+  Scenario: List localStorage for the current page's origin
+    Given Chrome is running with a page loaded
+    And the page has stored "theme" = "dark" in localStorage
+    When I run "agentchrome storage local list"
+    Then the output is a JSON array containing {"key": "theme", "value": "dark"}
+
+  Scenario: Set a value for an explicit origin
+    Given Chrome is running with a page loaded
+    When I run "agentchrome storage local set k v --origin https://example.com"
+    Then the JSON output should have "origin" equal to "https://example.com"
+
+  Scenario: Read a cross-origin iframe's sessionStorage
+    Given Chrome is running with a page loaded
+    And the page embeds a cross-origin iframe at index 1
+    When I run "agentchrome storage --frame 1 session list"
+    Then the entries of the iframe's origin are returned
+
+  Scenario: Getting a missing key fails
+    Given Chrome is running with a page loaded
+    When I run "agentchrome storage local get missing"
+    Then the exit code should be 1
+    And stderr should contain "not found"
+
+  Scenario: Dump IndexedDB records within a key range
+    Given Chrome is running with a page loaded
+    And the page stored records 1 through 30 in the "todos" store of "app-db"
+    When I run "agentchrome storage idb dump app-db todos --key-range 10..20"
+    Then the JSON output "entries" should have 11 items
+
+  Scenario: Clear every object store of a database
+    Given Chrome is running with a page loaded
+    And the page created the "app-db" database with stores "todos" and "meta"
+    When I run "agentchrome storage idb clear app-db"
+    Then the JSON output "cleared" should equal ["todos", "meta"]